@@ -4,6 +4,7 @@ use crate::arguments::Arguments;
 use crate::encode::Encode;
 use crate::types::Type;
 
+#[derive(Clone)]
 pub struct AnyArguments<'q> {
     #[doc(hidden)]
     pub values: AnyArgumentBuffer<'q>,
@@ -22,8 +23,12 @@ impl<'q> Arguments<'q> for AnyArguments<'q> {
     {
         let _ = value.encode(&mut self.values);
     }
+    fn try_clone(&self) -> Option<Self> {
+        Some(self.clone())
+    }
 }
 
+#[derive(Clone)]
 pub struct AnyArgumentBuffer<'q>(#[doc(hidden)] pub Vec<AnyValueKind<'q>>);
 
 impl<'q> Default for AnyArguments<'q> {
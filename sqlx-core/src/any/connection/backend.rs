@@ -1,8 +1,10 @@
 use crate::any::{Any, AnyArguments, AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo};
 use crate::describe::Describe;
+use crate::transaction::TransactionOptions;
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
+use futures_util::future;
 use std::fmt::Debug;
 
 pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
@@ -30,17 +32,50 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     /// Returns a [`Transaction`] for controlling and tracking the new transaction.
     fn begin(&mut self) -> BoxFuture<'_, crate::Result<()>>;
 
+    /// Begin a new transaction with the given options, or establish a savepoint within the
+    /// active transaction.
+    ///
+    /// Forward to [`TransactionManager::begin_with()`]. The default implementation only
+    /// supports the default options, with [`begin()`](Self::begin).
+    ///
+    /// [`TransactionManager::begin_with()`]: crate::transaction::TransactionManager::begin_with
+    fn begin_with(&mut self, options: TransactionOptions) -> BoxFuture<'_, crate::Result<()>> {
+        if options.is_default() {
+            return self.begin();
+        }
+
+        Box::pin(future::ready(Err(crate::Error::Configuration(
+            "transaction options are not supported by this database".into(),
+        ))))
+    }
+
     fn commit(&mut self) -> BoxFuture<'_, crate::Result<()>>;
 
     fn rollback(&mut self) -> BoxFuture<'_, crate::Result<()>>;
 
     fn start_rollback(&mut self);
 
+    /// Returns the current transaction depth.
+    ///
+    /// Forward to [`TransactionManager::get_transaction_depth()`].
+    ///
+    /// [`TransactionManager::get_transaction_depth()`]: crate::transaction::TransactionManager::get_transaction_depth
+    fn get_transaction_depth(&self) -> usize {
+        0
+    }
+
     /// The number of statements currently cached in the connection.
     fn cached_statements_size(&self) -> usize {
         0
     }
 
+    /// Forward to [`Connection::cached_statements()`].
+    ///
+    /// [`Connection::cached_statements()`]: method@crate::connection::Connection::cached_statements
+    fn cached_statements(&mut self) -> BoxFuture<'_, crate::Result<Vec<String>>> {
+        Box::pin(async move { Ok(Vec::new()) })
+    }
+
     /// Removes all statements from the cache, closing them on the server if
     /// needed.
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, crate::Result<()>> {
@@ -58,6 +93,21 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     #[doc(hidden)]
     fn should_flush(&self) -> bool;
 
+    /// Forward to [`Connection::rollback_leaked_transaction()`].
+    ///
+    /// [`Connection::rollback_leaked_transaction()`]: method@crate::connection::Connection::rollback_leaked_transaction
+    #[doc(hidden)]
+    fn rollback_leaked_transaction(&mut self) -> BoxFuture<'_, crate::Result<bool>> {
+        Box::pin(future::ready(Ok(false)))
+    }
+
+    /// Forward to [`Connection::cancel_token()`].
+    ///
+    /// [`Connection::cancel_token()`]: method@crate::connection::Connection::cancel_token
+    fn cancel_token(&self) -> Option<crate::connection::CancelToken> {
+        None
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(&mut self) -> crate::Result<&mut (dyn crate::migrate::Migrate + Send + 'static)> {
         Err(crate::Error::Configuration(
@@ -1,13 +1,13 @@
 use futures_core::future::BoxFuture;
 
 use crate::any::{Any, AnyConnectOptions};
-use crate::connection::{ConnectOptions, Connection};
+use crate::connection::{CancelToken, ConnectOptions, Connection};
 use crate::error::Error;
 
 use crate::database::Database;
 pub use backend::AnyConnectionBackend;
 
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 
 mod backend;
 mod executor;
@@ -90,10 +90,24 @@ impl Connection for AnyConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn cached_statements_size(&self) -> usize {
         self.backend.cached_statements_size()
     }
 
+    fn cached_statements(&mut self) -> BoxFuture<'_, crate::Result<Vec<String>>> {
+        self.backend.cached_statements()
+    }
+
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, crate::Result<()>> {
         self.backend.clear_cached_statements()
     }
@@ -111,4 +125,13 @@ impl Connection for AnyConnection {
     fn should_flush(&self) -> bool {
         self.backend.should_flush()
     }
+
+    #[doc(hidden)]
+    fn rollback_leaked_transaction(&mut self) -> BoxFuture<'_, Result<bool, Error>> {
+        self.backend.rollback_leaked_transaction()
+    }
+
+    fn cancel_token(&self) -> Option<CancelToken> {
+        self.backend.cancel_token()
+    }
 }
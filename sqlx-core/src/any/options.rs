@@ -1,5 +1,7 @@
 use crate::any::AnyConnection;
-use crate::connection::{ConnectOptions, LogSettings};
+use crate::connection::{
+    ConnectOptions, LogSettings, SqlRewriter, SqlRewriterSettings, StatementLogHandler,
+};
 use crate::error::Error;
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
@@ -19,6 +21,7 @@ use url::Url;
 pub struct AnyConnectOptions {
     pub database_url: Url,
     pub log_settings: LogSettings,
+    pub sql_rewriter: SqlRewriterSettings,
 }
 impl FromStr for AnyConnectOptions {
     type Err = Error;
@@ -29,6 +32,7 @@ impl FromStr for AnyConnectOptions {
                 .parse::<Url>()
                 .map_err(|e| Error::Configuration(e.into()))?,
             log_settings: LogSettings::default(),
+            sql_rewriter: SqlRewriterSettings::default(),
         })
     }
 }
@@ -40,6 +44,7 @@ impl ConnectOptions for AnyConnectOptions {
         Ok(AnyConnectOptions {
             database_url: url.clone(),
             log_settings: LogSettings::default(),
+            sql_rewriter: SqlRewriterSettings::default(),
         })
     }
 
@@ -58,4 +63,24 @@ impl ConnectOptions for AnyConnectOptions {
         self.log_settings.slow_statements_duration = duration;
         self
     }
+
+    fn log_with(mut self, handler: StatementLogHandler) -> Self {
+        self.log_settings.log_with(handler);
+        self
+    }
+
+    fn log_parameter_values(mut self, enabled: bool) -> Self {
+        self.log_settings.log_parameter_values(enabled);
+        self
+    }
+
+    fn sql_rewriter(mut self, rewriter: SqlRewriter) -> Self {
+        self.sql_rewriter.set(rewriter);
+        self
+    }
+
+    fn disable_statement_logging(mut self) -> Self {
+        self.log_settings.disable();
+        self
+    }
 }
@@ -56,3 +56,27 @@ impl<'r> Decode<'r, Any> for Vec<u8> {
         }
     }
 }
+
+impl<const N: usize> Type<Any> for [u8; N] {
+    fn type_info() -> AnyTypeInfo {
+        <[u8] as Type<Any>>::type_info()
+    }
+}
+
+impl<'q, const N: usize> Encode<'q, Any> for [u8; N] {
+    fn encode_by_ref(&self, buf: &mut <Any as HasArguments<'q>>::ArgumentBuffer) -> IsNull {
+        buf.0.push(AnyValueKind::Blob(Cow::Owned(self.to_vec())));
+        IsNull::No
+    }
+}
+
+impl<'r, const N: usize> Decode<'r, Any> for [u8; N] {
+    fn decode(value: <Any as HasValueRef<'r>>::ValueRef) -> Result<Self, BoxDynError> {
+        match value.kind {
+            AnyValueKind::Blob(blob) => (*blob)
+                .try_into()
+                .map_err(|_| format!("expected {N} bytes, got {}", blob.len()).into()),
+            other => other.unexpected(),
+        }
+    }
+}
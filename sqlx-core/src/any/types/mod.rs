@@ -10,7 +10,10 @@
 //! | `i64`                                 | BIGINT                                               |
 //! | `f32`                                 | FLOAT                                                |
 //! | `f64`                                 | DOUBLE                                               |
-//! | `&str`, [`String`]                    | VARCHAR, CHAR, TEXT                                  |
+//! | `&str`, [`String`], `Cow<str>`        | VARCHAR, CHAR, TEXT                                  |
+//!
+//! The text of `Any` rows is copied into strings owned by the row when it is received, which
+//! `&str` and `Cow<str>` borrow: `Cow<str>` is decoded as `Cow::Borrowed`.
 //!
 //! # Nullable
 //!
@@ -50,4 +53,6 @@ fn test_type_impls() {
     // These imply that there are also impls for the equivalent slice types.
     has_type::<Vec<u8>>();
     has_type::<String>();
+
+    has_type::<[u8; 32]>();
 }
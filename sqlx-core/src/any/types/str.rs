@@ -43,6 +43,33 @@ impl<'a> Decode<'a, Any> for &'a str {
     }
 }
 
+impl Type<Any> for Cow<'_, str> {
+    fn type_info() -> AnyTypeInfo {
+        <str as Type<Any>>::type_info()
+    }
+}
+
+impl<'q> Encode<'q, Any> for Cow<'q, str> {
+    fn encode(self, buf: &mut <Any as HasArguments<'q>>::ArgumentBuffer) -> IsNull {
+        buf.0.push(AnyValueKind::Text(self));
+        IsNull::No
+    }
+
+    fn encode_by_ref(&self, buf: &mut <Any as HasArguments<'q>>::ArgumentBuffer) -> IsNull {
+        buf.0.push(AnyValueKind::Text(self.clone()));
+        IsNull::No
+    }
+}
+
+impl<'r> Decode<'r, Any> for Cow<'r, str> {
+    fn decode(value: <Any as HasValueRef<'r>>::ValueRef) -> Result<Self, BoxDynError> {
+        match value.kind {
+            AnyValueKind::Text(text) => Ok(text),
+            other => other.unexpected(),
+        }
+    }
+}
+
 impl Type<Any> for String {
     fn type_info() -> AnyTypeInfo {
         <str as Type<Any>>::type_info()
@@ -21,6 +21,14 @@ pub trait Arguments<'q>: Send + Sized + Default {
     fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
         writer.write_str("?")
     }
+
+    /// Returns a copy of the arguments, to execute a query again, if they can be copied.
+    ///
+    /// A [retryable][crate::query::Query::retryable] query is only retried if its arguments
+    /// can be copied. The default implementation returns `None`.
+    fn try_clone(&self) -> Option<Self> {
+        None
+    }
 }
 
 pub trait IntoArguments<'q, DB: HasArguments<'q>>: Sized + Send {
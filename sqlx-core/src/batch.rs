@@ -0,0 +1,117 @@
+//! Execute a batch of independent statements, with [`execute_batch()`].
+
+use crate::connection::Connection;
+use crate::database::Database;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::transaction::TransactionManager;
+
+/// What [`execute_batch()`] does when a statement fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchMode {
+    /// Do not execute the statements after the first one which failed.
+    StopOnError,
+
+    /// Execute all the statements, regardless of the ones which failed.
+    ///
+    /// When the connection is in a transaction, each statement is executed in a savepoint which
+    /// is rolled back if it fails, so that the transaction can go on. Otherwise, Postgres would
+    /// refuse to execute any statement after the first error until the end of the transaction.
+    ContinueOnError,
+}
+
+/// Execute each of `statements` in order on `conn`, returning the result of each of them.
+///
+/// With [`BatchMode::StopOnError`], the results end with the error of the first statement which
+/// failed, if any, and the statements after it are not executed. With
+/// [`BatchMode::ContinueOnError`], there is one result for each statement.
+///
+/// The connection can be used as usual afterwards, whether some statements failed or not. The
+/// statements are executed one after the other, and are not wrapped in a transaction: use
+/// [`Connection::begin()`] and pass the transaction to make the batch atomic.
+///
+/// This is useful for setup scripts where some statements may have been applied already.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::batch::{execute_batch, BatchMode};
+///
+/// let results = execute_batch(
+///     conn,
+///     [
+///         "CREATE EXTENSION citext",
+///         "CREATE TABLE users (id BIGSERIAL PRIMARY KEY, email CITEXT NOT NULL)",
+///         "CREATE INDEX users_email ON users (email)",
+///     ],
+///     BatchMode::ContinueOnError,
+/// )
+/// .await;
+///
+/// for result in results {
+///     if let Err(e) = result {
+///         println!("skipped: {e}");
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn execute_batch<'q, C, I>(
+    conn: &mut C,
+    statements: I,
+    mode: BatchMode,
+) -> Vec<Result<<C::Database as Database>::QueryResult, Error>>
+where
+    C: Connection,
+    C::Database: Database<Connection = C>,
+    for<'c> &'c mut C: Executor<'c, Database = C::Database>,
+    I: IntoIterator,
+    I::Item: 'q + Execute<'q, C::Database>,
+{
+    let mut results = Vec::new();
+
+    for statement in statements {
+        let in_transaction =
+            <C::Database as Database>::TransactionManager::get_transaction_depth(conn) > 0;
+
+        let result = if in_transaction && mode == BatchMode::ContinueOnError {
+            execute_in_savepoint(conn, statement).await
+        } else {
+            conn.execute(statement).await
+        };
+
+        let failed = result.is_err();
+        results.push(result);
+
+        if failed && mode == BatchMode::StopOnError {
+            break;
+        }
+    }
+
+    results
+}
+
+async fn execute_in_savepoint<'q, C, E>(
+    conn: &mut C,
+    statement: E,
+) -> Result<<C::Database as Database>::QueryResult, Error>
+where
+    C: Connection,
+    C::Database: Database<Connection = C>,
+    for<'c> &'c mut C: Executor<'c, Database = C::Database>,
+    E: 'q + Execute<'q, C::Database>,
+{
+    let mut savepoint = conn.begin().await?;
+
+    match (&mut *savepoint).execute(statement).await {
+        Ok(result) => {
+            savepoint.commit().await?;
+            Ok(result)
+        }
+        Err(e) => {
+            savepoint.rollback().await?;
+            Err(e)
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use crate::database::Database;
 use crate::error::Error;
+use crate::ext::ustr::UStr;
+use crate::HashMap;
 
 use std::fmt::Debug;
 
@@ -82,3 +84,89 @@ macro_rules! impl_column_index_for_statement {
         }
     };
 }
+
+/// The columns of a result which are kept in its rows, as selected by
+/// [`Query::select_columns()`](crate::query::Query::select_columns).
+#[derive(Debug, Clone)]
+pub struct ColumnSelection {
+    // for each column of the result, its position among the selected ones, if selected
+    slots: Vec<Option<usize>>,
+    // for each selected column, its position in the result
+    indices: Vec<usize>,
+}
+
+impl ColumnSelection {
+    /// Select the columns named `selected` out of the `num_columns` columns of a result, which
+    /// are looked up in `column_names`.
+    ///
+    /// A column selected more than once is only kept the first time. Returns
+    /// [`Error::ColumnNotFound`] if a column is not in the result.
+    pub fn new<S: AsRef<str>>(
+        selected: &[S],
+        column_names: &HashMap<UStr, usize>,
+        num_columns: usize,
+    ) -> Result<Self, Error> {
+        let mut slots = vec![None; num_columns];
+        let mut indices = Vec::with_capacity(selected.len());
+
+        for name in selected {
+            let name = name.as_ref();
+            let index = *column_names
+                .get(name)
+                .ok_or_else(|| Error::ColumnNotFound(name.into()))?;
+
+            if slots[index].is_none() {
+                slots[index] = Some(indices.len());
+                indices.push(index);
+            }
+        }
+
+        Ok(Self { slots, indices })
+    }
+
+    /// Returns the position among the selected columns of the column at `index` in the result,
+    /// or `None` if it is not selected.
+    #[inline]
+    pub fn slot(&self, index: usize) -> Option<usize> {
+        self.slots.get(index).copied().flatten()
+    }
+
+    /// Returns the positions in the result of the selected columns, in the order in which they
+    /// were selected.
+    #[inline]
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Returns the number of selected columns.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns `true` if no columns are selected.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+#[test]
+fn test_column_selection() {
+    let column_names: HashMap<UStr, usize> = [("a", 0), ("b", 1), ("c", 2)]
+        .into_iter()
+        .map(|(name, index)| (UStr::Static(name), index))
+        .collect();
+
+    let selection = ColumnSelection::new(&["c", "a", "c"], &column_names, 3).unwrap();
+
+    assert_eq!(selection.indices(), &[2, 0]);
+    assert_eq!(selection.slot(0), Some(1));
+    assert_eq!(selection.slot(1), None);
+    assert_eq!(selection.slot(2), Some(0));
+
+    assert!(matches!(
+        ColumnSelection::new(&["d"], &column_names, 3),
+        Err(Error::ColumnNotFound(name)) if name == "d"
+    ));
+}
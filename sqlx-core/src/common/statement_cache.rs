@@ -38,6 +38,12 @@ impl<T> StatementCache<T> {
         lru_item
     }
 
+    /// The SQL of the statements in the cache, from the most recently used to the least recently
+    /// used.
+    pub fn keys(&self) -> impl Iterator<Item = &str> + '_ {
+        self.inner.iter().rev().map(|(k, _)| &**k)
+    }
+
     /// The number of statements in the cache.
     pub fn len(&self) -> usize {
         self.inner.len()
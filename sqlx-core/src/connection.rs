@@ -1,11 +1,18 @@
 use crate::database::{Database, HasStatementCache};
 use crate::error::Error;
+use crate::pool::RetryPolicy;
+use crate::reconnect::is_transient_connection_error;
 
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 use futures_core::future::BoxFuture;
+use futures_util::future;
 use log::LevelFilter;
-use std::fmt::Debug;
+use std::borrow::Cow;
+use std::cmp;
+use std::fmt::{self, Debug, Formatter};
+use std::future::Future;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 use url::Url;
 
@@ -42,6 +49,52 @@ pub trait Connection: Send {
     /// Checks if a connection to the database is still valid.
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>>;
 
+    /// Checks if a connection to the database is still valid, failing if the database doesn't
+    /// respond within `timeout`.
+    ///
+    /// A [`ping()`](Self::ping) may never complete if the connection is half-open, e.g. if the
+    /// server or the network went away without closing it. This fails with an [`Error::Io`] of
+    /// kind [`TimedOut`](std::io::ErrorKind::TimedOut) instead, after which the connection is
+    /// unusable, as the ping may still be in-flight: it should be closed, or discarded with
+    /// [`ReconnectingConnection`](crate::reconnect::ReconnectingConnection), and replaced.
+    ///
+    /// This is useful for the health checks of a pool, as a connection is closed if
+    /// [`PoolOptions::before_acquire`] fails:
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> sqlx::Result<()> {
+    /// use std::time::Duration;
+    /// use sqlx::Connection;
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// let pool = PgPoolOptions::new()
+    ///     .test_before_acquire(false)
+    ///     .before_acquire(|conn, _meta| {
+    ///         Box::pin(async move {
+    ///             conn.ping_with_timeout(Duration::from_secs(1)).await?;
+    ///             Ok(true)
+    ///         })
+    ///     })
+    ///     .connect("postgres:// …")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`PoolOptions::before_acquire`]: crate::pool::PoolOptions::before_acquire
+    fn ping_with_timeout(&mut self, timeout: Duration) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            crate::rt::timeout(timeout, self.ping())
+                .await
+                .map_err(|_| {
+                    Error::Io(std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        "timed out waiting for the database to respond to a ping",
+                    ))
+                })?
+        })
+    }
+
     /// Begin a new transaction or establish a savepoint within the active transaction.
     ///
     /// Returns a [`Transaction`] for controlling and tracking the new transaction.
@@ -49,6 +102,32 @@ pub trait Connection: Send {
     where
         Self: Sized;
 
+    /// Begin a new transaction with the given options, e.g. its isolation level.
+    ///
+    /// Returns a [`Transaction`] for controlling and tracking the new transaction.
+    ///
+    /// An error is returned if the database doesn't support the options, or if a transaction
+    /// is already active and the options are not the default ones, as this would establish a
+    /// savepoint, which has no options. See [`TransactionOptions`].
+    ///
+    /// The default implementation only supports the default options, with
+    /// [`begin()`](Self::begin).
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        if options.is_default() {
+            return self.begin();
+        }
+
+        Box::pin(future::ready(Err(Error::Configuration(
+            "transaction options are not supported by this database".into(),
+        ))))
+    }
+
     /// Execute the function inside a transaction.
     ///
     /// If the function returns an error, the transaction will be rolled back. If it does not
@@ -67,6 +146,41 @@ pub trait Connection: Send {
     /// # }
     /// ```
     fn transaction<'a, F, R, E>(&'a mut self, callback: F) -> BoxFuture<'a, Result<R, E>>
+    where
+        for<'c> F: FnOnce(&'c mut Transaction<'_, Self::Database>) -> BoxFuture<'c, Result<R, E>>
+            + 'a
+            + Send
+            + Sync,
+        Self: Sized,
+        R: Send,
+        E: From<Error> + Send,
+    {
+        self.transaction_with(TransactionOptions::default(), callback)
+    }
+
+    /// Execute the function inside a transaction begun with the given options.
+    ///
+    /// See [`transaction()`](Self::transaction) and [`begin_with()`](Self::begin_with).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use sqlx::postgres::{PgConnection, PgRow};
+    /// use sqlx::{Connection, IsolationLevel, TransactionOptions};
+    ///
+    /// # pub async fn _f(conn: &mut PgConnection) -> sqlx::Result<Vec<PgRow>> {
+    /// let options = TransactionOptions::new().isolation_level(IsolationLevel::RepeatableRead);
+    ///
+    /// conn.transaction_with(options, |txn| Box::pin(async move {
+    ///     sqlx::query("select * from ..").fetch_all(&mut **txn).await
+    /// })).await
+    /// # }
+    /// ```
+    fn transaction_with<'a, F, R, E>(
+        &'a mut self,
+        options: TransactionOptions,
+        callback: F,
+    ) -> BoxFuture<'a, Result<R, E>>
     where
         for<'c> F: FnOnce(&'c mut Transaction<'_, Self::Database>) -> BoxFuture<'c, Result<R, E>>
             + 'a
@@ -77,7 +191,7 @@ pub trait Connection: Send {
         E: From<Error> + Send,
     {
         Box::pin(async move {
-            let mut transaction = self.begin().await?;
+            let mut transaction = self.begin_with(options).await?;
             let ret = callback(&mut transaction).await;
 
             match ret {
@@ -103,6 +217,46 @@ pub trait Connection: Send {
         0
     }
 
+    /// Returns the SQL of the statements currently cached in the connection, from the most
+    /// recently used to the least recently used.
+    ///
+    /// As prepared statements are per-connection, this can be used to prepare the statements
+    /// used the most on new connections of a pool, instead of preparing them on first use:
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+    /// use std::sync::Arc;
+    /// use sqlx::{Connection, Executor};
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// // e.g. the statements cached by a connection of a pool which served requests
+    /// let statements: Arc<Vec<String>> = Arc::new(pool.acquire().await?.cached_statements().await?);
+    ///
+    /// let pool = PgPoolOptions::new()
+    ///     .after_connect(move |conn, _meta| {
+    ///         let statements = statements.clone();
+    ///
+    ///         Box::pin(async move {
+    ///             // preparing a statement adds it to the cache of the connection
+    ///             for sql in statements.iter() {
+    ///                 conn.prepare(sql).await?;
+    ///             }
+    ///
+    ///             Ok(())
+    ///         })
+    ///     })
+    ///     .connect("postgres:// …")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn cached_statements(&mut self) -> BoxFuture<'_, Result<Vec<String>, Error>>
+    where
+        Self::Database: HasStatementCache,
+    {
+        Box::pin(async move { Ok(Vec::new()) })
+    }
+
     /// Removes all statements from the cache, closing them on the server if
     /// needed.
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>>
@@ -132,6 +286,28 @@ pub trait Connection: Send {
     #[doc(hidden)]
     fn should_flush(&self) -> bool;
 
+    /// Roll back the transaction the connection is in, if any, including one which was not
+    /// begun with [`begin()`](Self::begin), e.g. with a plain `BEGIN`.
+    ///
+    /// Returns `true` if a transaction was rolled back. This is called when a connection is
+    /// returned to a [`Pool`](crate::pool::Pool), so that a leaked transaction is not inherited by
+    /// the next user of the connection.
+    ///
+    /// The default implementation does nothing and returns `false`, for drivers which can't tell
+    /// whether a transaction is open.
+    #[doc(hidden)]
+    fn rollback_leaked_transaction(&mut self) -> BoxFuture<'_, Result<bool, Error>> {
+        Box::pin(future::ready(Ok(false)))
+    }
+
+    /// Returns a token to cancel the query executing on this connection from another task,
+    /// or `None` if the driver doesn't support it.
+    ///
+    /// See [`CancelToken`] for details.
+    fn cancel_token(&self) -> Option<CancelToken> {
+        None
+    }
+
     /// Establish a new database connection.
     ///
     /// A value of [`Options`][Self::Options] is parsed from the provided connection string. This parsing
@@ -155,12 +331,112 @@ pub trait Connection: Send {
     }
 }
 
-#[derive(Clone, Debug)]
+/// A token to cancel the query executing on a connection, returned by
+/// [`Connection::cancel_token()`].
+///
+/// The connection itself is usually busy while a query executes (e.g. borrowed by the stream of
+/// its rows), so the token is independent of it and can be sent to another task. Cancelling is
+/// done on a best-effort basis, as the query may complete before the request reaches the
+/// database, and the way it is done depends on the driver:
+///
+/// * Postgres sends a cancel request for the backend of the connection on a new connection;
+/// * MySQL opens a new connection to run `KILL QUERY` for the connection;
+/// * SQLite interrupts the statements running on the connection.
+///
+/// A cancelled query fails with an error from the database. The connection can be used again
+/// once that error was received, but the query may be cancelled at any point, so its effects
+/// must be considered unknown unless it ran in a transaction which is rolled back.
+pub struct CancelToken {
+    cancel: BoxFuture<'static, Result<(), Error>>,
+}
+
+impl CancelToken {
+    /// Create a token, which cancels the query when `cancel` is executed.
+    #[doc(hidden)]
+    pub fn new(cancel: impl Future<Output = Result<(), Error>> + Send + 'static) -> Self {
+        CancelToken {
+            cancel: Box::pin(cancel),
+        }
+    }
+
+    /// Request the database to cancel the query executing on the connection, if any.
+    ///
+    /// Returns once the request was sent, which doesn't mean that the query stopped.
+    pub async fn cancel(self) -> Result<(), Error> {
+        self.cancel.await
+    }
+}
+
+impl Debug for CancelToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancelToken").finish_non_exhaustive()
+    }
+}
+
+/// A handler observing the executed statements, see [`ConnectOptions::log_with()`].
+pub type StatementLogHandler = Arc<dyn Fn(&StatementLogContext<'_>) + Send + Sync>;
+
+/// A statement which finished executing, given to a [`StatementLogHandler`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct StatementLogContext<'a> {
+    /// The SQL of the statement.
+    pub sql: &'a str,
+    /// The time taken to execute the statement and receive its results.
+    pub elapsed: Duration,
+    /// The number of rows affected by the statement.
+    pub rows_affected: u64,
+    /// The number of rows returned by the statement.
+    pub rows_returned: u64,
+    /// Whether the statement took longer than the duration set with
+    /// [`ConnectOptions::log_slow_statements()`].
+    ///
+    /// Always `false` when slow statements are not logged, e.g. after
+    /// [`ConnectOptions::disable_statement_logging()`].
+    pub was_slow: bool,
+}
+
+/// A function rewriting the SQL of the statements executed on a connection, see
+/// [`ConnectOptions::sql_rewriter()`].
+pub type SqlRewriter = Arc<dyn Fn(&str) -> Cow<'_, str> + Send + Sync>;
+
+/// The [`SqlRewriter`] of a connection, if any.
+#[derive(Clone, Default)]
+pub struct SqlRewriterSettings {
+    rewriter: Option<SqlRewriter>,
+}
+
+impl SqlRewriterSettings {
+    /// Sets the [`SqlRewriter`], replacing the previous one if any.
+    pub fn set(&mut self, rewriter: SqlRewriter) {
+        self.rewriter = Some(rewriter);
+    }
+
+    /// Returns `sql` rewritten by the [`SqlRewriter`], or as-is if there is none.
+    pub fn rewrite<'a>(&self, sql: &'a str) -> Cow<'a, str> {
+        match &self.rewriter {
+            Some(rewriter) => rewriter(sql),
+            None => Cow::Borrowed(sql),
+        }
+    }
+}
+
+impl Debug for SqlRewriterSettings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqlRewriterSettings")
+            .field("rewriter", &self.rewriter.as_ref().map(|_| "<function>"))
+            .finish()
+    }
+}
+
+#[derive(Clone)]
 #[non_exhaustive]
 pub struct LogSettings {
     pub statements_level: LevelFilter,
     pub slow_statements_level: LevelFilter,
     pub slow_statements_duration: Duration,
+    pub statement_handler: Option<StatementLogHandler>,
+    pub parameter_values: bool,
 }
 
 impl Default for LogSettings {
@@ -169,6 +445,8 @@ impl Default for LogSettings {
             statements_level: LevelFilter::Debug,
             slow_statements_level: LevelFilter::Warn,
             slow_statements_duration: Duration::from_secs(1),
+            statement_handler: None,
+            parameter_values: false,
         }
     }
 }
@@ -181,6 +459,33 @@ impl LogSettings {
         self.slow_statements_level = level;
         self.slow_statements_duration = duration;
     }
+    pub fn log_with(&mut self, handler: StatementLogHandler) {
+        self.statement_handler = Some(handler);
+    }
+    pub fn log_parameter_values(&mut self, enabled: bool) {
+        // only ever enabled in development, see `ConnectOptions::log_parameter_values()`
+        self.parameter_values = enabled && cfg!(debug_assertions);
+    }
+    pub fn disable(&mut self) {
+        self.log_statements(LevelFilter::Off);
+        self.log_slow_statements(LevelFilter::Off, Duration::default());
+        self.statement_handler = None;
+    }
+}
+
+impl Debug for LogSettings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogSettings")
+            .field("statements_level", &self.statements_level)
+            .field("slow_statements_level", &self.slow_statements_level)
+            .field("slow_statements_duration", &self.slow_statements_duration)
+            .field(
+                "statement_handler",
+                &self.statement_handler.as_ref().map(|_| "<function>"),
+            )
+            .field("parameter_values", &self.parameter_values)
+            .finish()
+    }
 }
 
 pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug + Clone {
@@ -194,6 +499,64 @@ pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug +
     where
         Self::Connection: Sized;
 
+    /// Establish a new database connection like [`connect()`](Self::connect), retrying if it
+    /// fails with a transient error, up to `max_attempts` attempts in total.
+    ///
+    /// This is meant for the startup of a service, when the database may not be ready yet.
+    /// An attempt is retried if it failed with an I/O error (e.g. the connection was refused or
+    /// the host name could not be resolved), or if the database reported a transient condition,
+    /// such as still starting up or having too many connections. Permanent failures, such as
+    /// invalid credentials, an unknown database or an invalid configuration, are returned
+    /// immediately.
+    ///
+    /// The delay before the first retry is `backoff`, and is doubled after each attempt, up to
+    /// 30 seconds (or `backoff` if it is longer). Each delay is randomly shortened by up to half,
+    /// so that many services started at the same time don't retry at the same time. The error of
+    /// the last attempt is returned if all of them failed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> sqlx::Result<()> {
+    /// use std::time::Duration;
+    /// use sqlx::ConnectOptions;
+    /// use sqlx::postgres::PgConnectOptions;
+    ///
+    /// let conn = "postgres://localhost/mydb"
+    ///     .parse::<PgConnectOptions>()?
+    ///     .connect_with_retry(10, Duration::from_millis(100))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn connect_with_retry(
+        &self,
+        max_attempts: u32,
+        backoff: Duration,
+    ) -> BoxFuture<'_, Result<Self::Connection, Error>>
+    where
+        Self::Connection: Sized,
+    {
+        let policy = RetryPolicy::new()
+            .max_attempts(max_attempts)
+            .base_delay(backoff)
+            .max_delay(cmp::max(backoff, Duration::from_secs(30)))
+            .retry_if(is_transient_connection_error);
+
+        Box::pin(async move {
+            let mut attempt = 1;
+
+            loop {
+                match self.connect().await {
+                    Ok(conn) => return Ok(conn),
+                    Err(e) => policy.backoff_or_fail(e, attempt).await?,
+                }
+
+                attempt += 1;
+            }
+        })
+    }
+
     /// Log executed statements with the specified `level`
     fn log_statements(self, level: LevelFilter) -> Self;
 
@@ -201,7 +564,119 @@ pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug +
     /// at the specified `level`.
     fn log_slow_statements(self, level: LevelFilter, duration: Duration) -> Self;
 
-    /// Entirely disables statement logging (both slow and regular).
+    /// Call `handler` for each executed statement, with its SQL, duration and number of rows.
+    ///
+    /// This allows observing the statements in a structured way, e.g. to record metrics. The
+    /// handler is called once the statement finished executing, in addition to the logging
+    /// configured with [`log_statements()`](Self::log_statements) and
+    /// [`log_slow_statements()`](Self::log_slow_statements), even if it is disabled. It should
+    /// return quickly, as it is called on the task executing the statement.
+    ///
+    /// The handler is removed by [`disable_statement_logging()`](Self::disable_statement_logging),
+    /// so it must be set after it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn example() -> sqlx::Result<()> {
+    /// use std::sync::Arc;
+    /// use sqlx::ConnectOptions;
+    /// use sqlx::postgres::PgConnectOptions;
+    ///
+    /// let options = "postgres://localhost/mydb"
+    ///     .parse::<PgConnectOptions>()?
+    ///     .disable_statement_logging()
+    ///     .log_with(Arc::new(|statement| {
+    ///         eprintln!(
+    ///             "{} took {:?} and returned {} rows",
+    ///             statement.sql, statement.elapsed, statement.rows_returned
+    ///         );
+    ///     }));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The default implementation ignores the handler, for drivers which don't support it.
+    fn log_with(self, _handler: StatementLogHandler) -> Self {
+        self
+    }
+
+    /// Log the values bound to the parameters of prepared statements along with them, to see
+    /// exactly what a query was executed with while debugging it.
+    ///
+    /// The values are logged in the `db.parameters` field of the statements logged with
+    /// [`log_statements()`](Self::log_statements) and
+    /// [`log_slow_statements()`](Self::log_slow_statements), as formatted from their encoded
+    /// form: numbers and strings are written as such, and values of other types as their bytes
+    /// in hexadecimal. Disabled by default.
+    ///
+    /// **This is meant for development only**: the values bound to queries often include
+    /// passwords, tokens or personal data, which must not end up in logs. To avoid enabling it
+    /// in production by mistake, this has no effect in builds without debug assertions, such as
+    /// `--release` builds.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn example() -> sqlx::Result<()> {
+    /// use sqlx::ConnectOptions;
+    /// use sqlx::postgres::PgConnectOptions;
+    ///
+    /// // logs e.g. `SELECT * FROM users WHERE id = $1 ... db.parameters="[42]"`
+    /// let options = "postgres://localhost/mydb"
+    ///     .parse::<PgConnectOptions>()?
+    ///     .log_parameter_values(true);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The default implementation does nothing, for drivers which don't support it.
+    fn log_parameter_values(self, _enabled: bool) -> Self {
+        self
+    }
+
+    /// Rewrite the SQL of each statement with `rewriter` before it is executed, e.g. to add
+    /// a comment or a `LIMIT` to queries, or to rename their tables for sharding.
+    ///
+    /// The rewriter is called with the SQL of every statement executed, prepared or described
+    /// on the connection, including the ones executed by SQLx itself (e.g. to look up the
+    /// types used by a query), so it should leave the SQL it doesn't recognize as-is. It should
+    /// return quickly, as it is called on the task executing the statement.
+    ///
+    /// The statements are rewritten before they are looked up in the statement cache, so the cache
+    /// is keyed by the rewritten SQL: a statement rewritten differently is prepared again.
+    /// The rewritten SQL is also the one which is logged.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # fn example(shard: &'static str) -> sqlx::Result<()> {
+    /// use std::borrow::Cow;
+    /// use std::sync::Arc;
+    /// use sqlx::ConnectOptions;
+    /// use sqlx::postgres::PgConnectOptions;
+    ///
+    /// // `{shard}` in the SQL of the queries is replaced with the schema of the shard
+    /// let options = "postgres://localhost/mydb"
+    ///     .parse::<PgConnectOptions>()?
+    ///     .sql_rewriter(Arc::new(move |sql| {
+    ///         if sql.contains("{shard}") {
+    ///             Cow::Owned(sql.replace("{shard}", shard))
+    ///         } else {
+    ///             Cow::Borrowed(sql)
+    ///         }
+    ///     }));
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The default implementation ignores the rewriter, for drivers which don't support it.
+    fn sql_rewriter(self, _rewriter: SqlRewriter) -> Self {
+        self
+    }
+
+    /// Entirely disables statement logging (both slow and regular), and removes the handler set
+    /// with [`log_with()`](Self::log_with).
     fn disable_statement_logging(self) -> Self {
         self.log_statements(LevelFilter::Off)
             .log_slow_statements(LevelFilter::Off, Duration::default())
@@ -105,6 +105,12 @@ pub trait Database:
 
     /// The schemes for database URLs that should match this driver.
     const URL_SCHEMES: &'static [&'static str];
+
+    /// The character quoting identifiers in SQL, e.g. `"` for `"my table"`.
+    ///
+    /// The character is escaped inside of an identifier by doubling it. It is the standard
+    /// double quote by default.
+    const IDENTIFIER_QUOTE: char = '"';
 }
 
 /// Associate [`Database`] with a [`ValueRef`](crate::value::ValueRef) of a generic lifetime.
@@ -100,6 +100,30 @@ pub enum Error {
     #[error("attempted to acquire a connection on a closed pool")]
     PoolClosed,
 
+    /// A query executed on a [`Pool`] took longer than [`PoolOptions::statement_timeout`].
+    ///
+    /// The query was cancelled and its connection closed.
+    ///
+    /// [`Pool`]: crate::pool::Pool
+    /// [`PoolOptions::statement_timeout`]: crate::pool::PoolOptions::statement_timeout
+    #[error("query timed out and was cancelled")]
+    QueryTimedOut,
+
+    /// [`Pool::acquire`] failed fast, as the circuit breaker of the pool is open after too
+    /// many consecutive failures to connect; see [`CircuitBreakerConfig`].
+    ///
+    /// [`Pool::acquire`]: crate::pool::Pool::acquire
+    /// [`CircuitBreakerConfig`]: crate::pool::CircuitBreakerConfig
+    #[error("pool circuit breaker is open after repeated failures to connect")]
+    CircuitOpen,
+
+    /// Two rows had the same key when collecting the results of a query into a map with
+    /// [`OnDuplicateKey::Error`].
+    ///
+    /// [`OnDuplicateKey::Error`]: crate::query_as::OnDuplicateKey::Error
+    #[error("more than one row returned for the key {0}")]
+    DuplicateKey(String),
+
     /// A background worker has crashed.
     #[error("attempted to communicate with a crashed background worker")]
     WorkerCrashed,
@@ -6,6 +6,7 @@ use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::{future, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
+use std::borrow::Cow;
 use std::fmt::Debug;
 
 /// A type that contains or can provide a database
@@ -206,6 +207,62 @@ pub trait Execute<'q, DB: Database>: Send + Sized {
 
     /// Returns `true` if the statement should be cached.
     fn persistent(&self) -> bool;
+
+    /// Returns the names of the only columns to keep in the returned rows, if any.
+    ///
+    /// See [`Query::select_columns()`](crate::query::Query::select_columns).
+    #[inline]
+    fn selected_columns(&self) -> Option<&'q [&'q str]> {
+        None
+    }
+
+    /// Returns the comment to prepend to the SQL of the query, if any.
+    ///
+    /// See [`Query::with_comment()`](crate::query::Query::with_comment).
+    #[inline]
+    fn comment(&self) -> Option<&'q str> {
+        None
+    }
+
+    /// Returns a copy of the query to execute it again after a transient error, if it is safe
+    /// to retry it.
+    ///
+    /// See [`Query::retryable()`](crate::query::Query::retryable).
+    #[inline]
+    fn clone_for_retry(&self) -> Option<Self> {
+        None
+    }
+}
+
+/// Returns `sql` with `comment` prepended as a `/* .. */` SQL comment, see
+/// [`Query::with_comment()`](crate::query::Query::with_comment).
+#[doc(hidden)]
+pub fn sql_with_comment<'q>(sql: impl Into<Cow<'q, str>>, comment: Option<&str>) -> Cow<'q, str> {
+    let sql = sql.into();
+
+    let Some(comment) = comment else {
+        return sql;
+    };
+
+    let mut commented = String::with_capacity(comment.len() + sql.len() + 7);
+    commented.push_str("/* ");
+
+    let mut last = ' ';
+
+    for ch in comment.chars() {
+        // `/*` and `*/` are split, so that the comment can neither be nested nor ended
+        if (last == '/' && ch == '*') || (last == '*' && ch == '/') {
+            commented.push(' ');
+        }
+
+        commented.push(ch);
+        last = ch;
+    }
+
+    commented.push_str(" */ ");
+    commented.push_str(&sql);
+
+    Cow::Owned(commented)
 }
 
 // NOTE: `Execute` is explicitly not implemented for String and &String to make it slightly more
@@ -253,3 +310,20 @@ impl<'q, DB: Database> Execute<'q, DB> for (&'q str, Option<<DB as HasArguments<
         true
     }
 }
+
+#[test]
+fn test_sql_with_comment() {
+    assert_eq!(sql_with_comment("SELECT 1", None), "SELECT 1");
+    assert_eq!(
+        sql_with_comment("SELECT 1", Some("request_id=abc")),
+        "/* request_id=abc */ SELECT 1"
+    );
+    assert_eq!(
+        sql_with_comment("SELECT 1", Some("*/ DROP TABLE users; /*")),
+        "/* * / DROP TABLE users; / * */ SELECT 1"
+    );
+    assert_eq!(
+        sql_with_comment("SELECT 1", Some("a*/*/b")),
+        "/* a* / * /b */ SELECT 1"
+    );
+}
@@ -0,0 +1,315 @@
+use either::Either;
+use futures_util::TryStreamExt;
+
+use crate::arguments::IntoArguments;
+use crate::column::{Column, ColumnIndex};
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::export::{export_column, ExportDatabase, ExportValue, WRITE_BUFFER_SIZE};
+use crate::io::{AsyncWrite, AsyncWriteExt};
+use crate::query::Query;
+use crate::row::Row;
+use crate::statement::Statement;
+
+/// Options for [`Query::write_csv()`].
+///
+/// The defaults follow [RFC 4180](https://www.rfc-editor.org/rfc/rfc4180): fields are separated
+/// by commas and quoted with double quotes when needed, and the first line is a header with
+/// the column names. Lines end with `\n`.
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    delimiter: u8,
+    quote: u8,
+    null: String,
+    header: bool,
+    flush_every: usize,
+}
+
+impl CsvOptions {
+    /// Returns the default options.
+    pub fn new() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            null: String::new(),
+            header: true,
+            flush_every: 1000,
+        }
+    }
+
+    /// Set the character separating fields. Defaults to `,`.
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the character used to quote fields. Defaults to `"`.
+    ///
+    /// Fields are only quoted if they contain the delimiter, the quote character or a line break,
+    /// or if they could be mistaken for `NULL`. Quote characters within a field are doubled.
+    pub fn quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// Set the string written for `NULL` values. Defaults to an empty string.
+    ///
+    /// It is written as-is and never quoted, while a string value equal to it is always quoted.
+    /// With the default, this means `NULL` is written as an empty field and an empty string
+    /// as `""`, the same as Postgres' `COPY ... (FORMAT csv)`.
+    pub fn null(mut self, null: impl Into<String>) -> Self {
+        self.null = null.into();
+        self
+    }
+
+    /// If `true` (the default), the first line is a header with the column names.
+    pub fn header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Flush the writer after every `rows` rows. Defaults to `1000`.
+    ///
+    /// The writer is always flushed once all rows have been written. A value of `0` disables
+    /// periodic flushing.
+    pub fn flush_every(mut self, rows: usize) -> Self {
+        self.flush_every = rows;
+        self
+    }
+
+    fn write_record<'a>(&self, buf: &mut Vec<u8>, fields: impl IntoIterator<Item = &'a str>) {
+        for (i, field) in fields.into_iter().enumerate() {
+            if i > 0 {
+                buf.push(self.delimiter);
+            }
+
+            self.write_field(buf, field);
+        }
+
+        buf.push(b'\n');
+    }
+
+    fn write_value(&self, buf: &mut Vec<u8>, value: &ExportValue) {
+        let text = match value {
+            ExportValue::Null => {
+                buf.extend_from_slice(self.null.as_bytes());
+                return;
+            }
+            ExportValue::Text(v) => return self.write_field(buf, v),
+            ExportValue::Bool(v) => v.to_string(),
+            ExportValue::SmallInt(v) => v.to_string(),
+            ExportValue::Integer(v) => v.to_string(),
+            ExportValue::BigInt(v) => v.to_string(),
+            ExportValue::Real(v) => v.to_string(),
+            ExportValue::Double(v) => v.to_string(),
+            ExportValue::Blob(v) => hex::encode(v),
+        };
+
+        // Quoted if needed, in case `null` is set to e.g. `0`
+        self.write_field(buf, &text);
+    }
+
+    fn write_field(&self, buf: &mut Vec<u8>, field: &str) {
+        let needs_quotes = field == self.null
+            || field
+                .bytes()
+                .any(|b| b == self.delimiter || b == self.quote || b == b'\n' || b == b'\r');
+
+        if !needs_quotes {
+            buf.extend_from_slice(field.as_bytes());
+            return;
+        }
+
+        buf.push(self.quote);
+
+        for b in field.bytes() {
+            if b == self.quote {
+                buf.push(b);
+            }
+
+            buf.push(b);
+        }
+
+        buf.push(self.quote);
+    }
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'q, DB, A> Query<'q, DB, A>
+where
+    DB: ExportDatabase,
+    A: 'q + IntoArguments<'q, DB> + Send,
+{
+    /// Execute the query and write the results to `writer` as CSV, without collecting them first.
+    ///
+    /// Unless disabled with [`CsvOptions::header()`], the first line is a header with the
+    /// column names. As these are only known once the first row is received, the header is
+    /// not written for a query which returns no rows, unless it was built from a prepared
+    /// [`Statement`].
+    ///
+    /// Integers, floats, booleans and strings are written in their usual text representation,
+    /// and byte arrays as hexadecimal. Other types are not supported,
+    /// see [the module documentation][crate::export] for details.
+    ///
+    /// Returns the number of rows written, not counting the header.
+    ///
+    /// ### Note: Runtime Features
+    /// This method uses the `AsyncWrite` trait which is re-exported from either Tokio or
+    /// `async-std` depending on which runtime feature is used. If both are enabled,
+    /// the Tokio version takes precedence.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use sqlx::export::CsvOptions;
+    ///
+    /// let mut out = Vec::new();
+    ///
+    /// sqlx::query("SELECT id, name FROM users")
+    ///     .write_csv(conn, &mut out, CsvOptions::new().delimiter(b';'))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_csv<'e, 'c: 'e, E, W>(
+        self,
+        executor: E,
+        mut writer: W,
+        options: CsvOptions,
+    ) -> Result<u64, Error>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+        W: AsyncWrite + Unpin,
+        usize: ColumnIndex<DB::Row>,
+    {
+        let mut buf = Vec::with_capacity(WRITE_BUFFER_SIZE);
+        let mut header = options.header;
+
+        if header {
+            if let Either::Right(statement) = &self.statement {
+                options.write_record(&mut buf, statement.columns().iter().map(Column::name));
+                header = false;
+            }
+        }
+
+        let mut rows = executor.fetch(self);
+        let mut written = 0u64;
+        let mut unflushed = 0;
+
+        while let Some(row) = rows.try_next().await? {
+            if header {
+                options.write_record(&mut buf, row.columns().iter().map(Column::name));
+                header = false;
+            }
+
+            for index in 0..row.len() {
+                if index > 0 {
+                    buf.push(options.delimiter);
+                }
+
+                options.write_value(&mut buf, &export_column(&row, index)?);
+            }
+
+            buf.push(b'\n');
+            written += 1;
+            unflushed += 1;
+
+            if options.flush_every > 0 && unflushed >= options.flush_every {
+                writer.write_all(&buf).await?;
+                writer.flush().await?;
+                buf.clear();
+                unflushed = 0;
+            } else if buf.len() >= WRITE_BUFFER_SIZE {
+                writer.write_all(&buf).await?;
+                buf.clear();
+            }
+        }
+
+        writer.write_all(&buf).await?;
+        writer.flush().await?;
+
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CsvOptions;
+    use crate::export::ExportValue;
+
+    fn write(options: &CsvOptions, values: &[ExportValue]) -> String {
+        let mut buf = Vec::new();
+
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                buf.push(options.delimiter);
+            }
+
+            options.write_value(&mut buf, value);
+        }
+
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_write_values() {
+        let options = CsvOptions::new();
+
+        assert_eq!(
+            write(
+                &options,
+                &[
+                    ExportValue::BigInt(-1),
+                    ExportValue::Double(1.5),
+                    ExportValue::Bool(true),
+                    ExportValue::Text("foo".into()),
+                    ExportValue::Blob(vec![0xde, 0xad]),
+                ]
+            ),
+            "-1,1.5,true,foo,dead"
+        );
+
+        assert_eq!(
+            write(
+                &options,
+                &[
+                    ExportValue::Null,
+                    ExportValue::Text("".into()),
+                    ExportValue::Text("a,b".into()),
+                    ExportValue::Text("say \"hi\"".into()),
+                    ExportValue::Text("two\nlines".into()),
+                    ExportValue::Blob(vec![]),
+                ]
+            ),
+            ",\"\",\"a,b\",\"say \"\"hi\"\"\",\"two\nlines\",\"\""
+        );
+    }
+
+    #[test]
+    fn test_write_values_with_options() {
+        let options = CsvOptions::new().delimiter(b';').quote(b'\'').null("NULL");
+
+        assert_eq!(
+            write(
+                &options,
+                &[
+                    ExportValue::Null,
+                    ExportValue::Text("NULL".into()),
+                    ExportValue::Text("".into()),
+                    ExportValue::Text("a,b;c".into()),
+                    ExportValue::Text("it's".into()),
+                    ExportValue::BigInt(0),
+                ]
+            ),
+            "NULL;'NULL';;'a,b;c';'it''s';0"
+        );
+    }
+}
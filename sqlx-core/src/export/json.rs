@@ -0,0 +1,182 @@
+use futures_util::{FutureExt, TryStreamExt};
+use serde_json::Number as JsonNumber;
+
+use crate::arguments::IntoArguments;
+use crate::column::{Column, ColumnIndex};
+use crate::decode::Decode;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::export::{export_column, ExportDatabase, ExportValue, WRITE_BUFFER_SIZE};
+use crate::io::{AsyncWrite, AsyncWriteExt};
+use crate::query::Query;
+use crate::row::Row;
+use crate::types::{JsonValue, Type};
+use crate::value::ValueRef;
+
+impl<'q, DB, A> Query<'q, DB, A>
+where
+    DB: ExportDatabase,
+    A: 'q + IntoArguments<'q, DB> + Send,
+{
+    /// Execute the query and write the results to `writer` as
+    /// [newline-delimited JSON](https://github.com/ndjson/ndjson-spec), without collecting
+    /// them first.
+    ///
+    /// Each row is written as a JSON object on its own line, with the column names as keys
+    /// (in the order of the columns).
+    /// Integers, floats, booleans and strings are mapped to the corresponding JSON values,
+    /// byte arrays to hexadecimal strings, and `JSON` columns are embedded as-is. Floats which
+    /// cannot be represented in JSON (infinities and `NaN`) are mapped to `null`.
+    /// Other types are not supported, see [the module documentation][crate::export] for details.
+    ///
+    /// Rows are written as soon as they are received, and the writer is flushed whenever no more
+    /// rows are immediately available, so consumers don't have to wait for a large batch
+    /// to be completed.
+    ///
+    /// Returns the number of rows written.
+    ///
+    /// ### Note: Runtime Features
+    /// This method uses the `AsyncWrite` trait which is re-exported from either Tokio or
+    /// `async-std` depending on which runtime feature is used. If both are enabled,
+    /// the Tokio version takes precedence.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// let mut out = Vec::new();
+    ///
+    /// sqlx::query("SELECT id, name FROM users")
+    ///     .write_ndjson(conn, &mut out)
+    ///     .await?;
+    ///
+    /// // {"id":1,"name":"Alice"}
+    /// // {"id":2,"name":"Bob"}
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_ndjson<'e, 'c: 'e, E, W>(
+        self,
+        executor: E,
+        mut writer: W,
+    ) -> Result<u64, Error>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+        W: AsyncWrite + Unpin,
+        usize: ColumnIndex<DB::Row>,
+        JsonValue: Type<DB> + for<'r> Decode<'r, DB>,
+    {
+        let mut buf = Vec::with_capacity(WRITE_BUFFER_SIZE);
+        let mut rows = executor.fetch(self);
+        let mut written = 0u64;
+
+        loop {
+            let row = match rows.try_next().now_or_never() {
+                Some(row) => row?,
+                None => {
+                    // No row is ready yet, so send out the ones we have while waiting
+                    if !buf.is_empty() {
+                        writer.write_all(&buf).await?;
+                        writer.flush().await?;
+                        buf.clear();
+                    }
+
+                    rows.try_next().await?
+                }
+            };
+
+            let Some(row) = row else {
+                break;
+            };
+
+            write_row(&mut buf, &row)?;
+            buf.push(b'\n');
+            written += 1;
+
+            if buf.len() >= WRITE_BUFFER_SIZE {
+                writer.write_all(&buf).await?;
+                buf.clear();
+            }
+        }
+
+        writer.write_all(&buf).await?;
+        writer.flush().await?;
+
+        Ok(written)
+    }
+}
+
+/// Write a row as a JSON object, with the column names as keys in the order of the columns.
+fn write_row<R>(buf: &mut Vec<u8>, row: &R) -> Result<(), Error>
+where
+    R: Row,
+    R::Database: ExportDatabase,
+    usize: ColumnIndex<R>,
+    JsonValue: Type<R::Database> + for<'r> Decode<'r, R::Database>,
+{
+    buf.push(b'{');
+
+    for (index, column) in row.columns().iter().enumerate() {
+        if index > 0 {
+            buf.push(b',');
+        }
+
+        // Serializing strings and JSON values to a `Vec` cannot fail
+        let _ = serde_json::to_writer(&mut *buf, column.name());
+        buf.push(b':');
+        let _ = serde_json::to_writer(&mut *buf, &column_to_json(row, index)?);
+    }
+
+    buf.push(b'}');
+
+    Ok(())
+}
+
+/// Map the value of a column of the row to JSON.
+fn column_to_json<R>(row: &R, index: usize) -> Result<JsonValue, Error>
+where
+    R: Row,
+    R::Database: ExportDatabase,
+    usize: ColumnIndex<R>,
+    JsonValue: Type<R::Database> + for<'r> Decode<'r, R::Database>,
+{
+    let value = row.try_get_raw(index)?;
+
+    // Some databases can also decode strings and byte arrays as JSON, which we don't want here
+    let is_json = {
+        let ty = value.type_info();
+
+        !value.is_null()
+            && !<R::Database as ExportDatabase>::is_exportable(&ty)
+            && <JsonValue as Type<R::Database>>::compatible(&ty)
+    };
+
+    if is_json {
+        return <JsonValue as Decode<R::Database>>::decode(value).map_err(|source| {
+            Error::ColumnDecode {
+                index: format!("{index:?}"),
+                source,
+            }
+        });
+    }
+
+    Ok(match export_column(row, index)? {
+        ExportValue::Null => JsonValue::Null,
+        ExportValue::Bool(v) => v.into(),
+        ExportValue::SmallInt(v) => v.into(),
+        ExportValue::Integer(v) => v.into(),
+        ExportValue::BigInt(v) => v.into(),
+        // Going through the shortest representation of the `f32` avoids exposing the
+        // imprecision of the conversion to `f64`, e.g. `0.1` would be `0.10000000149011612`
+        ExportValue::Real(v) => float_to_json(v.to_string().parse().unwrap_or(f64::NAN)),
+        ExportValue::Double(v) => float_to_json(v),
+        ExportValue::Text(v) => v.into(),
+        ExportValue::Blob(v) => hex::encode(v).into(),
+    })
+}
+
+fn float_to_json(v: f64) -> JsonValue {
+    JsonNumber::from_f64(v).map_or(JsonValue::Null, JsonValue::Number)
+}
@@ -0,0 +1,131 @@
+//! Streaming export of query results, with [`Query::write_csv()`][crate::query::Query::write_csv]
+//! or [`Query::write_ndjson()`][crate::query::Query::write_ndjson] (requires the `json` feature).
+//!
+//! Exported values are decoded generically, by checking the type of each column against a small
+//! set of Rust types supported by every database: integers, floats, booleans, strings and byte
+//! arrays. Columns of any other type (e.g. dates or decimals) cannot be exported directly and
+//! should be cast to text in the query instead, e.g. `SELECT created_at::text FROM users`.
+
+use crate::database::{Database, HasValueRef};
+use crate::decode::Decode;
+use crate::error::{BoxDynError, Error};
+use crate::row::Row;
+use crate::type_info::TypeInfo;
+use crate::types::Type;
+use crate::value::ValueRef;
+
+mod csv;
+
+#[cfg(feature = "json")]
+mod json;
+
+pub use self::csv::CsvOptions;
+
+// Rows are buffered and written once the buffer reaches this size
+pub(crate) const WRITE_BUFFER_SIZE: usize = 8 * 1024;
+
+/// A database whose values can be exported.
+///
+/// This is implemented for every database supporting the basic Rust types
+/// `bool`, `i16`, `i32`, `i64`, `f32`, `f64`, `String` and `Vec<u8>`.
+pub trait ExportDatabase: Database {
+    #[doc(hidden)]
+    fn is_exportable(ty: &Self::TypeInfo) -> bool;
+
+    #[doc(hidden)]
+    fn export_value(value: <Self as HasValueRef<'_>>::ValueRef)
+        -> Result<ExportValue, BoxDynError>;
+}
+
+/// A value decoded for export.
+#[doc(hidden)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportValue {
+    Null,
+    Bool(bool),
+    SmallInt(i16),
+    Integer(i32),
+    BigInt(i64),
+    Real(f32),
+    Double(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+impl<DB> ExportDatabase for DB
+where
+    DB: Database,
+    bool: Type<DB> + for<'r> Decode<'r, DB>,
+    i16: Type<DB> + for<'r> Decode<'r, DB>,
+    i32: Type<DB> + for<'r> Decode<'r, DB>,
+    i64: Type<DB> + for<'r> Decode<'r, DB>,
+    f32: Type<DB> + for<'r> Decode<'r, DB>,
+    f64: Type<DB> + for<'r> Decode<'r, DB>,
+    String: Type<DB> + for<'r> Decode<'r, DB>,
+    Vec<u8>: Type<DB> + for<'r> Decode<'r, DB>,
+{
+    fn is_exportable(ty: &DB::TypeInfo) -> bool {
+        <i64 as Type<DB>>::compatible(ty)
+            || <i32 as Type<DB>>::compatible(ty)
+            || <i16 as Type<DB>>::compatible(ty)
+            || <bool as Type<DB>>::compatible(ty)
+            || <f64 as Type<DB>>::compatible(ty)
+            || <f32 as Type<DB>>::compatible(ty)
+            || <String as Type<DB>>::compatible(ty)
+            || <Vec<u8> as Type<DB>>::compatible(ty)
+    }
+
+    fn export_value(
+        value: <Self as HasValueRef<'_>>::ValueRef,
+    ) -> Result<ExportValue, BoxDynError> {
+        if value.is_null() {
+            return Ok(ExportValue::Null);
+        }
+
+        let ty = value.type_info().into_owned();
+
+        macro_rules! decode_as {
+            ($($ty:ty => $variant:ident),* $(,)?) => {
+                $(
+                    if <$ty as Type<DB>>::compatible(&ty) {
+                        return Ok(ExportValue::$variant(<$ty as Decode<DB>>::decode(value)?));
+                    }
+                )*
+            };
+        }
+
+        // Wider types are checked first, as some databases accept any integer (or float) for
+        // the narrower ones. Similarly, integers are checked before `bool`.
+        decode_as!(
+            i64 => BigInt,
+            i32 => Integer,
+            i16 => SmallInt,
+            bool => Bool,
+            f64 => Double,
+            f32 => Real,
+            String => Text,
+            Vec<u8> => Blob,
+        );
+
+        Err(format!(
+            "cannot export a value of type {}; try casting it to text in the query",
+            ty.name()
+        )
+        .into())
+    }
+}
+
+/// Decode the value of a column of the row for export.
+pub(crate) fn export_column<R>(row: &R, index: usize) -> Result<ExportValue, Error>
+where
+    R: Row,
+    R::Database: ExportDatabase,
+    usize: crate::column::ColumnIndex<R>,
+{
+    let value = row.try_get_raw(index)?;
+
+    <R::Database as ExportDatabase>::export_value(value).map_err(|source| Error::ColumnDecode {
+        index: format!("{index:?}"),
+        source,
+    })
+}
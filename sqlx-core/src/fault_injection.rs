@@ -0,0 +1,238 @@
+//! Deterministic fault injection for testing error handling (requires the `fault-injection`
+//! feature).
+//!
+//! Connection failures are hard to trigger reliably in tests, which leaves retry and recovery
+//! code untested. A [`FaultInjector`] makes the queries of the code under test fail in a
+//! scripted way instead.
+
+use std::collections::VecDeque;
+use std::fmt::{self, Debug, Formatter};
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::{future, stream, StreamExt};
+
+use crate::database::Database;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::intercept::{InterceptedExecutor, Interceptor};
+use crate::sync::lock_poisoned;
+
+/// A script of faults to inject in the queries executed through the executors it wraps.
+///
+/// Queries are executed through [`wrap()`][Self::wrap], which wraps any [`Executor`], e.g.
+/// `faults.wrap(&mut conn)`, `faults.wrap(&mut *tx)` or `faults.wrap(&pool)`. Faults are
+/// scripted in advance and apply to the next queries executed through any executor wrapped by
+/// this injector (or one of its clones), in order:
+///
+/// * [`fail_next_with()`][Self::fail_next_with] makes the next query fail with the given error,
+///   without executing it;
+/// * [`drop_connection_after()`][Self::drop_connection_after] lets a number of queries succeed,
+///   and then drops the connection while the next one is executed: the query is run to
+///   completion, but its results are lost and it fails with an I/O error of kind
+///   [`ConnectionReset`][io::ErrorKind::ConnectionReset], as if the connection was lost before
+///   the response arrived.
+///
+/// A dropped connection stays unusable, with every following query failing the same way,
+/// until the injector is [cleared][Self::clear] (e.g. to simulate reconnecting). The wrapped
+/// executor itself is left untouched.
+///
+/// Queries are all the calls to [`Executor::fetch_many()`] and [`Executor::fetch_optional()`],
+/// which every query method goes through. Preparing or describing statements is never faulted,
+/// but fails as well once the connection has been dropped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example() -> sqlx::Result<()> {
+/// use sqlx::fault_injection::FaultInjector;
+/// use sqlx::{Connection, PgConnection};
+///
+/// let mut conn = PgConnection::connect("postgres:// …").await?;
+/// let faults = FaultInjector::new();
+///
+/// faults.fail_next_with(sqlx::Error::PoolTimedOut);
+///
+/// let res = sqlx::query("SELECT 1").execute(faults.wrap(&mut conn)).await;
+/// assert!(matches!(res, Err(sqlx::Error::PoolTimedOut)));
+///
+/// // the next query succeeds, and then the connection is lost during the second one
+/// faults.drop_connection_after(1);
+///
+/// sqlx::query("SELECT 1").execute(faults.wrap(&mut conn)).await?;
+/// assert!(sqlx::query("SELECT 1").execute(faults.wrap(&mut conn)).await.is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct FaultInjector {
+    faults: Arc<Mutex<Faults>>,
+}
+
+#[derive(Default)]
+struct Faults {
+    // errors to return from the next queries, in order
+    errors: VecDeque<Error>,
+    // number of queries to let through before dropping the connection
+    drop_after: Option<u64>,
+    // whether the connection was dropped
+    dropped: bool,
+}
+
+enum Fault {
+    Fail(Error),
+    Drop,
+}
+
+impl FaultInjector {
+    /// Create an injector without any fault scripted.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wrap an executor, so the queries executed through it are subject to the scripted faults.
+    pub fn wrap<E>(&self, executor: E) -> FaultyExecutor<E> {
+        InterceptedExecutor::new(executor, self.clone())
+    }
+
+    /// Make the next query fail with `error`, without executing it.
+    ///
+    /// Calling this again queues more errors, which are returned by the following queries
+    /// in the same order.
+    pub fn fail_next_with(&self, error: Error) -> &Self {
+        self.faults().errors.push_back(error);
+        self
+    }
+
+    /// Let `queries` queries succeed, then drop the connection during the next one.
+    ///
+    /// Queries failed by [`fail_next_with()`][Self::fail_next_with] are not counted. Calling this
+    /// again replaces the previous count, if the connection wasn't dropped yet.
+    pub fn drop_connection_after(&self, queries: u64) -> &Self {
+        self.faults().drop_after = Some(queries);
+        self
+    }
+
+    /// Remove all the scripted faults, and restore the connection if it was dropped.
+    pub fn clear(&self) {
+        *self.faults() = Faults::default();
+    }
+
+    /// Returns `true` if the connection was dropped.
+    pub fn is_dropped(&self) -> bool {
+        self.faults().dropped
+    }
+
+    fn faults(&self) -> MutexGuard<'_, Faults> {
+        lock_poisoned(&self.faults)
+    }
+
+    // Returns the fault to inject in the next query, if any
+    fn next_fault(&self) -> Option<Fault> {
+        let mut faults = self.faults();
+
+        if faults.dropped {
+            return Some(Fault::Fail(connection_dropped()));
+        }
+
+        if let Some(error) = faults.errors.pop_front() {
+            return Some(Fault::Fail(error));
+        }
+
+        match &mut faults.drop_after {
+            Some(0) => {
+                faults.drop_after = None;
+                faults.dropped = true;
+                Some(Fault::Drop)
+            }
+            Some(n) => {
+                *n -= 1;
+                None
+            }
+            None => None,
+        }
+    }
+}
+
+impl Debug for FaultInjector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let faults = self.faults();
+
+        f.debug_struct("FaultInjector")
+            .field("errors", &faults.errors)
+            .field("drop_after", &faults.drop_after)
+            .field("dropped", &faults.dropped)
+            .finish()
+    }
+}
+
+fn connection_dropped() -> Error {
+    Error::Io(io::Error::new(
+        io::ErrorKind::ConnectionReset,
+        "connection dropped by `FaultInjector`",
+    ))
+}
+
+/// An executor wrapped by [`FaultInjector::wrap()`].
+pub type FaultyExecutor<E> = InterceptedExecutor<E, FaultInjector>;
+
+impl<DB: Database> Interceptor<DB> for FaultInjector {
+    fn fetch_many<'c, 'e, 'q: 'e, E, Q>(
+        &self,
+        executor: E,
+        query: Q,
+    ) -> BoxStream<'e, Result<Either<DB::QueryResult, DB::Row>, Error>>
+    where
+        'c: 'e,
+        E: Executor<'c, Database = DB>,
+        Q: 'q + Execute<'q, DB>,
+    {
+        match self.next_fault() {
+            None => executor.fetch_many(query),
+            Some(Fault::Fail(error)) => Box::pin(stream::once(future::ready(Err(error)))),
+            Some(Fault::Drop) => {
+                let results = executor.fetch_many(query);
+
+                Box::pin(stream::once(async move {
+                    results.for_each(|_| future::ready(())).await;
+                    Err(connection_dropped())
+                }))
+            }
+        }
+    }
+
+    fn fetch_optional<'c, 'e, 'q: 'e, E, Q>(
+        &self,
+        executor: E,
+        query: Q,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>
+    where
+        'c: 'e,
+        E: Executor<'c, Database = DB>,
+        Q: 'q + Execute<'q, DB>,
+    {
+        match self.next_fault() {
+            None => executor.fetch_optional(query),
+            Some(Fault::Fail(error)) => Box::pin(future::ready(Err(error))),
+            Some(Fault::Drop) => {
+                let result = executor.fetch_optional(query);
+
+                Box::pin(async move {
+                    let _ = result.await;
+                    Err(connection_dropped())
+                })
+            }
+        }
+    }
+
+    fn check(&self) -> Result<(), Error> {
+        if self.faults().dropped {
+            return Err(connection_dropped());
+        }
+
+        Ok(())
+    }
+}
@@ -1,3 +1,7 @@
+use std::any::type_name;
+
+use crate::column::ColumnIndex;
+use crate::value::ValueRef;
 use crate::{error::Error, row::Row};
 
 /// A record that can be built from a row returned by the database.
@@ -142,6 +146,31 @@ use crate::{error::Error, row::Row};
 ///
 /// This field is compatible with the `default` attribute.
 ///
+/// A flattened field can also be an `Option`, e.g. for the columns of a `LEFT JOIN`, which are
+/// all `NULL` if there is no matching row. The field is then `None` if all the columns of the
+/// nested struct are `NULL`, and `Some` otherwise, which requires it to derive `FromRow` as well:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// struct User {
+///     id: i32,
+///     name: String,
+///     #[sqlx(flatten)]
+///     address: Option<Address>,
+/// }
+/// ```
+///
+/// Given a query such as:
+///
+/// ```sql
+/// SELECT users.id, name, country, city, road FROM users LEFT JOIN addresses ON ..;
+/// ```
+///
+/// As some columns of the nested struct may be `NULL` even if it is present, the field can
+/// instead be `None` only if a given column is `NULL`, usually its primary key, with
+/// `#[sqlx(flatten, skip_if_null = "address_id")]`. This is also required if the nested struct
+/// implements `FromRow` by hand.
+///
 /// #### `skip`
 ///
 /// This is a variant of the `default` attribute which instead always takes the value from
@@ -229,6 +258,37 @@ use crate::{error::Error, row::Row};
 ///
 /// In MySql, `BigInt` type matches `i64`, but you can convert it to `u64` by `try_from`.
 ///
+/// #### `from_columns`
+///
+/// A field can be decoded from several columns with `#[sqlx(from_columns("a", "b", ..))]`. The
+/// columns are decoded into a tuple, in order: either the type of the field, or the type given
+/// with `try_from`, which is then converted to the field type. This is useful for values stored
+/// in multiple columns, e.g. an amount of money and its currency:
+///
+/// ```rust,ignore
+/// struct Money {
+///     amount: Decimal,
+///     currency: String,
+/// }
+///
+/// impl From<(Decimal, String)> for Money {
+///     fn from((amount, currency): (Decimal, String)) -> Self {
+///         Money { amount, currency }
+///     }
+/// }
+///
+/// #[derive(sqlx::FromRow)]
+/// struct Product {
+///     id: i32,
+///     #[sqlx(from_columns("price", "currency"), try_from = "(Decimal, String)")]
+///     price: Money,
+///     #[sqlx(from_columns("width", "height"))]
+///     size: (i32, i32),
+/// }
+/// ```
+///
+/// The column names are used as-is, without [`rename_all`](#rename_all).
+///
 /// #### `json`
 ///
 /// If your database supports a JSON type, you can leverage `#[sqlx(json)]`
@@ -273,6 +333,66 @@ use crate::{error::Error, row::Row};
 /// ```
 pub trait FromRow<'r, R: Row>: Sized {
     fn from_row(row: &'r R) -> Result<Self, Error>;
+
+    /// Returns the names of the columns `Self` is built from, if known.
+    ///
+    /// This is implemented by `#[derive(FromRow)]`, to tell whether a flattened `Option<Self>`
+    /// field is `None`, i.e. whether all these columns are `NULL`.
+    #[doc(hidden)]
+    fn column_names() -> Option<Vec<&'static str>> {
+        None
+    }
+}
+
+/// Builds a flattened `Option<T>` field of a `#[derive(FromRow)]`, which is `None` if the
+/// `skip_if_null` column is `NULL`, or otherwise if all the columns of `T` are `NULL`.
+#[doc(hidden)]
+pub fn from_row_flatten_option<'r, R, T>(
+    row: &'r R,
+    skip_if_null: Option<&'r str>,
+) -> Result<Option<T>, Error>
+where
+    R: Row,
+    T: FromRow<'r, R>,
+    &'r str: ColumnIndex<R>,
+{
+    if let Some(column) = skip_if_null {
+        if row.try_get_raw(column)?.is_null() {
+            return Ok(None);
+        }
+
+        return T::from_row(row).map(Some);
+    }
+
+    let columns = T::column_names().ok_or_else(|| {
+        Error::Decode(
+            format!(
+                "cannot tell whether the flattened `{}` is NULL without `skip_if_null`, \
+                 as its columns are not known",
+                type_name::<T>()
+            )
+            .into(),
+        )
+    })?;
+
+    let mut found = false;
+
+    for column in columns {
+        match row.try_get_raw(column) {
+            Ok(value) if !value.is_null() => return T::from_row(row).map(Some),
+            Ok(_) => found = true,
+            // allowed for fields with `#[sqlx(default)]`
+            Err(Error::ColumnNotFound(_)) => {}
+            Err(error) => return Err(error),
+        }
+    }
+
+    if found {
+        Ok(None)
+    } else {
+        // none of the columns are in the row, which `T` may tell about
+        T::from_row(row).map(Some)
+    }
 }
 
 impl<'r, R> FromRow<'r, R> for ()
@@ -0,0 +1,124 @@
+//! The executor wrapper shared by [`fault_injection`][crate::fault_injection] and
+//! [`recording`][crate::recording].
+
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::future;
+
+use crate::database::{Database, HasStatement};
+use crate::describe::Describe;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+
+/// Intercepts the queries executed through an [`InterceptedExecutor`].
+///
+/// Preparing and describing statements is passed through to the wrapped executor once
+/// [`check()`][Self::check] succeeded.
+pub trait Interceptor<DB: Database>: Clone + std::fmt::Debug + Send {
+    /// Execute `query` through `executor`, see [`Executor::fetch_many()`].
+    fn fetch_many<'c, 'e, 'q: 'e, E, Q>(
+        &self,
+        executor: E,
+        query: Q,
+    ) -> BoxStream<'e, Result<Either<DB::QueryResult, DB::Row>, Error>>
+    where
+        'c: 'e,
+        E: Executor<'c, Database = DB>,
+        Q: 'q + Execute<'q, DB>;
+
+    /// Execute `query` through `executor`, see [`Executor::fetch_optional()`].
+    fn fetch_optional<'c, 'e, 'q: 'e, E, Q>(
+        &self,
+        executor: E,
+        query: Q,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>
+    where
+        'c: 'e,
+        E: Executor<'c, Database = DB>,
+        Q: 'q + Execute<'q, DB>;
+
+    /// Fail a statement before it is prepared or described.
+    fn check(&self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// An executor whose queries go through an [`Interceptor`].
+#[derive(Debug)]
+pub struct InterceptedExecutor<E, I> {
+    inner: E,
+    interceptor: I,
+}
+
+impl<E, I> InterceptedExecutor<E, I> {
+    pub(crate) fn new(inner: E, interceptor: I) -> Self {
+        Self { inner, interceptor }
+    }
+}
+
+impl<'c, E, I> Executor<'c> for InterceptedExecutor<E, I>
+where
+    E: Executor<'c>,
+    I: Interceptor<E::Database>,
+{
+    type Database = E::Database;
+
+    fn fetch_many<'e, 'q: 'e, Q>(
+        self,
+        query: Q,
+    ) -> BoxStream<
+        'e,
+        Result<
+            Either<<Self::Database as Database>::QueryResult, <Self::Database as Database>::Row>,
+            Error,
+        >,
+    >
+    where
+        'c: 'e,
+        Q: 'q + Execute<'q, Self::Database>,
+    {
+        self.interceptor.fetch_many(self.inner, query)
+    }
+
+    fn fetch_optional<'e, 'q: 'e, Q>(
+        self,
+        query: Q,
+    ) -> BoxFuture<'e, Result<Option<<Self::Database as Database>::Row>, Error>>
+    where
+        'c: 'e,
+        Q: 'q + Execute<'q, Self::Database>,
+    {
+        self.interceptor.fetch_optional(self.inner, query)
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [<Self::Database as Database>::TypeInfo],
+    ) -> BoxFuture<'e, Result<<Self::Database as HasStatement<'q>>::Statement, Error>>
+    where
+        'c: 'e,
+    {
+        if let Err(e) = self.interceptor.check() {
+            return Box::pin(future::ready(Err(e)));
+        }
+
+        self.inner.prepare_with(sql, parameters)
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Describe<Self::Database>, Error>>
+    where
+        'c: 'e,
+    {
+        if let Err(e) = self.interceptor.check() {
+            return Box::pin(future::ready(Err(e)));
+        }
+
+        self.inner.describe(sql)
+    }
+}
@@ -62,10 +62,12 @@ pub mod column;
 #[macro_use]
 pub mod statement;
 
+pub mod batch;
 pub mod common;
 pub mod database;
 pub mod describe;
 pub mod executor;
+pub mod export;
 pub mod from_row;
 pub mod fs;
 pub mod io;
@@ -74,6 +76,7 @@ pub mod net;
 pub mod query_as;
 pub mod query_builder;
 pub mod query_scalar;
+pub mod reconnect;
 pub mod row;
 pub mod rt;
 pub mod sync;
@@ -86,6 +89,15 @@ pub mod migrate;
 #[cfg(feature = "any")]
 pub mod any;
 
+#[cfg(feature = "fault-injection")]
+pub mod fault_injection;
+
+#[cfg(feature = "recording")]
+pub mod recording;
+
+#[cfg(any(feature = "fault-injection", feature = "recording"))]
+mod intercept;
+
 // Implements test support with automatic DB management.
 #[cfg(feature = "migrate")]
 pub mod testing;
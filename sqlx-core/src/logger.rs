@@ -1,4 +1,5 @@
-use crate::connection::LogSettings;
+use crate::connection::{LogSettings, StatementLogContext};
+use log::LevelFilter;
 use std::time::Instant;
 
 // Yes these look silly. `tracing` doesn't currently support dynamic levels
@@ -62,6 +63,7 @@ pub struct QueryLogger<'q> {
     rows_affected: u64,
     start: Instant,
     settings: LogSettings,
+    parameters: Option<String>,
 }
 
 impl<'q> QueryLogger<'q> {
@@ -72,6 +74,15 @@ impl<'q> QueryLogger<'q> {
             rows_affected: 0,
             start: Instant::now(),
             settings,
+            parameters: None,
+        }
+    }
+
+    /// Log the values of the parameters of the statement, formatted with `format`, if enabled
+    /// with [`ConnectOptions::log_parameter_values()`](crate::connection::ConnectOptions::log_parameter_values).
+    pub fn log_parameter_values(&mut self, format: impl FnOnce() -> String) {
+        if self.settings.parameter_values {
+            self.parameters = Some(format());
         }
     }
 
@@ -88,6 +99,17 @@ impl<'q> QueryLogger<'q> {
 
         let was_slow = elapsed >= self.settings.slow_statements_duration;
 
+        if let Some(handler) = &self.settings.statement_handler {
+            handler(&StatementLogContext {
+                sql: self.sql,
+                elapsed,
+                rows_affected: self.rows_affected,
+                rows_returned: self.rows_returned,
+                // the duration of a disabled slow statement log is meaningless
+                was_slow: was_slow && self.settings.slow_statements_level != LevelFilter::Off,
+            });
+        }
+
         let lvl = if was_slow {
             self.settings.slow_statements_level
         } else {
@@ -122,6 +144,7 @@ impl<'q> QueryLogger<'q> {
                         tracing_level,
                         summary,
                         db.statement = sql,
+                        db.parameters = self.parameters.as_deref(),
                         rows_affected = self.rows_affected,
                         rows_returned = self.rows_returned,
                         ?elapsed,
@@ -137,6 +160,7 @@ impl<'q> QueryLogger<'q> {
                         tracing_level,
                         summary,
                         db.statement = sql,
+                        db.parameters = self.parameters.as_deref(),
                         rows_affected = self.rows_affected,
                         rows_returned = self.rows_returned,
                         ?elapsed,
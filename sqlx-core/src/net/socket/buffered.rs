@@ -49,6 +49,11 @@ impl<S: Socket> BufferedSocket<S> {
         self.read_buf.read(len, &mut self.socket).await
     }
 
+    /// Data that has been read from the socket but not yet consumed.
+    pub fn read_buffer(&self) -> &[u8] {
+        &self.read_buf.read
+    }
+
     pub fn write_buffer(&self) -> &WriteBuffer {
         &self.write_buf
     }
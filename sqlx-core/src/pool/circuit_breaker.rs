@@ -0,0 +1,231 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+use crate::sync::lock_poisoned;
+
+/// The configuration of a circuit breaker making a [`Pool`][super::Pool] fail fast while the
+/// database can't be connected to, e.g. during an outage.
+///
+/// Set with [`PoolOptions::circuit_breaker()`][super::PoolOptions::circuit_breaker].
+///
+/// After [`failure_threshold`][Self::failure_threshold] consecutive attempts to open a new
+/// connection failed, the circuit is _open_: for the [`cooldown`][Self::cooldown] period,
+/// acquiring a connection fails right away with [`Error::CircuitOpen`], instead of waiting for
+/// connections which would fail or time out too, and piling up the tasks waiting for them.
+///
+/// Once the cooldown period has elapsed, the circuit is _half-open_: the next acquisition which
+/// needs a new connection is let through as a probe, while the others keep failing fast. If the
+/// probe connects, the circuit is _closed_ again and the pool works as usual; otherwise it opens
+/// again for another cooldown period.
+///
+/// Idle connections are still handed out while the circuit is closed or half-open, but not while
+/// it is open.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example() -> sqlx::Result<()> {
+/// use std::time::Duration;
+/// use sqlx::pool::CircuitBreakerConfig;
+/// use sqlx::postgres::PgPoolOptions;
+///
+/// let pool = PgPoolOptions::new()
+///     .circuit_breaker(
+///         CircuitBreakerConfig::new()
+///             .failure_threshold(3)
+///             .cooldown(Duration::from_secs(10)),
+///     )
+///     .connect_lazy("postgres:// …")?;
+///
+/// match pool.acquire().await {
+///     Ok(conn) => { /* .. */ }
+///     // the database is down, shed the load
+///     Err(sqlx::Error::CircuitOpen) => { /* .. */ }
+///     Err(e) => return Err(e),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    failure_threshold: u32,
+    cooldown: Duration,
+}
+
+impl CircuitBreakerConfig {
+    /// Returns a configuration opening the circuit after 5 consecutive failures to connect, for
+    /// 30 seconds.
+    pub fn new() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+
+    /// Set the number of consecutive failures to connect after which the circuit opens.
+    ///
+    /// Each failed attempt counts, including the ones retried by the pool until the acquire
+    /// timeout when the connection is refused. A value of `0` is treated as `1`.
+    pub fn failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = failure_threshold;
+        self
+    }
+
+    /// Get the number of consecutive failures to connect after which the circuit opens.
+    pub fn get_failure_threshold(&self) -> u32 {
+        self.failure_threshold
+    }
+
+    /// Set how long the circuit stays open before a probe connection is attempted.
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Get how long the circuit stays open before a probe connection is attempted.
+    pub fn get_cooldown(&self) -> Duration {
+        self.cooldown
+    }
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    // a probe connection has been attempted at `since`
+    HalfOpen { since: Instant },
+}
+
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<State>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(State::Closed { failures: 0 }),
+        }
+    }
+
+    /// Fails with [`Error::CircuitOpen`] if the circuit is open and its cooldown period hasn't
+    /// elapsed, before acquiring a connection.
+    pub(crate) fn check(&self, now: Instant) -> Result<(), Error> {
+        match *self.state() {
+            State::Open { until } if now < until => Err(Error::CircuitOpen),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fails with [`Error::CircuitOpen`] if a new connection can't be attempted now, or
+    /// lets it through, as the probe if the cooldown period just elapsed.
+    pub(crate) fn before_connect(&self, now: Instant) -> Result<(), Error> {
+        let mut state = self.state();
+
+        match *state {
+            State::Closed { .. } => Ok(()),
+            State::Open { until } if now < until => Err(Error::CircuitOpen),
+            // a probe which didn't finish within a cooldown period, e.g. because it was
+            // cancelled, is replaced by a new one
+            State::HalfOpen { since } if now < since + self.config.cooldown => {
+                Err(Error::CircuitOpen)
+            }
+            State::Open { .. } | State::HalfOpen { .. } => {
+                *state = State::HalfOpen { since: now };
+                Ok(())
+            }
+        }
+    }
+
+    pub(crate) fn on_connect_success(&self) {
+        *self.state() = State::Closed { failures: 0 };
+    }
+
+    pub(crate) fn on_connect_failure(&self, now: Instant) {
+        let mut state = self.state();
+
+        *state = match *state {
+            State::Closed { failures } if failures + 1 < self.config.failure_threshold => {
+                State::Closed {
+                    failures: failures + 1,
+                }
+            }
+            State::Open { until } => State::Open { until },
+            State::Closed { .. } | State::HalfOpen { .. } => {
+                tracing::warn!(
+                    cooldown = ?self.config.cooldown,
+                    "opening the circuit breaker of the pool after failing to connect"
+                );
+
+                State::Open {
+                    until: now + self.config.cooldown,
+                }
+            }
+        };
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        lock_poisoned(&self.state)
+    }
+}
+
+#[test]
+fn test_circuit_breaker() {
+    let breaker = CircuitBreaker::new(
+        CircuitBreakerConfig::new()
+            .failure_threshold(2)
+            .cooldown(Duration::from_secs(10)),
+    );
+    let start = Instant::now();
+    let later = |secs| start + Duration::from_secs(secs);
+
+    // a success resets the count of consecutive failures
+    breaker.on_connect_failure(start);
+    breaker.on_connect_success();
+    breaker.on_connect_failure(start);
+    assert!(breaker.check(start).is_ok());
+    assert!(breaker.before_connect(start).is_ok());
+
+    breaker.on_connect_failure(start);
+    assert!(matches!(breaker.check(later(5)), Err(Error::CircuitOpen)));
+    assert!(matches!(
+        breaker.before_connect(later(5)),
+        Err(Error::CircuitOpen)
+    ));
+
+    // only one probe is let through once the cooldown period elapsed
+    assert!(breaker.check(later(10)).is_ok());
+    assert!(breaker.before_connect(later(10)).is_ok());
+    assert!(matches!(
+        breaker.before_connect(later(11)),
+        Err(Error::CircuitOpen)
+    ));
+
+    // a failed probe opens the circuit again
+    breaker.on_connect_failure(later(12));
+    assert!(matches!(breaker.check(later(21)), Err(Error::CircuitOpen)));
+
+    // a successful probe closes it
+    assert!(breaker.before_connect(later(22)).is_ok());
+    breaker.on_connect_success();
+    assert!(breaker.before_connect(later(22)).is_ok());
+
+    // a probe which doesn't finish is replaced after a cooldown period
+    breaker.on_connect_failure(later(30));
+    breaker.on_connect_failure(later(30));
+    assert!(breaker.before_connect(later(40)).is_ok());
+    assert!(matches!(
+        breaker.before_connect(later(45)),
+        Err(Error::CircuitOpen)
+    ));
+    assert!(breaker.before_connect(later(50)).is_ok());
+}
@@ -272,12 +272,31 @@ impl<DB: Database> Floating<DB, Live<DB>> {
 
             // Connection is broken, don't try to gracefully close.
             self.close_hard().await;
-            false
-        } else {
-            // if the connection is still viable, release it to the pool
-            self.release();
-            true
+            return false;
+        }
+
+        // a transaction left open, e.g. by a plain `BEGIN`, must not leak to the next user
+        match self.raw.rollback_leaked_transaction().await {
+            Ok(false) => (),
+            Ok(true) => {
+                tracing::warn!(
+                    "rolled back a transaction left open on a connection released to the pool"
+                );
+            }
+            Err(error) => {
+                tracing::warn!(
+                    %error,
+                    "error occurred while rolling back a transaction left open on-release",
+                );
+
+                self.close_hard().await;
+                return false;
+            }
         }
+
+        // if the connection is still viable, release it to the pool
+        self.release();
+        true
     }
 
     pub async fn close(self) {
@@ -346,6 +365,18 @@ impl<DB: Database> Floating<DB, Idle<DB>> {
         self.guard
     }
 
+    /// Close a connection taken out of the idle queue without a permit for it, which only
+    /// decrements the pool size.
+    pub async fn close_without_permit(idle: Idle<DB>, pool: Arc<PoolInner<DB>>) {
+        Floating {
+            inner: idle,
+            guard: DecrementSizeGuard::new_permit(pool),
+        }
+        .close()
+        .await
+        .decrement_size();
+    }
+
     pub fn metadata(&self) -> PoolConnectionMetadata {
         // Use a single `now` value for consistency.
         let now = Instant::now();
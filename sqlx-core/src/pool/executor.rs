@@ -1,13 +1,17 @@
+use std::future::Future;
+use std::time::{Duration, Instant};
+
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::TryStreamExt;
 
+use crate::connection::Connection;
 use crate::database::{Database, HasStatement};
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
-use crate::pool::Pool;
+use crate::pool::{Pool, PoolConnection};
 
 impl<'p, DB: Database> Executor<'p> for &'_ Pool<DB>
 where
@@ -25,14 +29,58 @@ where
         let pool = self.clone();
 
         Box::pin(try_stream! {
-            let mut conn = pool.acquire().await?;
-            let mut s = conn.fetch_many(query);
+            let timeout = pool.0.options.statement_timeout;
+            let policy = pool.0.options.retry_policy.clone();
+            let mut query = query;
+            let mut attempt = 1;
 
-            while let Some(v) = s.try_next().await? {
-                r#yield!(v);
-            }
+            loop {
+                // a copy to execute the query again, if it is retryable
+                let retry = policy.as_ref().and_then(|_| query.clone_for_retry());
+
+                let error = match pool.acquire().await {
+                    Ok(mut conn) => {
+                        let deadline = Deadline::start(timeout);
+                        let mut s = conn.fetch_many(query);
+                        let mut yielded = false;
+
+                        let error = loop {
+                            let next = deadline.run(s.try_next()).await;
+
+                            match next {
+                                Some(Ok(Some(v))) => {
+                                    yielded = true;
+                                    r#yield!(v);
+                                }
+                                Some(Ok(None)) => return Ok(()),
+                                Some(Err(e)) => break e,
+                                None => {
+                                    drop(s);
+                                    return Err(cancel_timed_out(conn));
+                                }
+                            }
+                        };
+
+                        // The results already returned can't be taken back
+                        if yielded {
+                            return Err(error);
+                        }
+
+                        error
+                    }
+                    Err(e) => e,
+                };
+
+                match (&policy, retry) {
+                    (Some(policy), Some(retry)) => {
+                        policy.backoff_or_fail(error, attempt).await?;
+                        query = retry;
+                    }
+                    _ => return Err(error),
+                }
 
-            Ok(())
+                attempt += 1;
+            }
         })
     }
 
@@ -45,7 +93,43 @@ where
     {
         let pool = self.clone();
 
-        Box::pin(async move { pool.acquire().await?.fetch_optional(query).await })
+        Box::pin(async move {
+            let timeout = pool.0.options.statement_timeout;
+            let policy = pool.0.options.retry_policy.clone();
+            let mut query = query;
+            let mut attempt = 1;
+
+            loop {
+                // a copy to execute the query again, if it is retryable
+                let retry = policy.as_ref().and_then(|_| query.clone_for_retry());
+
+                let res = match pool.acquire().await {
+                    Ok(mut conn) => {
+                        let res = Deadline::start(timeout)
+                            .run(conn.fetch_optional(query))
+                            .await;
+
+                        match res {
+                            Some(res) => res,
+                            // A query which timed out is likely to time out again
+                            None => return Err(cancel_timed_out(conn)),
+                        }
+                    }
+                    Err(e) => Err(e),
+                };
+
+                match (res, &policy, retry) {
+                    (Ok(row), _, _) => return Ok(row),
+                    (Err(e), Some(policy), Some(retry)) => {
+                        policy.backoff_or_fail(e, attempt).await?;
+                        query = retry;
+                    }
+                    (Err(e), _, _) => return Err(e),
+                }
+
+                attempt += 1;
+            }
+        })
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -69,6 +153,46 @@ where
     }
 }
 
+// The deadline of a query, from `PoolOptions::statement_timeout`
+#[derive(Clone, Copy)]
+struct Deadline(Option<Instant>);
+
+impl Deadline {
+    fn start(timeout: Option<Duration>) -> Self {
+        Deadline(timeout.map(|timeout| Instant::now() + timeout))
+    }
+
+    // Await `fut`, or return `None` if the deadline is reached first
+    async fn run<F: Future>(self, fut: F) -> Option<F::Output> {
+        match self.0 {
+            Some(deadline) => {
+                let timeout = deadline.saturating_duration_since(Instant::now());
+
+                crate::rt::timeout(timeout, fut).await.ok()
+            }
+            None => Some(fut.await),
+        }
+    }
+}
+
+// Cancel the query which timed out on `conn`, then close it in the background, as it may still
+// be busy with the query
+fn cancel_timed_out<DB: Database>(conn: PoolConnection<DB>) -> Error {
+    let token = conn.cancel_token();
+
+    crate::rt::spawn(async move {
+        if let Some(token) = token {
+            if let Err(e) = token.cancel().await {
+                tracing::warn!(%e, "failed to cancel a query which timed out");
+            }
+        }
+
+        let _ = conn.close().await;
+    });
+
+    Error::QueryTimedOut
+}
+
 // Causes an overflow when evaluating `&mut DB::Connection: Executor`.
 //
 //
@@ -1,3 +1,4 @@
+use super::circuit_breaker::CircuitBreaker;
 use super::connection::{Floating, Idle, Live};
 use crate::connection::ConnectOptions;
 use crate::connection::Connection;
@@ -27,6 +28,7 @@ pub(crate) struct PoolInner<DB: Database> {
     pub(super) num_idle: AtomicUsize,
     is_closed: AtomicBool,
     pub(super) on_closed: event_listener::Event,
+    circuit_breaker: Option<CircuitBreaker>,
     pub(super) options: PoolOptions<DB>,
 }
 
@@ -54,6 +56,7 @@ impl<DB: Database> PoolInner<DB> {
             num_idle: AtomicUsize::new(0),
             is_closed: AtomicBool::new(false),
             on_closed: event_listener::Event::new(),
+            circuit_breaker: options.circuit_breaker.clone().map(CircuitBreaker::new),
             options,
         };
 
@@ -107,8 +110,11 @@ impl<DB: Database> PoolInner<DB> {
     }
 
     pub(crate) fn close_event(&self) -> CloseEvent {
+        // Listen before checking the flag, or a `close()` in-between would never be noticed.
+        let listener = self.on_closed.listen();
+
         CloseEvent {
-            listener: (!self.is_closed()).then(|| self.on_closed.listen()),
+            listener: (!self.is_closed()).then_some(listener),
         }
     }
 
@@ -241,11 +247,19 @@ impl<DB: Database> PoolInner<DB> {
             return Err(Error::PoolClosed);
         }
 
+        if let Some(circuit_breaker) = &self.circuit_breaker {
+            circuit_breaker.check(Instant::now())?;
+        }
+
         let deadline = Instant::now() + self.options.acquire_timeout;
 
+        // Once the pool starts closing, no connection will be given out anymore: fail right away
+        // instead of waiting for a permit, checking an idle connection or opening a new one.
+        let mut close_event = self.close_event();
+
         crate::rt::timeout(
             self.options.acquire_timeout,
-            async {
+            close_event.do_until(async {
                 loop {
                     // Handles the close-event internally
                     let permit = self.acquire_permit().await?;
@@ -283,10 +297,56 @@ impl<DB: Database> PoolInner<DB> {
                     // Attempt to connect...
                     return self.connect(deadline, guard).await;
                 }
-            }
+            })
+        )
+            .await
+            .map_err(|_| Error::PoolTimedOut)??
+    }
+
+    /// Wait for all connections to be returned, then acquire one while blocking any other
+    /// acquisition. The other idle connections are closed.
+    ///
+    /// The returned permits block the other acquisitions, and must be released only after the
+    /// connection is returned.
+    pub(super) async fn acquire_exclusive(
+        self: &Arc<Self>,
+    ) -> Result<(AsyncSemaphoreReleaser<'_>, Floating<DB, Live<DB>>), Error> {
+        if self.is_closed() {
+            return Err(Error::PoolClosed);
+        }
+
+        if self.parent().is_some() {
+            // The permits of a child pool are stolen from its parent, so they can't all be held
+            return Err(Error::Configuration(
+                "exclusive access is not supported for child pools".into(),
+            ));
+        }
+
+        let deadline = Instant::now() + self.options.acquire_timeout;
+
+        // Hold all the permits but one, so the last connection to be returned is the only one
+        // that can be acquired, by us
+        let others = self.options.max_connections.saturating_sub(1);
+
+        let blocker = crate::rt::timeout(
+            self.options.acquire_timeout,
+            self.close_event().do_until(self.semaphore.acquire(others)),
         )
+        .await
+        .map_err(|_| Error::PoolTimedOut)??;
+
+        let conn = crate::rt::timeout(deadline_as_timeout::<DB>(deadline)?, self.acquire())
             .await
-            .map_err(|_| Error::PoolTimedOut)?
+            .map_err(|_| Error::PoolTimedOut)??;
+
+        // No other connection can be checked out now, so the idle ones are all the others
+        while let Some(idle) = self.idle_conns.pop() {
+            self.num_idle.fetch_sub(1, Ordering::AcqRel);
+
+            Floating::close_without_permit(idle, (*self).clone()).await;
+        }
+
+        Ok((blocker, conn))
     }
 
     pub(super) async fn connect(
@@ -304,6 +364,10 @@ impl<DB: Database> PoolInner<DB> {
         loop {
             let timeout = deadline_as_timeout::<DB>(deadline)?;
 
+            if let Some(circuit_breaker) = &self.circuit_breaker {
+                circuit_breaker.before_connect(Instant::now())?;
+            }
+
             // clone the connect options arc so it can be used without holding the RwLockReadGuard
             // across an async await point
             let connect_options = self
@@ -314,7 +378,16 @@ impl<DB: Database> PoolInner<DB> {
 
             // result here is `Result<Result<C, Error>, TimeoutError>`
             // if this block does not return, sleep for the backoff timeout and try again
-            match crate::rt::timeout(timeout, connect_options.connect()).await {
+            let result = crate::rt::timeout(timeout, connect_options.connect()).await;
+
+            if let Some(circuit_breaker) = &self.circuit_breaker {
+                match &result {
+                    Ok(Ok(_)) => circuit_breaker.on_connect_success(),
+                    _ => circuit_breaker.on_connect_failure(Instant::now()),
+                }
+            }
+
+            match result {
                 // successfully established connection
                 Ok(Ok(mut raw)) => {
                     // See comment on `PoolOptions::after_connect`
@@ -593,6 +666,13 @@ impl<DB: Database> DecrementSizeGuard<DB> {
         self.cancel();
     }
 
+    /// Decrease the pool size without releasing a semaphore permit, for a connection which
+    /// doesn't hold one.
+    pub fn decrement_size(self) {
+        self.pool.size.fetch_sub(1, Ordering::AcqRel);
+        self.cancel();
+    }
+
     pub fn cancel(mut self) {
         self.cancelled = true;
     }
@@ -70,6 +70,7 @@ use crate::database::Database;
 use crate::error::Error;
 use crate::transaction::Transaction;
 use event_listener::EventListener;
+use futures_core::future::BoxFuture;
 use futures_core::FusedFuture;
 use futures_util::FutureExt;
 use std::fmt;
@@ -85,12 +86,16 @@ mod executor;
 #[macro_use]
 pub mod maybe;
 
+mod circuit_breaker;
 mod connection;
 mod inner;
 mod options;
+mod retry;
 
+pub use self::circuit_breaker::CircuitBreakerConfig;
 pub use self::connection::PoolConnection;
 pub use self::options::{PoolConnectionMetadata, PoolOptions};
+pub use self::retry::RetryPolicy;
 
 #[doc(hidden)]
 pub use self::maybe::MaybePoolConnection;
@@ -287,7 +292,8 @@ impl<DB: Database> Pool<DB> {
     /// The default configuration is mainly suited for testing and light-duty applications.
     /// For production applications, you'll likely want to make at least few tweaks.
     ///
-    /// See [`PoolOptions::new()`] for details.
+    /// See [`PoolOptions::new()`] for details. Pool settings can also be given in the URL,
+    /// see [`PoolOptions::from_url()`].
     pub async fn connect(url: &str) -> Result<Self, Error> {
         PoolOptions::<DB>::new().connect(url).await
     }
@@ -320,7 +326,8 @@ impl<DB: Database> Pool<DB> {
     /// The default configuration is mainly suited for testing and light-duty applications.
     /// For production applications, you'll likely want to make at least few tweaks.
     ///
-    /// See [`PoolOptions::new()`] for details.
+    /// See [`PoolOptions::new()`] for details. Pool settings can also be given in the URL,
+    /// see [`PoolOptions::from_url()`].
     pub fn connect_lazy(url: &str) -> Result<Self, Error> {
         PoolOptions::<DB>::new().connect_lazy(url)
     }
@@ -390,6 +397,95 @@ impl<DB: Database> Pool<DB> {
         }
     }
 
+    /// Check that the pool can serve queries, e.g. for a readiness probe.
+    ///
+    /// This acquires a connection (opening one if none is idle), checks that it is alive with
+    /// [`Connection::ping()`] and returns it to the pool right away. The whole check is capped by
+    /// [`PoolOptions::acquire_timeout`], after which [`Error::PoolTimedOut`] is returned.
+    /// Custom validation of the connection can be added with [`PoolOptions::before_acquire`].
+    ///
+    /// A connection which fails the check is closed instead of being returned to the pool.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool) {
+    /// // in the handler of a readiness probe
+    /// let ready = pool.readiness_check().await.is_ok();
+    /// # }
+    /// ```
+    pub async fn readiness_check(&self) -> Result<(), Error> {
+        let deadline = Instant::now() + self.0.options.acquire_timeout;
+
+        let mut conn = self.acquire().await?;
+
+        match crate::rt::timeout(deadline_as_timeout::<DB>(deadline)?, conn.ping()).await {
+            Ok(Ok(())) => {
+                conn.return_to_pool().await;
+                Ok(())
+            }
+            Ok(Err(e)) => {
+                let _ = conn.close().await;
+                Err(e)
+            }
+            // The connection is in an unknown state, so it is dropped without being closed
+            Err(_) => {
+                drop(conn.detach());
+                Err(Error::PoolTimedOut)
+            }
+        }
+    }
+
+    /// Execute the function with exclusive access to the pool.
+    ///
+    /// This waits for all checked-out connections to be returned to the pool, closes all of them
+    /// but one, and calls the function with that connection. Until the function completes, no
+    /// other connection can be acquired from the pool: other calls to [`acquire()`][Self::acquire]
+    /// and the like wait, or time out as usual.
+    ///
+    /// This is meant for maintenance operations which can't run concurrently with the normal
+    /// use of the pool, such as `VACUUM` in SQLite or `REINDEX` in Postgres. Afterwards,
+    /// the connection is returned to the pool and normal operation resumes, opening new
+    /// connections as needed.
+    ///
+    /// Connections are acquired in order, so tasks which try to acquire one after this is called
+    /// wait until the function completes, even before it starts. Waiting for the connections and
+    /// acquiring one is capped by [`PoolOptions::acquire_timeout`], after which this returns
+    /// [`Error::PoolTimedOut`].
+    ///
+    /// Returns an error for a pool created with [`PoolOptions::parent()`], which can't be drained.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::SqlitePool) -> sqlx::Result<()> {
+    /// use sqlx::Executor;
+    ///
+    /// pool.with_exclusive(|conn| Box::pin(async move {
+    ///     conn.execute("VACUUM").await?;
+    ///     Ok::<_, sqlx::Error>(())
+    /// }))
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_exclusive<F, R, E>(&self, callback: F) -> Result<R, E>
+    where
+        for<'c> F: FnOnce(&'c mut DB::Connection) -> BoxFuture<'c, Result<R, E>> + Send,
+        R: Send,
+        E: From<Error> + Send,
+    {
+        // The other acquisitions are blocked until this is dropped
+        let (_blocker, conn) = self.0.acquire_exclusive().await?;
+        let mut conn = conn.reattach();
+
+        let ret = callback(&mut conn).await;
+
+        conn.return_to_pool().await;
+
+        ret
+    }
+
     /// Shut down the connection pool, immediately waking all tasks waiting for a connection.
     ///
     /// Upon calling this method, any currently waiting or subsequent calls to [`Pool::acquire`] and
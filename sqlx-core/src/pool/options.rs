@@ -2,11 +2,14 @@ use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
 use crate::pool::inner::PoolInner;
-use crate::pool::Pool;
+use crate::pool::{CircuitBreakerConfig, Pool, RetryPolicy};
 use futures_core::future::BoxFuture;
+use std::borrow::Cow;
 use std::fmt::{self, Debug, Formatter};
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use url::Url;
 
 /// Configuration options for [`Pool`][super::Pool].
 ///
@@ -79,6 +82,11 @@ pub struct PoolOptions<DB: Database> {
     pub(crate) max_lifetime: Option<Duration>,
     pub(crate) idle_timeout: Option<Duration>,
     pub(crate) fair: bool,
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    pub(crate) statement_timeout: Option<Duration>,
+    pub(crate) circuit_breaker: Option<CircuitBreakerConfig>,
+    // the keys of `URL_PARAMS` whose setting was set explicitly, so the URL doesn't override it
+    pub(crate) set_url_params: Vec<&'static str>,
 
     pub(crate) parent_pool: Option<Pool<DB>>,
 }
@@ -99,6 +107,10 @@ impl<DB: Database> Clone for PoolOptions<DB> {
             max_lifetime: self.max_lifetime,
             idle_timeout: self.idle_timeout,
             fair: self.fair,
+            retry_policy: self.retry_policy.clone(),
+            statement_timeout: self.statement_timeout,
+            circuit_breaker: self.circuit_breaker.clone(),
+            set_url_params: self.set_url_params.clone(),
             parent_pool: self.parent_pool.as_ref().map(Pool::clone),
         }
     }
@@ -147,10 +159,102 @@ impl<DB: Database> PoolOptions<DB> {
             idle_timeout: Some(Duration::from_secs(10 * 60)),
             max_lifetime: Some(Duration::from_secs(30 * 60)),
             fair: true,
+            retry_policy: None,
+            statement_timeout: None,
+            circuit_breaker: None,
+            set_url_params: Vec::new(),
             parent_pool: None,
         }
     }
 
+    /// Returns the default configuration, with the pool settings given as query parameters
+    /// of the connection URL applied.
+    ///
+    /// This allows configuring the pool entirely through the connection URL, e.g.
+    /// `postgres://localhost/app?max_connections=50&acquire_timeout=5`. The recognized keys are:
+    ///
+    /// | Key | Setting | Value |
+    /// |---|---|---|
+    /// | `max_connections` | [`max_connections`][Self::max_connections] | integer |
+    /// | `min_connections` | [`min_connections`][Self::min_connections] | integer |
+    /// | `acquire_timeout` | [`acquire_timeout`][Self::acquire_timeout] | seconds |
+    /// | `idle_timeout` | [`idle_timeout`][Self::idle_timeout] | seconds, or `none` |
+    /// | `max_lifetime` | [`max_lifetime`][Self::max_lifetime] | seconds, or `none` |
+    /// | `test_before_acquire` | [`test_before_acquire`][Self::test_before_acquire] | `true` or `false` |
+    /// | `statement_timeout` | [`statement_timeout`][Self::statement_timeout] | seconds, or `none` |
+    ///
+    /// Durations may be fractional, e.g. `acquire_timeout=0.5`. Other query parameters are
+    /// ignored here, as they are connection options handled by the driver.
+    ///
+    /// The same keys are also applied by [`connect()`][Self::connect] and
+    /// [`connect_lazy()`][Self::connect_lazy] (and so by [`Pool::connect()`] and
+    /// [`Pool::connect_lazy()`]), for the settings which were not set explicitly, and removed
+    /// from the URL before parsing the connection options.
+    ///
+    /// Returns an error if the URL cannot be parsed, or if the value of a recognized key
+    /// is invalid.
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> sqlx::Result<()> {
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// let url = "postgres://localhost/app?max_connections=50&acquire_timeout=5";
+    ///
+    /// let options = PgPoolOptions::from_url(url)?;
+    /// assert_eq!(options.get_max_connections(), 50);
+    ///
+    /// // the same as `PgPoolOptions::from_url(url)?.connect(url)`
+    /// let pool = PgPoolOptions::new().connect(url).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_url(url: &str) -> Result<Self, Error> {
+        let url: Url = url.parse().map_err(Error::config)?;
+
+        Self::new().apply_url_params(&url)
+    }
+
+    /// Apply the pool settings given in `url` which were not set explicitly, if it can be parsed
+    /// (otherwise the driver will report it when parsing the connection options).
+    fn with_url_params(self, url: &str) -> Result<Self, Error> {
+        match Url::parse(url) {
+            Ok(url) => self.apply_url_params(&url),
+            Err(_) => Ok(self),
+        }
+    }
+
+    fn apply_url_params(mut self, url: &Url) -> Result<Self, Error> {
+        let set_url_params = self.set_url_params.clone();
+
+        for (key, value) in url.query_pairs() {
+            if set_url_params.contains(&&*key) {
+                continue;
+            }
+
+            self = match &*key {
+                "max_connections" => self.max_connections(parse_url_param(&key, &value)?),
+                "min_connections" => self.min_connections(parse_url_param(&key, &value)?),
+                "acquire_timeout" => self.acquire_timeout(parse_url_duration(&key, &value)?),
+                "idle_timeout" => self.idle_timeout(parse_url_optional_duration(&key, &value)?),
+                "max_lifetime" => self.max_lifetime(parse_url_optional_duration(&key, &value)?),
+                "test_before_acquire" => self.test_before_acquire(parse_url_param(&key, &value)?),
+                "statement_timeout" => {
+                    self.statement_timeout(parse_url_optional_duration(&key, &value)?)
+                }
+                // a connection option, for the driver
+                _ => self,
+            };
+        }
+
+        Ok(self)
+    }
+
+    fn set_url_param(&mut self, key: &'static str) {
+        if !self.set_url_params.contains(&key) {
+            self.set_url_params.push(key);
+        }
+    }
+
     /// Set the maximum number of connections that this pool should maintain.
     ///
     /// Be mindful of the connection limits for your database as well as other applications
@@ -158,6 +262,7 @@ impl<DB: Database> PoolOptions<DB> {
     /// application in high-availability deployments).
     pub fn max_connections(mut self, max: u32) -> Self {
         self.max_connections = max;
+        self.set_url_param("max_connections");
         self
     }
 
@@ -190,6 +295,7 @@ impl<DB: Database> PoolOptions<DB> {
     /// [`max_connections`]: Self::max_connections
     pub fn min_connections(mut self, min: u32) -> Self {
         self.min_connections = min;
+        self.set_url_param("min_connections");
         self
     }
 
@@ -213,6 +319,7 @@ impl<DB: Database> PoolOptions<DB> {
     ///     * If [`after_connect`][Self::after_connect] is set, that will also be executed.
     pub fn acquire_timeout(mut self, timeout: Duration) -> Self {
         self.acquire_timeout = timeout;
+        self.set_url_param("acquire_timeout");
         self
     }
 
@@ -237,6 +344,7 @@ impl<DB: Database> PoolOptions<DB> {
     /// [`idle_timeout`]: Self::idle_timeout
     pub fn max_lifetime(mut self, lifetime: impl Into<Option<Duration>>) -> Self {
         self.max_lifetime = lifetime.into();
+        self.set_url_param("max_lifetime");
         self
     }
 
@@ -252,6 +360,7 @@ impl<DB: Database> PoolOptions<DB> {
     /// For usage-based database server billing, this can be a cost saver.
     pub fn idle_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
         self.idle_timeout = timeout.into();
+        self.set_url_param("idle_timeout");
         self
     }
 
@@ -266,6 +375,7 @@ impl<DB: Database> PoolOptions<DB> {
     /// Defaults to `true`.
     pub fn test_before_acquire(mut self, test: bool) -> Self {
         self.test_before_acquire = test;
+        self.set_url_param("test_before_acquire");
         self
     }
 
@@ -274,6 +384,68 @@ impl<DB: Database> PoolOptions<DB> {
         self.test_before_acquire
     }
 
+    /// Set the policy for retrying queries executed directly on the pool that fail with
+    /// a transient error, such as a lost connection or a deadlock.
+    ///
+    /// Only the queries marked as [retryable][crate::query::Query::retryable] are retried;
+    /// see [`RetryPolicy`] for details.
+    ///
+    /// When set to `None` (the default), queries are never retried.
+    pub fn retry_policy(mut self, policy: impl Into<Option<RetryPolicy>>) -> Self {
+        self.retry_policy = policy.into();
+        self
+    }
+
+    /// Get the policy for retrying queries executed directly on the pool.
+    pub fn get_retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry_policy.as_ref()
+    }
+
+    /// Set the maximum duration of the queries executed directly on the pool.
+    ///
+    /// A query which takes longer is cancelled on the server, with
+    /// [`Connection::cancel_token()`], and fails with [`Error::QueryTimedOut`]. Its connection
+    /// is closed in the background once the cancellation was requested, as it may still be busy
+    /// with the query. Unlike a timeout configured in the database (e.g. the `statement_timeout`
+    /// setting of Postgres), this is enforced by SQLx and works the same with every driver.
+    ///
+    /// The duration is measured from when a connection was acquired for the query until its last
+    /// result is received, including the time the caller takes to consume the rows of a stream.
+    /// It doesn't include the time waiting for a connection, which is capped by
+    /// [`acquire_timeout`][Self::acquire_timeout]. With a [`retry_policy`][Self::retry_policy],
+    /// each attempt has the full duration, and a query which timed out is never retried.
+    ///
+    /// Queries executed on a connection acquired from the pool (including in transactions) are
+    /// not covered.
+    ///
+    /// When set to `None` (the default), queries have no maximum duration.
+    pub fn statement_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.statement_timeout = timeout.into();
+        self.set_url_param("statement_timeout");
+        self
+    }
+
+    /// Get the maximum duration of the queries executed directly on the pool.
+    pub fn get_statement_timeout(&self) -> Option<Duration> {
+        self.statement_timeout
+    }
+
+    /// Set the circuit breaker making [`acquire()`][Pool::acquire] fail fast with
+    /// [`Error::CircuitOpen`] after repeated failures to connect, e.g. while the database is down;
+    /// see [`CircuitBreakerConfig`] for details.
+    ///
+    /// When set to `None` (the default), every acquisition needing a new connection attempts to
+    /// open one until the [`acquire_timeout`][Self::acquire_timeout].
+    pub fn circuit_breaker(mut self, config: impl Into<Option<CircuitBreakerConfig>>) -> Self {
+        self.circuit_breaker = config.into();
+        self
+    }
+
+    /// Get the circuit breaker of the pool.
+    pub fn get_circuit_breaker(&self) -> Option<&CircuitBreakerConfig> {
+        self.circuit_breaker.as_ref()
+    }
+
     /// If set to `true`, calls to `acquire()` are fair and connections  are issued
     /// in first-come-first-serve order. If `false`, "drive-by" tasks may steal idle connections
     /// ahead of tasks that have been waiting.
@@ -478,8 +650,13 @@ impl<DB: Database> PoolOptions<DB> {
     /// * MySQL: [`MySqlConnectOptions`][crate::mysql::MySqlConnectOptions]
     /// * SQLite: [`SqliteConnectOptions`][crate::sqlite::SqliteConnectOptions]
     /// * MSSQL: [`MssqlConnectOptions`][crate::mssql::MssqlConnectOptions]
+    ///
+    /// The pool settings recognized by [`from_url()`][Self::from_url] are removed from the URL
+    /// first, and applied unless they were set explicitly.
     pub async fn connect(self, url: &str) -> Result<Pool<DB>, Error> {
-        self.connect_with(url.parse()?).await
+        self.with_url_params(url)?
+            .connect_with(strip_url_params(url).parse()?)
+            .await
     }
 
     /// Create a new pool from this `PoolOptions` and immediately open at least one connection.
@@ -521,8 +698,13 @@ impl<DB: Database> PoolOptions<DB> {
     /// * MySQL: [`MySqlConnectOptions`][crate::mysql::MySqlConnectOptions]
     /// * SQLite: [`SqliteConnectOptions`][crate::sqlite::SqliteConnectOptions]
     /// * MSSQL: [`MssqlConnectOptions`][crate::mssql::MssqlConnectOptions]
+    ///
+    /// The pool settings recognized by [`from_url()`][Self::from_url] are removed from the URL
+    /// first, and applied unless they were set explicitly.
     pub fn connect_lazy(self, url: &str) -> Result<Pool<DB>, Error> {
-        Ok(self.connect_lazy_with(url.parse()?))
+        Ok(self
+            .with_url_params(url)?
+            .connect_lazy_with(strip_url_params(url).parse()?))
     }
 
     /// Create a new pool from this `PoolOptions`, but don't open any connections right now.
@@ -535,6 +717,74 @@ impl<DB: Database> PoolOptions<DB> {
     }
 }
 
+/// The query parameters parsed by [`PoolOptions::from_url()`].
+const URL_PARAMS: &[&str] = &[
+    "max_connections",
+    "min_connections",
+    "acquire_timeout",
+    "idle_timeout",
+    "max_lifetime",
+    "test_before_acquire",
+    "statement_timeout",
+];
+
+fn parse_url_param<T>(key: &str, value: &str) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    value.parse().map_err(|e| {
+        Error::Configuration(format!("invalid value {value:?} for `{key}`: {e}").into())
+    })
+}
+
+fn parse_url_duration(key: &str, value: &str) -> Result<Duration, Error> {
+    let secs: f64 = parse_url_param(key, value)?;
+
+    Duration::try_from_secs_f64(secs).map_err(|e| {
+        Error::Configuration(format!("invalid value {value:?} for `{key}`: {e}").into())
+    })
+}
+
+fn parse_url_optional_duration(key: &str, value: &str) -> Result<Option<Duration>, Error> {
+    if value.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+
+    parse_url_duration(key, value).map(Some)
+}
+
+/// Remove the pool settings from the query parameters of `url`, so drivers don't reject them.
+///
+/// `url` is returned as-is if it has no pool settings, or cannot be parsed (which the driver
+/// will report).
+fn strip_url_params(url: &str) -> Cow<'_, str> {
+    let Ok(mut parsed) = Url::parse(url) else {
+        return Cow::Borrowed(url);
+    };
+
+    if !parsed
+        .query_pairs()
+        .any(|(key, _)| URL_PARAMS.contains(&&*key))
+    {
+        return Cow::Borrowed(url);
+    }
+
+    let rest: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| !URL_PARAMS.contains(&&**key))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+
+    parsed.set_query(None);
+
+    if !rest.is_empty() {
+        parsed.query_pairs_mut().extend_pairs(rest);
+    }
+
+    Cow::Owned(parsed.into())
+}
+
 impl<DB: Database> Debug for PoolOptions<DB> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("PoolOptions")
@@ -544,6 +794,27 @@ impl<DB: Database> Debug for PoolOptions<DB> {
             .field("max_lifetime", &self.max_lifetime)
             .field("idle_timeout", &self.idle_timeout)
             .field("test_before_acquire", &self.test_before_acquire)
+            .field("retry_policy", &self.retry_policy)
+            .field("statement_timeout", &self.statement_timeout)
+            .field("circuit_breaker", &self.circuit_breaker)
             .finish()
     }
 }
+
+#[test]
+fn test_strip_url_params() {
+    assert_eq!(
+        strip_url_params("postgres://localhost/app?sslmode=require"),
+        "postgres://localhost/app?sslmode=require"
+    );
+    assert_eq!(
+        strip_url_params(
+            "postgres://localhost/app?max_connections=5&sslmode=require&idle_timeout=none"
+        ),
+        "postgres://localhost/app?sslmode=require"
+    );
+    assert_eq!(
+        strip_url_params("sqlite::memory:?acquire_timeout=1.5"),
+        "sqlite::memory:"
+    );
+}
@@ -0,0 +1,220 @@
+use std::collections::hash_map::RandomState;
+use std::fmt::{self, Debug, Formatter};
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+use std::{cmp, iter};
+
+use crate::error::Error;
+use crate::reconnect::is_transient_connection_error;
+
+/// A policy for automatically retrying queries executed directly on a [`Pool`][super::Pool]
+/// which fail with a transient error.
+///
+/// Set with [`PoolOptions::retry_policy()`][super::PoolOptions::retry_policy].
+///
+/// ### Idempotency
+/// A query is only retried if it is safe to execute more than once, which only the application
+/// knows: queries have to be marked as such with [`Query::retryable()`], and all the others are
+/// executed exactly once. Note that the first attempt may have been executed by the server
+/// before the connection was lost.
+///
+/// Queries executed on a connection or transaction acquired from the pool are never retried.
+///
+/// [`Query::retryable()`]: crate::query::Query::retryable
+///
+/// ### Streams
+/// Queries returning a stream of results (e.g. [`fetch()`][crate::query::Query::fetch]) are only
+/// retried if they fail before the first result is returned.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example() -> sqlx::Result<()> {
+/// use std::time::Duration;
+/// use sqlx::pool::RetryPolicy;
+/// use sqlx::postgres::PgPoolOptions;
+///
+/// let pool = PgPoolOptions::new()
+///     .retry_policy(
+///         RetryPolicy::new()
+///             .max_attempts(5)
+///             .base_delay(Duration::from_millis(50)),
+///     )
+///     .connect("postgres:// …")
+///     .await?;
+///
+/// // retried automatically if the connection is lost
+/// let count: i64 = sqlx::query_scalar("SELECT count(*) FROM users")
+///     .retryable(true)
+///     .fetch_one(&pool)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    retry_if: Arc<dyn Fn(&Error) -> bool + Send + Sync + 'static>,
+}
+
+impl RetryPolicy {
+    /// Returns a policy of up to 3 attempts in total, with an exponential backoff starting
+    /// at 10 milliseconds, capped at 1 second, and with jitter.
+    ///
+    /// By default, errors are retried if they indicate the connection was lost or the server is
+    /// restarting, or if the database reported a deadlock or serialization failure
+    /// (which roll back the statement).
+    pub fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(10),
+            max_delay: Duration::from_secs(1),
+            jitter: true,
+            retry_if: Arc::new(is_retryable_by_default),
+        }
+    }
+
+    /// Set the maximum number of attempts to execute a query, including the first one.
+    ///
+    /// A value of `1` (or `0`) disables retrying.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Get the maximum number of attempts to execute a query, including the first one.
+    pub fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Set the delay before the first retry.
+    ///
+    /// The delay is doubled for every subsequent retry, up to [`max_delay`][Self::max_delay].
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Get the delay before the first retry.
+    pub fn get_base_delay(&self) -> Duration {
+        self.base_delay
+    }
+
+    /// Set the maximum delay between two attempts.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Get the maximum delay between two attempts.
+    pub fn get_max_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    /// If `true`, each delay is randomly shortened by up to half, to avoid many tasks
+    /// retrying at the same time.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the predicate deciding which errors are worth retrying.
+    ///
+    /// This replaces the default predicate described in [`new()`][Self::new].
+    pub fn retry_if<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Error) -> bool + Send + Sync + 'static,
+    {
+        self.retry_if = Arc::new(predicate);
+        self
+    }
+
+    /// Returns `true` if the error is retryable according to this policy.
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        (self.retry_if)(error)
+    }
+
+    /// The delay before retrying after the given (1-based) attempt failed.
+    fn delay(&self, attempt: u32) -> Duration {
+        let delay = iter::successors(Some(self.base_delay), |d| d.checked_mul(2))
+            .nth((attempt - 1) as usize)
+            .map_or(self.max_delay, |d| cmp::min(d, self.max_delay));
+
+        if self.jitter {
+            // We don't need a proper random number generator for this
+            let random = RandomState::new().build_hasher().finish();
+            delay.mul_f64(0.5 + (random % 1000) as f64 / 2000.0)
+        } else {
+            delay
+        }
+    }
+
+    /// Returns the error if it shouldn't be retried after the given (1-based) attempt failed,
+    /// or waits before the next attempt otherwise.
+    pub(crate) async fn backoff_or_fail(&self, error: Error, attempt: u32) -> Result<(), Error> {
+        if attempt >= self.max_attempts || !self.is_retryable(&error) {
+            return Err(error);
+        }
+
+        crate::rt::sleep(self.delay(attempt)).await;
+
+        Ok(())
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for RetryPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .field("jitter", &self.jitter)
+            .finish()
+    }
+}
+
+fn is_retryable_by_default(error: &Error) -> bool {
+    if is_transient_connection_error(error) {
+        return true;
+    }
+
+    // SQLSTATE `40001` is `serialization_failure` (also used by MySQL for deadlocks)
+    // and `40P01` is Postgres' `deadlock_detected`.
+    match error.as_database_error().and_then(|e| e.code()) {
+        Some(code) => matches!(&*code, "40001" | "40P01"),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetryPolicy;
+    use std::time::Duration;
+
+    #[test]
+    fn test_retry_delay() {
+        let policy = RetryPolicy::new()
+            .base_delay(Duration::from_millis(10))
+            .max_delay(Duration::from_millis(50))
+            .jitter(false);
+
+        assert_eq!(policy.delay(1), Duration::from_millis(10));
+        assert_eq!(policy.delay(2), Duration::from_millis(20));
+        assert_eq!(policy.delay(3), Duration::from_millis(40));
+        assert_eq!(policy.delay(4), Duration::from_millis(50));
+        assert_eq!(policy.delay(100), Duration::from_millis(50));
+
+        let delay = policy.jitter(true).delay(2);
+        assert!(delay >= Duration::from_millis(10) && delay <= Duration::from_millis(20));
+    }
+}
@@ -19,6 +19,10 @@ pub struct Query<'q, DB: Database, A> {
     pub(crate) arguments: Option<A>,
     pub(crate) database: PhantomData<DB>,
     pub(crate) persistent: bool,
+    pub(crate) selected_columns: Option<&'q [&'q str]>,
+    pub(crate) comment: Option<&'q str>,
+    // set by `retryable()`
+    pub(crate) clone_arguments: Option<fn(&A) -> Option<A>>,
 }
 
 /// SQL query that will map its results to owned Rust types.
@@ -63,7 +67,35 @@ where
 
     #[inline]
     fn persistent(&self) -> bool {
-        self.persistent
+        // a query with a comment is never cached, see `Query::with_comment()`
+        self.persistent && self.comment.is_none()
+    }
+
+    #[inline]
+    fn selected_columns(&self) -> Option<&'q [&'q str]> {
+        self.selected_columns
+    }
+
+    #[inline]
+    fn comment(&self) -> Option<&'q str> {
+        self.comment
+    }
+
+    fn clone_for_retry(&self) -> Option<Self> {
+        let clone_arguments = self.clone_arguments?;
+
+        Some(Query {
+            statement: self.statement,
+            arguments: match &self.arguments {
+                Some(arguments) => Some(clone_arguments(arguments)?),
+                None => None,
+            },
+            database: PhantomData,
+            persistent: self.persistent,
+            selected_columns: self.selected_columns,
+            comment: self.comment,
+            clone_arguments: self.clone_arguments,
+        })
     }
 }
 
@@ -83,6 +115,31 @@ impl<'q, DB: Database> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
 
         self
     }
+
+    /// If `true`, the query is retried when executed directly on a [`Pool`] which has a
+    /// [retry policy][crate::pool::PoolOptions::retry_policy], and it fails with a
+    /// transient error, such as a lost connection or a deadlock.
+    ///
+    /// Only mark queries which are safe to execute more than once: the first attempt may have
+    /// been executed by the server before the connection was lost. This is the case of most
+    /// `SELECT`s, but not of those with side effects, e.g. `SELECT ... FOR UPDATE` or
+    /// `SELECT nextval('my_seq')`.
+    ///
+    /// Queries whose arguments can't be copied (see [`Arguments::try_clone()`]), e.g. Postgres
+    /// queries binding a `Json` value, are executed once.
+    ///
+    /// Default: `false`.
+    ///
+    /// [`Pool`]: crate::pool::Pool
+    pub fn retryable(mut self, value: bool) -> Self {
+        self.clone_arguments = if value {
+            Some(<DB as HasArguments<'q>>::Arguments::try_clone)
+        } else {
+            None
+        };
+
+        self
+    }
 }
 
 impl<'q, DB, A> Query<'q, DB, A>
@@ -103,6 +160,83 @@ where
     }
 }
 
+impl<'q, DB, A> Query<'q, DB, A>
+where
+    DB: Database,
+{
+    /// Only keep the given columns in the returned rows, in that order.
+    ///
+    /// The server still sends all the columns of the result, but only the values of the
+    /// selected ones are tracked when decoding a row, which reduces the work done for each row
+    /// of very wide results, e.g. `SELECT *` over a table with hundreds of columns of which only
+    /// a few are used.
+    ///
+    /// The selected columns are then all the row contains: [`Row::columns()`] only returns
+    /// them, and they are indexed by their position in `columns`. An error is returned when
+    /// executing the query if a column is not in the result. Statements which don't return
+    /// rows are unaffected.
+    ///
+    /// This is not supported by `Any` connections, which return all the columns.
+    ///
+    /// [`Row::columns()`]: crate::row::Row::columns
+    ///
+    /// ### Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use sqlx::Row;
+    ///
+    /// let row = sqlx::query("SELECT * FROM wide_table WHERE id = $1")
+    ///     .bind(1_i64)
+    ///     .select_columns(&["id", "name"])
+    ///     .fetch_one(conn)
+    ///     .await?;
+    ///
+    /// let name: String = row.try_get("name")?;
+    /// assert_eq!(row.columns().len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn select_columns(mut self, columns: &'q [&'q str]) -> Self {
+        self.selected_columns = Some(columns);
+        self
+    }
+
+    /// Prepend a comment to the SQL of the query, e.g. to tag it with the request it is
+    /// executed for, to correlate the activity of the database with the application.
+    ///
+    /// The comment is sent as `/* comment */` before the SQL, so it shows up where the database
+    /// reports the SQL of queries, e.g. `pg_stat_activity` or the slow query log. Any `/*` or
+    /// `*/` in it is split with a space, so it can't end the comment.
+    ///
+    /// As the comment is usually different for each execution, a query with a comment is
+    /// prepared every time it is executed and bypasses the statement cache: it neither reuses
+    /// a cached statement, which the database would report with the comment it was prepared
+    /// with, if any, nor fills the cache with a statement for each comment.
+    ///
+    /// This is not supported by `Any` connections, which ignore the comment.
+    ///
+    /// ### Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection, request_id: &str) -> sqlx::Result<()> {
+    /// let comment = format!("request_id='{request_id}'");
+    ///
+    /// // executed as `/* request_id='..' */ SELECT * FROM users WHERE id = $1`
+    /// let user = sqlx::query("SELECT * FROM users WHERE id = $1")
+    ///     .bind(1_i64)
+    ///     .with_comment(&comment)
+    ///     .fetch_one(conn)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_comment(mut self, comment: &'q str) -> Self {
+        self.comment = Some(comment);
+        self
+    }
+}
+
 impl<'q, DB, A: Send> Query<'q, DB, A>
 where
     DB: Database,
@@ -249,7 +383,17 @@ where
 
     #[inline]
     fn persistent(&self) -> bool {
-        self.inner.arguments.is_some()
+        self.inner.arguments.is_some() && Execute::persistent(&self.inner)
+    }
+
+    #[inline]
+    fn selected_columns(&self) -> Option<&'q [&'q str]> {
+        self.inner.selected_columns
+    }
+
+    #[inline]
+    fn comment(&self) -> Option<&'q str> {
+        self.inner.comment
     }
 }
 
@@ -406,6 +550,9 @@ where
         arguments: Some(Default::default()),
         statement: Either::Right(statement),
         persistent: true,
+        selected_columns: None,
+        comment: None,
+        clone_arguments: None,
     }
 }
 
@@ -423,6 +570,9 @@ where
         arguments: Some(arguments),
         statement: Either::Right(statement),
         persistent: true,
+        selected_columns: None,
+        comment: None,
+        clone_arguments: None,
     }
 }
 
@@ -436,6 +586,9 @@ where
         arguments: Some(Default::default()),
         statement: Either::Left(sql),
         persistent: true,
+        selected_columns: None,
+        comment: None,
+        clone_arguments: None,
     }
 }
 
@@ -450,5 +603,8 @@ where
         arguments: Some(arguments),
         statement: Either::Left(sql),
         persistent: true,
+        selected_columns: None,
+        comment: None,
+        clone_arguments: None,
     }
 }
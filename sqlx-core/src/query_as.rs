@@ -1,3 +1,6 @@
+use std::collections::hash_map::{Entry, HashMap};
+use std::fmt::Debug;
+use std::hash::Hash;
 use std::marker::PhantomData;
 
 use either::Either;
@@ -45,6 +48,24 @@ where
     fn persistent(&self) -> bool {
         self.inner.persistent()
     }
+
+    #[inline]
+    fn selected_columns(&self) -> Option<&'q [&'q str]> {
+        self.inner.selected_columns()
+    }
+
+    #[inline]
+    fn comment(&self) -> Option<&'q str> {
+        self.inner.comment()
+    }
+
+    #[inline]
+    fn clone_for_retry(&self) -> Option<Self> {
+        Some(QueryAs {
+            inner: self.inner.clone_for_retry()?,
+            output: PhantomData,
+        })
+    }
 }
 
 impl<'q, DB: Database, O> QueryAs<'q, DB, O, <DB as HasArguments<'q>>::Arguments> {
@@ -55,6 +76,14 @@ impl<'q, DB: Database, O> QueryAs<'q, DB, O, <DB as HasArguments<'q>>::Arguments
         self.inner = self.inner.bind(value);
         self
     }
+
+    /// If `true`, the query is retried after a transient error when executed on a pool.
+    ///
+    /// See [`Query::retryable`](Query::retryable).
+    pub fn retryable(mut self, value: bool) -> Self {
+        self.inner = self.inner.retryable(value);
+        self
+    }
 }
 
 impl<'q, DB, O, A> QueryAs<'q, DB, O, A>
@@ -75,6 +104,27 @@ where
     }
 }
 
+impl<'q, DB, O, A> QueryAs<'q, DB, O, A>
+where
+    DB: Database,
+{
+    /// Only keep the given columns in the returned rows, in that order.
+    ///
+    /// See [`Query::select_columns`](Query::select_columns).
+    pub fn select_columns(mut self, columns: &'q [&'q str]) -> Self {
+        self.inner = self.inner.select_columns(columns);
+        self
+    }
+
+    /// Prepend a comment to the SQL of the query.
+    ///
+    /// See [`Query::with_comment`](Query::with_comment).
+    pub fn with_comment(mut self, comment: &'q str) -> Self {
+        self.inner = self.inner.with_comment(comment);
+        self
+    }
+}
+
 // FIXME: This is very close, nearly 1:1 with `Map`
 // noinspection DuplicatedCode
 impl<'q, DB, O, A> QueryAs<'q, DB, O, A>
@@ -133,6 +183,70 @@ where
         self.fetch(executor).try_collect().await
     }
 
+    /// Execute the query and return all the generated results, collected into a [`HashMap`]
+    /// keyed by the result of `key` for each of them.
+    ///
+    /// With [`OnDuplicateKey::Error`], this returns [`Error::DuplicateKey`] as soon as two
+    /// results have the same key, without fetching the remaining ones. With
+    /// [`OnDuplicateKey::KeepLast`], the last of them is kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use sqlx::query::OnDuplicateKey;
+    ///
+    /// #[derive(sqlx::FromRow)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String,
+    /// }
+    ///
+    /// let users = sqlx::query_as::<_, User>("SELECT id, name FROM users")
+    ///     .fetch_all_keyed_by(conn, |user| user.id, OnDuplicateKey::Error)
+    ///     .await?;
+    ///
+    /// if let Some(user) = users.get(&1) {
+    ///     println!("user 1 is {}", user.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_all_keyed_by<'e, 'c: 'e, E, K, F>(
+        self,
+        executor: E,
+        mut key: F,
+        on_duplicate: OnDuplicateKey,
+    ) -> Result<HashMap<K, O>, Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        O: 'e,
+        A: 'e,
+        K: Eq + Hash + Debug,
+        F: FnMut(&O) -> K,
+    {
+        let mut results = self.fetch(executor);
+        let mut map = HashMap::new();
+
+        while let Some(result) = results.try_next().await? {
+            match map.entry(key(&result)) {
+                Entry::Vacant(entry) => {
+                    entry.insert(result);
+                }
+                Entry::Occupied(entry) if on_duplicate == OnDuplicateKey::Error => {
+                    return Err(Error::DuplicateKey(format!("{:?}", entry.key())));
+                }
+                Entry::Occupied(mut entry) => {
+                    entry.insert(result);
+                }
+            }
+        }
+
+        Ok(map)
+    }
+
     /// Execute the query and returns exactly one row.
     pub async fn fetch_one<'e, 'c: 'e, E>(self, executor: E) -> Result<O, Error>
     where
@@ -165,6 +279,15 @@ where
     }
 }
 
+/// What [`QueryAs::fetch_all_keyed_by`] does when two results have the same key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnDuplicateKey {
+    /// Return [`Error::DuplicateKey`].
+    Error,
+    /// Keep the last of the results with the same key.
+    KeepLast,
+}
+
 /// Make a SQL query that is mapped to a concrete type
 /// using [`FromRow`].
 #[inline]
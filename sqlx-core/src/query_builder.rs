@@ -121,6 +121,64 @@ where
         self
     }
 
+    /// Append an identifier, e.g. the name of a table or column, quoted and escaped for the
+    /// database.
+    ///
+    /// Unlike [`.push()`][Self::push], this can be used with identifiers from untrusted input,
+    /// as they are always quoted and cannot be interpreted as anything else. It doesn't check
+    /// that the identifier is one of the expected ones, however.
+    ///
+    /// The name is split on dots into the parts of a qualified name, e.g. `schema.table` or
+    /// `table.column`, which are quoted separately. Each part is quoted with
+    /// [`Database::IDENTIFIER_QUOTE`]: double quotes for Postgres and SQLite, and backticks for
+    /// MySQL. Quote characters in a part are escaped by doubling them.
+    ///
+    /// Note that quoted identifiers are case-sensitive.
+    ///
+    /// ### Note: `Any`
+    /// With `Any`, the database is only known once the query is executed, so double quotes
+    /// are always used. MySQL only accepts these with the `ANSI_QUOTES` SQL mode enabled.
+    ///
+    /// ### Example
+    ///
+    /// ```rust
+    /// # use sqlx::{QueryBuilder, Postgres};
+    /// let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new("SELECT * FROM ");
+    ///
+    /// query_builder.push_identifier("public.users").push(" ORDER BY ");
+    /// query_builder.push_identifier("last \"name\"");
+    ///
+    /// assert_eq!(
+    ///     query_builder.sql(),
+    ///     r#"SELECT * FROM "public"."users" ORDER BY "last ""name""""#
+    /// );
+    /// ```
+    pub fn push_identifier(&mut self, name: &str) -> &mut Self {
+        self.sanity_check();
+
+        let quote = DB::IDENTIFIER_QUOTE;
+
+        for (i, part) in name.split('.').enumerate() {
+            if i > 0 {
+                self.query.push('.');
+            }
+
+            self.query.push(quote);
+
+            for ch in part.chars() {
+                if ch == quote {
+                    self.query.push(quote);
+                }
+
+                self.query.push(ch);
+            }
+
+            self.query.push(quote);
+        }
+
+        self
+    }
+
     /// Push a bind argument placeholder (`?` or `$N` for Postgres) and bind a value to it.
     ///
     /// ### Note: Database-specific Limits
@@ -453,6 +511,9 @@ where
             arguments: self.arguments.take(),
             database: PhantomData,
             persistent: true,
+            selected_columns: None,
+            comment: None,
+            clone_arguments: None,
         }
     }
 
@@ -564,6 +625,20 @@ where
         self
     }
 
+    /// Push the separator if applicable, then append a quoted identifier.
+    ///
+    /// See [`QueryBuilder::push_identifier()`] for details.
+    pub fn push_identifier(&mut self, name: &str) -> &mut Self {
+        if self.push_separator {
+            self.query_builder.push(&self.separator);
+        }
+
+        self.query_builder.push_identifier(name);
+        self.push_separator = true;
+
+        self
+    }
+
     /// Push the separator if applicable, then append a bind argument.
     ///
     /// See [`QueryBuilder::push_bind()`] for details.
@@ -618,6 +693,14 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_push_identifier() {
+        let mut qb: QueryBuilder<'_, Postgres> = QueryBuilder::new("SELECT * FROM ");
+        qb.push_identifier("my schema.us\"ers");
+
+        assert_eq!(qb.query, r#"SELECT * FROM "my schema"."us""ers""#);
+    }
+
     #[test]
     #[should_panic]
     fn test_push_panics_when_no_arguments() {
@@ -42,6 +42,23 @@ where
     fn persistent(&self) -> bool {
         self.inner.persistent()
     }
+
+    #[inline]
+    fn selected_columns(&self) -> Option<&'q [&'q str]> {
+        self.inner.selected_columns()
+    }
+
+    #[inline]
+    fn comment(&self) -> Option<&'q str> {
+        self.inner.comment()
+    }
+
+    #[inline]
+    fn clone_for_retry(&self) -> Option<Self> {
+        Some(QueryScalar {
+            inner: self.inner.clone_for_retry()?,
+        })
+    }
 }
 
 impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as HasArguments<'q>>::Arguments> {
@@ -52,6 +69,14 @@ impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as HasArguments<'q>>::Argum
         self.inner = self.inner.bind(value);
         self
     }
+
+    /// If `true`, the query is retried after a transient error when executed on a pool.
+    ///
+    /// See [`Query::retryable`](crate::query::Query::retryable).
+    pub fn retryable(mut self, value: bool) -> Self {
+        self.inner = self.inner.retryable(value);
+        self
+    }
 }
 
 impl<'q, DB, O, A> QueryScalar<'q, DB, O, A>
@@ -72,6 +97,27 @@ where
     }
 }
 
+impl<'q, DB, O, A> QueryScalar<'q, DB, O, A>
+where
+    DB: Database,
+{
+    /// Only keep the given columns in the returned rows, in that order.
+    ///
+    /// See [`Query::select_columns`](crate::query::Query::select_columns).
+    pub fn select_columns(mut self, columns: &'q [&'q str]) -> Self {
+        self.inner = self.inner.select_columns(columns);
+        self
+    }
+
+    /// Prepend a comment to the SQL of the query.
+    ///
+    /// See [`Query::with_comment`](crate::query::Query::with_comment).
+    pub fn with_comment(mut self, comment: &'q str) -> Self {
+        self.inner = self.inner.with_comment(comment);
+        self
+    }
+}
+
 // FIXME: This is very close, nearly 1:1 with `Map`
 // noinspection DuplicatedCode
 impl<'q, DB, O, A> QueryScalar<'q, DB, O, A>
@@ -0,0 +1,285 @@
+//! Automatic reconnection for standalone (non-pooled) connections.
+
+use std::fmt::{self, Debug, Formatter};
+use std::io;
+use std::sync::Arc;
+
+use futures_core::future::BoxFuture;
+
+use crate::connection::{ConnectOptions, Connection};
+use crate::database::Database;
+use crate::error::Error;
+use crate::transaction::TransactionManager;
+
+type AfterConnect<C> =
+    Arc<dyn for<'c> Fn(&'c mut C) -> BoxFuture<'c, Result<(), Error>> + Send + Sync + 'static>;
+
+/// A single connection that transparently re-establishes itself after a transient failure.
+///
+/// Pools already replace broken connections, but a long-lived standalone connection
+/// (e.g. a dedicated worker connection) is dead for good after a network blip.
+/// `ReconnectingConnection` keeps the [`ConnectOptions`] it was created with and,
+/// when an operation fails with a transient connection error, discards the broken
+/// connection and opens a new one the next time it is needed.
+///
+/// Operations are run through [`run()`][Self::run] or, if they are safe to repeat,
+/// [`run_idempotent()`][Self::run_idempotent] which additionally retries the operation once
+/// on a fresh connection.
+///
+/// Errors which are not related to the connection itself (e.g. errors returned by the database)
+/// are always returned as-is and never cause reconnection.
+///
+/// ### Transactions
+/// An open transaction cannot be transparently resumed on a new connection. If the connection
+/// is inside a transaction when the operation is started, the error is returned to the caller
+/// instead of retrying, though the connection will still be re-established on the next use.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example() -> sqlx::Result<()> {
+/// use sqlx::postgres::{PgConnectOptions, PgConnection};
+/// use sqlx::ReconnectingConnection;
+///
+/// let options: PgConnectOptions = "postgres://localhost/mydb".parse()?;
+///
+/// let mut conn = ReconnectingConnection::<PgConnection>::new(options).after_connect(|conn| {
+///     Box::pin(async move {
+///         sqlx::query("SET application_name = 'worker'")
+///             .execute(conn)
+///             .await?;
+///
+///         Ok(())
+///     })
+/// });
+///
+/// let count: i64 = conn
+///     .run_idempotent(|conn| {
+///         Box::pin(async move {
+///             sqlx::query_scalar("SELECT count(*) FROM jobs")
+///                 .fetch_one(conn)
+///                 .await
+///         })
+///     })
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ReconnectingConnection<C: Connection> {
+    options: C::Options,
+    after_connect: Option<AfterConnect<C>>,
+    conn: Option<C>,
+}
+
+impl<C: Connection> ReconnectingConnection<C> {
+    /// Create a new `ReconnectingConnection` using the given options.
+    ///
+    /// The connection is established lazily on first use.
+    pub fn new(options: C::Options) -> Self {
+        Self {
+            options,
+            after_connect: None,
+            conn: None,
+        }
+    }
+
+    /// Establish a connection immediately using the given options.
+    pub async fn connect_with(options: C::Options) -> Result<Self, Error> {
+        let mut this = Self::new(options);
+        this.connection().await?;
+        Ok(this)
+    }
+
+    /// Perform an asynchronous action after every connection is established,
+    /// including reconnections.
+    ///
+    /// Use this to (re-)apply session setup such as `SET` statements, which do not carry over
+    /// to a new connection.
+    ///
+    /// If the callback returns an error, the new connection is discarded and the error
+    /// is returned.
+    pub fn after_connect<F>(mut self, callback: F) -> Self
+    where
+        for<'c> F: Fn(&'c mut C) -> BoxFuture<'c, Result<(), Error>> + Send + Sync + 'static,
+    {
+        self.after_connect = Some(Arc::new(callback));
+        self
+    }
+
+    /// Get the options used to (re-)establish the connection.
+    pub fn connect_options(&self) -> &C::Options {
+        &self.options
+    }
+
+    /// Returns `true` if a connection is currently established.
+    ///
+    /// This does not check if the connection is still usable; see [`Connection::ping()`].
+    pub fn is_connected(&self) -> bool {
+        self.conn.is_some()
+    }
+
+    /// Get a mutable reference to the underlying connection, establishing it first if necessary.
+    ///
+    /// Errors encountered while using the connection directly are not intercepted;
+    /// use [`run()`][Self::run] to have a broken connection replaced automatically.
+    pub async fn connection(&mut self) -> Result<&mut C, Error> {
+        if self.conn.is_none() {
+            let mut conn = self.options.connect().await?;
+
+            if let Some(after_connect) = &self.after_connect {
+                if let Err(e) = after_connect(&mut conn).await {
+                    let _ = conn.close_hard().await;
+                    return Err(e);
+                }
+            }
+
+            self.conn = Some(conn);
+        }
+
+        Ok(self
+            .conn
+            .as_mut()
+            .expect("BUG: connection should have been established"))
+    }
+
+    /// Run an operation on the connection without retrying it.
+    ///
+    /// If the operation fails with a transient connection error, the connection is discarded
+    /// and re-established the next time it is used, but the error is still returned.
+    ///
+    /// Use this for operations which are not safe to repeat, like non-idempotent writes.
+    pub async fn run<R, F>(&mut self, f: F) -> Result<R, Error>
+    where
+        for<'c> F: FnOnce(&'c mut C) -> BoxFuture<'c, Result<R, Error>>,
+    {
+        let res = f(self.connection().await?).await;
+        self.check_error(res)
+    }
+
+    /// Run an operation on the connection, retrying it once on a new connection if it fails
+    /// with a transient connection error.
+    ///
+    /// The operation is not retried if the connection was inside a transaction when the
+    /// operation was started.
+    ///
+    /// Only use this for operations which are safe to repeat. It is possible for
+    /// the first attempt to have been executed by the server before the connection was lost.
+    pub async fn run_idempotent<R, F>(&mut self, mut f: F) -> Result<R, Error>
+    where
+        C::Database: Database<Connection = C>,
+        for<'c> F: FnMut(&'c mut C) -> BoxFuture<'c, Result<R, Error>>,
+    {
+        let conn = self.connection().await?;
+        let in_transaction =
+            <C::Database as Database>::TransactionManager::get_transaction_depth(conn) > 0;
+
+        let res = f(conn).await;
+
+        match self.check_error(res) {
+            Err(e) if !in_transaction && is_transient_connection_error(&e) => {
+                let res = f(self.connection().await?).await;
+                self.check_error(res)
+            }
+            res => res,
+        }
+    }
+
+    /// Explicitly close the underlying connection, if one is established.
+    ///
+    /// See [`Connection::close()`] for details.
+    pub async fn close(mut self) -> Result<(), Error> {
+        match self.conn.take() {
+            Some(conn) => conn.close().await,
+            None => Ok(()),
+        }
+    }
+
+    fn check_error<R>(&mut self, res: Result<R, Error>) -> Result<R, Error> {
+        if let Err(e) = &res {
+            if is_transient_connection_error(e) {
+                // The connection is assumed to be broken; drop it and reconnect on next use.
+                self.conn = None;
+            }
+        }
+
+        res
+    }
+}
+
+impl<C: Connection> Debug for ReconnectingConnection<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReconnectingConnection")
+            .field("options", &self.options)
+            .field("connected", &self.conn.is_some())
+            .finish()
+    }
+}
+
+/// Returns `true` if the error means that the connection was lost, or couldn't be established,
+/// for a reason which may not happen again.
+///
+/// This is used both to reconnect ([`ReconnectingConnection`]) and to retry establishing a
+/// connection ([`ConnectOptions::connect_with_retry()`][crate::connection::ConnectOptions::connect_with_retry]).
+pub(crate) fn is_transient_connection_error(e: &Error) -> bool {
+    match e {
+        // the connection was refused, reset or timed out, the host name couldn't be resolved..
+        Error::Io(e) => !matches!(
+            e.kind(),
+            io::ErrorKind::PermissionDenied | io::ErrorKind::Unsupported
+        ),
+        Error::WorkerCrashed => true,
+        _ => match e.as_database_error().and_then(|e| e.code()) {
+            // Class `08` are connection exceptions (e.g. MySQL's "too many connections"),
+            // except for `08P01`, Postgres' `protocol_violation`, which would happen again.
+            // `57P01` to `57P03` are sent by Postgres when the server is shutting down,
+            // restarting or still starting up, and `53300` is its `too_many_connections`.
+            Some(code) => {
+                matches!(&*code, "57P01" | "57P02" | "57P03" | "53300")
+                    || (code.starts_with("08") && code != "08P01")
+            }
+            None => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_transient_connection_error;
+    use crate::error::Error;
+    use std::io;
+
+    #[test]
+    fn test_is_transient_connection_error() {
+        assert!(is_transient_connection_error(&Error::Io(
+            io::ErrorKind::ConnectionReset.into()
+        )));
+        assert!(is_transient_connection_error(&Error::Io(
+            io::ErrorKind::UnexpectedEof.into()
+        )));
+        assert!(is_transient_connection_error(&Error::WorkerCrashed));
+
+        assert!(!is_transient_connection_error(&Error::Io(
+            io::ErrorKind::PermissionDenied.into()
+        )));
+        assert!(!is_transient_connection_error(&Error::RowNotFound));
+        assert!(!is_transient_connection_error(&Error::PoolTimedOut));
+    }
+
+    #[test]
+    fn test_is_transient_connection_error_while_connecting() {
+        assert!(is_transient_connection_error(&Error::Io(
+            io::ErrorKind::ConnectionRefused.into()
+        )));
+        assert!(is_transient_connection_error(&Error::Io(io::Error::new(
+            io::ErrorKind::Other,
+            "failed to lookup address information"
+        ))));
+
+        assert!(!is_transient_connection_error(&Error::Configuration(
+            "invalid port".into()
+        )));
+        assert!(!is_transient_connection_error(&Error::Tls(
+            "bad certificate".into()
+        )));
+    }
+}
@@ -0,0 +1,567 @@
+//! Recording of query workloads, to replay them against another database (requires the
+//! `recording` feature).
+//!
+//! A [`QueryRecorder`] writes the queries of an application to a file, with their timing and
+//! outcome. The recording can then be re-executed with [`replay()`], e.g. to benchmark a schema
+//! change against production-like traffic.
+//!
+//! ### Format
+//! A recording is a file of [JSON Lines](https://jsonlines.org/), with one object per query,
+//! in the order in which the queries finished:
+//!
+//! | Field | Description |
+//! |-------|-------------|
+//! | `sql` | The SQL of the query. |
+//! | `prepared` | Whether the query was prepared, i.e. whether it had arguments. |
+//! | `persistent` | Whether the prepared statement was cached. |
+//! | `arguments` | The arguments of the query, in a driver-specific format. Only present if [captured][QueryRecorder::capture_arguments]. |
+//! | `started_us` | When the query started, in microseconds since the recorder was created. |
+//! | `duration_us` | The time taken by the query, until its last result was received, in microseconds. |
+//! | `rows_affected` | The number of rows affected by the query. |
+//! | `rows_returned` | The number of rows returned by the query. |
+//! | `error` | The error returned by the query, if any. |
+//!
+//! ### Limitations
+//! * Transactions are not started or ended through executors, so `BEGIN`, `COMMIT` and
+//!   `ROLLBACK` are not recorded. The queries executed in a transaction are replayed outside
+//!   of it.
+//! * The queries of concurrent tasks (e.g. through a pool) are recorded as they finish, and
+//!   replayed one at a time on a single connection.
+//! * Queries which are dropped before completing are recorded with the results received so
+//!   far, but are replayed to completion.
+
+use std::fmt::{self, Debug, Formatter};
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::{BoxStream, Stream};
+use futures_util::{StreamExt, TryStreamExt};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::arguments::Arguments;
+use crate::connection::Connection;
+use crate::database::{Database, HasArguments, HasStatement};
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::intercept::{InterceptedExecutor, Interceptor};
+use crate::sync::lock_poisoned;
+
+/// A database whose queries can be recorded by a [`QueryRecorder`] and [replayed][replay()].
+pub trait RecordDatabase: Database {
+    /// The serializable form of the arguments of a query.
+    type RecordedArguments: Serialize + DeserializeOwned + Send + 'static;
+
+    /// Capture the arguments of a query, as they were encoded.
+    fn record_arguments(
+        arguments: &<Self as HasArguments<'_>>::Arguments,
+    ) -> Self::RecordedArguments;
+
+    /// Rebuild the arguments of a query from their recorded form.
+    fn replay_arguments<'q>(
+        recorded: Self::RecordedArguments,
+    ) -> Result<<Self as HasArguments<'q>>::Arguments, Error>;
+
+    /// Returns the number of rows affected by a query.
+    fn rows_affected(result: &Self::QueryResult) -> u64;
+}
+
+/// Records the queries executed through the executors it wraps.
+///
+/// Only the queries executed through the [`Executor`]s it [wraps][Self::wrap] are recorded,
+/// so a pool should be wrapped with `recorder.wrap(&pool)` wherever it is used. Each query
+/// is written to the output of the recorder once it completes (see the
+/// [module documentation][self] for the format). The recorder is cheap to clone, and all its
+/// clones write to the same output.
+///
+/// The arguments of the queries may contain sensitive data, so they are only recorded if
+/// enabled with [`capture_arguments()`][Self::capture_arguments]. Without them, the queries
+/// which have parameters fail when replayed.
+///
+/// The records are written synchronously, so the output should be buffered (as with
+/// [`create()`][Self::create]). Writing errors don't fail the queries, and are returned by
+/// [`flush()`][Self::flush] instead.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example() -> sqlx::Result<()> {
+/// use sqlx::recording::QueryRecorder;
+/// use sqlx::PgPool;
+///
+/// let pool = PgPool::connect("postgres:// …").await?;
+/// let recorder = QueryRecorder::create("workload.jsonl")?.capture_arguments(true);
+///
+/// sqlx::query("SELECT * FROM users WHERE id = $1")
+///     .bind(42_i64)
+///     .fetch_optional(recorder.wrap(&pool))
+///     .await?;
+///
+/// recorder.flush()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct QueryRecorder {
+    shared: Arc<RecorderShared>,
+    capture_arguments: bool,
+}
+
+struct RecorderShared {
+    started: Instant,
+    output: Mutex<Output>,
+}
+
+struct Output {
+    writer: Box<dyn Write + Send>,
+    // the first error which occurred while writing
+    error: Option<io::Error>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Record<A> {
+    sql: String,
+    prepared: bool,
+    persistent: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    arguments: Option<A>,
+    started_us: u64,
+    duration_us: u64,
+    rows_affected: u64,
+    rows_returned: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl QueryRecorder {
+    /// Create a recorder which writes to `writer`.
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        QueryRecorder {
+            shared: Arc::new(RecorderShared {
+                started: Instant::now(),
+                output: Mutex::new(Output {
+                    writer: Box::new(writer),
+                    error: None,
+                }),
+            }),
+            capture_arguments: false,
+        }
+    }
+
+    /// Create a recorder which writes to the file at `path`, replacing it if it exists.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+
+    /// Set whether to record the arguments of the queries.
+    ///
+    /// This is disabled by default, as the arguments may contain sensitive data. It applies to
+    /// the executors wrapped by this recorder afterwards.
+    pub fn capture_arguments(mut self, capture: bool) -> Self {
+        self.capture_arguments = capture;
+        self
+    }
+
+    /// Wrap an executor, so the queries executed through it are recorded.
+    pub fn wrap<E>(&self, executor: E) -> RecordingExecutor<E> {
+        InterceptedExecutor::new(executor, self.clone())
+    }
+
+    /// Flush the output of the recorder.
+    ///
+    /// Returns the first error which occurred while writing the records since the last call,
+    /// if any.
+    pub fn flush(&self) -> io::Result<()> {
+        let mut output = self.output();
+
+        if let Some(error) = output.error.take() {
+            return Err(error);
+        }
+
+        output.writer.flush()
+    }
+
+    fn output(&self) -> MutexGuard<'_, Output> {
+        lock_poisoned(&self.shared.output)
+    }
+
+    fn start<DB: RecordDatabase>(
+        &self,
+        sql: &str,
+        persistent: bool,
+        arguments: Option<&<DB as HasArguments<'_>>::Arguments>,
+    ) -> PendingRecord<DB::RecordedArguments> {
+        let started = Instant::now();
+
+        PendingRecord {
+            recorder: self.clone(),
+            started,
+            record: Record {
+                sql: sql.to_owned(),
+                prepared: arguments.is_some(),
+                persistent,
+                arguments: arguments
+                    .filter(|_| self.capture_arguments)
+                    .map(DB::record_arguments),
+                started_us: micros(started.duration_since(self.shared.started)),
+                duration_us: 0,
+                rows_affected: 0,
+                rows_returned: 0,
+                error: None,
+            },
+        }
+    }
+
+    fn write<A: Serialize>(&self, record: &Record<A>) {
+        let mut output = self.output();
+
+        let res = serde_json::to_writer(&mut output.writer, record)
+            .map_err(io::Error::from)
+            .and_then(|_| output.writer.write_all(b"\n"));
+
+        if let Err(e) = res {
+            output.error.get_or_insert(e);
+        }
+    }
+}
+
+impl Debug for QueryRecorder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryRecorder")
+            .field("capture_arguments", &self.capture_arguments)
+            .finish_non_exhaustive()
+    }
+}
+
+fn micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
+
+// A query being executed, which is recorded once dropped
+struct PendingRecord<A: Serialize> {
+    recorder: QueryRecorder,
+    started: Instant,
+    record: Record<A>,
+}
+
+impl<A: Serialize> Drop for PendingRecord<A> {
+    fn drop(&mut self) {
+        self.record.duration_us = micros(self.started.elapsed());
+        self.recorder.write(&self.record);
+    }
+}
+
+// A query with its arguments already taken
+struct WithArguments<Q, A> {
+    query: Q,
+    arguments: Option<A>,
+}
+
+impl<'q, DB, Q> Execute<'q, DB> for WithArguments<Q, <DB as HasArguments<'q>>::Arguments>
+where
+    DB: Database,
+    Q: Execute<'q, DB>,
+{
+    fn sql(&self) -> &'q str {
+        self.query.sql()
+    }
+
+    fn statement(&self) -> Option<&<DB as HasStatement<'q>>::Statement> {
+        self.query.statement()
+    }
+
+    fn take_arguments(&mut self) -> Option<<DB as HasArguments<'q>>::Arguments> {
+        self.arguments.take()
+    }
+
+    fn persistent(&self) -> bool {
+        self.query.persistent()
+    }
+
+    fn selected_columns(&self) -> Option<&'q [&'q str]> {
+        self.query.selected_columns()
+    }
+
+    fn comment(&self) -> Option<&'q str> {
+        self.query.comment()
+    }
+
+    fn clone_for_retry(&self) -> Option<Self> {
+        Some(WithArguments {
+            query: self.query.clone_for_retry()?,
+            arguments: match &self.arguments {
+                Some(arguments) => Some(arguments.try_clone()?),
+                None => None,
+            },
+        })
+    }
+}
+
+/// An executor wrapped by [`QueryRecorder::wrap()`].
+pub type RecordingExecutor<E> = InterceptedExecutor<E, QueryRecorder>;
+
+impl<DB: RecordDatabase> Interceptor<DB> for QueryRecorder {
+    fn fetch_many<'c, 'e, 'q: 'e, E, Q>(
+        &self,
+        executor: E,
+        mut query: Q,
+    ) -> BoxStream<'e, Result<Either<DB::QueryResult, DB::Row>, Error>>
+    where
+        'c: 'e,
+        E: Executor<'c, Database = DB>,
+        Q: 'q + Execute<'q, DB>,
+    {
+        let arguments = query.take_arguments();
+        let pending = self.start::<DB>(query.sql(), query.persistent(), arguments.as_ref());
+
+        Box::pin(RecordingStream::<DB> {
+            inner: executor.fetch_many(WithArguments { query, arguments }),
+            pending: Some(pending),
+        })
+    }
+
+    fn fetch_optional<'c, 'e, 'q: 'e, E, Q>(
+        &self,
+        executor: E,
+        mut query: Q,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>
+    where
+        'c: 'e,
+        E: Executor<'c, Database = DB>,
+        Q: 'q + Execute<'q, DB>,
+    {
+        let arguments = query.take_arguments();
+        let pending = self.start::<DB>(query.sql(), query.persistent(), arguments.as_ref());
+
+        let result = executor.fetch_optional(WithArguments { query, arguments });
+
+        Box::pin(async move {
+            // move the whole record into the future, so it's written once the query completes
+            let mut pending = pending;
+            let result = result.await;
+
+            match &result {
+                Ok(row) => pending.record.rows_returned = u64::from(row.is_some()),
+                Err(e) => pending.record.error = Some(e.to_string()),
+            }
+
+            result
+        })
+    }
+}
+
+// The results of a query, counted until the query completes or is dropped
+struct RecordingStream<'e, DB: RecordDatabase> {
+    inner: BoxStream<'e, Result<Either<DB::QueryResult, DB::Row>, Error>>,
+    pending: Option<PendingRecord<DB::RecordedArguments>>,
+}
+
+// The inner stream is boxed, and the record is never pinned
+impl<DB: RecordDatabase> Unpin for RecordingStream<'_, DB> {}
+
+impl<DB: RecordDatabase> Stream for RecordingStream<'_, DB> {
+    type Item = Result<Either<DB::QueryResult, DB::Row>, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let next = match self.inner.poll_next_unpin(cx) {
+            Poll::Ready(next) => next,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        if let Some(pending) = &mut self.pending {
+            match &next {
+                Some(Ok(Either::Left(result))) => {
+                    pending.record.rows_affected += DB::rows_affected(result);
+                }
+                Some(Ok(Either::Right(_))) => pending.record.rows_returned += 1,
+                Some(Err(e)) => pending.record.error = Some(e.to_string()),
+                None => {}
+            }
+        }
+
+        if !matches!(next, Some(Ok(_))) {
+            // record the query as soon as it completes
+            self.pending = None;
+        }
+
+        Poll::Ready(next)
+    }
+}
+
+/// The outcome of a replayed query, compared with its recording.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ReplayedQuery {
+    /// The SQL of the query.
+    pub sql: String,
+    /// The time taken by the query when it was recorded.
+    pub recorded_duration: Duration,
+    /// The time taken by the query when it was replayed.
+    pub duration: Duration,
+    /// The number of rows affected by the query when it was replayed.
+    pub rows_affected: u64,
+    /// The number of rows returned by the query when it was replayed.
+    pub rows_returned: u64,
+    /// The error returned by the query when it was recorded, if any.
+    pub recorded_error: Option<String>,
+    /// The error returned by the query when it was replayed, if any.
+    pub error: Option<Error>,
+}
+
+/// The outcome of [`replay()`].
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct ReplayReport {
+    /// The replayed queries, in order.
+    pub queries: Vec<ReplayedQuery>,
+}
+
+impl ReplayReport {
+    /// Returns the total time taken by the queries when they were recorded.
+    pub fn recorded_duration(&self) -> Duration {
+        self.queries.iter().map(|q| q.recorded_duration).sum()
+    }
+
+    /// Returns the total time taken by the queries when they were replayed.
+    pub fn duration(&self) -> Duration {
+        self.queries.iter().map(|q| q.duration).sum()
+    }
+
+    /// Returns the queries which failed when they were replayed.
+    pub fn errors(&self) -> impl Iterator<Item = &ReplayedQuery> {
+        self.queries.iter().filter(|q| q.error.is_some())
+    }
+}
+
+// A recorded query to replay
+struct ReplayQuery<'q, DB: Database> {
+    sql: &'q str,
+    arguments: Option<<DB as HasArguments<'q>>::Arguments>,
+    persistent: bool,
+}
+
+impl<'q, DB: Database> Execute<'q, DB> for ReplayQuery<'q, DB> {
+    fn sql(&self) -> &'q str {
+        self.sql
+    }
+
+    fn statement(&self) -> Option<&<DB as HasStatement<'q>>::Statement> {
+        None
+    }
+
+    fn take_arguments(&mut self) -> Option<<DB as HasArguments<'q>>::Arguments> {
+        self.arguments.take()
+    }
+
+    fn persistent(&self) -> bool {
+        self.persistent
+    }
+}
+
+/// Replay a recording made by a [`QueryRecorder`] on `conn`.
+///
+/// The queries are executed one at a time, in the order in which they were recorded and as fast
+/// as possible. A query which fails doesn't stop the replay: its error is returned in the
+/// report, to be compared with the recorded outcome. Queries which have parameters fail if
+/// their arguments were not [captured][QueryRecorder::capture_arguments].
+///
+/// Returns an error if reading the recording fails or if it is malformed.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example() -> sqlx::Result<()> {
+/// use std::fs::File;
+/// use std::io::BufReader;
+///
+/// use sqlx::recording::replay;
+/// use sqlx::{Connection, PgConnection};
+///
+/// let mut conn = PgConnection::connect("postgres:// …").await?;
+/// let report = replay(&mut conn, BufReader::new(File::open("workload.jsonl")?)).await?;
+///
+/// println!(
+///     "replayed {} queries in {:?} (recorded: {:?}), {} failed",
+///     report.queries.len(),
+///     report.duration(),
+///     report.recorded_duration(),
+///     report.errors().count(),
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub async fn replay<C, R>(conn: &mut C, recording: R) -> Result<ReplayReport, Error>
+where
+    C: Connection,
+    C::Database: RecordDatabase,
+    R: BufRead,
+    for<'c> &'c mut C: Executor<'c, Database = C::Database>,
+{
+    let mut report = ReplayReport::default();
+
+    for (line_no, line) in recording.lines().enumerate() {
+        let line = line?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record: Record<<C::Database as RecordDatabase>::RecordedArguments> =
+            serde_json::from_str(&line).map_err(|e| {
+                Error::Decode(format!("invalid record on line {}: {e}", line_no + 1).into())
+            })?;
+
+        let arguments = match (record.prepared, record.arguments) {
+            (false, _) => None,
+            (true, Some(arguments)) => Some(C::Database::replay_arguments(arguments)?),
+            (true, None) => Some(Default::default()),
+        };
+
+        let mut replayed = ReplayedQuery {
+            sql: String::new(),
+            recorded_duration: Duration::from_micros(record.duration_us),
+            duration: Duration::ZERO,
+            rows_affected: 0,
+            rows_returned: 0,
+            recorded_error: record.error,
+            error: None,
+        };
+
+        let started = Instant::now();
+
+        let mut results = (&mut *conn).fetch_many(ReplayQuery::<C::Database> {
+            sql: &record.sql,
+            arguments,
+            persistent: record.persistent,
+        });
+
+        loop {
+            match results.try_next().await {
+                Ok(Some(Either::Left(result))) => {
+                    replayed.rows_affected += C::Database::rows_affected(&result)
+                }
+                Ok(Some(Either::Right(_))) => replayed.rows_returned += 1,
+                Ok(None) => break,
+                Err(e) => {
+                    replayed.error = Some(e);
+                    break;
+                }
+            }
+        }
+
+        drop(results);
+
+        replayed.duration = started.elapsed();
+        replayed.sql = record.sql;
+        report.queries.push(replayed);
+    }
+
+    Ok(report)
+}
@@ -131,6 +131,46 @@ pub trait Row: Unpin + Send + Sync + 'static {
         })
     }
 
+    /// Index into the database row and decode a single value, or return `default` if it is
+    /// `NULL`.
+    ///
+    /// This is the same as `try_get::<Option<T>, _>(index)` followed by `unwrap_or(default)`,
+    /// which is mostly useful for aggregates: `SUM`, `AVG`, `MAX` and the like are `NULL` over no
+    /// rows or only `NULL` values, e.g. `SELECT SUM(amount) FROM orders` with no orders returns a
+    /// single row, where the sum is `NULL` rather than `0`.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use sqlx::Row;
+    ///
+    /// let row = sqlx::query("SELECT SUM(amount) FROM orders")
+    ///     .fetch_one(conn)
+    ///     .await?;
+    ///
+    /// let total: i64 = row.try_get_or(0, 0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    ///  * [`ColumnNotFound`] if the column by the given name was not found.
+    ///  * [`ColumnIndexOutOfBounds`] if the `usize` index was greater than the number of columns in the row.
+    ///  * [`ColumnDecode`] if the value could not be decoded into the requested type.
+    ///
+    /// [`ColumnDecode`]: Error::ColumnDecode
+    /// [`ColumnNotFound`]: Error::ColumnNotFound
+    /// [`ColumnIndexOutOfBounds`]: Error::ColumnIndexOutOfBounds
+    ///
+    #[inline]
+    fn try_get_or<'r, T, I>(&'r self, index: I, default: T) -> Result<T, Error>
+    where
+        I: ColumnIndex<Self>,
+        T: Decode<'r, Self::Database> + Type<Self::Database>,
+    {
+        Ok(self.try_get::<Option<T>, I>(index)?.unwrap_or(default))
+    }
+
     /// Index into the database row and decode a single value.
     ///
     /// Unlike [`try_get`](Self::try_get), this method does not check that the type
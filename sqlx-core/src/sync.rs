@@ -143,3 +143,11 @@ impl AsyncSemaphoreReleaser<'_> {
         crate::rt::missing_rt(())
     }
 }
+
+/// Lock a [`std::sync::Mutex`], even if a thread panicked while holding it.
+///
+/// Only for data which a panic can't leave in an inconsistent state, e.g. because it's only
+/// ever replaced or updated with a single, non-panicking operation.
+pub fn lock_poisoned<T>(mutex: &std::sync::Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
@@ -3,6 +3,7 @@ use std::fmt::{self, Debug, Formatter};
 use std::ops::{Deref, DerefMut};
 
 use futures_core::future::BoxFuture;
+use futures_util::future;
 
 use crate::database::Database;
 use crate::error::Error;
@@ -20,6 +21,26 @@ pub trait TransactionManager {
         conn: &mut <Self::Database as Database>::Connection,
     ) -> BoxFuture<'_, Result<(), Error>>;
 
+    /// Begin a new transaction with the given options, or establish a savepoint within the
+    /// active transaction.
+    ///
+    /// Savepoints have no options: an error is returned if the options are not the default
+    /// ones when a transaction is already active, or if the database can't express them.
+    ///
+    /// The default implementation only supports the default options, with [`begin()`](Self::begin).
+    fn begin_with(
+        conn: &mut <Self::Database as Database>::Connection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        if options.is_default() {
+            return Self::begin(conn);
+        }
+
+        Box::pin(future::ready(Err(Error::Configuration(
+            "transaction options are not supported by this database".into(),
+        ))))
+    }
+
     /// Commit the active transaction or release the most recent savepoint.
     fn commit(
         conn: &mut <Self::Database as Database>::Connection,
@@ -32,6 +53,141 @@ pub trait TransactionManager {
 
     /// Starts to abort the active transaction or restore from the most recent snapshot.
     fn start_rollback(conn: &mut <Self::Database as Database>::Connection);
+
+    /// Returns the current transaction depth.
+    ///
+    /// Transaction depth indicates the level of nested transactions:
+    /// - Level 0: No active transaction.
+    /// - Level 1: A transaction is active.
+    /// - Level 2 or higher: A transaction is active and one or more SAVEPOINTs have been created within it.
+    ///
+    /// The default implementation always returns `0`, for drivers which don't track it.
+    /// Such drivers should override it, as no transaction being active means, for instance,
+    /// that [`ReconnectingConnection::run_idempotent()`] may retry an operation.
+    ///
+    /// [`ReconnectingConnection::run_idempotent()`]: crate::reconnect::ReconnectingConnection::run_idempotent
+    fn get_transaction_depth(_conn: &<Self::Database as Database>::Connection) -> usize {
+        0
+    }
+}
+
+/// The isolation level of a transaction, from the SQL standard.
+///
+/// See [`TransactionOptions::isolation_level()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IsolationLevel {
+    /// The changes not committed yet by other transactions may be seen.
+    ///
+    /// Behaves like `ReadCommitted` in PostgreSQL, which doesn't implement dirty reads.
+    ReadUncommitted,
+
+    /// Only the changes committed before each statement of the transaction started are seen.
+    ReadCommitted,
+
+    /// Only the changes committed before the transaction started are seen.
+    RepeatableRead,
+
+    /// The transaction behaves as if it was executed alone, after or before any other
+    /// concurrent transaction. A transaction which can't be serialized fails instead.
+    Serializable,
+}
+
+impl IsolationLevel {
+    /// Returns the name of the isolation level in SQL, e.g. `REPEATABLE READ`.
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+/// Options to begin a transaction with, see [`Connection::begin_with()`].
+///
+/// The default options leave the characteristics of the transaction to the database, as with
+/// [`Connection::begin()`]. Each database only supports some of the options: an error is
+/// returned when beginning the transaction if they can't be expressed, instead of ignoring
+/// them.
+///
+/// [`Connection::begin()`]: crate::connection::Connection::begin()
+/// [`Connection::begin_with()`]: crate::connection::Connection::begin_with()
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::{Connection, IsolationLevel, TransactionOptions};
+///
+/// let mut tx = conn
+///     .begin_with(
+///         TransactionOptions::new()
+///             .isolation_level(IsolationLevel::Serializable)
+///             .read_only(true),
+///     )
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct TransactionOptions {
+    isolation_level: Option<IsolationLevel>,
+    read_only: bool,
+    deferrable: bool,
+}
+
+impl TransactionOptions {
+    /// Creates the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the isolation level of the transaction.
+    ///
+    /// By default, the default isolation level of the database or session is used.
+    pub fn isolation_level(mut self, isolation_level: IsolationLevel) -> Self {
+        self.isolation_level = Some(isolation_level);
+        self
+    }
+
+    /// Sets whether the transaction is read-only, in which case it can't modify tables.
+    ///
+    /// Not supported by SQLite. The default is `false`.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets whether the transaction is deferrable.
+    ///
+    /// Only supported by PostgreSQL, where it only has an effect on `SERIALIZABLE` and read-only
+    /// transactions: these wait until they can run without any risk of a serialization failure.
+    /// The default is `false`.
+    pub fn deferrable(mut self, deferrable: bool) -> Self {
+        self.deferrable = deferrable;
+        self
+    }
+
+    /// Returns the isolation level of the transaction, if set.
+    pub fn get_isolation_level(&self) -> Option<IsolationLevel> {
+        self.isolation_level
+    }
+
+    /// Returns `true` if the transaction is read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns `true` if the transaction is deferrable.
+    pub fn is_deferrable(&self) -> bool {
+        self.deferrable
+    }
+
+    /// Returns `true` if these are the default options.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 /// An in-progress database transaction or savepoint.
@@ -65,11 +221,19 @@ where
     #[doc(hidden)]
     pub fn begin(
         conn: impl Into<MaybePoolConnection<'c, DB>>,
+    ) -> BoxFuture<'c, Result<Self, Error>> {
+        Self::begin_with(conn, TransactionOptions::default())
+    }
+
+    #[doc(hidden)]
+    pub fn begin_with(
+        conn: impl Into<MaybePoolConnection<'c, DB>>,
+        options: TransactionOptions,
     ) -> BoxFuture<'c, Result<Self, Error>> {
         let mut conn = conn.into();
 
         Box::pin(async move {
-            DB::TransactionManager::begin(&mut conn).await?;
+            DB::TransactionManager::begin_with(&mut conn, options).await?;
 
             Ok(Self {
                 connection: conn,
@@ -93,6 +257,59 @@ where
 
         Ok(())
     }
+
+    /// Run the function in a savepoint of this transaction.
+    ///
+    /// The savepoint is released if the function returns `Ok`, and rolled back if it returns
+    /// `Err`. Either way, this transaction can still be used afterwards: on error, only the
+    /// changes made by the function are undone, which allows recovering from the failure of
+    /// an operation without aborting the whole transaction.
+    ///
+    /// Errors of the function are returned as-is. Failing to establish, release or roll back the
+    /// savepoint is returned as an error converted from [`Error`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+    /// use sqlx::Executor;
+    ///
+    /// let mut tx = pool.begin().await?;
+    ///
+    /// let res = tx
+    ///     .with_savepoint(|sp| Box::pin(async move {
+    ///         sp.execute("INSERT INTO users (email) VALUES ('taken@example.com')").await
+    ///     }))
+    ///     .await;
+    ///
+    /// if res.is_err() {
+    ///     // the failed insert was rolled back, the transaction can go on
+    ///     tx.execute("INSERT INTO users (email) VALUES ('other@example.com')").await?;
+    /// }
+    ///
+    /// tx.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_savepoint<F, R, E>(&mut self, callback: F) -> Result<R, E>
+    where
+        for<'t> F: FnOnce(&'t mut Transaction<'_, DB>) -> BoxFuture<'t, Result<R, E>> + Send,
+        R: Send,
+        E: From<Error> + Send,
+    {
+        let mut savepoint = Transaction::begin(&mut **self).await?;
+
+        match callback(&mut savepoint).await {
+            Ok(ret) => {
+                savepoint.commit().await?;
+                Ok(ret)
+            }
+            Err(e) => {
+                savepoint.rollback().await?;
+                Err(e)
+            }
+        }
+    }
 }
 
 // NOTE: fails to compile due to lack of lazy normalization
@@ -240,6 +457,20 @@ where
     }
 }
 
+/// Returns an error if a savepoint would be established with non-default options, as
+/// savepoints have none.
+pub fn check_savepoint_options(depth: usize, options: &TransactionOptions) -> Result<(), Error> {
+    if depth > 0 && !options.is_default() {
+        return Err(Error::Configuration(
+            "transaction options cannot be used within an active transaction, \
+             which begins a savepoint"
+                .into(),
+        ));
+    }
+
+    Ok(())
+}
+
 pub fn begin_ansi_transaction_sql(depth: usize) -> Cow<'static, str> {
     if depth == 0 {
         Cow::Borrowed("BEGIN")
@@ -56,6 +56,16 @@ pub mod time {
     pub use time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
 }
 
+#[cfg(feature = "jiff")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
+pub mod jiff {
+    #[doc(no_inline)]
+    pub use jiff::civil::{Date, DateTime, Time};
+
+    #[doc(no_inline)]
+    pub use jiff::Timestamp;
+}
+
 #[cfg(feature = "bigdecimal")]
 #[cfg_attr(docsrs, doc(cfg(feature = "bigdecimal")))]
 #[doc(no_inline)]
@@ -155,6 +165,9 @@ pub use text::Text;
 ///   `VARCHAR`). Affects Postgres only.
 /// * `#[sqlx(rename_all = "<strategy>")]` on struct definition: See [`derive docs in FromRow`](crate::from_row::FromRow#rename_all)
 /// * `#[sqlx(no_pg_array)]`: do not emit a `PgHasArrayType` impl (see above).
+/// * `#[sqlx(range_type_name = "<SQL range type name>")]`: emit a `PgHasRangeType` impl, so that
+///   `PgRange<T>` can be used for a range type over this type. Also supported on enumerations and
+///   composite structs. Affects Postgres only.
 ///
 /// ### Enumeration
 ///
@@ -178,6 +191,65 @@ pub use text::Text;
 /// enum Color { Red, Green, Blue }
 /// ```
 ///
+/// ##### Unknown values
+///
+/// Decoding a value matching none of the variants fails, e.g. when another application added
+/// a value to the SQL enumeration which this one doesn't know yet. To decode it instead, add a
+/// variant with `#[sqlx(other)]` and a single field, which holds the value: a `String` (or
+/// another type converting from `&str`) without `#[repr(_)]`, or the integer type with. It is
+/// encoded back as that value.
+///
+/// With `#[repr(_)]`, the other variants need an explicit discriminant, as an enumeration with a
+/// field can't be cast to its integer type.
+///
+/// ```rust,ignore
+/// #[derive(sqlx::Type)]
+/// #[sqlx(type_name = "color", rename_all = "lowercase")]
+/// enum Color {
+///     Red,
+///     Green,
+///     Blue,
+///     #[sqlx(other)]
+///     Unknown(String),
+/// }
+///
+/// #[derive(sqlx::Type)]
+/// #[repr(i32)]
+/// enum Priority {
+///     Low = 1,
+///     High = 2,
+///     #[sqlx(other)]
+///     Unknown(i32),
+/// }
+/// ```
+///
+/// ##### Arrays (PostgreSQL)
+///
+/// Arrays of user-defined types (e.g. `Vec<Color>`) require a `PgHasArrayType` impl, which gives
+/// the name of the array type. `#[sqlx(array)]` emits it; Postgres names array types after their
+/// element type with a leading underscore, which is the name it uses (e.g. `_color`, or
+/// `app._color` for `app.color`):
+///
+/// ```rust,ignore
+/// #[derive(sqlx::Type)]
+/// #[sqlx(type_name = "color", rename_all = "lowercase", array)]
+/// enum Color { Red, Green, Blue }
+///
+/// let colors: Vec<Color> = sqlx::query_scalar("SELECT '{red, blue}'::color[]")
+///     .fetch_one(&mut pg_connection)
+///     .await?;
+///
+/// let rows = sqlx::query("SELECT * FROM paint WHERE color = ANY($1)")
+///     .bind(vec![Color::Red, Color::Blue])
+///     .fetch_all(&mut pg_connection)
+///     .await?;
+/// ```
+///
+/// `#[sqlx(array_type_name = "<SQL array type name>")]` emits it with another name.
+///
+/// This is also supported on records, and on newtypes of domain types (which have a `type_name`
+/// but are not `transparent`).
+///
 /// ### Records
 ///
 /// User-defined composite types are supported through deriving a `struct`.
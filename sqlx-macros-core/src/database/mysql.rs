@@ -44,6 +44,18 @@ impl_database_ext! {
         #[cfg(feature = "time")]
         sqlx::types::time::OffsetDateTime,
 
+        #[cfg(feature = "jiff")]
+        sqlx::types::jiff::Time,
+
+        #[cfg(feature = "jiff")]
+        sqlx::types::jiff::Date,
+
+        #[cfg(feature = "jiff")]
+        sqlx::types::jiff::DateTime,
+
+        #[cfg(feature = "jiff")]
+        sqlx::types::jiff::Timestamp,
+
         #[cfg(feature = "bigdecimal")]
         sqlx::types::BigDecimal,
 
@@ -23,6 +23,16 @@ impl_database_ext! {
 
         sqlx::postgres::types::PgLQuery,
 
+        sqlx::postgres::types::PgLsn,
+
+        sqlx::postgres::types::PgXid,
+
+        sqlx::postgres::types::PgXid8,
+
+        sqlx::postgres::types::PgTxidSnapshot,
+
+        sqlx::postgres::types::PgTid,
+
         #[cfg(feature = "uuid")]
         sqlx::types::Uuid,
 
@@ -56,6 +66,18 @@ impl_database_ext! {
         #[cfg(feature = "time")]
         sqlx::postgres::types::PgTimeTz<sqlx::types::time::Time, sqlx::types::time::UtcOffset>,
 
+        #[cfg(feature = "jiff")]
+        sqlx::types::jiff::Time,
+
+        #[cfg(feature = "jiff")]
+        sqlx::types::jiff::Date,
+
+        #[cfg(feature = "jiff")]
+        sqlx::types::jiff::DateTime,
+
+        #[cfg(feature = "jiff")]
+        sqlx::types::jiff::Timestamp,
+
         #[cfg(feature = "bigdecimal")]
         sqlx::types::BigDecimal,
 
@@ -87,6 +109,9 @@ impl_database_ext! {
         Vec<f64> | &[f64],
         Vec<sqlx::postgres::types::Oid> | &[sqlx::postgres::types::Oid],
         Vec<sqlx::postgres::types::PgMoney> | &[sqlx::postgres::types::PgMoney],
+        Vec<sqlx::postgres::types::PgLsn> | &[sqlx::postgres::types::PgLsn],
+        Vec<sqlx::postgres::types::PgXid> | &[sqlx::postgres::types::PgXid],
+        Vec<sqlx::postgres::types::PgXid8> | &[sqlx::postgres::types::PgXid8],
 
         #[cfg(feature = "uuid")]
         Vec<sqlx::types::Uuid> | &[sqlx::types::Uuid],
@@ -115,6 +140,18 @@ impl_database_ext! {
         #[cfg(feature = "time")]
         Vec<sqlx::types::time::OffsetDateTime> | &[sqlx::types::time::OffsetDateTime],
 
+        #[cfg(feature = "jiff")]
+        Vec<sqlx::types::jiff::Time> | &[sqlx::types::jiff::Time],
+
+        #[cfg(feature = "jiff")]
+        Vec<sqlx::types::jiff::Date> | &[sqlx::types::jiff::Date],
+
+        #[cfg(feature = "jiff")]
+        Vec<sqlx::types::jiff::DateTime> | &[sqlx::types::jiff::DateTime],
+
+        #[cfg(feature = "jiff")]
+        Vec<sqlx::types::jiff::Timestamp> | &[sqlx::types::jiff::Timestamp],
+
         #[cfg(feature = "bigdecimal")]
         Vec<sqlx::types::BigDecimal> | &[sqlx::types::BigDecimal],
 
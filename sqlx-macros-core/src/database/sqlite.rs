@@ -30,6 +30,15 @@ impl_database_ext! {
         #[cfg(feature = "time")]
         sqlx::types::time::Date,
 
+        #[cfg(feature = "jiff")]
+        sqlx::types::jiff::Timestamp,
+
+        #[cfg(feature = "jiff")]
+        sqlx::types::jiff::DateTime,
+
+        #[cfg(feature = "jiff")]
+        sqlx::types::jiff::Date,
+
         #[cfg(feature = "uuid")]
         sqlx::types::Uuid,
     },
@@ -1,8 +1,8 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
 use syn::{
-    punctuated::Punctuated, spanned::Spanned, token::Comma, Attribute, DeriveInput, Field, Lit,
-    Meta, MetaNameValue, NestedMeta, Type, Variant,
+    punctuated::Punctuated, spanned::Spanned, token::Comma, Attribute, DeriveInput, Field, Fields,
+    Lit, Meta, MetaNameValue, NestedMeta, Type, Variant,
 };
 
 macro_rules! assert_attribute {
@@ -54,6 +54,9 @@ pub enum RenameAll {
 pub struct SqlxContainerAttributes {
     pub transparent: bool,
     pub type_name: Option<TypeName>,
+    pub range_type_name: Option<TypeName>,
+    pub array_type_name: Option<TypeName>,
+    pub array: bool,
     pub rename_all: Option<RenameAll>,
     pub repr: Option<Ident>,
     pub no_pg_array: bool,
@@ -67,12 +70,18 @@ pub struct SqlxChildAttributes {
     pub try_from: Option<Type>,
     pub skip: bool,
     pub json: bool,
+    pub from_columns: Option<Vec<String>>,
+    pub skip_if_null: Option<String>,
+    pub other: bool,
 }
 
 pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContainerAttributes> {
     let mut transparent = None;
     let mut repr = None;
     let mut type_name = None;
+    let mut range_type_name = None;
+    let mut array_type_name = None;
+    let mut array = None;
     let mut rename_all = None;
     let mut no_pg_array = None;
     let mut default = None;
@@ -97,6 +106,10 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
                                 try_set!(no_pg_array, true, value);
                             }
 
+                            Meta::Path(p) if p.is_ident("array") => {
+                                try_set!(array, true, value);
+                            }
+
                             Meta::NameValue(MetaNameValue {
                                 path,
                                 lit: Lit::Str(val),
@@ -131,6 +144,36 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
                                 )
                             }
 
+                            Meta::NameValue(MetaNameValue {
+                                path,
+                                lit: Lit::Str(val),
+                                ..
+                            }) if path.is_ident("range_type_name") => {
+                                try_set!(
+                                    range_type_name,
+                                    TypeName {
+                                        val: val.value(),
+                                        span: value.span(),
+                                    },
+                                    value
+                                )
+                            }
+
+                            Meta::NameValue(MetaNameValue {
+                                path,
+                                lit: Lit::Str(val),
+                                ..
+                            }) if path.is_ident("array_type_name") => {
+                                try_set!(
+                                    array_type_name,
+                                    TypeName {
+                                        val: val.value(),
+                                        span: value.span(),
+                                    },
+                                    value
+                                )
+                            }
+
                             Meta::Path(p) if p.is_ident("default") => {
                                 try_set!(default, true, value)
                             }
@@ -160,6 +203,9 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
         transparent: transparent.unwrap_or(false),
         repr,
         type_name,
+        range_type_name,
+        array_type_name,
+        array: array.unwrap_or(false),
         rename_all,
         no_pg_array: no_pg_array.unwrap_or(false),
         default: default.unwrap_or(false),
@@ -173,6 +219,9 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
     let mut flatten = false;
     let mut skip: bool = false;
     let mut json = false;
+    let mut from_columns = None;
+    let mut skip_if_null = None;
+    let mut other = false;
 
     for attr in input.iter().filter(|a| a.path.is_ident("sqlx")) {
         let meta = attr
@@ -193,10 +242,36 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
                             lit: Lit::Str(val),
                             ..
                         }) if path.is_ident("try_from") => try_set!(try_from, val.parse()?, value),
+                        Meta::NameValue(MetaNameValue {
+                            path,
+                            lit: Lit::Str(val),
+                            ..
+                        }) if path.is_ident("skip_if_null") => {
+                            try_set!(skip_if_null, val.clone(), value)
+                        }
                         Meta::Path(path) if path.is_ident("default") => default = true,
                         Meta::Path(path) if path.is_ident("flatten") => flatten = true,
                         Meta::Path(path) if path.is_ident("skip") => skip = true,
                         Meta::Path(path) if path.is_ident("json") => json = true,
+                        Meta::Path(path) if path.is_ident("other") => other = true,
+                        Meta::List(list) if list.path.is_ident("from_columns") => {
+                            let mut columns = Vec::new();
+
+                            for column in list.nested.iter() {
+                                match column {
+                                    NestedMeta::Lit(Lit::Str(column)) => {
+                                        columns.push(column.value())
+                                    }
+                                    u => fail!(u, "expected a column name"),
+                                }
+                            }
+
+                            if columns.is_empty() {
+                                fail!(list, "expected at least one column name");
+                            }
+
+                            try_set!(from_columns, columns, value)
+                        }
                         u => fail!(u, "unexpected attribute"),
                     },
                     u => fail!(u, "unexpected attribute"),
@@ -210,6 +285,19 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
                 "Cannot use `json` and `flatten` together on the same field"
             );
         }
+
+        if from_columns.is_some() && (json || flatten || rename.is_some()) {
+            fail!(
+                attr,
+                "Cannot use `from_columns` with `json`, `flatten` or `rename` on the same field"
+            );
+        }
+    }
+
+    if let Some(column) = &skip_if_null {
+        if !flatten {
+            fail!(column, "`skip_if_null` can only be used with `flatten`");
+        }
     }
 
     Ok(SqlxChildAttributes {
@@ -219,9 +307,52 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
         try_from,
         skip,
         json,
+        from_columns,
+        skip_if_null: skip_if_null.map(|column| column.value()),
+        other,
     })
 }
 
+/// Returns the variant of an enum with `#[sqlx(other)]`, which holds the values matching none of
+/// the other variants, if any.
+pub fn find_other_variant(
+    variants: &Punctuated<Variant, Comma>,
+) -> syn::Result<Option<(&Variant, &Type)>> {
+    let mut other = None;
+
+    for variant in variants {
+        let attributes = parse_child_attributes(&variant.attrs)?;
+
+        if !attributes.other {
+            continue;
+        }
+
+        assert_attribute!(
+            other.is_none(),
+            "duplicate #[sqlx(other)]; only one variant can hold the other values",
+            variant
+        );
+
+        assert_attribute!(
+            attributes.rename.is_none(),
+            "unexpected #[sqlx(rename = ..)] with #[sqlx(other)]",
+            variant
+        );
+
+        let ty = match &variant.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => fail!(
+                variant,
+                "expected one unnamed field holding the value with #[sqlx(other)], e.g. `Unknown(String)`"
+            ),
+        };
+
+        other = Some((variant, ty));
+    }
+
+    Ok(other)
+}
+
 pub fn check_transparent_attributes(
     input: &DeriveInput,
     field: &Field,
@@ -234,6 +365,18 @@ pub fn check_transparent_attributes(
         field
     );
 
+    assert_attribute!(
+        !(attributes.transparent && attributes.array_type_name.is_some()),
+        "unexpected #[sqlx(array_type_name = ..)]; the `PgHasArrayType` impl of transparent types delegates to the inner type",
+        field
+    );
+
+    assert_attribute!(
+        !(attributes.transparent && attributes.array),
+        "unexpected #[sqlx(array)]; the `PgHasArrayType` impl of transparent types delegates to the inner type",
+        field
+    );
+
     let ch_attributes = parse_child_attributes(&field.attrs)?;
 
     assert_attribute!(
@@ -242,6 +385,8 @@ pub fn check_transparent_attributes(
         field
     );
 
+    assert_attribute!(!ch_attributes.other, "unexpected #[sqlx(other)]", field);
+
     Ok(attributes)
 }
 
@@ -256,7 +401,7 @@ pub fn check_enum_attributes(input: &DeriveInput) -> syn::Result<SqlxContainerAt
 
     assert_attribute!(
         !attributes.no_pg_array,
-        "unused #[sqlx(no_pg_array)]; derive does not emit `PgHasArrayType` impls for enums",
+        "unused #[sqlx(no_pg_array)]; derive only emits `PgHasArrayType` impls for enums with #[sqlx(array)] or #[sqlx(array_type_name = ..)]",
         input
     );
 
@@ -271,12 +416,26 @@ pub fn check_weak_enum_attributes(
 
     assert_attribute!(attributes.repr.is_some(), "expected #[repr(..)]", input);
 
+    assert_attribute!(
+        attributes.array_type_name.is_none(),
+        "unexpected #[sqlx(array_type_name = ..)]; enums with #[repr(..)] are encoded as their integer type",
+        input
+    );
+
+    assert_attribute!(
+        !attributes.array,
+        "unexpected #[sqlx(array)]; enums with #[repr(..)] are encoded as their integer type",
+        input
+    );
+
     assert_attribute!(
         attributes.rename_all.is_none(),
         "unexpected #[sqlx(c = ..)]",
         input
     );
 
+    let other = find_other_variant(variants)?;
+
     for variant in variants {
         let attributes = parse_child_attributes(&variant.attrs)?;
 
@@ -285,6 +444,14 @@ pub fn check_weak_enum_attributes(
             "unexpected #[sqlx(rename = ..)]",
             variant
         );
+
+        // an enum with a field can't be cast to its integer type, so the values of its variants
+        // are taken from their discriminants
+        assert_attribute!(
+            other.is_none() || attributes.other || variant.discriminant.is_some(),
+            "expected an explicit discriminant with a #[sqlx(other)] variant, e.g. `Red = 1`",
+            variant
+        );
     }
 
     Ok(attributes)
@@ -292,10 +459,12 @@ pub fn check_weak_enum_attributes(
 
 pub fn check_strong_enum_attributes(
     input: &DeriveInput,
-    _variants: &Punctuated<Variant, Comma>,
+    variants: &Punctuated<Variant, Comma>,
 ) -> syn::Result<SqlxContainerAttributes> {
     let attributes = check_enum_attributes(input)?;
 
+    find_other_variant(variants)?;
+
     assert_attribute!(attributes.repr.is_none(), "unexpected #[repr(..)]", input);
 
     Ok(attributes)
@@ -321,7 +490,7 @@ pub fn check_struct_attributes<'a>(
 
     assert_attribute!(
         !attributes.no_pg_array,
-        "unused #[sqlx(no_pg_array)]; derive does not emit `PgHasArrayType` impls for custom structs",
+        "unused #[sqlx(no_pg_array)]; derive only emits `PgHasArrayType` impls for custom structs with #[sqlx(array)] or #[sqlx(array_type_name = ..)]",
         input
     );
 
@@ -335,6 +504,8 @@ pub fn check_struct_attributes<'a>(
             "unexpected #[sqlx(rename = ..)]",
             field
         );
+
+        assert_attribute!(!attributes.other, "unexpected #[sqlx(other)]", field);
     }
 
     Ok(attributes)
@@ -1,6 +1,7 @@
 use super::attributes::{
     check_strong_enum_attributes, check_struct_attributes, check_transparent_attributes,
-    check_weak_enum_attributes, parse_child_attributes, parse_container_attributes,
+    check_weak_enum_attributes, find_other_variant, parse_child_attributes,
+    parse_container_attributes,
 };
 use super::rename_all;
 use proc_macro2::TokenStream;
@@ -101,16 +102,37 @@ fn expand_derive_decode_weak_enum(
     let ident = &input.ident;
     let ident_s = ident.to_string();
 
+    let other = find_other_variant(variants)?;
+
     let arms = variants
         .iter()
+        .filter(|v| !matches!(other, Some((other, _)) if other.ident == v.ident))
         .map(|v| {
             let id = &v.ident;
-            parse_quote! {
-                _ if (#ident::#id as #repr) == value => ::std::result::Result::Ok(#ident::#id),
+
+            match (&other, &v.discriminant) {
+                (Some(_), Some((_, discriminant))) => parse_quote! {
+                    _ if (#discriminant) == value => ::std::result::Result::Ok(#ident::#id),
+                },
+                _ => parse_quote! {
+                    _ if (#ident::#id as #repr) == value => ::std::result::Result::Ok(#ident::#id),
+                },
             }
         })
         .collect::<Vec<Arm>>();
 
+    let fallback = match other {
+        Some((other, _)) => {
+            let id = &other.ident;
+            quote!(_ => ::std::result::Result::Ok(#ident::#id(value)))
+        }
+        None => quote!(
+            _ => ::std::result::Result::Err(::std::boxed::Box::new(::sqlx::Error::Decode(
+                ::std::format!("invalid value {:?} for enum {}", value, #ident_s).into(),
+            )))
+        ),
+    };
+
     Ok(quote!(
         #[automatically_derived]
         impl<'r, DB: ::sqlx::Database> ::sqlx::decode::Decode<'r, DB> for #ident
@@ -129,9 +151,7 @@ fn expand_derive_decode_weak_enum(
 
                 match value {
                     #(#arms)*
-                    _ => ::std::result::Result::Err(::std::boxed::Box::new(::sqlx::Error::Decode(
-                        ::std::format!("invalid value {:?} for enum {}", value, #ident_s).into(),
-                    )))
+                    #fallback
                 }
             }
         }
@@ -147,27 +167,40 @@ fn expand_derive_decode_strong_enum(
     let ident = &input.ident;
     let ident_s = ident.to_string();
 
-    let value_arms = variants.iter().map(|v| -> Arm {
-        let id = &v.ident;
-        let attributes = parse_child_attributes(&v.attrs).unwrap();
+    let other = find_other_variant(variants)?;
+
+    let value_arms = variants
+        .iter()
+        .filter(|v| !matches!(other, Some((other, _)) if other.ident == v.ident))
+        .map(|v| -> Arm {
+            let id = &v.ident;
+            let attributes = parse_child_attributes(&v.attrs).unwrap();
+
+            if let Some(rename) = attributes.rename {
+                parse_quote!(#rename => ::std::result::Result::Ok(#ident :: #id),)
+            } else if let Some(pattern) = cattr.rename_all {
+                let name = rename_all(&*id.to_string(), pattern);
 
-        if let Some(rename) = attributes.rename {
-            parse_quote!(#rename => ::std::result::Result::Ok(#ident :: #id),)
-        } else if let Some(pattern) = cattr.rename_all {
-            let name = rename_all(&*id.to_string(), pattern);
+                parse_quote!(#name => ::std::result::Result::Ok(#ident :: #id),)
+            } else {
+                let name = id.to_string();
+                parse_quote!(#name => ::std::result::Result::Ok(#ident :: #id),)
+            }
+        });
 
-            parse_quote!(#name => ::std::result::Result::Ok(#ident :: #id),)
-        } else {
-            let name = id.to_string();
-            parse_quote!(#name => ::std::result::Result::Ok(#ident :: #id),)
+    let fallback = match other {
+        Some((other, _)) => {
+            let id = &other.ident;
+            quote!(_ => ::std::result::Result::Ok(#ident::#id(::std::convert::From::from(value))))
         }
-    });
+        None => quote!(_ => Err(format!("invalid value {:?} for enum {}", value, #ident_s).into())),
+    };
 
     let values = quote! {
         match value {
             #(#value_arms)*
 
-            _ => Err(format!("invalid value {:?} for enum {}", value, #ident_s).into())
+            #fallback
         }
     };
 
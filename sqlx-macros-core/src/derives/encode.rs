@@ -1,6 +1,7 @@
 use super::attributes::{
     check_strong_enum_attributes, check_struct_attributes, check_transparent_attributes,
-    check_weak_enum_attributes, parse_child_attributes, parse_container_attributes,
+    check_weak_enum_attributes, find_other_variant, parse_child_attributes,
+    parse_container_attributes,
 };
 use super::rename_all;
 use proc_macro2::{Span, TokenStream};
@@ -108,11 +109,22 @@ fn expand_derive_encode_weak_enum(
     let repr = attr.repr.unwrap();
     let ident = &input.ident;
 
+    let other = find_other_variant(variants)?;
+
     let mut values = Vec::new();
 
     for v in variants {
         let id = &v.ident;
-        values.push(quote!(#ident :: #id => (#ident :: #id as #repr),));
+
+        match (&other, &v.discriminant) {
+            (Some((other, _)), _) if other.ident == v.ident => {
+                values.push(quote!(#ident :: #id(value) => *value,));
+            }
+            (Some(_), Some((_, discriminant))) => {
+                values.push(quote!(#ident :: #id => #discriminant,));
+            }
+            _ => values.push(quote!(#ident :: #id => (#ident :: #id as #repr),)),
+        }
     }
 
     Ok(quote!(
@@ -125,7 +137,7 @@ fn expand_derive_encode_weak_enum(
                 &self,
                 buf: &mut <DB as ::sqlx::database::HasArguments<'q>>::ArgumentBuffer,
             ) -> ::sqlx::encode::IsNull {
-                let value = match self {
+                let value: #repr = match self {
                     #(#values)*
                 };
 
@@ -147,13 +159,17 @@ fn expand_derive_encode_strong_enum(
 
     let ident = &input.ident;
 
+    let other = find_other_variant(variants)?;
+
     let mut value_arms = Vec::new();
 
     for v in variants {
         let id = &v.ident;
         let attributes = parse_child_attributes(&v.attrs)?;
 
-        if let Some(rename) = attributes.rename {
+        if attributes.other {
+            continue;
+        } else if let Some(rename) = attributes.rename {
             value_arms.push(quote!(#ident :: #id => #rename,));
         } else if let Some(pattern) = cattr.rename_all {
             let name = rename_all(&*id.to_string(), pattern);
@@ -165,11 +181,30 @@ fn expand_derive_encode_strong_enum(
         }
     }
 
+    // the value of the other variant is encoded as its own type, which has to encode as a string
+    let (other_predicate, other_encode_arm, other_size_hint_arm) = match other {
+        Some((other, ty)) => {
+            let id = &other.ident;
+
+            (
+                quote!(#ty: ::sqlx::encode::Encode<'q, DB>,),
+                quote!(#ident :: #id(value) => {
+                    return <#ty as ::sqlx::encode::Encode<'q, DB>>::encode_by_ref(value, buf);
+                }),
+                quote!(#ident :: #id(value) => {
+                    return <#ty as ::sqlx::encode::Encode<'q, DB>>::size_hint(value);
+                }),
+            )
+        }
+        None => Default::default(),
+    };
+
     Ok(quote!(
         #[automatically_derived]
         impl<'q, DB: ::sqlx::Database> ::sqlx::encode::Encode<'q, DB> for #ident
         where
             &'q ::std::primitive::str: ::sqlx::encode::Encode<'q, DB>,
+            #other_predicate
         {
             fn encode_by_ref(
                 &self,
@@ -177,6 +212,7 @@ fn expand_derive_encode_strong_enum(
             ) -> ::sqlx::encode::IsNull {
                 let val = match self {
                     #(#value_arms)*
+                    #other_encode_arm
                 };
 
                 <&::std::primitive::str as ::sqlx::encode::Encode<'q, DB>>::encode(val, buf)
@@ -185,6 +221,7 @@ fn expand_derive_encode_strong_enum(
             fn size_hint(&self) -> ::std::primitive::usize {
                 let val = match self {
                     #(#value_arms)*
+                    #other_size_hint_arm
                 };
 
                 <&::std::primitive::str as ::sqlx::encode::Encode<'q, DB>>::size_hint(&val)
@@ -2,11 +2,15 @@ use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{
     parse_quote, punctuated::Punctuated, token::Comma, Data, DataStruct, DeriveInput, Expr, Field,
-    Fields, FieldsNamed, FieldsUnnamed, Lifetime, Stmt,
+    Fields, FieldsNamed, FieldsUnnamed, GenericArgument, Lifetime, PathArguments, Stmt, Type,
+    WherePredicate,
 };
 
 use super::{
-    attributes::{parse_child_attributes, parse_container_attributes},
+    attributes::{
+        parse_child_attributes, parse_container_attributes, SqlxChildAttributes,
+        SqlxContainerAttributes,
+    },
     rename_all,
 };
 
@@ -76,6 +80,27 @@ fn expand_derive_from_row_struct(
         default_instance = None;
     }
 
+    // the types to decode the columns of `from_columns` fields into are checked upfront, as
+    // errors can't be returned while expanding the fields
+    for field in fields {
+        let attributes = parse_child_attributes(&field.attrs)?;
+
+        if let Some(columns) = &attributes.from_columns {
+            from_columns_types(field, attributes.try_from.as_ref(), columns)?;
+        }
+
+        if attributes.skip_if_null.is_some()
+            && (attributes.try_from.is_some() || option_inner_type(&field.ty).is_none())
+        {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                "`skip_if_null` expects a field of type `Option<T>`, without `try_from`",
+            ));
+        }
+    }
+
+    let column_names = expand_column_names(fields, &container_attributes, &lifetime);
+
     let reads: Vec<Stmt> = fields
         .iter()
         .filter_map(|field| -> Option<Stmt> {
@@ -89,16 +114,25 @@ fn expand_derive_from_row_struct(
                 ));
             }
 
-            let id_s = attributes
-                .rename
-                .or_else(|| Some(id.to_string().trim_start_matches("r#").to_owned()))
-                .map(|s| match container_attributes.rename_all {
-                    Some(pattern) => rename_all(&s, pattern),
-                    None => s,
-                })
-                .unwrap();
+            let id_s = column_name(field, &attributes, &container_attributes);
 
             let expr: Expr = match (attributes.flatten, attributes.try_from, attributes.json) {
+                // From columns
+                (_, try_from, _) if attributes.from_columns.is_some() => {
+                    let columns = attributes.from_columns.as_deref().unwrap();
+                    expand_from_columns(field, try_from.as_ref(), columns, &lifetime, predicates)
+                }
+                // Flatten into an `Option`
+                (true, None, false) if option_inner_type(ty).is_some() => {
+                    let inner = option_inner_type(ty).unwrap();
+                    let skip_if_null: Expr = match &attributes.skip_if_null {
+                        Some(column) => parse_quote!(::std::option::Option::Some(#column)),
+                        None => parse_quote!(::std::option::Option::None),
+                    };
+
+                    predicates.push(parse_quote!(#inner: ::sqlx::FromRow<#lifetime, R>));
+                    parse_quote!(::sqlx::__from_row_flatten_option::<R, #inner>(row, #skip_if_null))
+                }
                 // <No attributes>
                 (false, None, false) => {
                     predicates
@@ -190,10 +224,165 @@ fn expand_derive_from_row_struct(
                     #(#names),*
                 })
             }
+
+            fn column_names() -> ::std::option::Option<::std::vec::Vec<&'static ::std::primitive::str>> {
+                #[allow(unused_mut)]
+                let mut columns = ::std::vec::Vec::new();
+
+                #(#column_names)*
+
+                ::std::option::Option::Some(columns)
+            }
         }
     ))
 }
 
+/// Returns the name of the column of a field, unless it is flattened or decoded from several
+/// columns.
+fn column_name(
+    field: &Field,
+    attributes: &SqlxChildAttributes,
+    container_attributes: &SqlxContainerAttributes,
+) -> String {
+    let name = match &attributes.rename {
+        Some(rename) => rename.clone(),
+        None => field
+            .ident
+            .as_ref()
+            .unwrap()
+            .to_string()
+            .trim_start_matches("r#")
+            .to_owned(),
+    };
+
+    match container_attributes.rename_all {
+        Some(pattern) => rename_all(&name, pattern),
+        None => name,
+    }
+}
+
+/// Returns the statements pushing the names of the columns of the fields to `columns`, for
+/// `FromRow::column_names()`.
+fn expand_column_names(
+    fields: &Punctuated<Field, Comma>,
+    container_attributes: &SqlxContainerAttributes,
+    lifetime: &Lifetime,
+) -> Vec<Stmt> {
+    fields
+        .iter()
+        .filter_map(|field| -> Option<Stmt> {
+            let attributes = parse_child_attributes(&field.attrs).unwrap();
+
+            if attributes.skip {
+                return None;
+            }
+
+            if let Some(columns) = &attributes.from_columns {
+                return Some(parse_quote!(columns.extend([#(#columns),*]);));
+            }
+
+            if !attributes.flatten {
+                let column = column_name(field, &attributes, container_attributes);
+
+                return Some(parse_quote!(columns.push(#column);));
+            }
+
+            if let Some(column) = &attributes.skip_if_null {
+                return Some(parse_quote!(columns.push(#column);));
+            }
+
+            let nested = match (&attributes.try_from, option_inner_type(&field.ty)) {
+                (Some(try_from), _) => try_from,
+                (None, Some(inner)) => inner,
+                (None, None) => &field.ty,
+            };
+
+            Some(parse_quote!(
+                columns.extend(<#nested as ::sqlx::FromRow<#lifetime, R>>::column_names()?);
+            ))
+        })
+        .collect()
+}
+
+/// Returns `T` if `ty` is `Option<T>`.
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+
+    let segment = path.path.segments.last()?;
+
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+
+    match arguments.args.first() {
+        Some(GenericArgument::Type(inner)) if arguments.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
+/// Returns the types of the columns of a `#[sqlx(from_columns(..))]` field, from the tuple it is
+/// decoded into: its `try_from` type, or otherwise its own type.
+fn from_columns_types<'a>(
+    field: &'a Field,
+    try_from: Option<&'a Type>,
+    columns: &[String],
+) -> syn::Result<Vec<&'a Type>> {
+    let decoded = try_from.unwrap_or(&field.ty);
+
+    match decoded {
+        Type::Tuple(tuple) if tuple.elems.len() == columns.len() => Ok(tuple.elems.iter().collect()),
+        _ => Err(syn::Error::new_spanned(
+            decoded,
+            format!(
+                "`from_columns` with {} columns expects a tuple of {} types, as the type of the field \
+                 or with `try_from`",
+                columns.len(),
+                columns.len()
+            ),
+        )),
+    }
+}
+
+fn expand_from_columns(
+    field: &Field,
+    try_from: Option<&Type>,
+    columns: &[String],
+    lifetime: &Lifetime,
+    predicates: &mut Punctuated<WherePredicate, Comma>,
+) -> Expr {
+    let ty = &field.ty;
+    let types = from_columns_types(field, try_from, columns).unwrap();
+
+    for column_ty in &types {
+        predicates.push(parse_quote!(#column_ty: ::sqlx::decode::Decode<#lifetime, R::Database>));
+        predicates.push(parse_quote!(#column_ty: ::sqlx::types::Type<R::Database>));
+    }
+
+    let decoded = try_from.unwrap_or(ty);
+    let decode: Expr = parse_quote!(
+        (|| ::std::result::Result::<#decoded, ::sqlx::Error>::Ok((
+            #(row.try_get::<#types, _>(#columns)?,)*
+        )))()
+    );
+
+    match try_from {
+        Some(try_from) => parse_quote!(
+            #decode.and_then(|v| {
+                <#ty as ::std::convert::TryFrom<#try_from>>::try_from(v).map_err(|_| {
+                    ::sqlx::Error::ColumnNotFound("FromRow: try_from failed".to_string())
+                })
+            })
+        ),
+        None => decode,
+    }
+}
+
 fn expand_derive_from_row_struct_unnamed(
     input: &DeriveInput,
     fields: &Punctuated<Field, Comma>,
@@ -1,6 +1,6 @@
 use super::attributes::{
     check_strong_enum_attributes, check_struct_attributes, check_transparent_attributes,
-    check_weak_enum_attributes, parse_container_attributes, TypeName,
+    check_weak_enum_attributes, parse_container_attributes, SqlxContainerAttributes, TypeName,
 };
 use proc_macro2::{Ident, TokenStream};
 use quote::{quote, quote_spanned};
@@ -102,6 +102,8 @@ fn expand_derive_has_sql_type_transparent(
             ));
         }
 
+        tokens.extend(expand_pg_range_type(input, &attr));
+
         return Ok(tokens);
     }
 
@@ -120,6 +122,9 @@ fn expand_derive_has_sql_type_transparent(
         ));
     }
 
+    tts.extend(expand_pg_array_type(input, &attr));
+    tts.extend(expand_pg_range_type(input, &attr));
+
     Ok(tts)
 }
 
@@ -128,9 +133,9 @@ fn expand_derive_has_sql_type_weak_enum(
     variants: &Punctuated<Variant, Comma>,
 ) -> syn::Result<TokenStream> {
     let attr = check_weak_enum_attributes(input, variants)?;
-    let repr = attr.repr.unwrap();
+    let repr = attr.repr.as_ref().unwrap();
     let ident = &input.ident;
-    let ts = quote!(
+    let mut ts = quote!(
         #[automatically_derived]
         impl<DB: ::sqlx::Database> ::sqlx::Type<DB> for #ident
         where
@@ -146,6 +151,8 @@ fn expand_derive_has_sql_type_weak_enum(
         }
     );
 
+    ts.extend(expand_pg_range_type(input, &attr));
+
     Ok(ts)
 }
 
@@ -186,6 +193,9 @@ fn expand_derive_has_sql_type_strong_enum(
         ));
     }
 
+    tts.extend(expand_pg_array_type(input, &attributes));
+    tts.extend(expand_pg_range_type(input, &attributes));
+
     if cfg!(feature = "sqlite") {
         tts.extend(quote!(
             #[automatically_derived]
@@ -226,9 +236,92 @@ fn expand_derive_has_sql_type_struct(
         ));
     }
 
+    tts.extend(expand_pg_array_type(input, &attributes));
+    tts.extend(expand_pg_range_type(input, &attributes));
+
     Ok(tts)
 }
 
+/// Implement `PgHasArrayType` if `array` or an `array_type_name` is given, so `Vec<T>` and other
+/// arrays can be used.
+fn expand_pg_array_type(input: &DeriveInput, attr: &SqlxContainerAttributes) -> TokenStream {
+    if !cfg!(feature = "postgres") || attr.no_pg_array {
+        return TokenStream::new();
+    }
+
+    let default_name;
+    let array_type_name = match &attr.array_type_name {
+        Some(array_type_name) => array_type_name,
+        None if attr.array => {
+            default_name = TypeName {
+                val: default_array_type_name(
+                    &attr
+                        .type_name
+                        .as_ref()
+                        .map_or(input.ident.to_string(), |tn| tn.val.clone()),
+                ),
+                span: input.ident.span(),
+            };
+            &default_name
+        }
+        None => return TokenStream::new(),
+    };
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let array_name = array_type_name.get();
+
+    quote_spanned!(array_type_name.span=>
+        #[automatically_derived]
+        impl #impl_generics ::sqlx::postgres::PgHasArrayType for #ident #ty_generics #where_clause {
+            fn array_type_info() -> ::sqlx::postgres::PgTypeInfo {
+                ::sqlx::postgres::PgTypeInfo::with_name(#array_name)
+            }
+        }
+    )
+}
+
+/// Implement `PgHasRangeType` if a `range_type_name` is given, so `PgRange<T>` can be used.
+fn expand_pg_range_type(input: &DeriveInput, attr: &SqlxContainerAttributes) -> TokenStream {
+    let Some(range_type_name) = &attr.range_type_name else {
+        return TokenStream::new();
+    };
+
+    if !cfg!(feature = "postgres") {
+        return TokenStream::new();
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let range_name = range_type_name.get();
+    // Postgres names the array type of a range after it, like for any other type
+    let range_array_name = default_array_type_name(&range_type_name.val);
+
+    quote_spanned!(range_type_name.span=>
+        #[automatically_derived]
+        impl #impl_generics ::sqlx::postgres::PgHasRangeType for #ident #ty_generics #where_clause {
+            fn range_type_info() -> ::sqlx::postgres::PgTypeInfo {
+                ::sqlx::postgres::PgTypeInfo::with_name(#range_name)
+            }
+
+            fn range_array_type_info() -> ::sqlx::postgres::PgTypeInfo {
+                ::sqlx::postgres::PgTypeInfo::with_name(#range_array_name)
+            }
+        }
+    )
+}
+
+/// The name Postgres gives to the array type of `type_name`, i.e. with a leading underscore
+/// after the schema if there is one (e.g. `app.mood` becomes `app._mood`).
+fn default_array_type_name(type_name: &str) -> String {
+    match type_name.rsplit_once('.') {
+        Some((schema, name)) => format!("{schema}._{name}"),
+        None => format!("_{type_name}"),
+    }
+}
+
 fn type_name(ident: &Ident, explicit_name: Option<&TypeName>) -> TokenStream {
     explicit_name.map(|tn| tn.get()).unwrap_or_else(|| {
         let s = ident.to_string();
@@ -56,19 +56,7 @@ pub fn quote_args<DB: DatabaseExt>(
                         // type ascription is deprecated
                         Some((ty, true)) => return Ok(create_warning(name.clone(), &ty, &expr)),
                         None => {
-                            DB::param_type_for_id(&param_ty)
-                                .ok_or_else(|| {
-                                    if let Some(feature_gate) = <DB as DatabaseExt>::get_feature_gate(&param_ty) {
-                                        format!(
-                                            "optional sqlx feature `{}` required for type {} of param #{}",
-                                            feature_gate,
-                                            param_ty,
-                                            i + 1,
-                                        )
-                                    } else {
-                                        format!("unsupported type {} for param #{}", param_ty, i + 1)
-                                    }
-                                })?
+                            param_type::<DB>(i, param_ty)?
                                 .parse::<TokenStream>()
                                 .map_err(|_| format!("Rust type mapping for {param_ty} not parsable"))?
 
@@ -116,6 +104,25 @@ pub fn quote_args<DB: DatabaseExt>(
     })
 }
 
+/// Returns the Rust type for the SQL type of the parameter, or an error message if not supported.
+pub(super) fn param_type<DB: DatabaseExt>(
+    i: usize,
+    param_ty: &DB::TypeInfo,
+) -> Result<&'static str, String> {
+    DB::param_type_for_id(param_ty).ok_or_else(|| {
+        if let Some(feature_gate) = <DB as DatabaseExt>::get_feature_gate(param_ty) {
+            format!(
+                "optional sqlx feature `{}` required for type {} of param #{}",
+                feature_gate,
+                param_ty,
+                i + 1,
+            )
+        } else {
+            format!("unsupported type {} for param #{}", param_ty, i + 1)
+        }
+    })
+}
+
 fn create_warning(name: Ident, ty: &Type, expr: &Expr) -> TokenStream {
     let Expr::Type(ExprType { expr: stripped, .. }) = expr else {
         return quote!();
@@ -0,0 +1,106 @@
+use std::fmt::Write;
+
+use either::Either;
+use sqlx_core::column::Column;
+use url::Url;
+
+use crate::database::DatabaseExt;
+use crate::query::args::param_type;
+use crate::query::output::explain_column;
+use crate::query::QueryDriver;
+
+/// Describe `query` against the database at `database_url`, and explain in a human-readable form
+/// what the query macros would see: the Rust types expected for its parameters, and the
+/// Rust types and nullability of its columns.
+///
+/// This is meant as a debugging aid when a query macro fails with an unclear error, e.g. to find
+/// out which column has a type that isn't supported. Column names can use the same overrides
+/// as in the macros (e.g. `SELECT id AS "id!"`).
+///
+/// Unlike the macros, this always connects to the database, regardless of `SQLX_OFFLINE`.
+///
+/// ```rust,no_run
+/// let explained = sqlx_macros_core::query::explain_for_macro(
+///     "SELECT id, name FROM users WHERE id = $1",
+///     &std::env::var("DATABASE_URL").unwrap(),
+///     sqlx_macros_core::FOSS_DRIVERS,
+/// )
+/// .unwrap();
+///
+/// println!("{explained}");
+/// // parameters:
+/// //     #1: i64
+/// // columns:
+/// //     1. "id": i64 (non-null)
+/// //     2. "name": Option<String> (nullable)
+/// ```
+pub fn explain_for_macro<'a>(
+    query: &str,
+    database_url: &str,
+    drivers: impl IntoIterator<Item = &'a QueryDriver>,
+) -> crate::Result<String> {
+    let database_url_parsed: Url = database_url.parse()?;
+
+    for driver in drivers {
+        if driver.url_schemes.contains(&database_url_parsed.scheme()) {
+            return (driver.explain)(query, database_url);
+        }
+    }
+
+    Err(format!(
+        "no database driver found matching URL scheme {:?}; the corresponding Cargo feature may need to be enabled",
+        database_url_parsed.scheme()
+    )
+    .into())
+}
+
+pub(super) fn explain_with<DB: DatabaseExt>(
+    query: &str,
+    database_url: &str,
+) -> crate::Result<String> {
+    let describe = DB::describe_blocking(query, database_url)?;
+
+    // `write!()` to a `String` cannot fail
+    let mut out = String::new();
+
+    match describe.parameters() {
+        Some(Either::Left(params)) if !params.is_empty() => {
+            out.push_str("parameters:\n");
+
+            for (i, param_ty) in params.iter().enumerate() {
+                let _ = match param_type::<DB>(i, param_ty) {
+                    Ok(ty) => writeln!(out, "    #{}: {ty}", i + 1),
+                    Err(e) => writeln!(out, "    #{}: error: {e}", i + 1),
+                };
+            }
+        }
+        Some(Either::Right(count)) if count > 0 => {
+            let _ = writeln!(
+                out,
+                "parameters: {count} (types are unknown, only the number of arguments is checked)"
+            );
+        }
+        Some(_) => out.push_str("parameters: none\n"),
+        None => out.push_str("parameters: unknown (not checked)\n"),
+    }
+
+    if describe.columns().is_empty() {
+        out.push_str("columns: none\n");
+    } else {
+        out.push_str("columns:\n");
+
+        for (i, column) in describe.columns().iter().enumerate() {
+            let _ = match explain_column(&describe, i) {
+                Ok((ty, nullability)) => writeln!(
+                    out,
+                    "    {}. {:?}: {ty} ({nullability})",
+                    i + 1,
+                    column.name()
+                ),
+                Err(e) => writeln!(out, "    {}. {:?}: error: {e}", i + 1, column.name()),
+            };
+        }
+    }
+
+    Ok(out)
+}
@@ -6,6 +6,7 @@ use once_cell::sync::Lazy;
 use proc_macro2::TokenStream;
 use syn::Type;
 
+pub use explain::explain_for_macro;
 pub use input::QueryMacroInput;
 use quote::{format_ident, quote};
 use sqlx_core::database::Database;
@@ -19,6 +20,7 @@ use url::Url;
 
 mod args;
 mod data;
+mod explain;
 mod input;
 mod output;
 
@@ -27,6 +29,7 @@ pub struct QueryDriver {
     db_name: &'static str,
     url_schemes: &'static [&'static str],
     expand: fn(QueryMacroInput, QueryDataSource) -> crate::Result<TokenStream>,
+    explain: fn(&str, &str) -> crate::Result<String>,
 }
 
 impl QueryDriver {
@@ -38,6 +41,7 @@ impl QueryDriver {
             db_name: DB::NAME,
             url_schemes: DB::URL_SCHEMES,
             expand: expand_with::<DB>,
+            explain: explain::explain_with::<DB>,
         }
     }
 }
@@ -85,7 +85,7 @@ fn column_to_rust<DB: DatabaseExt>(describe: &Describe<DB>, i: usize) -> crate::
     let column = &describe.columns()[i];
 
     // add raw prefix to all identifiers
-    let decl = ColumnDecl::parse(&column.name())
+    let decl = ColumnDecl::parse(column.name())
         .map_err(|e| format!("column name {:?} is invalid: {}", column.name(), e))?;
 
     let ColumnOverride { nullability, type_ } = decl.r#override;
@@ -220,37 +220,88 @@ pub fn quote_query_scalar<DB: DatabaseExt>(
 }
 
 fn get_column_type<DB: DatabaseExt>(i: usize, column: &DB::Column) -> TokenStream {
-    let type_info = &*column.type_info();
-
-    <DB as DatabaseExt>::return_type_for_id(&type_info).map_or_else(
-        || {
-            let message =
-                if let Some(feature_gate) = <DB as DatabaseExt>::get_feature_gate(&type_info) {
-                    format!(
-                        "optional sqlx feature `{feat}` required for type {ty} of {col}",
-                        ty = &type_info,
-                        feat = feature_gate,
-                        col = DisplayColumn {
-                            idx: i,
-                            name: &*column.name()
-                        }
-                    )
-                } else {
-                    format!(
-                        "unsupported type {ty} of {col}",
-                        ty = type_info,
-                        col = DisplayColumn {
-                            idx: i,
-                            name: &*column.name()
-                        }
-                    )
-                };
-            syn::Error::new(Span::call_site(), message).to_compile_error()
-        },
+    column_type::<DB>(i, column).map_or_else(
+        |message| syn::Error::new(Span::call_site(), message).to_compile_error(),
         |t| t.parse().unwrap(),
     )
 }
 
+/// Returns the Rust type for the SQL type of the column, or an error message if not supported.
+fn column_type<DB: DatabaseExt>(i: usize, column: &DB::Column) -> Result<&'static str, String> {
+    let type_info = &*column.type_info();
+
+    <DB as DatabaseExt>::return_type_for_id(&type_info).ok_or_else(|| {
+        if let Some(feature_gate) = <DB as DatabaseExt>::get_feature_gate(&type_info) {
+            format!(
+                "optional sqlx feature `{feat}` required for type {ty} of {col}",
+                ty = &type_info,
+                feat = feature_gate,
+                col = DisplayColumn {
+                    idx: i,
+                    name: &*column.name()
+                }
+            )
+        } else {
+            format!(
+                "unsupported type {ty} of {col}",
+                ty = type_info,
+                col = DisplayColumn {
+                    idx: i,
+                    name: &*column.name()
+                }
+            )
+        }
+    })
+}
+
+/// Returns the Rust type the query macros would use for the column, and a description
+/// of its nullability, for [`explain_for_macro()`][super::explain_for_macro].
+pub(super) fn explain_column<DB: DatabaseExt>(
+    describe: &Describe<DB>,
+    i: usize,
+) -> crate::Result<(String, &'static str)> {
+    let column = &describe.columns()[i];
+
+    let decl = ColumnDecl::parse(column.name())
+        .map_err(|e| format!("column name {:?} is invalid: {}", column.name(), e))?;
+
+    let ColumnOverride { nullability, type_ } = decl.r#override;
+
+    let (nullable, nullability) = match (nullability, describe.nullable(i)) {
+        (ColumnNullabilityOverride::NonNull, _) => (false, "overridden as non-null"),
+        (ColumnNullabilityOverride::Nullable, _) => (true, "overridden as nullable"),
+        (ColumnNullabilityOverride::None, Some(false)) => (false, "non-null"),
+        (ColumnNullabilityOverride::None, Some(true)) => (true, "nullable"),
+        (ColumnNullabilityOverride::None, None) => (true, "unknown, assumed nullable"),
+    };
+
+    let type_ = match type_ {
+        ColumnTypeOverride::Exact(type_) => display_tokens(type_.to_token_stream()),
+        ColumnTypeOverride::Wildcard => "_".to_string(),
+        ColumnTypeOverride::None => column_type::<DB>(i, column)?.to_string(),
+    };
+
+    if nullable {
+        Ok((format!("Option<{type_}>"), nullability))
+    } else {
+        Ok((type_, nullability))
+    }
+}
+
+/// Formats tokens of a type closer to how it would be written, e.g. `Vec<u8>` instead of
+/// `Vec < u8 >`.
+fn display_tokens(tokens: TokenStream) -> String {
+    tokens
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(":: ", "::")
+        .replace(" <", "<")
+        .replace("< ", "<")
+        .replace(" >", ">")
+        .replace(" ,", ",")
+        .replace("& ", "&")
+}
+
 impl ColumnDecl {
     fn parse(col_name: &str) -> crate::Result<Self> {
         // find the end of the identifier because we want to use our own logic to parse it
@@ -6,16 +6,16 @@ use crate::{
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
-use futures_util::{StreamExt, TryFutureExt, TryStreamExt};
+use futures_util::{pin_mut, TryStreamExt};
 use sqlx_core::any::{
     Any, AnyArguments, AnyColumn, AnyConnectOptions, AnyConnectionBackend, AnyQueryResult, AnyRow,
     AnyStatement, AnyTypeInfo, AnyTypeInfoKind,
 };
-use sqlx_core::connection::Connection;
+use sqlx_core::connection::{CancelToken, Connection};
 use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
-use sqlx_core::transaction::TransactionManager;
+use sqlx_core::transaction::{TransactionManager, TransactionOptions};
 
 sqlx_core::declare_driver_with_optional_migrate!(DRIVER = MySql);
 
@@ -40,6 +40,10 @@ impl AnyConnectionBackend for MySqlConnection {
         MySqlTransactionManager::begin(self)
     }
 
+    fn begin_with(&mut self, options: TransactionOptions) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        MySqlTransactionManager::begin_with(self, options)
+    }
+
     fn commit(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
         MySqlTransactionManager::commit(self)
     }
@@ -52,6 +56,14 @@ impl AnyConnectionBackend for MySqlConnection {
         MySqlTransactionManager::start_rollback(self)
     }
 
+    fn get_transaction_depth(&self) -> usize {
+        MySqlTransactionManager::get_transaction_depth(self)
+    }
+
+    fn cached_statements(&mut self) -> BoxFuture<'_, sqlx_core::Result<Vec<String>>> {
+        Connection::cached_statements(self)
+    }
+
     fn shrink_buffers(&mut self) {
         Connection::shrink_buffers(self);
     }
@@ -64,6 +76,14 @@ impl AnyConnectionBackend for MySqlConnection {
         Connection::should_flush(self)
     }
 
+    fn rollback_leaked_transaction(&mut self) -> BoxFuture<'_, sqlx_core::Result<bool>> {
+        Connection::rollback_leaked_transaction(self)
+    }
+
+    fn cancel_token(&self) -> Option<CancelToken> {
+        Connection::cancel_token(self)
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(
         &mut self,
@@ -79,16 +99,21 @@ impl AnyConnectionBackend for MySqlConnection {
         let persistent = arguments.is_some();
         let args = arguments.as_ref().map(AnyArguments::convert_to);
 
-        Box::pin(
-            self.run(query, args, persistent)
-                .try_flatten_stream()
-                .map(|res| {
-                    Ok(match res? {
-                        Either::Left(result) => Either::Left(map_result(result)),
-                        Either::Right(row) => Either::Right(AnyRow::try_from(&row)?),
-                    })
-                }),
-        )
+        Box::pin(try_stream! {
+            let query = self.connect_options.sql_rewriter.rewrite(query);
+
+            let s = self.run(&query, args, persistent, None).await?;
+            pin_mut!(s);
+
+            while let Some(v) = s.try_next().await? {
+                r#yield!(match v {
+                    Either::Left(result) => Either::Left(map_result(result)),
+                    Either::Right(row) => Either::Right(AnyRow::try_from(&row)?),
+                });
+            }
+
+            Ok(())
+        })
     }
 
     fn fetch_optional<'q>(
@@ -100,7 +125,9 @@ impl AnyConnectionBackend for MySqlConnection {
         let args = arguments.as_ref().map(AnyArguments::convert_to);
 
         Box::pin(async move {
-            let stream = self.run(query, args, persistent).await?;
+            let query = self.connect_options.sql_rewriter.rewrite(query);
+
+            let stream = self.run(&query, args, persistent, None).await?;
             futures_util::pin_mut!(stream);
 
             if let Some(Either::Right(row)) = stream.try_next().await? {
@@ -191,6 +218,7 @@ impl<'a> TryFrom<&'a AnyConnectOptions> for MySqlConnectOptions {
     fn try_from(any_opts: &'a AnyConnectOptions) -> Result<Self, Self::Error> {
         let mut opts = Self::parse_from_url(&any_opts.database_url)?;
         opts.log_settings = any_opts.log_settings.clone();
+        opts.sql_rewriter = any_opts.sql_rewriter.clone();
         Ok(opts)
     }
 }
@@ -1,4 +1,11 @@
+use std::fmt::Write;
+use std::ops::Range;
+
+use byteorder::{ByteOrder, LittleEndian};
+
 use crate::encode::{Encode, IsNull};
+use crate::protocol::text::{ColumnFlags, ColumnType};
+use crate::type_info::TypeInfo;
 use crate::types::Type;
 use crate::{MySql, MySqlTypeInfo};
 pub(crate) use sqlx_core::arguments::*;
@@ -9,6 +16,8 @@ pub struct MySqlArguments {
     pub(crate) values: Vec<u8>,
     pub(crate) types: Vec<MySqlTypeInfo>,
     pub(crate) null_bitmap: Vec<u8>,
+    // offset of each value in `values`
+    pub(crate) offsets: Vec<usize>,
 }
 
 impl MySqlArguments {
@@ -21,6 +30,7 @@ impl MySqlArguments {
 
         self.types.push(ty);
         self.null_bitmap.resize((index / 8) + 1, 0);
+        self.offsets.push(self.values.len());
 
         if let IsNull::Yes = value.encode(&mut self.values) {
             self.null_bitmap[index / 8] |= (1 << (index % 8)) as u8;
@@ -31,6 +41,126 @@ impl MySqlArguments {
     pub fn len(&self) -> usize {
         self.types.len()
     }
+
+    // The range of the encoded value of a parameter in `values`.
+    pub(crate) fn value_range(&self, index: usize) -> Range<usize> {
+        let end = self
+            .offsets
+            .get(index + 1)
+            .copied()
+            .unwrap_or(self.values.len());
+
+        self.offsets[index]..end
+    }
+
+    // The data of a parameter to send with `COM_STMT_SEND_LONG_DATA`, if its encoded value is
+    // longer than `threshold` and of a type which supports it (strings and byte arrays).
+    pub(crate) fn long_data(&self, index: usize, threshold: usize) -> Option<&[u8]> {
+        let range = self.value_range(index);
+
+        if threshold == 0 || range.len() <= threshold {
+            return None;
+        }
+
+        match self.types[index].r#type {
+            ColumnType::VarChar
+            | ColumnType::VarString
+            | ColumnType::String
+            | ColumnType::TinyBlob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob
+            | ColumnType::Blob
+            | ColumnType::Json => {}
+            _ => return None,
+        }
+
+        // the value is the data with a length-encoded prefix
+        let value = &self.values[range];
+
+        Some(&value[lenenc_prefix_len(value[0])..])
+    }
+
+    // The values of the arguments formatted for logging, see
+    // `ConnectOptions::log_parameter_values()`.
+    pub(crate) fn format_values(&self) -> String {
+        let mut formatted = String::from("[");
+
+        for (index, ty) in self.types.iter().enumerate() {
+            if index > 0 {
+                formatted.push_str(", ");
+            }
+
+            if self.null_bitmap[index / 8] & (1 << (index % 8)) != 0 {
+                formatted.push_str("NULL");
+            } else {
+                format_value(&mut formatted, ty, &self.values[self.value_range(index)]);
+            }
+        }
+
+        formatted.push(']');
+        formatted
+    }
+}
+
+// The length of the prefix of a length-encoded value
+// <https://dev.mysql.com/doc/internals/en/integer.html#packet-Protocol::LengthEncodedInteger>
+fn lenenc_prefix_len(first: u8) -> usize {
+    match first {
+        0xfc => 3,
+        0xfd => 4,
+        0xfe => 9,
+        _ => 1,
+    }
+}
+
+// Numbers and strings are formatted as such, and the other values as their bytes in hexadecimal.
+fn format_value(formatted: &mut String, ty: &MySqlTypeInfo, value: &[u8]) {
+    let unsigned = ty.flags.contains(ColumnFlags::UNSIGNED);
+
+    let _ = match ty.r#type {
+        ColumnType::Tiny | ColumnType::Short | ColumnType::Long | ColumnType::LongLong
+            if (1..=8).contains(&value.len()) =>
+        {
+            if unsigned {
+                write!(formatted, "{}", LittleEndian::read_uint(value, value.len()))
+            } else {
+                write!(formatted, "{}", LittleEndian::read_int(value, value.len()))
+            }
+        }
+
+        ColumnType::Float if value.len() == 4 => {
+            write!(formatted, "{}", LittleEndian::read_f32(value))
+        }
+
+        ColumnType::Double if value.len() == 8 => {
+            write!(formatted, "{}", LittleEndian::read_f64(value))
+        }
+
+        ColumnType::VarChar
+        | ColumnType::VarString
+        | ColumnType::String
+        | ColumnType::TinyBlob
+        | ColumnType::MediumBlob
+        | ColumnType::LongBlob
+        | ColumnType::Blob
+        | ColumnType::Json
+        | ColumnType::Decimal
+        | ColumnType::NewDecimal
+        | ColumnType::Enum
+        | ColumnType::Set
+            if !value.is_empty() =>
+        {
+            let data = &value[lenenc_prefix_len(value[0])..];
+
+            // a character set of 63 is `binary`
+            match std::str::from_utf8(data) {
+                Ok(text) if ty.char_set != 63 => write!(formatted, "{text:?}"),
+                _ => write!(formatted, "x'{}'", hex::encode(data)),
+            }
+        }
+
+        _ => write!(formatted, "{} x'{}'", ty.name(), hex::encode(value)),
+    };
 }
 
 impl<'q> Arguments<'q> for MySqlArguments {
@@ -47,4 +177,25 @@ impl<'q> Arguments<'q> for MySqlArguments {
     {
         self.add(value)
     }
+    fn try_clone(&self) -> Option<Self> {
+        Some(self.clone())
+    }
+}
+
+#[test]
+fn test_format_values() {
+    let mut arguments = MySqlArguments::default();
+
+    arguments.add(1_i64);
+    arguments.add(-2_i8);
+    arguments.add(u32::MAX);
+    arguments.add("alice");
+    arguments.add(&[1_u8, 2][..]);
+    arguments.add(None::<i32>);
+    arguments.add(1.5_f64);
+
+    assert_eq!(
+        arguments.format_values(),
+        "[1, -2, 4294967295, \"alice\", x'0102', NULL, 1.5]"
+    );
 }
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use bytes::buf::Buf;
 use bytes::Bytes;
 use futures_core::future::BoxFuture;
@@ -29,6 +31,9 @@ impl MySqlConnection {
             transaction_depth: 0,
             cache_statement: StatementCache::new(options.statement_cache_capacity),
             log_settings: options.log_settings.clone(),
+            fetch_size: options.fetch_size,
+            long_data_threshold: options.long_data_threshold,
+            connect_options: Arc::new(options.clone()),
         })
     }
 }
@@ -111,6 +116,7 @@ impl<'a> DoHandshake<'a> {
             server_version_patch,
         );
 
+        stream.connection_id = handshake.connection_id;
         stream.capabilities &= handshake.server_capabilities;
         stream.capabilities |= Capabilities::PROTOCOL_41;
 
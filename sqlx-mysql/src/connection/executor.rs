@@ -1,14 +1,16 @@
 use super::MySqlStream;
+use crate::column::ColumnSelection;
 use crate::connection::stream::Waiting;
 use crate::describe::Describe;
 use crate::error::Error;
-use crate::executor::{Execute, Executor};
+use crate::executor::{sql_with_comment, Execute, Executor};
 use crate::ext::ustr::UStr;
 use crate::io::MySqlBufExt;
 use crate::logger::QueryLogger;
-use crate::protocol::response::Status;
+use crate::protocol::response::{EofPacket, Status};
 use crate::protocol::statement::{
-    BinaryRow, Execute as StatementExecute, Prepare, PrepareOk, StmtClose,
+    BinaryRow, Execute as StatementExecute, Prepare, PrepareOk, StmtClose, StmtFetch, StmtReset,
+    StmtSendLongData,
 };
 use crate::protocol::text::{ColumnDefinition, ColumnFlags, Query, TextRow};
 use crate::statement::{MySqlStatement, MySqlStatementMetadata};
@@ -88,9 +90,13 @@ impl MySqlConnection {
         sql: &'q str,
         arguments: Option<MySqlArguments>,
         persistent: bool,
+        // the only columns to keep in the rows, see `Query::select_columns()`
+        selected_columns: Option<&'q [&'q str]>,
     ) -> Result<impl Stream<Item = Result<Either<MySqlQueryResult, MySqlRow>, Error>> + 'e, Error>
     {
         let mut logger = QueryLogger::new(sql, self.log_settings.clone());
+        let fetch_size = self.fetch_size;
+        let long_data_threshold = self.long_data_threshold;
 
         self.stream.wait_until_ready().await?;
         self.stream.waiting.push_back(Waiting::Result);
@@ -101,21 +107,45 @@ impl MySqlConnection {
             // to re-use this memory freely between result sets
             let mut columns = Arc::new(Vec::new());
 
+            // the statement whose rows are fetched from a cursor, if any
+            let mut cursor = None;
+
+            // the columns kept in the rows of the current result set, if only some are selected
+            let mut selected = None;
+
             let (mut column_names, format, mut needs_metadata) = if let Some(arguments) = arguments {
+                logger.log_parameter_values(|| arguments.format_values());
+
+                let cached = self.cache_statement.contains_key(sql);
                 let (id, metadata) = self.get_or_prepare(
                     sql,
                     persistent,
                 )
                 .await?;
 
+                let long_data = send_long_data(
+                    &mut self.stream,
+                    id,
+                    &arguments,
+                    long_data_threshold,
+                    cached,
+                )
+                .await?;
+
                 // https://dev.mysql.com/doc/internals/en/com-stmt-execute.html
                 self.stream
                     .send_packet(StatementExecute {
                         statement: id,
                         arguments: &arguments,
+                        cursor: fetch_size > 0,
+                        long_data: &long_data,
                     })
                     .await?;
 
+                if fetch_size > 0 {
+                    cursor = Some(id);
+                }
+
                 (metadata.column_names, MySqlValueFormat::Binary, false)
             } else {
                 // https://dev.mysql.com/doc/internals/en/com-query.html
@@ -133,6 +163,7 @@ impl MySqlConnection {
                     // first packet in a query response is OK or ERR
                     // this indicates either a successful query with no rows at all or a failed query
                     let ok = packet.ok()?;
+                    self.stream.server_status = ok.status;
 
                     let rows_affected = ok.affected_rows;
                     logger.increase_rows_affected(rows_affected);
@@ -164,7 +195,21 @@ impl MySqlConnection {
                     // full metadata
                     needs_metadata = true;
 
-                    recv_result_columns(&mut self.stream, num_columns, Arc::make_mut(&mut columns)).await?;
+                    let eof = recv_result_columns(&mut self.stream, num_columns, Arc::make_mut(&mut columns)).await?;
+
+                    // the server only opens a cursor for statements returning rows, this is
+                    // indicated by the status of the EOF packet after the column definitions
+                    // (with `DEPRECATE_EOF`, that packet is only sent for a cursor and is handled
+                    // along with the rows below)
+                    if let (Some(statement), Some(eof)) = (cursor, eof) {
+                        if eof.status.contains(Status::SERVER_STATUS_CURSOR_EXISTS) {
+                            fetch_from_cursor(&mut self.stream, statement, fetch_size).await?;
+                        }
+                    }
+                }
+
+                if let Some(selected_columns) = selected_columns {
+                    selected = Some(SelectedColumns::new(selected_columns, &columns, &column_names)?);
                 }
 
                 // finally, there will be none or many result-rows
@@ -173,6 +218,17 @@ impl MySqlConnection {
 
                     if packet[0] == 0xfe && packet.len() < 9 {
                         let eof = packet.eof(self.stream.capabilities)?;
+                        self.stream.server_status = eof.status;
+
+                        if let Some(statement) = cursor {
+                            if eof.status.contains(Status::SERVER_STATUS_CURSOR_EXISTS)
+                                && !eof.status.contains(Status::SERVER_STATUS_LAST_ROW_SENT)
+                            {
+                                // more rows are left in the cursor
+                                fetch_from_cursor(&mut self.stream, statement, fetch_size).await?;
+                                continue;
+                            }
+                        }
 
                         r#yield!(Either::Left(MySqlQueryResult {
                             rows_affected: 0,
@@ -189,16 +245,23 @@ impl MySqlConnection {
                         return Ok(());
                     }
 
+                    let selection = selected.as_ref().map(|selected| &selected.selection);
+
                     let row = match format {
-                        MySqlValueFormat::Binary => packet.decode_with::<BinaryRow, _>(&columns)?.0,
-                        MySqlValueFormat::Text => packet.decode_with::<TextRow, _>(&columns)?.0,
+                        MySqlValueFormat::Binary => packet.decode_with::<BinaryRow, _>((&columns, selection))?.0,
+                        MySqlValueFormat::Text => packet.decode_with::<TextRow, _>((&columns, selection))?.0,
+                    };
+
+                    let (row_columns, row_column_names) = match &selected {
+                        Some(selected) => (&selected.columns, &selected.column_names),
+                        None => (&columns, &column_names),
                     };
 
                     let v = Either::Right(MySqlRow {
                         row,
                         format,
-                        columns: Arc::clone(&columns),
-                        column_names: Arc::clone(&column_names),
+                        columns: Arc::clone(row_columns),
+                        column_names: Arc::clone(row_column_names),
                     });
 
                     logger.increment_rows_returned();
@@ -210,6 +273,44 @@ impl MySqlConnection {
     }
 }
 
+// The columns of a result set which are kept in its rows
+struct SelectedColumns {
+    selection: ColumnSelection,
+    columns: Arc<Vec<MySqlColumn>>,
+    column_names: Arc<HashMap<UStr, usize>>,
+}
+
+impl SelectedColumns {
+    fn new(
+        selected_columns: &[&str],
+        columns: &[MySqlColumn],
+        column_names: &HashMap<UStr, usize>,
+    ) -> Result<Self, Error> {
+        let selection = ColumnSelection::new(selected_columns, column_names, columns.len())?;
+
+        let columns: Vec<MySqlColumn> = selection
+            .indices()
+            .iter()
+            .enumerate()
+            .map(|(ordinal, &index)| MySqlColumn {
+                ordinal,
+                ..columns[index].clone()
+            })
+            .collect();
+
+        let column_names = columns
+            .iter()
+            .map(|column| (column.name.clone(), column.ordinal))
+            .collect();
+
+        Ok(Self {
+            selection,
+            columns: Arc::new(columns),
+            column_names: Arc::new(column_names),
+        })
+    }
+}
+
 impl<'c> Executor<'c> for &'c mut MySqlConnection {
     type Database = MySql;
 
@@ -223,10 +324,14 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
     {
         let sql = query.sql();
         let arguments = query.take_arguments();
+        let comment = query.comment();
         let persistent = query.persistent();
+        let selected_columns = query.selected_columns();
 
         Box::pin(try_stream! {
-            let s = self.run(sql, arguments, persistent).await?;
+            let sql = sql_with_comment(self.connect_options.sql_rewriter.rewrite(sql), comment);
+
+            let s = self.run(&sql, arguments, persistent, selected_columns).await?;
             pin_mut!(s);
 
             while let Some(v) = s.try_next().await? {
@@ -269,7 +374,8 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         Box::pin(async move {
             self.stream.wait_until_ready().await?;
 
-            let (_, metadata) = self.get_or_prepare(sql, true).await?;
+            let rewritten = self.connect_options.sql_rewriter.rewrite(sql);
+            let (_, metadata) = self.get_or_prepare(&rewritten, true).await?;
 
             Ok(MySqlStatement {
                 sql: Cow::Borrowed(sql),
@@ -287,7 +393,8 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         Box::pin(async move {
             self.stream.wait_until_ready().await?;
 
-            let (_, metadata) = self.get_or_prepare(sql, false).await?;
+            let sql = self.connect_options.sql_rewriter.rewrite(sql);
+            let (_, metadata) = self.get_or_prepare(&sql, false).await?;
 
             let columns = (&*metadata.columns).clone();
 
@@ -312,7 +419,7 @@ async fn recv_result_columns(
     stream: &mut MySqlStream,
     num_columns: usize,
     columns: &mut Vec<MySqlColumn>,
-) -> Result<(), Error> {
+) -> Result<Option<EofPacket>, Error> {
     columns.clear();
     columns.reserve(num_columns);
 
@@ -321,9 +428,72 @@ async fn recv_result_columns(
     }
 
     if num_columns > 0 {
-        stream.maybe_recv_eof().await?;
+        stream.maybe_recv_eof().await
+    } else {
+        Ok(None)
+    }
+}
+
+// Send the parameters longer than `threshold` with `COM_STMT_SEND_LONG_DATA`, in chunks of at most
+// `threshold` bytes, so they are not limited by the maximum size of a packet.
+//
+// Returns which parameters were sent, to be omitted from `COM_STMT_EXECUTE`.
+async fn send_long_data(
+    stream: &mut MySqlStream,
+    statement: u32,
+    arguments: &MySqlArguments,
+    threshold: usize,
+    cached: bool,
+) -> Result<Vec<bool>, Error> {
+    let long_data: Vec<bool> = (0..arguments.len())
+        .map(|index| arguments.long_data(index, threshold).is_some())
+        .collect();
+
+    if !long_data.contains(&true) {
+        return Ok(long_data);
+    }
+
+    if cached {
+        // Discard any long data left over from a previous execution of the statement, e.g. if it
+        // was cancelled before `COM_STMT_EXECUTE` was sent
+        // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_stmt_reset.html
+        stream.send_packet(StmtReset { statement }).await?;
+        stream.recv_ok().await?;
+    }
+
+    for index in 0..arguments.len() {
+        let Some(data) = arguments.long_data(index, threshold) else {
+            continue;
+        };
+
+        // The server doesn't respond to `COM_STMT_SEND_LONG_DATA`
+        for chunk in data.chunks(threshold) {
+            stream
+                .send_packet(StmtSendLongData {
+                    statement,
+                    param: index as u16,
+                    data: chunk,
+                })
+                .await?;
+        }
     }
 
+    Ok(long_data)
+}
+
+async fn fetch_from_cursor(
+    stream: &mut MySqlStream,
+    statement: u32,
+    rows: u32,
+) -> Result<(), Error> {
+    // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_stmt_fetch.html
+
+    // the packet is written to the buffer without awaiting first, so if we are cancelled
+    // it is still flushed by `wait_until_ready()` and the rows it requests are drained
+    stream.sequence_id = 0;
+    stream.write_packet(StmtFetch { statement, rows });
+    stream.flush().await?;
+
     Ok(())
 }
 
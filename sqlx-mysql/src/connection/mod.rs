@@ -1,4 +1,5 @@
 use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
 
 use futures_core::future::BoxFuture;
 use futures_util::FutureExt;
@@ -7,10 +8,12 @@ pub(crate) use stream::{MySqlStream, Waiting};
 
 use crate::common::StatementCache;
 use crate::error::Error;
-use crate::protocol::statement::StmtClose;
+use crate::executor::Executor;
+use crate::protocol::response::Status;
+use crate::protocol::statement::{StmtClose, StmtReset};
 use crate::protocol::text::{Ping, Quit};
 use crate::statement::MySqlStatementMetadata;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 use crate::{MySql, MySqlConnectOptions};
 
 mod auth;
@@ -35,6 +38,69 @@ pub struct MySqlConnection {
     cache_statement: StatementCache<(u32, MySqlStatementMetadata)>,
 
     log_settings: LogSettings,
+
+    // number of rows to fetch at a time from a cursor, or `0` to not use cursors
+    fetch_size: u32,
+
+    // size above which parameters are sent with `COM_STMT_SEND_LONG_DATA`, or `0` to never use it
+    long_data_threshold: usize,
+
+    // options used to connect, to cancel queries from another connection
+    pub(crate) connect_options: Arc<MySqlConnectOptions>,
+}
+
+impl MySqlConnection {
+    /// Sets the number of rows to fetch at a time from a server-side cursor for the
+    /// following queries, or `0` to not use cursors.
+    ///
+    /// See [`MySqlConnectOptions::fetch_size()`] for details.
+    pub fn set_fetch_size(&mut self, rows: u32) {
+        self.fetch_size = rows;
+    }
+
+    /// Returns the number of rows fetched at a time from a server-side cursor, or `0` if cursors
+    /// are not used.
+    pub fn fetch_size(&self) -> u32 {
+        self.fetch_size
+    }
+
+    /// Set the size in bytes above which parameters are sent with `COM_STMT_SEND_LONG_DATA`
+    /// for the following queries, or `0` to always send them along with the query.
+    ///
+    /// See [`MySqlConnectOptions::long_data_threshold()`] for details.
+    pub fn set_long_data_threshold(&mut self, bytes: usize) {
+        self.long_data_threshold = bytes;
+    }
+
+    /// Returns the size in bytes above which parameters are sent with `COM_STMT_SEND_LONG_DATA`,
+    /// or `0` if they are always sent along with the query.
+    pub fn long_data_threshold(&self) -> usize {
+        self.long_data_threshold
+    }
+
+    /// Reset the cached prepared statement of `sql` with
+    /// [`COM_STMT_RESET`](https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_stmt_reset.html),
+    /// if there is one.
+    ///
+    /// This discards the parameter data sent for the statement with `COM_STMT_SEND_LONG_DATA`
+    /// and closes its cursor, if it has one open. Returns `false` if the statement is not cached.
+    ///
+    /// SQLx already resets cached statements before sending long parameters (see
+    /// [`MySqlConnectOptions::long_data_threshold()`]),
+    /// so this is not needed to reuse the statements of its queries. It is meant for statements
+    /// whose state was changed outside of SQLx.
+    pub async fn reset_cached_statement(&mut self, sql: &str) -> Result<bool, Error> {
+        let Some((statement, _)) = self.cache_statement.get_mut(sql) else {
+            return Ok(false);
+        };
+        let statement = *statement;
+
+        self.stream.wait_until_ready().await?;
+        self.stream.send_packet(StmtReset { statement }).await?;
+        self.stream.recv_ok().await?;
+
+        Ok(true)
+    }
 }
 
 impl Debug for MySqlConnection {
@@ -83,6 +149,10 @@ impl Connection for MySqlConnection {
         self.cache_statement.len()
     }
 
+    fn cached_statements(&mut self) -> BoxFuture<'_, Result<Vec<String>, Error>> {
+        Box::pin(async move { Ok(self.cache_statement.keys().map(str::to_owned).collect()) })
+    }
+
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             while let Some((statement_id, _)) = self.cache_statement.remove_lru() {
@@ -102,6 +172,40 @@ impl Connection for MySqlConnection {
         !self.stream.write_buffer().is_empty()
     }
 
+    #[doc(hidden)]
+    fn rollback_leaked_transaction(&mut self) -> BoxFuture<'_, Result<bool, Error>> {
+        Box::pin(async move {
+            // the server reports whether a transaction is open in the status of the OK or EOF
+            // packet ending each response, so it is up-to-date once the pending ones are read
+            self.stream.wait_until_ready().await?;
+
+            if !self
+                .stream
+                .server_status
+                .contains(Status::SERVER_STATUS_IN_TRANS)
+            {
+                return Ok(false);
+            }
+
+            self.execute("ROLLBACK").await?;
+            self.transaction_depth = 0;
+
+            Ok(true)
+        })
+    }
+
+    fn cancel_token(&self) -> Option<CancelToken> {
+        let options = Arc::clone(&self.connect_options);
+        let id = self.stream.connection_id;
+
+        Some(CancelToken::new(async move {
+            let mut conn = MySqlConnection::establish(&options).await?;
+
+            conn.execute(&*format!("KILL QUERY {id}")).await?;
+            conn.close().await
+        }))
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -109,6 +213,16 @@ impl Connection for MySqlConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn shrink_buffers(&mut self) {
         self.stream.shrink_buffers();
     }
@@ -16,9 +16,13 @@ pub struct MySqlStream<S = Box<dyn Socket>> {
     // Wrapping the socket in `Box` allows us to unsize in-place.
     pub(crate) socket: BufferedSocket<S>,
     pub(crate) server_version: (u16, u16, u16),
+    // id of the connection on the server, used to cancel queries
+    pub(crate) connection_id: u32,
     pub(super) capabilities: Capabilities,
     pub(crate) sequence_id: u8,
     pub(crate) waiting: VecDeque<Waiting>,
+    // the status of the server as of the last OK or EOF packet, e.g. whether a transaction is open
+    pub(crate) server_status: Status,
     pub(crate) charset: CharSet,
     pub(crate) collation: Collation,
     pub(crate) is_tls: bool,
@@ -59,8 +63,10 @@ impl<S: Socket> MySqlStream<S> {
 
         Self {
             waiting: VecDeque::new(),
+            server_status: Status::empty(),
             capabilities,
             server_version: (0, 0, 0),
+            connection_id: 0,
             sequence_id: 0,
             collation,
             charset,
@@ -80,6 +86,7 @@ impl<S: Socket> MySqlStream<S> {
 
                 if !packet.is_empty() && packet[0] == 0xfe && packet.len() < 9 {
                     let eof = packet.eof(self.capabilities)?;
+                    self.server_status = eof.status;
 
                     if eof.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                         *self.waiting.front_mut().unwrap() = Waiting::Result;
@@ -94,13 +101,21 @@ impl<S: Socket> MySqlStream<S> {
 
                 if !packet.is_empty() && (packet[0] == 0x00 || packet[0] == 0xff) {
                     let ok = packet.ok()?;
+                    self.server_status = ok.status;
 
                     if !ok.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                         self.waiting.pop_front();
                     }
                 } else {
                     *self.waiting.front_mut().unwrap() = Waiting::Row;
-                    self.skip_result_metadata(packet).await?;
+
+                    let eof = self.skip_result_metadata(packet).await?;
+
+                    // without `DEPRECATE_EOF`, no rows follow if a cursor was opened
+                    if matches!(eof, Some(eof) if eof.status.contains(Status::SERVER_STATUS_CURSOR_EXISTS))
+                    {
+                        self.waiting.pop_front();
+                    }
                 }
             }
         }
@@ -190,7 +205,9 @@ impl<S: Socket> MySqlStream<S> {
     }
 
     pub(crate) async fn recv_ok(&mut self) -> Result<OkPacket, Error> {
-        self.recv_packet().await?.ok()
+        let ok = self.recv_packet().await?.ok()?;
+        self.server_status = ok.status;
+        Ok(ok)
     }
 
     pub(crate) async fn maybe_recv_eof(&mut self) -> Result<Option<EofPacket>, Error> {
@@ -201,25 +218,28 @@ impl<S: Socket> MySqlStream<S> {
         }
     }
 
-    async fn skip_result_metadata(&mut self, mut packet: Packet<Bytes>) -> Result<(), Error> {
+    async fn skip_result_metadata(
+        &mut self,
+        mut packet: Packet<Bytes>,
+    ) -> Result<Option<EofPacket>, Error> {
         let num_columns: u64 = packet.get_uint_lenenc(); // column count
 
         for _ in 0..num_columns {
             let _ = self.recv_packet().await?;
         }
 
-        self.maybe_recv_eof().await?;
-
-        Ok(())
+        self.maybe_recv_eof().await
     }
 
     pub fn boxed_socket(self) -> MySqlStream {
         MySqlStream {
             socket: self.socket.boxed(),
             server_version: self.server_version,
+            connection_id: self.connection_id,
             capabilities: self.capabilities,
             sequence_id: self.sequence_id,
             waiting: self.waiting,
+            server_status: self.server_status,
             charset: self.charset,
             collation: self.collation,
             is_tls: self.is_tls,
@@ -4,12 +4,14 @@ use crate::error::Error;
 use crate::net::tls::TlsConfig;
 use crate::net::{tls, BufferedSocket, Socket, WithSocket};
 use crate::protocol::connect::SslRequest;
+use crate::protocol::response::Status;
 use crate::protocol::Capabilities;
 use crate::{MySqlConnectOptions, MySqlSslMode};
 use std::collections::VecDeque;
 
 struct MapStream {
     server_version: (u16, u16, u16),
+    connection_id: u32,
     capabilities: Capabilities,
     sequence_id: u8,
     waiting: VecDeque<Waiting>,
@@ -81,6 +83,7 @@ pub(super) async fn maybe_upgrade<S: Socket>(
         tls_config,
         MapStream {
             server_version: stream.server_version,
+            connection_id: stream.connection_id,
             capabilities: stream.capabilities,
             sequence_id: stream.sequence_id,
             waiting: stream.waiting,
@@ -98,9 +101,12 @@ impl WithSocket for MapStream {
         MySqlStream {
             socket: BufferedSocket::new(Box::new(socket)),
             server_version: self.server_version,
+            connection_id: self.connection_id,
             capabilities: self.capabilities,
             sequence_id: self.sequence_id,
             waiting: self.waiting,
+            // the TLS upgrade happens before the server sent any status
+            server_status: Status::empty(),
             charset: self.charset,
             collation: self.collation,
             is_tls: true,
@@ -29,6 +29,9 @@ impl Database for MySql {
     const NAME: &'static str = "MySQL";
 
     const URL_SCHEMES: &'static [&'static str] = &["mysql", "mariadb"];
+
+    // double quotes delimit strings, unless the `ANSI_QUOTES` SQL mode is enabled
+    const IDENTIFIER_QUOTE: char = '`';
 }
 
 impl<'r> HasValueRef<'r> for MySql {
@@ -0,0 +1,118 @@
+use serde::Serialize;
+use sqlx_core::query_builder::QueryBuilder;
+
+use crate::types::Json;
+use crate::MySql;
+
+/// An extension trait to build expressions on `JSON` columns with a [`QueryBuilder`].
+///
+/// The JSON paths and values are always bound as parameters, so they can come from untrusted
+/// input. The columns are quoted with [`QueryBuilder::push_identifier()`].
+///
+/// The `->` and `->>` operators of MySQL only accept a string literal as the path, which is why
+/// the equivalent functions are used instead.
+///
+/// Extracted values are sent as JSON documents, e.g. `"red"` for a string: decode them with
+/// [`Json<T>`] or [`JsonValue`](serde_json::Value), which parse the document, or use
+/// [`push_json_extract_unquoted()`](Self::push_json_extract_unquoted) to get scalars as text.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::MySqlConnection) -> sqlx::Result<()> {
+/// use sqlx::mysql::{MySql, MySqlJsonExt};
+/// use sqlx::QueryBuilder;
+///
+/// let mut query_builder = QueryBuilder::<MySql>::new("SELECT id, ");
+///
+/// query_builder
+///     .push_json_extract_unquoted("attributes", "$.color")
+///     .push(" FROM products WHERE ")
+///     .push_json_contains("attributes", vec!["outdoor"], Some("$.tags"));
+///
+/// let products: Vec<(u64, Option<String>)> = query_builder
+///     .build_query_as()
+///     .fetch_all(conn)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait MySqlJsonExt<'args> {
+    /// Append `JSON_EXTRACT(column, path)`, the value at `path` in the JSON document of
+    /// `column`, or `NULL` if there is none.
+    ///
+    /// This is equivalent to `column->path`.
+    fn push_json_extract(&mut self, column: &str, path: impl Into<String>) -> &mut Self;
+
+    /// Append `JSON_UNQUOTE(JSON_EXTRACT(column, path))`, the value at `path` in the JSON
+    /// document of `column` as text, without quotes for strings.
+    ///
+    /// This is equivalent to `column->>path`.
+    fn push_json_extract_unquoted(&mut self, column: &str, path: impl Into<String>) -> &mut Self;
+
+    /// Append `JSON_CONTAINS(column, candidate[, path])`, which is true if the JSON document
+    /// of `column`, or its value at `path`, contains `candidate`.
+    ///
+    /// A scalar or an object is contained in an array if any of its elements contains it, and
+    /// an object is contained in another if all its keys are, with their values contained.
+    fn push_json_contains<T>(
+        &mut self,
+        column: &str,
+        candidate: T,
+        path: Option<&str>,
+    ) -> &mut Self
+    where
+        T: 'args + Serialize + Send;
+}
+
+impl<'args> MySqlJsonExt<'args> for QueryBuilder<'args, MySql> {
+    fn push_json_extract(&mut self, column: &str, path: impl Into<String>) -> &mut Self {
+        self.push("JSON_EXTRACT(")
+            .push_identifier(column)
+            .push(", ")
+            .push_bind(path.into())
+            .push(")")
+    }
+
+    fn push_json_extract_unquoted(&mut self, column: &str, path: impl Into<String>) -> &mut Self {
+        self.push("JSON_UNQUOTE(")
+            .push_json_extract(column, path)
+            .push(")")
+    }
+
+    fn push_json_contains<T>(&mut self, column: &str, candidate: T, path: Option<&str>) -> &mut Self
+    where
+        T: 'args + Serialize + Send,
+    {
+        self.push("JSON_CONTAINS(")
+            .push_identifier(column)
+            .push(", ")
+            .push_bind(Json(candidate));
+
+        if let Some(path) = path {
+            self.push(", ").push_bind(path.to_owned());
+        }
+
+        self.push(")")
+    }
+}
+
+#[test]
+fn test_json_expressions() {
+    let mut query_builder = QueryBuilder::<MySql>::new("SELECT ");
+
+    query_builder
+        .push_json_extract("attributes", "$.size")
+        .push(", ")
+        .push_json_extract_unquoted("p.attributes", "$.color")
+        .push(" FROM products p WHERE ")
+        .push_json_contains("attributes", "outdoor", Some("$.tags"))
+        .push(" OR ")
+        .push_json_contains("attributes", 1, None);
+
+    assert_eq!(
+        query_builder.sql(),
+        "SELECT JSON_EXTRACT(`attributes`, ?), JSON_UNQUOTE(JSON_EXTRACT(`p`.`attributes`, ?)) \
+         FROM products p WHERE JSON_CONTAINS(`attributes`, ?, ?) OR JSON_CONTAINS(`attributes`, ?)"
+    );
+}
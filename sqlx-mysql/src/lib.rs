@@ -17,6 +17,8 @@ mod connection;
 mod database;
 mod error;
 mod io;
+#[cfg(feature = "json")]
+mod json;
 mod options;
 mod protocol;
 mod query_result;
@@ -33,13 +35,20 @@ mod migrate;
 #[cfg(feature = "migrate")]
 mod testing;
 
+#[cfg(feature = "recording")]
+mod recording;
+
 pub use arguments::MySqlArguments;
 pub use column::MySqlColumn;
 pub use connection::MySqlConnection;
 pub use database::MySql;
 pub use error::MySqlDatabaseError;
+#[cfg(feature = "json")]
+pub use json::MySqlJsonExt;
 pub use options::{MySqlConnectOptions, MySqlSslMode};
 pub use query_result::MySqlQueryResult;
+#[cfg(feature = "recording")]
+pub use recording::MySqlRecordedArgument;
 pub use row::MySqlRow;
 pub use statement::MySqlStatement;
 pub use transaction::MySqlTransactionManager;
@@ -1,4 +1,4 @@
-use crate::connection::ConnectOptions;
+use crate::connection::{ConnectOptions, SqlRewriter, StatementLogHandler};
 use crate::error::Error;
 use crate::executor::Executor;
 use crate::{MySqlConnectOptions, MySqlConnection};
@@ -76,4 +76,24 @@ impl ConnectOptions for MySqlConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn log_with(mut self, handler: StatementLogHandler) -> Self {
+        self.log_settings.log_with(handler);
+        self
+    }
+
+    fn log_parameter_values(mut self, enabled: bool) -> Self {
+        self.log_settings.log_parameter_values(enabled);
+        self
+    }
+
+    fn sql_rewriter(mut self, rewriter: SqlRewriter) -> Self {
+        self.sql_rewriter.set(rewriter);
+        self
+    }
+
+    fn disable_statement_logging(mut self) -> Self {
+        self.log_settings.disable();
+        self
+    }
 }
@@ -4,7 +4,10 @@ mod connect;
 mod parse;
 mod ssl_mode;
 
-use crate::{connection::LogSettings, net::tls::CertificateInput};
+use crate::{
+    connection::{LogSettings, SqlRewriterSettings},
+    net::tls::CertificateInput,
+};
 pub use ssl_mode::MySqlSslMode;
 
 /// Options and flags which can be used to configure a MySQL connection.
@@ -29,6 +32,8 @@ pub use ssl_mode::MySqlSslMode;
 /// | `ssl-ca` | `None` | Sets the name of a file containing a list of trusted SSL Certificate Authorities. |
 /// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. Set to `0` to disable. |
 /// | `socket` | `None` | Path to the unix domain socket, which will be used instead of TCP if set. |
+/// | `fetch-size` | `0` | The number of rows to fetch at a time from a server-side cursor. Set to `0` to disable cursors. See [`MySqlConnectOptions::fetch_size()`]. |
+/// | `long-data-threshold` | `1048576` | The size in bytes above which parameters are sent in chunks. Set to `0` to disable. See [`MySqlConnectOptions::long_data_threshold()`]. |
 ///
 /// # Example
 ///
@@ -75,8 +80,11 @@ pub struct MySqlConnectOptions {
     pub(crate) charset: String,
     pub(crate) collation: Option<String>,
     pub(crate) log_settings: LogSettings,
+    pub(crate) sql_rewriter: SqlRewriterSettings,
     pub(crate) pipes_as_concat: bool,
     pub(crate) enable_cleartext_plugin: bool,
+    pub(crate) fetch_size: u32,
+    pub(crate) long_data_threshold: usize,
 }
 
 impl Default for MySqlConnectOptions {
@@ -103,8 +111,11 @@ impl MySqlConnectOptions {
             ssl_client_key: None,
             statement_cache_capacity: 100,
             log_settings: Default::default(),
+            sql_rewriter: Default::default(),
             pipes_as_concat: true,
             enable_cleartext_plugin: false,
+            fetch_size: 0,
+            long_data_threshold: 1024 * 1024,
         }
     }
 
@@ -347,4 +358,40 @@ impl MySqlConnectOptions {
         self.enable_cleartext_plugin = flag_val;
         self
     }
+
+    /// Sets the number of rows to fetch at a time when streaming the results of a query.
+    ///
+    /// By default, the server sends all the rows of a result set at once, and the rows which
+    /// haven't been consumed yet are buffered by the client. If this is set to a non-zero value,
+    /// prepared statements are instead executed with a read-only server-side cursor, from which
+    /// `rows` rows are fetched at a time as the results are consumed. This keeps the memory usage
+    /// bounded for large result sets, at the cost of a round-trip per batch; the server
+    /// materializes the result of the query in a temporary table instead.
+    ///
+    /// Queries built with [`query()`][sqlx_core::query::query] and the like are always
+    /// prepared, even without bind parameters, but SQL strings executed directly are not.
+    ///
+    /// This can also be changed for an established connection with
+    /// [`MySqlConnection::set_fetch_size()`][crate::MySqlConnection::set_fetch_size].
+    ///
+    /// The default is `0`, which disables cursors.
+    pub fn fetch_size(mut self, rows: u32) -> Self {
+        self.fetch_size = rows;
+        self
+    }
+
+    /// Sets the size in bytes above which the string and byte array parameters of prepared
+    /// statements are sent separately with
+    /// [`COM_STMT_SEND_LONG_DATA`](https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_stmt_send_long_data.html),
+    /// in chunks of at most this size, or `0` to always send them along with the query.
+    ///
+    /// A query and its parameters are otherwise sent in a single packet, which the server
+    /// rejects if it is larger than its `max_allowed_packet` setting (4 MiB by default before
+    /// MySQL 8.0). This should be lower than `max_allowed_packet` to bind larger values.
+    ///
+    /// The default is 1 MiB.
+    pub fn long_data_threshold(mut self, bytes: usize) -> Self {
+        self.long_data_threshold = bytes;
+        self
+    }
 }
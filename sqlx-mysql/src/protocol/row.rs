@@ -1,6 +1,7 @@
 use std::ops::Range;
 
 use bytes::Bytes;
+use sqlx_core::column::ColumnSelection;
 
 #[derive(Debug)]
 pub(crate) struct Row {
@@ -9,6 +10,23 @@ pub(crate) struct Row {
 }
 
 impl Row {
+    // The values of a row of `num_columns` columns before decoding, with only the selected ones
+    pub(crate) fn empty_values(
+        num_columns: usize,
+        selection: Option<&ColumnSelection>,
+    ) -> Vec<Option<Range<usize>>> {
+        vec![None; selection.map_or(num_columns, ColumnSelection::len)]
+    }
+
+    // The position in the row of the column at `index` in the result, if it is selected
+    #[inline]
+    pub(crate) fn slot(index: usize, selection: Option<&ColumnSelection>) -> Option<usize> {
+        match selection {
+            Some(selection) => selection.slot(index),
+            None => Some(index),
+        }
+    }
+
     pub(crate) fn get(&self, index: usize) -> Option<&[u8]> {
         self.values[index]
             .as_ref()
@@ -9,13 +9,21 @@ use crate::MySqlArguments;
 pub struct Execute<'q> {
     pub statement: u32,
     pub arguments: &'q MySqlArguments,
+    // open a read-only cursor, so the rows are only sent on `COM_STMT_FETCH`
+    pub cursor: bool,
+    // the parameters whose values were sent with `COM_STMT_SEND_LONG_DATA`, and are omitted
+    pub long_data: &'q [bool],
 }
 
 impl<'q> Encode<'_, Capabilities> for Execute<'q> {
     fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
         buf.push(0x17); // COM_STMT_EXECUTE
         buf.extend(&self.statement.to_le_bytes());
-        buf.push(0); // NO_CURSOR
+        buf.push(if self.cursor {
+            0x01 // CURSOR_TYPE_READ_ONLY
+        } else {
+            0x00 // CURSOR_TYPE_NO_CURSOR
+        });
         buf.extend(&1_u32.to_le_bytes()); // iterations (always 1): int<4>
 
         if !self.arguments.types.is_empty() {
@@ -32,7 +40,16 @@ impl<'q> Encode<'_, Capabilities> for Execute<'q> {
                 });
             }
 
-            buf.extend(&*self.arguments.values);
+            if !self.long_data.contains(&true) {
+                buf.extend(&*self.arguments.values);
+                return;
+            }
+
+            for index in 0..self.arguments.types.len() {
+                if !self.long_data.get(index).copied().unwrap_or(false) {
+                    buf.extend(&self.arguments.values[self.arguments.value_range(index)]);
+                }
+            }
         }
     }
 }
@@ -3,9 +3,15 @@ mod prepare;
 mod prepare_ok;
 mod row;
 mod stmt_close;
+mod stmt_fetch;
+mod stmt_reset;
+mod stmt_send_long_data;
 
 pub(crate) use execute::Execute;
 pub(crate) use prepare::Prepare;
 pub(crate) use prepare_ok::PrepareOk;
 pub(crate) use row::BinaryRow;
 pub(crate) use stmt_close::StmtClose;
+pub(crate) use stmt_fetch::StmtFetch;
+pub(crate) use stmt_reset::StmtReset;
+pub(crate) use stmt_send_long_data::StmtSendLongData;
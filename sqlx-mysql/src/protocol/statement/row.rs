@@ -6,6 +6,7 @@ use crate::io::{BufExt, Decode};
 use crate::protocol::text::ColumnType;
 use crate::protocol::Row;
 use crate::MySqlColumn;
+use sqlx_core::column::ColumnSelection;
 
 // https://dev.mysql.com/doc/internals/en/binary-protocol-resultset-row.html#packet-ProtocolBinary::ResultsetRow
 // https://dev.mysql.com/doc/internals/en/binary-protocol-value.html
@@ -13,8 +14,11 @@ use crate::MySqlColumn;
 #[derive(Debug)]
 pub(crate) struct BinaryRow(pub(crate) Row);
 
-impl<'de> Decode<'de, &'de [MySqlColumn]> for BinaryRow {
-    fn decode_with(mut buf: Bytes, columns: &'de [MySqlColumn]) -> Result<Self, Error> {
+impl<'de> Decode<'de, (&'de [MySqlColumn], Option<&'de ColumnSelection>)> for BinaryRow {
+    fn decode_with(
+        mut buf: Bytes,
+        (columns, selection): (&'de [MySqlColumn], Option<&'de ColumnSelection>),
+    ) -> Result<Self, Error> {
         let header = buf.get_u8();
         if header != 0 {
             return Err(err_protocol!(
@@ -29,7 +33,7 @@ impl<'de> Decode<'de, &'de [MySqlColumn]> for BinaryRow {
         let null_bitmap_len = (columns.len() + 9) / 8;
         let null_bitmap = buf.get_bytes(null_bitmap_len);
 
-        let mut values = Vec::with_capacity(columns.len());
+        let mut values = Row::empty_values(columns.len(), selection);
 
         for (column_idx, column) in columns.iter().enumerate() {
             // NOTE: the column index starts at the 3rd bit
@@ -38,7 +42,6 @@ impl<'de> Decode<'de, &'de [MySqlColumn]> for BinaryRow {
                 null_bitmap[column_null_idx / 8] & (1 << (column_null_idx % 8) as u8) != 0;
 
             if is_null {
-                values.push(None);
                 continue;
             }
 
@@ -82,7 +85,9 @@ impl<'de> Decode<'de, &'de [MySqlColumn]> for BinaryRow {
 
             let offset = offset - buf.len();
 
-            values.push(Some(offset..(offset + size)));
+            if let Some(slot) = Row::slot(column_idx, selection) {
+                values[slot] = Some(offset..(offset + size));
+            }
 
             buf.advance(size);
         }
@@ -0,0 +1,20 @@
+use crate::io::Encode;
+use crate::protocol::Capabilities;
+
+// https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_stmt_send_long_data.html
+
+#[derive(Debug)]
+pub struct StmtSendLongData<'a> {
+    pub statement: u32,
+    pub param: u16,
+    pub data: &'a [u8],
+}
+
+impl Encode<'_, Capabilities> for StmtSendLongData<'_> {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
+        buf.push(0x18); // COM_STMT_SEND_LONG_DATA
+        buf.extend(&self.statement.to_le_bytes());
+        buf.extend(&self.param.to_le_bytes());
+        buf.extend(self.data);
+    }
+}
@@ -5,27 +5,32 @@ use crate::error::Error;
 use crate::io::Decode;
 use crate::io::MySqlBufExt;
 use crate::protocol::Row;
+use sqlx_core::column::ColumnSelection;
 
 #[derive(Debug)]
 pub(crate) struct TextRow(pub(crate) Row);
 
-impl<'de> Decode<'de, &'de [MySqlColumn]> for TextRow {
-    fn decode_with(mut buf: Bytes, columns: &'de [MySqlColumn]) -> Result<Self, Error> {
+impl<'de> Decode<'de, (&'de [MySqlColumn], Option<&'de ColumnSelection>)> for TextRow {
+    fn decode_with(
+        mut buf: Bytes,
+        (columns, selection): (&'de [MySqlColumn], Option<&'de ColumnSelection>),
+    ) -> Result<Self, Error> {
         let storage = buf.clone();
         let offset = buf.len();
 
-        let mut values = Vec::with_capacity(columns.len());
+        let mut values = Row::empty_values(columns.len(), selection);
 
-        for _ in columns {
+        for column_idx in 0..columns.len() {
             if buf[0] == 0xfb {
                 // NULL is sent as 0xfb
-                values.push(None);
                 buf.advance(1);
             } else {
                 let size = buf.get_uint_lenenc() as usize;
                 let offset = offset - buf.len();
 
-                values.push(Some(offset..(offset + size)));
+                if let Some(slot) = Row::slot(column_idx, selection) {
+                    values[slot] = Some(offset..(offset + size));
+                }
 
                 buf.advance(size);
             }
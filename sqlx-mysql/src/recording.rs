@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use sqlx_core::database::HasArguments;
+use sqlx_core::recording::RecordDatabase;
+
+use crate::error::Error;
+use crate::protocol::text::{ColumnFlags, ColumnType};
+use crate::{MySql, MySqlArguments, MySqlQueryResult, MySqlTypeInfo};
+
+/// The recorded form of an argument of a query, with its value in the binary protocol format.
+///
+/// See the `sqlx::recording` module for details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MySqlRecordedArgument {
+    #[serde(rename = "type")]
+    ty: u8,
+
+    #[serde(default, skip_serializing_if = "is_false")]
+    unsigned: bool,
+
+    // the hex-encoded value
+    value: Option<String>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+impl RecordDatabase for MySql {
+    type RecordedArguments = Vec<MySqlRecordedArgument>;
+
+    fn record_arguments(arguments: &MySqlArguments) -> Vec<MySqlRecordedArgument> {
+        arguments
+            .types
+            .iter()
+            .enumerate()
+            .map(|(index, ty)| {
+                let is_null = arguments.null_bitmap[index / 8] & (1 << (index % 8)) != 0;
+
+                MySqlRecordedArgument {
+                    // only the type and signedness of parameters are sent to the server
+                    ty: ty.r#type as u8,
+                    unsigned: ty.flags.contains(ColumnFlags::UNSIGNED),
+                    value: (!is_null)
+                        .then(|| hex::encode(&arguments.values[arguments.value_range(index)])),
+                }
+            })
+            .collect()
+    }
+
+    fn replay_arguments<'q>(
+        recorded: Vec<MySqlRecordedArgument>,
+    ) -> Result<<MySql as HasArguments<'q>>::Arguments, Error> {
+        let mut arguments = MySqlArguments::default();
+
+        for (index, argument) in recorded.into_iter().enumerate() {
+            let mut ty = MySqlTypeInfo::binary(ColumnType::try_from_u16(argument.ty)?);
+
+            if argument.unsigned {
+                ty.flags |= ColumnFlags::UNSIGNED;
+            }
+
+            arguments.types.push(ty);
+            arguments.null_bitmap.resize((index / 8) + 1, 0);
+            arguments.offsets.push(arguments.values.len());
+
+            match argument.value {
+                Some(value) => arguments
+                    .values
+                    .extend(hex::decode(value).map_err(|e| Error::Decode(e.into()))?),
+                None => arguments.null_bitmap[index / 8] |= (1 << (index % 8)) as u8,
+            }
+        }
+
+        Ok(arguments)
+    }
+
+    fn rows_affected(result: &MySqlQueryResult) -> u64 {
+        result.rows_affected()
+    }
+}
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use futures_core::future::BoxFuture;
 
 use crate::connection::Waiting;
@@ -15,10 +17,17 @@ impl TransactionManager for MySqlTransactionManager {
     type Database = MySql;
 
     fn begin(conn: &mut MySqlConnection) -> BoxFuture<'_, Result<(), Error>> {
+        Self::begin_with(conn, TransactionOptions::default())
+    }
+
+    fn begin_with(
+        conn: &mut MySqlConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             let depth = conn.transaction_depth;
-
-            conn.execute(&*begin_ansi_transaction_sql(depth)).await?;
+            conn.execute(&*begin_transaction_sql(depth, &options)?)
+                .await?;
             conn.transaction_depth = depth + 1;
 
             Ok(())
@@ -63,4 +72,76 @@ impl TransactionManager for MySqlTransactionManager {
             conn.transaction_depth = depth - 1;
         }
     }
+
+    fn get_transaction_depth(conn: &MySqlConnection) -> usize {
+        conn.transaction_depth
+    }
+}
+
+// The isolation level can only be set by `SET TRANSACTION`, which applies to the next transaction
+// only, as the statement beginning the transaction can only set the access mode. Both are sent
+// in one query, so that the isolation level can't be left set for another transaction when
+// beginning this one is cancelled in between.
+fn begin_transaction_sql(
+    depth: usize,
+    options: &TransactionOptions,
+) -> Result<Cow<'static, str>, Error> {
+    check_savepoint_options(depth, options)?;
+
+    if options.is_deferrable() {
+        return Err(Error::Configuration(
+            "MySQL does not support deferrable transactions".into(),
+        ));
+    }
+
+    if depth > 0 || options.is_default() {
+        return Ok(begin_ansi_transaction_sql(depth));
+    }
+
+    let mut sql = String::new();
+
+    if let Some(isolation_level) = options.get_isolation_level() {
+        sql.push_str("SET TRANSACTION ISOLATION LEVEL ");
+        sql.push_str(isolation_level.as_sql());
+        sql.push_str("; ");
+    }
+
+    sql.push_str("START TRANSACTION");
+
+    if options.is_read_only() {
+        sql.push_str(" READ ONLY");
+    }
+
+    Ok(Cow::Owned(sql))
+}
+
+#[test]
+fn test_begin_transaction_sql() {
+    let options = TransactionOptions::new();
+    assert_eq!(begin_transaction_sql(0, &options).unwrap(), "BEGIN");
+    assert_eq!(
+        begin_transaction_sql(1, &options).unwrap(),
+        "SAVEPOINT _sqlx_savepoint_1"
+    );
+
+    let options = options.read_only(true);
+    assert_eq!(
+        begin_transaction_sql(0, &options).unwrap(),
+        "START TRANSACTION READ ONLY"
+    );
+
+    let options = options.isolation_level(IsolationLevel::ReadCommitted);
+    assert_eq!(
+        begin_transaction_sql(0, &options).unwrap(),
+        "SET TRANSACTION ISOLATION LEVEL READ COMMITTED; START TRANSACTION READ ONLY"
+    );
+
+    assert!(matches!(
+        begin_transaction_sql(1, &options),
+        Err(Error::Configuration(_))
+    ));
+    assert!(matches!(
+        begin_transaction_sql(0, &TransactionOptions::new().deferrable(true)),
+        Err(Error::Configuration(_))
+    ));
 }
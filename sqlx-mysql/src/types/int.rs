@@ -1,3 +1,6 @@
+use std::any::type_name;
+use std::fmt::Display;
+
 use byteorder::{ByteOrder, LittleEndian};
 
 use crate::decode::Decode;
@@ -100,21 +103,31 @@ fn int_decode(value: MySqlValueRef<'_>) -> Result<i64, BoxDynError> {
     })
 }
 
+/// Convert a decoded integer to the target type, checking that it fits.
+pub(crate) fn int_convert<I, T>(int: I) -> Result<T, BoxDynError>
+where
+    I: Copy + Display,
+    T: TryFrom<I>,
+{
+    T::try_from(int)
+        .map_err(|_| format!("value {int} is out of range for `{}`", type_name::<T>()).into())
+}
+
 impl Decode<'_, MySql> for i8 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        int_convert(int_decode(value)?)
     }
 }
 
 impl Decode<'_, MySql> for i16 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        int_convert(int_decode(value)?)
     }
 }
 
 impl Decode<'_, MySql> for i32 {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
-        int_decode(value)?.try_into().map_err(Into::into)
+        int_convert(int_decode(value)?)
     }
 }
 
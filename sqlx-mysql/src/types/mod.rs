@@ -15,8 +15,11 @@
 //! | `u64`                                 | BIGINT UNSIGNED                                      |
 //! | `f32`                                 | FLOAT                                                |
 //! | `f64`                                 | DOUBLE                                               |
-//! | `&str`, [`String`]                    | VARCHAR, CHAR, TEXT                                  |
-//! | `&[u8]`, `Vec<u8>`                    | VARBINARY, BINARY, BLOB                              |
+//! | `&str`, [`String`], `Cow<str>`        | VARCHAR, CHAR, TEXT                                  |
+//! | `&[u8]`, `Vec<u8>`, `[u8; N]`         | VARBINARY, BINARY, BLOB                              |
+//!
+//! `&str` and `Cow<str>` borrow the buffer of the row, with both the text and binary protocols;
+//! `Cow<str>` is always decoded as `Cow::Borrowed`.
 //!
 //! ##### Note: `BOOLEAN`/`BOOL` Type
 //! MySQL and MariaDB treat `BOOLEAN` as an alias of the `TINYINT` type:
@@ -58,6 +61,17 @@
 //! | `time::Date`                          | DATE                                                 |
 //! | `time::Time`                          | TIME                                                 |
 //!
+//! ### [`jiff`](https://crates.io/crates/jiff)
+//!
+//! Requires the `jiff` Cargo feature flag.
+//!
+//! | Rust type                             | MySQL type(s)                                        |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `jiff::civil::DateTime`               | DATETIME                                             |
+//! | `jiff::Timestamp`                     | TIMESTAMP                                            |
+//! | `jiff::civil::Date`                   | DATE                                                 |
+//! | `jiff::civil::Time`                   | TIME                                                 |
+//!
 //! ### [`bigdecimal`](https://crates.io/crates/bigdecimal)
 //! Requires the `bigdecimal` Cargo feature flag.
 //!
@@ -81,6 +95,9 @@
 //! | `uuid::Uuid`                          | BINARY(16), VARCHAR, CHAR, TEXT                      |
 //! | `uuid::fmt::Hyphenated`               | CHAR(36)                                             |
 //! | `uuid::fmt::Simple`                   | CHAR(32)                                             |
+//! | [`MySqlUuidBinary`]                   | BINARY(16)                                           |
+//! | [`MySqlUuidBinarySwapped`]            | BINARY(16), as by `UUID_TO_BIN(uuid, 1)`             |
+//! | [`MySqlUuidText`]                     | CHAR(36), CHAR(32)                                   |
 //!
 //! ### [`json`](https://crates.io/crates/serde_json)
 //!
@@ -122,5 +139,11 @@ mod chrono;
 #[cfg(feature = "time")]
 mod time;
 
+#[cfg(feature = "jiff")]
+mod jiff;
+
 #[cfg(feature = "uuid")]
 mod uuid;
+
+#[cfg(feature = "uuid")]
+pub use uuid::{MySqlUuidBinary, MySqlUuidBinarySwapped, MySqlUuidText};
@@ -97,3 +97,151 @@ impl Decode<'_, MySql> for Simple {
             .map(|u| u.simple())
     }
 }
+
+/// A UUID stored in `BINARY(16)`, as by `UUID_TO_BIN(uuid)`.
+///
+/// This is how [`Uuid`] itself is encoded, but unlike it, this is only compatible with columns
+/// of binary strings, so that decoding a UUID stored as text, e.g. in `CHAR(36)`, is a type
+/// error instead of failing on the length of the value. See [`MySqlUuidText`] for the UUIDs
+/// stored as text.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct MySqlUuidBinary(pub Uuid);
+
+/// A UUID stored in `BINARY(16)` with its time fields swapped, as by `UUID_TO_BIN(uuid, 1)`.
+///
+/// MySQL applications commonly store version 1 UUIDs with their "swap flag" set: the
+/// time-high field is moved to the front and the time-low one to the back, so that UUIDs
+/// generated one after the other are stored in ascending order, which is better for indexes.
+/// The value must then be read with `BIN_TO_UUID(value, 1)`, or decoded as this type.
+///
+/// The bytes of the `BINARY(16)` value are the bytes of the UUID in the order
+/// `6 7 4 5 0 1 2 3 8 9 10 11 12 13 14 15`.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct MySqlUuidBinarySwapped(pub Uuid);
+
+/// A UUID stored as text, in `CHAR(36)` or `CHAR(32)`.
+///
+/// This is encoded in the hyphenated form, e.g. `b731678f-636f-4135-bc6f-19440c13bd19`, and
+/// decoded from either the hyphenated or the simple form, e.g. `b731678f636f4135bc6f19440c13bd19`.
+/// Use [`Simple`] to encode the simple form.
+///
+/// Unlike [`Uuid`], this is only compatible with columns of text strings, see
+/// [`MySqlUuidBinary`].
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct MySqlUuidText(pub Uuid);
+
+// the character set of the binary strings, e.g. in `BINARY(16)`
+const BINARY_CHARSET: u16 = 63;
+
+// the order of the bytes of a UUID with the "swap flag" of `UUID_TO_BIN()`,
+// and the one to get them back as by `BIN_TO_UUID()`
+const SWAPPED_BYTES: [usize; 16] = [6, 7, 4, 5, 0, 1, 2, 3, 8, 9, 10, 11, 12, 13, 14, 15];
+const UNSWAPPED_BYTES: [usize; 16] = [4, 5, 6, 7, 2, 3, 0, 1, 8, 9, 10, 11, 12, 13, 14, 15];
+
+fn swap_bytes(bytes: &[u8; 16]) -> [u8; 16] {
+    SWAPPED_BYTES.map(|i| bytes[i])
+}
+
+fn unswap_bytes(bytes: &[u8; 16]) -> [u8; 16] {
+    UNSWAPPED_BYTES.map(|i| bytes[i])
+}
+
+fn decode_binary(value: MySqlValueRef<'_>) -> Result<[u8; 16], BoxDynError> {
+    let bytes = <&[u8] as Decode<MySql>>::decode(value)?;
+
+    bytes
+        .try_into()
+        .map_err(|_| format!("expected 16 bytes, got {}", bytes.len()).into())
+}
+
+impl Type<MySql> for MySqlUuidBinary {
+    fn type_info() -> MySqlTypeInfo {
+        <&[u8] as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <&[u8] as Type<MySql>>::compatible(ty) && ty.char_set == BINARY_CHARSET
+    }
+}
+
+impl Encode<'_, MySql> for MySqlUuidBinary {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        buf.put_bytes_lenenc(self.0.as_bytes());
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, MySql> for MySqlUuidBinary {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(Self(Uuid::from_bytes(decode_binary(value)?)))
+    }
+}
+
+impl Type<MySql> for MySqlUuidBinarySwapped {
+    fn type_info() -> MySqlTypeInfo {
+        <MySqlUuidBinary as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <MySqlUuidBinary as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, MySql> for MySqlUuidBinarySwapped {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        buf.put_bytes_lenenc(&swap_bytes(self.0.as_bytes()));
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, MySql> for MySqlUuidBinarySwapped {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(Self(Uuid::from_bytes(unswap_bytes(&decode_binary(value)?))))
+    }
+}
+
+impl Type<MySql> for MySqlUuidText {
+    fn type_info() -> MySqlTypeInfo {
+        <&str as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <&str as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, MySql> for MySqlUuidText {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        buf.put_str_lenenc(self.0.hyphenated().encode_lower(&mut Uuid::encode_buffer()));
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, MySql> for MySqlUuidText {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        let text = <&str as Decode<MySql>>::decode(value)?;
+
+        // `parse_str` also accepts the braced and URN forms
+        if !matches!(text.len(), 32 | 36) {
+            return Err(format!("expected a UUID of 32 or 36 characters, got {text:?}").into());
+        }
+
+        Ok(Self(Uuid::parse_str(text)?))
+    }
+}
+
+#[test]
+fn test_swap_bytes() {
+    let uuid = Uuid::parse_str("b731678f-636f-4135-bc6f-19440c13bd19").unwrap();
+    let swapped = swap_bytes(uuid.as_bytes());
+
+    // `UUID_TO_BIN('b731678f-636f-4135-bc6f-19440c13bd19', 1)`
+    assert_eq!(
+        Uuid::from_bytes(swapped).simple().to_string(),
+        "4135636fb731678fbc6f19440c13bd19"
+    );
+    assert_eq!(unswap_bytes(&swapped), *uuid.as_bytes());
+}
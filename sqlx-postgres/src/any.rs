@@ -4,17 +4,17 @@ use crate::{
 };
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
-use futures_util::{StreamExt, TryFutureExt, TryStreamExt};
+use futures_util::{pin_mut, TryStreamExt};
 
 pub use sqlx_core::any::*;
 
 use crate::type_info::PgType;
-use sqlx_core::connection::Connection;
+use sqlx_core::connection::{CancelToken, Connection};
 use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
 use sqlx_core::ext::ustr::UStr;
-use sqlx_core::transaction::TransactionManager;
+use sqlx_core::transaction::{TransactionManager, TransactionOptions};
 
 sqlx_core::declare_driver_with_optional_migrate!(DRIVER = Postgres);
 
@@ -39,6 +39,10 @@ impl AnyConnectionBackend for PgConnection {
         PgTransactionManager::begin(self)
     }
 
+    fn begin_with(&mut self, options: TransactionOptions) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        PgTransactionManager::begin_with(self, options)
+    }
+
     fn commit(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
         PgTransactionManager::commit(self)
     }
@@ -51,6 +55,14 @@ impl AnyConnectionBackend for PgConnection {
         PgTransactionManager::start_rollback(self)
     }
 
+    fn get_transaction_depth(&self) -> usize {
+        PgTransactionManager::get_transaction_depth(self)
+    }
+
+    fn cached_statements(&mut self) -> BoxFuture<'_, sqlx_core::Result<Vec<String>>> {
+        Connection::cached_statements(self)
+    }
+
     fn shrink_buffers(&mut self) {
         Connection::shrink_buffers(self);
     }
@@ -63,6 +75,14 @@ impl AnyConnectionBackend for PgConnection {
         Connection::should_flush(self)
     }
 
+    fn rollback_leaked_transaction(&mut self) -> BoxFuture<'_, sqlx_core::Result<bool>> {
+        Connection::rollback_leaked_transaction(self)
+    }
+
+    fn cancel_token(&self) -> Option<CancelToken> {
+        Connection::cancel_token(self)
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(
         &mut self,
@@ -78,16 +98,21 @@ impl AnyConnectionBackend for PgConnection {
         let persistent = arguments.is_some();
         let args = arguments.as_ref().map(AnyArguments::convert_to);
 
-        Box::pin(
-            self.run(query, args, 0, persistent, None)
-                .try_flatten_stream()
-                .map(
-                    move |res: sqlx_core::Result<Either<PgQueryResult, PgRow>>| match res? {
-                        Either::Left(result) => Ok(Either::Left(map_result(result))),
-                        Either::Right(row) => Ok(Either::Right(AnyRow::try_from(&row)?)),
-                    },
-                ),
-        )
+        Box::pin(try_stream! {
+            let query = self.connect_options.sql_rewriter.rewrite(query);
+
+            let s = self.run(&query, args, 0, 0, persistent, None, None).await?;
+            pin_mut!(s);
+
+            while let Some(v) = s.try_next().await? {
+                r#yield!(match v {
+                    Either::Left(result) => Either::Left(map_result(result)),
+                    Either::Right(row) => Either::Right(AnyRow::try_from(&row)?),
+                });
+            }
+
+            Ok(())
+        })
     }
 
     fn fetch_optional<'q>(
@@ -99,7 +124,9 @@ impl AnyConnectionBackend for PgConnection {
         let args = arguments.as_ref().map(AnyArguments::convert_to);
 
         Box::pin(async move {
-            let stream = self.run(query, args, 1, persistent, None).await?;
+            let query = self.connect_options.sql_rewriter.rewrite(query);
+
+            let stream = self.run(&query, args, 1, 0, persistent, None, None).await?;
             futures_util::pin_mut!(stream);
 
             if let Some(Either::Right(row)) = stream.try_next().await? {
@@ -222,6 +249,7 @@ impl<'a> TryFrom<&'a AnyConnectOptions> for PgConnectOptions {
     fn try_from(value: &'a AnyConnectOptions) -> Result<Self, Self::Error> {
         let mut opts = PgConnectOptions::parse_from_url(&value.database_url)?;
         opts.log_settings = value.log_settings.clone();
+        opts.sql_rewriter = value.sql_rewriter.clone();
         Ok(opts)
     }
 }
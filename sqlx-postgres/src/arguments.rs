@@ -1,11 +1,13 @@
 use std::fmt::{self, Write};
 use std::ops::{Deref, DerefMut};
 
+use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::Error;
 use crate::ext::ustr::UStr;
-use crate::types::Type;
-use crate::{PgConnection, PgTypeInfo, Postgres};
+use crate::type_info::PgType;
+use crate::types::{Oid, Type};
+use crate::{PgConnection, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 
 pub(crate) use sqlx_core::arguments::Arguments;
 
@@ -21,10 +23,10 @@ pub(crate) use sqlx_core::arguments::Arguments;
 
 #[derive(Default)]
 pub struct PgArgumentBuffer {
-    buffer: Vec<u8>,
+    pub(crate) buffer: Vec<u8>,
 
     // Number of arguments
-    count: usize,
+    pub(crate) count: usize,
 
     // Whenever an `Encode` impl needs to defer some work until after we resolve parameter types
     // it can use `patch`.
@@ -32,7 +34,7 @@ pub struct PgArgumentBuffer {
     // This currently is only setup to be useful if there is a *fixed-size* slot that needs to be
     // tweaked from the input type. However, that's the only use case we currently have.
     //
-    patches: Vec<(
+    pub(crate) patches: Vec<(
         usize, // offset
         usize, // argument index
         Box<dyn Fn(&mut [u8], &PgTypeInfo) + 'static + Send + Sync>,
@@ -45,7 +47,7 @@ pub struct PgArgumentBuffer {
     // This is done for Records and Arrays as the OID is needed well before we are in an async
     // function and can just ask postgres.
     //
-    type_holes: Vec<(usize, UStr)>, // Vec<{ offset, type_name }>
+    pub(crate) type_holes: Vec<(usize, UStr)>, // Vec<{ offset, type_name }>
 }
 
 /// Implementation of [`Arguments`] for PostgreSQL.
@@ -102,6 +104,76 @@ impl PgArguments {
 
         Ok(())
     }
+
+    // The values of the arguments formatted for logging, see
+    // `ConnectOptions::log_parameter_values()`, once the patches are applied. `parameters` are
+    // the types of the parameters of the statement.
+    pub(crate) fn format_values(&self, parameters: &[PgTypeInfo]) -> String {
+        let mut formatted = String::from("[");
+        let mut offset = 0;
+
+        for (index, ty) in self.types.iter().enumerate() {
+            let len = i32::from_be_bytes(self.buffer[offset..offset + 4].try_into().unwrap());
+            let start = offset + 4;
+            let end = start + usize::try_from(len).unwrap_or(0);
+
+            if index > 0 {
+                formatted.push_str(", ");
+            }
+
+            format_value(
+                &mut formatted,
+                parameters.get(index).unwrap_or(ty),
+                (len >= 0).then(|| &self.buffer[start..end]),
+            );
+
+            offset = end;
+        }
+
+        formatted.push(']');
+        formatted
+    }
+}
+
+// Numbers and strings are formatted as such, and the other values as their bytes in hexadecimal.
+fn format_value(formatted: &mut String, ty: &PgTypeInfo, value: Option<&[u8]>) {
+    let Some(bytes) = value else {
+        formatted.push_str("NULL");
+        return;
+    };
+
+    let value = PgValueRef {
+        value: Some(bytes),
+        row: None,
+        type_info: ty.clone(),
+        format: PgValueFormat::Binary,
+    };
+
+    let decoded = match ty.0 {
+        PgType::Bool => <bool as Decode<Postgres>>::decode(value).map(|v| v.to_string()),
+        PgType::Int2 => <i16 as Decode<Postgres>>::decode(value).map(|v| v.to_string()),
+        PgType::Int4 => <i32 as Decode<Postgres>>::decode(value).map(|v| v.to_string()),
+        PgType::Int8 => <i64 as Decode<Postgres>>::decode(value).map(|v| v.to_string()),
+        PgType::Float4 => <f32 as Decode<Postgres>>::decode(value).map(|v| v.to_string()),
+        PgType::Float8 => <f64 as Decode<Postgres>>::decode(value).map(|v| v.to_string()),
+        PgType::Oid => <Oid as Decode<Postgres>>::decode(value).map(|v| v.0.to_string()),
+        PgType::Text | PgType::Varchar | PgType::Bpchar | PgType::Name | PgType::Unknown => {
+            <&str as Decode<Postgres>>::decode(value).map(|v| format!("{v:?}"))
+        }
+        _ => Err("not formatted".into()),
+    };
+
+    match decoded {
+        Ok(decoded) => formatted.push_str(&decoded),
+        Err(_) => {
+            let _ = write!(
+                formatted,
+                "{} '\\x{}'",
+                ty.0.display_name(),
+                hex::encode(bytes)
+            );
+        }
+    }
 }
 
 impl<'q> Arguments<'q> for PgArguments {
@@ -122,6 +194,23 @@ impl<'q> Arguments<'q> for PgArguments {
     fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
         write!(writer, "${}", self.buffer.count)
     }
+
+    fn try_clone(&self) -> Option<Self> {
+        // the patches are closures, which can't be copied
+        if !self.buffer.patches.is_empty() {
+            return None;
+        }
+
+        Some(PgArguments {
+            types: self.types.clone(),
+            buffer: PgArgumentBuffer {
+                buffer: self.buffer.buffer.clone(),
+                count: self.buffer.count,
+                patches: Vec::new(),
+                type_holes: self.buffer.type_holes.clone(),
+            },
+        })
+    }
 }
 
 impl PgArgumentBuffer {
@@ -184,3 +273,19 @@ impl DerefMut for PgArgumentBuffer {
         &mut self.buffer
     }
 }
+
+#[test]
+fn test_format_values() {
+    let mut arguments = PgArguments::default();
+
+    arguments.add(1_i32);
+    arguments.add("alice");
+    arguments.add(None::<i64>);
+    arguments.add(true);
+    arguments.add(&[1_u8, 2][..]);
+
+    assert_eq!(
+        arguments.format_values(&[]),
+        "[1, \"alice\", NULL, true, BYTEA '\\x0102']"
+    );
+}
@@ -0,0 +1,235 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::error::Error;
+use crate::executor::{sql_with_comment, Execute};
+use crate::logger::QueryLogger;
+use crate::message::{self, Bind, CommandComplete, MessageFormat, Query};
+use crate::statement::PgStatementMetadata;
+use crate::{PgArguments, PgConnection, PgQueryResult, PgValueFormat, Postgres};
+
+impl PgConnection {
+    /// Queue statements to be sent to the server together, with a single write to the socket.
+    ///
+    /// Each statement executed on a connection is normally sent, then its results are awaited
+    /// before the next one can be sent. With this, the statements are only buffered until
+    /// [`PgDeferredFlush::flush()`] sends them all at once and waits for all their results,
+    /// which saves a round-trip and a write to the socket for each statement.
+    ///
+    /// This is meant for bursts of independent statements, e.g. inserts or updates: their
+    /// results are returned in order, but rows returned by a statement are discarded, as with
+    /// [`Executor::execute()`](sqlx_core::executor::Executor::execute). Each statement is
+    /// executed on its own, in a transaction if one is open, so an error in one of them doesn't
+    /// prevent the next ones from being executed, unless they are in the same transaction.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// let mut deferred = conn.defer_flush();
+    ///
+    /// for (id, name) in [(1, "Alice"), (2, "Bob")] {
+    ///     deferred.execute(
+    ///         sqlx::query("UPDATE users SET name = $1 WHERE id = $2")
+    ///             .bind(name)
+    ///             .bind(id),
+    ///     );
+    /// }
+    ///
+    /// deferred.execute("DELETE FROM sessions WHERE expired");
+    ///
+    /// for result in deferred.flush().await? {
+    ///     println!("{} rows affected", result?.rows_affected());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn defer_flush<'q>(&mut self) -> PgDeferredFlush<'_, 'q> {
+        PgDeferredFlush {
+            conn: self,
+            queries: Vec::new(),
+        }
+    }
+}
+
+/// Statements queued on a connection to be sent together, see [`PgConnection::defer_flush()`].
+///
+/// Nothing is sent to the server until [`flush()`](Self::flush) is called: the statements are
+/// discarded if this is dropped before.
+pub struct PgDeferredFlush<'c, 'q> {
+    conn: &'c mut PgConnection,
+    queries: Vec<DeferredQuery<'q>>,
+}
+
+struct DeferredQuery<'q> {
+    sql: Cow<'q, str>,
+    arguments: Option<PgArguments>,
+    persistent: bool,
+    metadata: Option<Arc<PgStatementMetadata>>,
+}
+
+impl<'c, 'q> PgDeferredFlush<'c, 'q> {
+    /// Queue a statement, to be executed when the queued statements are flushed.
+    ///
+    /// Prepared statements are prepared when flushing, before the statements are sent, if they
+    /// are not cached already.
+    pub fn execute<E>(&mut self, mut query: E) -> &mut Self
+    where
+        E: Execute<'q, Postgres>,
+    {
+        let comment = query.comment();
+
+        self.queries.push(DeferredQuery {
+            sql: sql_with_comment(
+                self.conn.connect_options.sql_rewriter.rewrite(query.sql()),
+                comment,
+            ),
+            metadata: query.statement().map(|s| Arc::clone(&s.metadata)),
+            arguments: query.take_arguments(),
+            persistent: query.persistent(),
+        });
+
+        self
+    }
+
+    /// Returns the number of queued statements.
+    pub fn len(&self) -> usize {
+        self.queries.len()
+    }
+
+    /// Returns `true` if no statements are queued.
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+
+    /// Send the queued statements to the server, and wait for their results.
+    ///
+    /// The result of each statement is returned, in the order in which they were queued.
+    /// The outer error is returned if the connection itself failed, in which case the status
+    /// of the statements is unknown.
+    pub async fn flush(self) -> Result<Vec<Result<PgQueryResult, Error>>, Error> {
+        let PgDeferredFlush { conn, queries } = self;
+
+        // the statements have to be prepared beforehand, as this needs a round-trip for each
+        let mut prepared = Vec::with_capacity(queries.len());
+        let mut sqls = Vec::with_capacity(queries.len());
+
+        for (index, query) in queries.into_iter().enumerate() {
+            let DeferredQuery {
+                sql,
+                arguments,
+                persistent,
+                metadata,
+            } = query;
+
+            prepared.push(match arguments {
+                Some(arguments) => {
+                    // a statement evicted from the cache is closed right away, which must not
+                    // happen to one of the statements prepared for this batch
+                    let store_to_cache = persistent && index < conn.cache_statement.capacity();
+
+                    conn.prepare_deferred(&sql, arguments, store_to_cache, metadata)
+                        .await
+                        .map(Some)
+                }
+                None => Ok(None),
+            });
+            sqls.push(sql);
+        }
+
+        conn.wait_until_ready().await?;
+
+        let mut loggers = Vec::with_capacity(prepared.len());
+
+        for (sql, query) in sqls.iter().zip(&prepared) {
+            let Ok(statement) = query else {
+                continue;
+            };
+
+            let mut logger = QueryLogger::new(sql, conn.log_settings.clone());
+
+            match statement {
+                Some((statement, arguments)) => {
+                    logger.log_parameter_values(|| arguments.format_values(&[]));
+
+                    conn.stream.write(Bind {
+                        portal: None,
+                        statement: *statement,
+                        formats: &[PgValueFormat::Binary],
+                        num_params: arguments.types.len() as i16,
+                        params: &arguments.buffer,
+                        result_formats: &[PgValueFormat::Binary],
+                    });
+                    conn.stream.write(message::Execute {
+                        portal: None,
+                        limit: 0,
+                    });
+                    conn.stream.write(message::Close::Portal(None));
+
+                    // each statement is synced on its own, so that an error only aborts it
+                    conn.write_sync();
+                }
+                None => {
+                    conn.stream.write(Query(sql));
+                    conn.pending_ready_for_query_count += 1;
+                }
+            }
+
+            loggers.push(logger);
+        }
+
+        conn.stream.flush().await?;
+
+        let mut loggers = loggers.into_iter();
+        let mut results = Vec::with_capacity(prepared.len());
+
+        for query in prepared {
+            if let Err(e) = query {
+                results.push(Err(e));
+                continue;
+            }
+
+            let mut logger = loggers.next();
+            let mut result = Ok(PgQueryResult::default());
+
+            // every message up to the `ReadyForQuery` of the statement belongs to it
+            loop {
+                let message = match conn.stream.recv().await {
+                    Ok(message) => message,
+                    Err(Error::Database(e)) => {
+                        result = Err(Error::Database(e));
+                        continue;
+                    }
+                    Err(e) => return Err(e),
+                };
+
+                match message.format {
+                    MessageFormat::CommandComplete => {
+                        let cc: CommandComplete = message.decode()?;
+                        let rows_affected = cc.rows_affected();
+
+                        if let Some(logger) = &mut logger {
+                            logger.increase_rows_affected(rows_affected);
+                        }
+
+                        if let Ok(result) = &mut result {
+                            result.extend([PgQueryResult { rows_affected }]);
+                        }
+                    }
+
+                    MessageFormat::ReadyForQuery => {
+                        conn.handle_ready_for_query(message)?;
+                        break;
+                    }
+
+                    // the rows returned are discarded, as with `execute()`
+                    _ => {}
+                }
+            }
+
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+}
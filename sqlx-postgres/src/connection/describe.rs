@@ -156,11 +156,24 @@ impl PgConnection {
             return Ok(info);
         }
 
+        self.sync_type_cache();
+
         // next we check a local cache for user-defined type names <-> object id
         if let Some(info) = self.cache_type_info.get(&oid) {
             return Ok(info.clone());
         }
 
+        // then the cache shared with other connections, if any
+        let type_cache = self.connect_options.type_cache.clone();
+
+        if let Some(info) = type_cache.as_ref().and_then(|cache| cache.get_info(oid)) {
+            self.cache_type_info.insert(oid, info.clone());
+            self.cache_type_oid
+                .insert(info.0.name().to_string().into(), oid);
+
+            return Ok(info);
+        }
+
         // fallback to asking the database directly for a type name
         if should_fetch {
             let info = self.fetch_type_by_oid(oid).await?;
@@ -171,6 +184,10 @@ impl PgConnection {
             self.cache_type_oid
                 .insert(info.0.name().to_string().into(), oid);
 
+            if let Some(cache) = &type_cache {
+                cache.insert_info(oid, &info);
+            }
+
             Ok(info)
         } else {
             // we are not in a place that *can* run a query
@@ -339,10 +356,19 @@ WHERE rngtypid = $1
     }
 
     pub(crate) async fn fetch_type_id_by_name(&mut self, name: &str) -> Result<Oid, Error> {
+        self.sync_type_cache();
+
         if let Some(oid) = self.cache_type_oid.get(name) {
             return Ok(*oid);
         }
 
+        let type_cache = self.connect_options.type_cache.clone();
+
+        if let Some(oid) = type_cache.as_ref().and_then(|cache| cache.get_oid(name)) {
+            self.cache_type_oid.insert(name.to_string().into(), oid);
+            return Ok(oid);
+        }
+
         // language=SQL
         let (oid,): (Oid,) = query_as("SELECT $1::regtype::oid")
             .bind(name)
@@ -353,9 +379,30 @@ WHERE rngtypid = $1
             })?;
 
         self.cache_type_oid.insert(name.to_string().into(), oid);
+
+        if let Some(cache) = &type_cache {
+            cache.insert_oid(name, oid);
+        }
+
         Ok(oid)
     }
 
+    // Drops the locally cached types if the shared type cache has been cleared since they were
+    // cached
+    fn sync_type_cache(&mut self) {
+        let Some(cache) = &self.connect_options.type_cache else {
+            return;
+        };
+
+        let generation = cache.generation();
+
+        if generation != self.type_cache_generation {
+            self.cache_type_info.clear();
+            self.cache_type_oid.clear();
+            self.type_cache_generation = generation;
+        }
+    }
+
     pub(crate) async fn get_nullable_for_columns(
         &mut self,
         stmt_id: Oid,
@@ -1,3 +1,6 @@
+use std::io;
+use std::sync::Arc;
+
 use crate::HashMap;
 
 use crate::common::StatementCache;
@@ -8,13 +11,102 @@ use crate::message::{
     Authentication, BackendKeyData, MessageFormat, Password, ReadyForQuery, Startup,
 };
 use crate::types::Oid;
-use crate::{PgConnectOptions, PgConnection};
+use crate::{
+    PgConnectOptions, PgConnection, PgLoadBalanceHosts, PgTargetSessionAttrs, PgTypeCache,
+};
+use rand::seq::SliceRandom;
+use sqlx_core::connection::Connection;
+use sqlx_core::executor::Executor;
+use sqlx_core::row::Row;
+use sqlx_core::rt;
 
 // https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.5.7.3
 // https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.5.7.11
 
 impl PgConnection {
     pub(crate) async fn establish(options: &PgConnectOptions) -> Result<Self, Error> {
+        if options.fallback_hosts.is_empty() {
+            return Self::connect_to_host(options).await;
+        }
+
+        let mut hosts: Vec<_> = options.get_hosts().collect();
+
+        if options.load_balance_hosts == PgLoadBalanceHosts::Random {
+            hosts.shuffle(&mut rand::thread_rng());
+        }
+
+        // Like libpq, try each host in turn until one has a matching session
+        let mut last_error = None;
+
+        for (host, port) in hosts {
+            let options = options.clone().host(host).port(port);
+
+            match Self::connect_to_host(&options).await {
+                Ok(conn) => return Ok(conn),
+                Err(error) => {
+                    tracing::debug!(%host, port, %error, "failed to connect to host, trying the next one");
+
+                    last_error = Some(error);
+                }
+            }
+        }
+
+        Err(last_error.expect("there is always at least one host"))
+    }
+
+    // Connect to `options.host`, within the connect timeout
+    async fn connect_to_host(options: &PgConnectOptions) -> Result<Self, Error> {
+        let connect = async {
+            let mut conn = Self::establish_with_host(options).await?;
+
+            if conn
+                .has_target_session_attrs(options.target_session_attrs)
+                .await?
+            {
+                return Ok(conn);
+            }
+
+            let _ = conn.close().await;
+
+            Err(Error::Configuration(
+                format!(
+                    "session at {}:{} does not match target_session_attrs={}",
+                    options.host, options.port, options.target_session_attrs
+                )
+                .into(),
+            ))
+        };
+
+        match options.connect_timeout {
+            Some(timeout) => rt::timeout(timeout, connect).await.map_err(|_| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("connection to {}:{} timed out", options.host, options.port),
+                ))
+            })?,
+            None => connect.await,
+        }
+    }
+
+    async fn has_target_session_attrs(
+        &mut self,
+        attrs: PgTargetSessionAttrs,
+    ) -> Result<bool, Error> {
+        let (query, expected) = match attrs {
+            PgTargetSessionAttrs::Any => return Ok(true),
+            PgTargetSessionAttrs::ReadWrite => ("SHOW transaction_read_only", "off"),
+            PgTargetSessionAttrs::ReadOnly => ("SHOW transaction_read_only", "on"),
+            PgTargetSessionAttrs::Primary => ("SELECT pg_is_in_recovery()", "f"),
+            PgTargetSessionAttrs::Standby => ("SELECT pg_is_in_recovery()", "t"),
+        };
+
+        // Without arguments, this is a simple query which returns the value as text
+        let row = self.fetch_one(query).await?;
+
+        Ok(row.try_get_unchecked::<&str, _>(0)? == expected)
+    }
+
+    async fn establish_with_host(options: &PgConnectOptions) -> Result<Self, Error> {
         // Upgrade to TLS if we were asked to and the server supports it
         let mut stream = PgStream::connect(options).await?;
 
@@ -22,9 +114,6 @@ impl PgConnection {
         // and sends a startup message.
 
         let mut params = vec![
-            // Sets the display format for date and time values,
-            // as well as the rules for interpreting ambiguous date input values.
-            ("DateStyle", "ISO, MDY"),
             // Sets the client-side encoding (character set).
             // <https://www.postgresql.org/docs/devel/multibyte.html#MULTIBYTE-CHARSET-SUPPORTED>
             ("client_encoding", "UTF8"),
@@ -32,6 +121,14 @@ impl PgConnection {
             ("TimeZone", "UTC"),
         ];
 
+        // Startup parameters take precedence over `options`, so only set this if
+        // it wasn't overridden there
+        if !options.has_option("DateStyle") {
+            // Sets the display format for date and time values,
+            // as well as the rules for interpreting ambiguous date input values.
+            params.push(("DateStyle", "ISO, MDY"));
+        }
+
         if let Some(ref extra_float_digits) = options.extra_float_digits {
             params.push(("extra_float_digits", extra_float_digits));
         }
@@ -44,6 +141,25 @@ impl PgConnection {
             params.push(("options", options));
         }
 
+        // These are the settings changed by `SET SESSION CHARACTERISTICS AS TRANSACTION`;
+        // as startup parameters, they are also the values restored by `RESET ALL`
+        if let Some(level) = options.default_transaction_isolation {
+            params.push(("default_transaction_isolation", level.as_sql()));
+        }
+
+        if let Some(read_only) = options.default_transaction_read_only {
+            params.push((
+                "default_transaction_read_only",
+                if read_only { "on" } else { "off" },
+            ));
+        }
+
+        if options.replication {
+            // Connect in logical replication mode; the replication protocol commands
+            // as well as simple-protocol SQL are accepted.
+            params.push(("replication", "database"));
+        }
+
         stream
             .send(Startup {
                 username: Some(&options.username),
@@ -59,7 +175,6 @@ impl PgConnection {
 
         let mut process_id = 0;
         let mut secret_key = 0;
-        let transaction_status;
 
         loop {
             let message = stream.recv().await?;
@@ -120,7 +235,7 @@ impl PgConnection {
 
                 MessageFormat::ReadyForQuery => {
                     // start-up is completed. The frontend can now issue commands
-                    transaction_status =
+                    stream.transaction_status =
                         ReadyForQuery::decode(message.contents)?.transaction_status;
 
                     break;
@@ -139,13 +254,18 @@ impl PgConnection {
             stream,
             process_id,
             secret_key,
-            transaction_status,
+            connect_options: Arc::new(options.clone()),
             transaction_depth: 0,
             pending_ready_for_query_count: 0,
+            pending_sync: false,
             next_statement_id: Oid(1),
             cache_statement: StatementCache::new(options.statement_cache_capacity),
             cache_type_oid: HashMap::new(),
             cache_type_info: HashMap::new(),
+            type_cache_generation: options
+                .type_cache
+                .as_ref()
+                .map_or(0, PgTypeCache::generation),
             log_settings: options.log_settings.clone(),
         })
     }
@@ -1,6 +1,7 @@
+use crate::connection::normalize::normalize_statement_cache_key;
 use crate::describe::Describe;
 use crate::error::Error;
-use crate::executor::{Execute, Executor};
+use crate::executor::{sql_with_comment, Execute, Executor};
 use crate::logger::QueryLogger;
 use crate::message::{
     self, Bind, Close, CommandComplete, DataRow, MessageFormat, ParameterDescription, Parse, Query,
@@ -10,13 +11,14 @@ use crate::statement::PgStatementMetadata;
 use crate::type_info::PgType;
 use crate::types::Oid;
 use crate::{
-    statement::PgStatement, PgArguments, PgConnection, PgQueryResult, PgRow, PgTypeInfo,
+    statement::PgStatement, PgArguments, PgColumn, PgConnection, PgQueryResult, PgRow, PgTypeInfo,
     PgValueFormat, Postgres,
 };
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
 use futures_util::{pin_mut, TryStreamExt};
+use sqlx_core::column::ColumnSelection;
 use sqlx_core::Either;
 use std::{borrow::Cow, sync::Arc};
 
@@ -155,11 +157,30 @@ impl PgConnection {
 
     pub(crate) fn write_sync(&mut self) {
         self.stream.write(message::Sync);
+        self.pending_sync = false;
 
         // all SYNC messages will return a ReadyForQuery
         self.pending_ready_for_query_count += 1;
     }
 
+    // Prepares a statement to execute later, see `PgConnection::defer_flush()`
+    pub(super) async fn prepare_deferred(
+        &mut self,
+        sql: &str,
+        mut arguments: PgArguments,
+        store_to_cache: bool,
+        metadata: Option<Arc<PgStatementMetadata>>,
+    ) -> Result<(Oid, PgArguments), Error> {
+        let (statement, metadata) = self
+            .get_or_prepare(sql, &arguments.types, store_to_cache, metadata)
+            .await?;
+
+        // patch holes created during encoding
+        arguments.apply_patches(self, &metadata.parameters).await?;
+
+        Ok((statement, arguments))
+    }
+
     async fn get_or_prepare<'a>(
         &mut self,
         sql: &str,
@@ -170,14 +191,20 @@ impl PgConnection {
         // a statement object
         metadata: Option<Arc<PgStatementMetadata>>,
     ) -> Result<(Oid, Arc<PgStatementMetadata>), Error> {
-        if let Some(statement) = self.cache_statement.get_mut(sql) {
+        let key = if self.connect_options.normalize_statement_cache_key {
+            normalize_statement_cache_key(sql)
+        } else {
+            Cow::Borrowed(sql)
+        };
+
+        if let Some(statement) = self.cache_statement.get_mut(&key) {
             return Ok((*statement).clone());
         }
 
         let statement = prepare(self, sql, parameters, metadata).await?;
 
         if store_to_cache && self.cache_statement.is_enabled() {
-            if let Some((id, _)) = self.cache_statement.insert(sql, statement.clone()) {
+            if let Some((id, _)) = self.cache_statement.insert(&key, statement.clone()) {
                 self.stream.write(Close::Statement(id));
                 self.write_sync();
 
@@ -191,13 +218,18 @@ impl PgConnection {
         Ok(statement)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn run<'e, 'c: 'e, 'q: 'e>(
         &'c mut self,
         query: &'q str,
         arguments: Option<PgArguments>,
         limit: u8,
+        // if non-zero, the rows are fetched this many at a time, see `fetch_with_batch_size()`
+        batch_size: u32,
         persistent: bool,
         metadata_opt: Option<Arc<PgStatementMetadata>>,
+        // the only columns to keep in the rows, see `Query::select_columns()`
+        selected_columns: Option<&'q [&'q str]>,
     ) -> Result<impl Stream<Item = Result<Either<PgQueryResult, PgRow>, Error>> + 'e, Error> {
         let mut logger = QueryLogger::new(query, self.log_settings.clone());
 
@@ -205,6 +237,8 @@ impl PgConnection {
         self.wait_until_ready().await?;
 
         let mut metadata: Arc<PgStatementMetadata>;
+        let mut selection: Option<ColumnSelection> = None;
+        let mut column_formats: Option<Arc<[PgValueFormat]>> = None;
 
         let format = if let Some(mut arguments) = arguments {
             // prepare the statement if this our first time executing it
@@ -218,6 +252,17 @@ impl PgConnection {
             // patch holes created during encoding
             arguments.apply_patches(self, &metadata.parameters).await?;
 
+            logger.log_parameter_values(|| arguments.format_values(&metadata.parameters));
+
+            let result_formats = result_formats(&self.connect_options.text_result_types, &metadata);
+
+            selection = select_columns(&mut metadata, selected_columns)?;
+
+            column_formats = result_formats.as_ref().map(|formats| match &selection {
+                Some(selection) => selection.indices().iter().map(|&i| formats[i]).collect(),
+                None => formats.iter().copied().collect(),
+            });
+
             // consume messages till `ReadyForQuery` before bind and execute
             self.wait_until_ready().await?;
 
@@ -228,32 +273,46 @@ impl PgConnection {
                 formats: &[PgValueFormat::Binary],
                 num_params: arguments.types.len() as i16,
                 params: &*arguments.buffer,
-                result_formats: &[PgValueFormat::Binary],
+                result_formats: result_formats
+                    .as_deref()
+                    .unwrap_or(&[PgValueFormat::Binary]),
             });
 
-            // executes the portal up to the passed limit
-            // the protocol-level limit acts nearly identically to the `LIMIT` in SQL
-            self.stream.write(message::Execute {
-                portal: None,
-                limit: limit.into(),
-            });
-            // From https://www.postgresql.org/docs/current/protocol-flow.html:
-            //
-            // "An unnamed portal is destroyed at the end of the transaction, or as
-            // soon as the next Bind statement specifying the unnamed portal as
-            // destination is issued. (Note that a simple Query message also
-            // destroys the unnamed portal."
-
-            // we ask the database server to close the unnamed portal and free the associated resources
-            // earlier - after the execution of the current query.
-            self.stream.write(message::Close::Portal(None));
-
-            // finally, [Sync] asks postgres to process the messages that we sent and respond with
-            // a [ReadyForQuery] message when it's completely done. Theoretically, we could send
-            // dozens of queries before a [Sync] and postgres can handle that. Execution on the server
-            // is still serial but it would reduce round-trips. Some kind of builder pattern that is
-            // termed batching might suit this.
-            self.write_sync();
+            if batch_size > 0 {
+                // executes the portal for one batch of rows; the following batches are requested
+                // as the rows are consumed. As a [Sync] would destroy the unnamed portal outside
+                // of a transaction, we only [Flush] until the portal has been fully executed.
+                self.stream.write(message::Execute {
+                    portal: None,
+                    limit: batch_size,
+                });
+                self.stream.write(message::Flush);
+                self.pending_sync = true;
+            } else {
+                // executes the portal up to the passed limit
+                // the protocol-level limit acts nearly identically to the `LIMIT` in SQL
+                self.stream.write(message::Execute {
+                    portal: None,
+                    limit: limit.into(),
+                });
+                // From https://www.postgresql.org/docs/current/protocol-flow.html:
+                //
+                // "An unnamed portal is destroyed at the end of the transaction, or as
+                // soon as the next Bind statement specifying the unnamed portal as
+                // destination is issued. (Note that a simple Query message also
+                // destroys the unnamed portal."
+
+                // we ask the database server to close the unnamed portal and free the associated resources
+                // earlier - after the execution of the current query.
+                self.stream.write(message::Close::Portal(None));
+
+                // finally, [Sync] asks postgres to process the messages that we sent and respond with
+                // a [ReadyForQuery] message when it's completely done. Theoretically, we could send
+                // dozens of queries before a [Sync] and postgres can handle that. Execution on the server
+                // is still serial but it would reduce round-trips. Some kind of builder pattern that is
+                // termed batching might suit this.
+                self.write_sync();
+            }
 
             // prepared statements are binary
             PgValueFormat::Binary
@@ -294,6 +353,13 @@ impl PgConnection {
                         // a SQL command completed normally
                         let cc: CommandComplete = message.decode()?;
 
+                        if self.pending_sync {
+                            // the portal executed in batches is done
+                            self.stream.write(message::Close::Portal(None));
+                            self.write_sync();
+                            self.stream.flush().await?;
+                        }
+
                         let rows_affected = cc.rows_affected();
                         logger.increase_rows_affected(rows_affected);
                         r#yield!(Either::Left(PgQueryResult {
@@ -308,7 +374,17 @@ impl PgConnection {
                     // Message::ErrorResponse is handled in self.stream.recv()
 
                     // incomplete query execution has finished
-                    MessageFormat::PortalSuspended => {}
+                    MessageFormat::PortalSuspended => {
+                        if self.pending_sync {
+                            // the previous batch has been consumed, request the next one
+                            self.stream.write(message::Execute {
+                                portal: None,
+                                limit: batch_size,
+                            });
+                            self.stream.write(message::Flush);
+                            self.stream.flush().await?;
+                        }
+                    }
 
                     MessageFormat::RowDescription => {
                         // indicates that a *new* set of rows are about to be returned
@@ -321,16 +397,22 @@ impl PgConnection {
                             columns,
                             parameters: Vec::default(),
                         });
+
+                        selection = select_columns(&mut metadata, selected_columns)?;
                     }
 
                     MessageFormat::DataRow => {
                         logger.increment_rows_returned();
 
                         // one of the set of rows returned by a SELECT, FETCH, etc query
-                        let data: DataRow = message.decode()?;
+                        let data = match &selection {
+                            Some(selection) => DataRow::decode_selected(message.contents, selection)?,
+                            None => message.decode()?,
+                        };
                         let row = PgRow {
                             data,
                             format,
+                            column_formats: column_formats.clone(),
                             metadata: Arc::clone(&metadata),
                         };
 
@@ -357,6 +439,161 @@ impl PgConnection {
     }
 }
 
+// Only keeps the selected columns in the metadata of the rows, if any
+// The format of each column in the results of a prepared statement, if some of them are
+// requested in text format, see `PgConnectOptions::text_result_format()`
+fn result_formats(
+    text_result_types: &[PgTypeInfo],
+    metadata: &PgStatementMetadata,
+) -> Option<Vec<PgValueFormat>> {
+    let is_text = |column: &PgColumn| {
+        text_result_types
+            .iter()
+            .any(|ty| match (ty.oid(), column.type_info.oid()) {
+                (Some(a), Some(b)) => a == b,
+                _ => ty.0.name() == column.type_info.0.name(),
+            })
+    };
+
+    if !metadata.columns.iter().any(is_text) {
+        return None;
+    }
+
+    Some(
+        metadata
+            .columns
+            .iter()
+            .map(|column| {
+                if is_text(column) {
+                    PgValueFormat::Text
+                } else {
+                    PgValueFormat::Binary
+                }
+            })
+            .collect(),
+    )
+}
+
+fn select_columns(
+    metadata: &mut Arc<PgStatementMetadata>,
+    selected_columns: Option<&[&str]>,
+) -> Result<Option<ColumnSelection>, Error> {
+    let Some(selected_columns) = selected_columns else {
+        return Ok(None);
+    };
+
+    // statements which don't return rows are left as they are
+    if metadata.columns.is_empty() {
+        return Ok(None);
+    }
+
+    let selection = ColumnSelection::new(
+        selected_columns,
+        &metadata.column_names,
+        metadata.columns.len(),
+    )?;
+
+    *metadata = Arc::new(metadata.select(&selection));
+
+    Ok(Some(selection))
+}
+
+impl PgConnection {
+    /// Execute the query and return the generated rows, fetching them `batch_size` at a time.
+    ///
+    /// Normally, the server sends all the rows of a query as fast as they can be consumed. With
+    /// this, the server returns only `batch_size` rows at first, and the next batch is requested
+    /// once those have been consumed. This trades more round-trips for a bounded number of rows
+    /// in flight, and lets the server stop early if the stream is dropped.
+    ///
+    /// Only prepared statements are executed in batches. Queries built with
+    /// [`query()`][sqlx_core::query::query] and the like are always prepared, even without bind
+    /// parameters, but SQL strings executed directly are not. A `batch_size` of `0` fetches all
+    /// rows at once, as with [`Executor::fetch()`].
+    ///
+    /// See also [`PgFetchBatchExt::fetch_with_batch_size()`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use futures::TryStreamExt;
+    ///
+    /// let mut rows = conn.fetch_with_batch_size(sqlx::query("SELECT * FROM events"), 1000);
+    ///
+    /// while let Some(row) = rows.try_next().await? {
+    ///     // ...
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fetch_with_batch_size<'e, 'q: 'e, E>(
+        &'e mut self,
+        mut query: E,
+        batch_size: u32,
+    ) -> BoxStream<'e, Result<PgRow, Error>>
+    where
+        E: 'q + Execute<'q, Postgres>,
+    {
+        let sql = query.sql();
+        let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
+        let arguments = query.take_arguments();
+        let comment = query.comment();
+        let persistent = query.persistent();
+        let selected_columns = query.selected_columns();
+
+        Box::pin(try_stream! {
+            let sql = sql_with_comment(self.connect_options.sql_rewriter.rewrite(sql), comment);
+
+            let s = self.run(&sql, arguments, 0, batch_size, persistent, metadata, selected_columns)
+                .await?;
+            pin_mut!(s);
+
+            while let Some(v) = s.try_next().await? {
+                if let Either::Right(row) = v {
+                    r#yield!(row);
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// An extension trait to fetch the rows of a query in batches of a fixed size,
+/// see [`PgConnection::fetch_with_batch_size()`].
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use futures::TryStreamExt;
+/// use sqlx::postgres::PgFetchBatchExt;
+///
+/// let mut rows = sqlx::query("SELECT * FROM events WHERE kind = $1")
+///     .bind("click")
+///     .fetch_with_batch_size(conn, 1000);
+///
+/// while let Some(row) = rows.try_next().await? {
+///     // ...
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub trait PgFetchBatchExt<'q>: 'q + Execute<'q, Postgres> + Sized {
+    /// Execute the query and return the generated rows, fetching them `batch_size` at a time.
+    fn fetch_with_batch_size<'e>(
+        self,
+        conn: &'e mut PgConnection,
+        batch_size: u32,
+    ) -> BoxStream<'e, Result<PgRow, Error>>
+    where
+        'q: 'e,
+    {
+        conn.fetch_with_batch_size(self, batch_size)
+    }
+}
+
+impl<'q, E: 'q + Execute<'q, Postgres>> PgFetchBatchExt<'q> for E {}
+
 impl<'c> Executor<'c> for &'c mut PgConnection {
     type Database = Postgres;
 
@@ -371,10 +608,15 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         let sql = query.sql();
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
         let arguments = query.take_arguments();
+        let comment = query.comment();
         let persistent = query.persistent();
+        let selected_columns = query.selected_columns();
 
         Box::pin(try_stream! {
-            let s = self.run(sql, arguments, 0, persistent, metadata).await?;
+            let sql = sql_with_comment(self.connect_options.sql_rewriter.rewrite(sql), comment);
+
+            let s = self.run(&sql, arguments, 0, 0, persistent, metadata, selected_columns)
+                .await?;
             pin_mut!(s);
 
             while let Some(v) = s.try_next().await? {
@@ -396,10 +638,24 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         let sql = query.sql();
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
         let arguments = query.take_arguments();
+        let comment = query.comment();
         let persistent = query.persistent();
+        let selected_columns = query.selected_columns();
 
         Box::pin(async move {
-            let s = self.run(sql, arguments, 1, persistent, metadata).await?;
+            let sql = sql_with_comment(self.connect_options.sql_rewriter.rewrite(sql), comment);
+
+            let s = self
+                .run(
+                    &sql,
+                    arguments,
+                    1,
+                    0,
+                    persistent,
+                    metadata,
+                    selected_columns,
+                )
+                .await?;
             pin_mut!(s);
 
             while let Some(s) = s.try_next().await? {
@@ -423,7 +679,10 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         Box::pin(async move {
             self.wait_until_ready().await?;
 
-            let (_, metadata) = self.get_or_prepare(sql, parameters, true, None).await?;
+            let rewritten = self.connect_options.sql_rewriter.rewrite(sql);
+            let (_, metadata) = self
+                .get_or_prepare(&rewritten, parameters, true, None)
+                .await?;
 
             Ok(PgStatement {
                 sql: Cow::Borrowed(sql),
@@ -442,7 +701,8 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         Box::pin(async move {
             self.wait_until_ready().await?;
 
-            let (stmt_id, metadata) = self.get_or_prepare(sql, &[], true, None).await?;
+            let sql = self.connect_options.sql_rewriter.rewrite(sql);
+            let (stmt_id, metadata) = self.get_or_prepare(&sql, &[], true, None).await?;
 
             let nullable = self.get_nullable_for_columns(stmt_id, &metadata).await?;
 
@@ -10,20 +10,26 @@ use crate::error::Error;
 use crate::ext::ustr::UStr;
 use crate::io::Decode;
 use crate::message::{
-    Close, Message, MessageFormat, Query, ReadyForQuery, Terminate, TransactionStatus,
+    CancelRequest, Close, Message, MessageFormat, PgTransactionStatus, Query, ReadyForQuery,
+    Terminate,
 };
 use crate::statement::PgStatementMetadata;
-use crate::transaction::Transaction;
+use crate::transaction::{Transaction, TransactionOptions};
 use crate::types::Oid;
 use crate::{PgConnectOptions, PgTypeInfo, Postgres};
 
 pub(crate) use sqlx_core::connection::*;
 
+pub use self::deferred::PgDeferredFlush;
+pub use self::executor::PgFetchBatchExt;
 pub use self::stream::PgStream;
 
+mod deferred;
 pub(crate) mod describe;
 mod establish;
 mod executor;
+mod normalize;
+mod raw;
 mod sasl;
 mod stream;
 mod tls;
@@ -37,14 +43,15 @@ pub struct PgConnection {
 
     // process id of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
     process_id: u32,
 
     // secret key of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
     secret_key: u32,
 
+    // options used to connect, to send cancel requests to the same server
+    pub(crate) connect_options: Arc<PgConnectOptions>,
+
     // sequence of statement IDs for use in preparing statements
     // in PostgreSQL, the statement is prepared to a user-supplied identifier
     next_statement_id: Oid,
@@ -56,11 +63,17 @@ pub struct PgConnection {
     cache_type_info: HashMap<Oid, PgTypeInfo>,
     cache_type_oid: HashMap<UStr, Oid>,
 
+    // the generation of the shared type cache the types above have been cached in, if any,
+    // see `PgTypeCache::clear()`
+    type_cache_generation: u64,
+
     // number of ReadyForQuery messages that we are currently expecting
     pub(crate) pending_ready_for_query_count: usize,
 
-    // current transaction status
-    transaction_status: TransactionStatus,
+    // whether a Sync still has to be sent to end the current extended query,
+    // which is delayed while a portal is executed in batches
+    pending_sync: bool,
+
     pub(crate) transaction_depth: usize,
 
     log_settings: LogSettings,
@@ -72,8 +85,70 @@ impl PgConnection {
         self.stream.server_version_num
     }
 
+    /// Returns the transaction status of the connection, as reported by the server when it was
+    /// last ready for a query.
+    ///
+    /// This tells whether the connection is in a transaction, including one started with a plain
+    /// `BEGIN` rather than [`begin()`](Connection::begin), and whether that transaction has
+    /// failed, so that any further query would be rejected with "current transaction is aborted"
+    /// until it is rolled back. A transaction is reported as failed as soon as a query returns an
+    /// error in it.
+    ///
+    /// The status is not updated by queries which were only queued, e.g. the rollback of a
+    /// dropped [`Transaction`], until the connection is used again.
+    pub fn transaction_status(&self) -> PgTransactionStatus {
+        self.stream.transaction_status
+    }
+
+    /// Rollback the current transaction, if any, to return the connection to an idle state.
+    ///
+    /// This issues a `ROLLBACK` if the connection is in a transaction, whether it has failed or
+    /// not, and does nothing otherwise. It rolls back the whole transaction, including any
+    /// savepoints of nested [`Transaction`]s, which then no longer have anything to roll back.
+    ///
+    /// This can be used to recover a connection after an error, or in
+    /// [`PoolOptions::after_release()`](sqlx_core::pool::PoolOptions::after_release) so that a
+    /// transaction left open by a plain `BEGIN` does not leak into the next use of the connection:
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> sqlx::Result<()> {
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// let pool = PgPoolOptions::new()
+    ///     .after_release(|conn, _meta| {
+    ///         Box::pin(async move {
+    ///             conn.recover().await?;
+    ///
+    ///             Ok(true)
+    ///         })
+    ///     })
+    ///     .connect("postgres:// …")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn recover(&mut self) -> Result<(), Error> {
+        // handle any queued query first, e.g. the rollback of a dropped transaction
+        self.wait_until_ready().await?;
+
+        if self.stream.transaction_status != PgTransactionStatus::Idle {
+            self.queue_simple_query("ROLLBACK");
+            self.wait_until_ready().await?;
+        }
+
+        self.transaction_depth = 0;
+
+        Ok(())
+    }
+
     // will return when the connection is ready for another query
     pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
+        if self.pending_sync {
+            // a portal executed in batches was abandoned or failed, the Sync makes the
+            // server discard its remaining rows, or recover from the error
+            self.write_sync();
+        }
+
         if !self.stream.write_buffer_mut().is_empty() {
             self.stream.flush().await?;
         }
@@ -96,14 +171,15 @@ impl PgConnection {
             .await?;
 
         self.pending_ready_for_query_count -= 1;
-        self.transaction_status = r.transaction_status;
+        self.stream.transaction_status = r.transaction_status;
 
         Ok(())
     }
 
     fn handle_ready_for_query(&mut self, message: Message) -> Result<(), Error> {
         self.pending_ready_for_query_count -= 1;
-        self.transaction_status = ReadyForQuery::decode(message.contents)?.transaction_status;
+        self.stream.transaction_status =
+            ReadyForQuery::decode(message.contents)?.transaction_status;
 
         Ok(())
     }
@@ -170,10 +246,24 @@ impl Connection for PgConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn cached_statements_size(&self) -> usize {
         self.cache_statement.len()
     }
 
+    fn cached_statements(&mut self) -> BoxFuture<'_, Result<Vec<String>, Error>> {
+        Box::pin(async move { Ok(self.cache_statement.keys().map(str::to_owned).collect()) })
+    }
+
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             self.cache_type_oid.clear();
@@ -212,6 +302,38 @@ impl Connection for PgConnection {
     fn should_flush(&self) -> bool {
         !self.stream.write_buffer().is_empty()
     }
+
+    #[doc(hidden)]
+    fn rollback_leaked_transaction(&mut self) -> BoxFuture<'_, Result<bool, Error>> {
+        Box::pin(async move {
+            self.wait_until_ready().await?;
+
+            let leaked = self.stream.transaction_status != PgTransactionStatus::Idle;
+
+            self.recover().await?;
+
+            Ok(leaked)
+        })
+    }
+
+    fn cancel_token(&self) -> Option<CancelToken> {
+        let options = Arc::clone(&self.connect_options);
+        let request = CancelRequest {
+            process_id: self.process_id,
+            secret_key: self.secret_key,
+        };
+
+        Some(CancelToken::new(async move {
+            let mut stream = PgStream::connect(&options).await?;
+
+            stream.send(request).await?;
+
+            // The server closes the connection without responding once it handled the request
+            let _ = stream.read_buffered(1).await;
+
+            Ok(())
+        }))
+    }
 }
 
 // Implement `AsMut<Self>` so that `PgConnection` can be wrapped in
@@ -0,0 +1,188 @@
+use std::borrow::Cow;
+
+/// Normalizes the SQL of a statement to use it as its key in the statement cache, see
+/// [`PgConnectOptions::normalize_statement_cache_key()`][crate::PgConnectOptions::normalize_statement_cache_key].
+///
+/// Runs of whitespace are collapsed into a single space, and leading and trailing whitespace is
+/// removed, outside of string literals, quoted identifiers and comments. A line break is kept
+/// where it is significant: after a line comment, which it ends, or after a string literal,
+/// which can be continued on the next line (`'foo'\n'bar'` is `'foobar'`).
+///
+/// The SQL is returned unchanged if it contains a backslash, as whether it escapes a quote
+/// depends on the kind of the string literal and on `standard_conforming_strings`.
+pub(crate) fn normalize_statement_cache_key(sql: &str) -> Cow<'_, str> {
+    if sql.contains('\\') {
+        return Cow::Borrowed(sql);
+    }
+
+    let bytes = sql.as_bytes();
+    let mut key = String::with_capacity(sql.len());
+    let mut keep_line_break = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+
+        match bytes[i] {
+            b if is_whitespace(b) => {
+                let mut line_break = false;
+
+                while i < bytes.len() && is_whitespace(bytes[i]) {
+                    line_break |= bytes[i] == b'\n';
+                    i += 1;
+                }
+
+                if !key.is_empty() && i < bytes.len() {
+                    key.push(if line_break && keep_line_break {
+                        '\n'
+                    } else {
+                        ' '
+                    });
+                }
+
+                continue;
+            }
+
+            quote @ (b'\'' | b'"') => {
+                // a doubled quote ends the literal and starts another one, which is copied as well
+                i = find(bytes, i + 1, &[quote]).map_or(bytes.len(), |end| end + 1);
+                keep_line_break = quote == b'\'';
+            }
+
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = find(bytes, i, b"\n").unwrap_or(bytes.len());
+                keep_line_break = true;
+            }
+
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = end_of_block_comment(bytes, i);
+                keep_line_break = false;
+            }
+
+            b'$' if dollar_quote_tag(bytes, i).is_some() => {
+                let tag = dollar_quote_tag(bytes, i).unwrap();
+
+                i = find(bytes, i + tag.len(), tag).map_or(bytes.len(), |end| end + tag.len());
+                keep_line_break = false;
+            }
+
+            _ => {
+                i += sql[i..].chars().next().map_or(1, char::len_utf8);
+                keep_line_break = false;
+            }
+        }
+
+        key.push_str(&sql[start..i]);
+    }
+
+    if key == sql {
+        Cow::Borrowed(sql)
+    } else {
+        Cow::Owned(key)
+    }
+}
+
+// the whitespace of the lexer of Postgres
+fn is_whitespace(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r' | b'\x0c')
+}
+
+fn is_identifier_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b >= 0x80
+}
+
+fn find(bytes: &[u8], from: usize, needle: &[u8]) -> Option<usize> {
+    bytes[from..]
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|position| from + position)
+}
+
+// block comments can be nested
+fn end_of_block_comment(bytes: &[u8], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+
+    while i < bytes.len() {
+        match &bytes[i..] {
+            [b'/', b'*', ..] => {
+                depth += 1;
+                i += 2;
+            }
+            [b'*', b'/', ..] => {
+                depth -= 1;
+                i += 2;
+
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    bytes.len()
+}
+
+// `$$` or `$tag$` starting a dollar-quoted string, but not a parameter such as `$1` or a `$` in
+// an identifier such as `a$b`
+fn dollar_quote_tag(bytes: &[u8], start: usize) -> Option<&[u8]> {
+    if start > 0 && is_identifier_byte(bytes[start - 1]) {
+        return None;
+    }
+
+    let mut end = start + 1;
+
+    if bytes
+        .get(end)
+        .filter(|b| b.is_ascii_alphabetic() || **b == b'_' || **b >= 0x80)
+        .is_some()
+    {
+        while end < bytes.len() && is_identifier_byte(bytes[end]) && bytes[end] != b'$' {
+            end += 1;
+        }
+    }
+
+    match bytes.get(end) {
+        Some(b'$') => Some(&bytes[start..=end]),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_normalize_statement_cache_key() {
+    for (sql, key) in [
+        ("SELECT 1", "SELECT 1"),
+        (
+            "  SELECT\n\t*\n   FROM  users \n  WHERE id = $1 ",
+            "SELECT * FROM users WHERE id = $1",
+        ),
+        (
+            "SELECT '  a  b  ', \"  c  \" FROM t",
+            "SELECT '  a  b  ', \"  c  \" FROM t",
+        ),
+        ("SELECT 'it''s  '  ,  1", "SELECT 'it''s  ' , 1"),
+        ("SELECT 'foo'\n  'bar'", "SELECT 'foo'\n'bar'"),
+        ("SELECT 'foo'  'bar'", "SELECT 'foo' 'bar'"),
+        ("SELECT 1 -- one  \n  ,  2", "SELECT 1 -- one  \n, 2"),
+        (
+            "SELECT 1 /* a  /* b */  c */  ,  2",
+            "SELECT 1 /* a  /* b */  c */ , 2",
+        ),
+        (
+            "SELECT $$  a  $$ ,  $x$  $$  $x$",
+            "SELECT $$  a  $$ , $x$  $$  $x$",
+        ),
+        ("SELECT  $1,  a$b  ,  $2", "SELECT $1, a$b , $2"),
+        ("SELECT  'é  '  ,  é", "SELECT 'é  ' , é"),
+        ("SELECT  'unterminated  ", "SELECT 'unterminated  "),
+        ("SELECT  E'\\'  '", "SELECT  E'\\'  '"),
+    ] {
+        assert_eq!(normalize_statement_cache_key(sql), key, "{sql:?}");
+    }
+
+    assert!(matches!(
+        normalize_statement_cache_key("SELECT 1"),
+        Cow::Borrowed(_)
+    ));
+}
@@ -0,0 +1,79 @@
+use sqlx_core::bytes::Bytes;
+
+use crate::error::Error;
+use crate::io::{Decode, Encode};
+use crate::message::ReadyForQuery;
+use crate::PgConnection;
+
+// A message with an arbitrary type, framed as any other message
+struct RawMessage<'a> {
+    format: u8,
+    body: &'a [u8],
+}
+
+impl Encode<'_> for RawMessage<'_> {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: ()) {
+        buf.reserve(5 + self.body.len());
+        buf.push(self.format);
+        buf.extend(&((4 + self.body.len()) as i32).to_be_bytes());
+        buf.extend(self.body);
+    }
+}
+
+impl PgConnection {
+    /// Send a message of the given type to the server, bypassing SQLx.
+    ///
+    /// This is an escape hatch to use parts of the
+    /// [frontend/backend protocol](https://www.postgresql.org/docs/current/protocol.html) which
+    /// SQLx doesn't support (yet), e.g. protocol extensions. It is not covered by semver and may
+    /// change or be removed in any release.
+    ///
+    /// `body` is the contents of the message, which is framed with the message type and length.
+    /// Any pending work of SQLx on this connection (e.g. the rest of a dropped query) is
+    /// completed first, then the message is sent immediately.
+    ///
+    /// # Invariants
+    ///
+    /// SQLx doesn't keep track of the raw messages sent, so their responses must be handled
+    /// with [`recv_raw_message()`][Self::recv_raw_message] before the connection can be used
+    /// for anything else (including being returned to a pool):
+    ///
+    /// * every message or sequence of messages for which the server sends a `ReadyForQuery`
+    ///   (e.g. `Query` or `Sync`) must be followed by receiving all the responses, up to and
+    ///   including that `ReadyForQuery`;
+    /// * messages for which the server doesn't respond by itself (e.g. `Parse` or `Bind`)
+    ///   must be followed by a `Sync` before SQLx uses the connection again;
+    /// * the session must be left in a state SQLx expects, e.g. the statements prepared by
+    ///   SQLx (named `sqlx_s_*`) must not be closed, and a transaction must not be started
+    ///   or ended while SQLx tracks one with [`Transaction`][sqlx_core::transaction::Transaction].
+    ///
+    /// If the future of this function or of [`recv_raw_message()`][Self::recv_raw_message] is
+    /// cancelled, or an error is returned, the state of the connection is unknown and it should
+    /// be closed.
+    #[doc(hidden)]
+    pub async fn send_raw_message(&mut self, format: u8, body: &[u8]) -> Result<(), Error> {
+        self.wait_until_ready().await?;
+
+        self.stream.send(RawMessage { format, body }).await
+    }
+
+    /// Receive the next message from the server, bypassing SQLx.
+    ///
+    /// Returns the type and contents of the message, without its length. No message is handled
+    /// by SQLx, so this includes `ErrorResponse`, `NoticeResponse`, `NotificationResponse` and
+    /// `ParameterStatus` messages, which are otherwise handled transparently. Only the transaction
+    /// status reported by a `ReadyForQuery` is recorded.
+    ///
+    /// See [`send_raw_message()`][Self::send_raw_message] for the invariants to maintain.
+    #[doc(hidden)]
+    pub async fn recv_raw_message(&mut self) -> Result<(u8, Bytes), Error> {
+        let (format, contents) = self.stream.recv_raw().await?;
+
+        if format == b'Z' {
+            self.stream.transaction_status =
+                ReadyForQuery::decode(contents.clone())?.transaction_status;
+        }
+
+        Ok((format, contents))
+    }
+}
@@ -10,7 +10,9 @@ use sqlx_core::bytes::{Buf, Bytes};
 use crate::connection::tls::MaybeUpgradeTls;
 use crate::error::Error;
 use crate::io::{Decode, Encode};
-use crate::message::{Message, MessageFormat, Notice, Notification, ParameterStatus};
+use crate::message::{
+    Message, MessageFormat, Notice, Notification, ParameterStatus, PgTransactionStatus,
+};
 use crate::net::{self, BufferedSocket, Socket};
 use crate::{PgConnectOptions, PgDatabaseError, PgSeverity};
 
@@ -36,6 +38,9 @@ pub struct PgStream {
     pub(crate) parameter_statuses: BTreeMap<String, String>,
 
     pub(crate) server_version_num: Option<u32>,
+
+    // transaction status reported by the last `ReadyForQuery`, or anticipated from an error
+    pub(crate) transaction_status: PgTransactionStatus,
 }
 
 impl PgStream {
@@ -52,6 +57,7 @@ impl PgStream {
             notifications: None,
             parameter_statuses: BTreeMap::default(),
             server_version_num: None,
+            transaction_status: PgTransactionStatus::Idle,
         })
     }
 
@@ -83,16 +89,38 @@ impl PgStream {
     }
 
     pub(crate) async fn recv_unchecked(&mut self) -> Result<Message, Error> {
+        let (format, contents) = self.recv_raw().await?;
+        let format = MessageFormat::try_from_u8(format)?;
+
+        Ok(Message { format, contents })
+    }
+
+    // Get the type and contents of the next message, even if the type is unknown to us
+    pub(crate) async fn recv_raw(&mut self) -> Result<(u8, Bytes), Error> {
         // all packets in postgres start with a 5-byte header
         // this header contains the message type and the total length of the message
         let mut header: Bytes = self.inner.read(5).await?;
 
-        let format = MessageFormat::try_from_u8(header.get_u8())?;
+        let format = header.get_u8();
         let size = (header.get_u32() - 4) as usize;
 
         let contents = self.inner.read(size).await?;
 
-        Ok(Message { format, contents })
+        Ok((format, contents))
+    }
+
+    // Returns `true` if a complete message has already been read from the socket,
+    // in which case `recv_unchecked()` will return without waiting on the server
+    pub(crate) fn has_buffered_message(&self) -> bool {
+        let buf = self.inner.read_buffer();
+
+        if buf.len() < 5 {
+            return false;
+        }
+
+        let size = u32::from_be_bytes([buf[1], buf[2], buf[3], buf[4]]) as usize;
+
+        buf.len() > size
     }
 
     // Get the next message from the server
@@ -104,6 +132,13 @@ impl PgStream {
             match message.format {
                 MessageFormat::ErrorResponse => {
                     // An error returned from the database server.
+
+                    // an error aborts the current transaction block, which the server only
+                    // reports with the next `ReadyForQuery`, once the error has been returned
+                    if self.transaction_status == PgTransactionStatus::InTransaction {
+                        self.transaction_status = PgTransactionStatus::InFailedTransaction;
+                    }
+
                     return Err(PgDatabaseError(message.decode()?).into());
                 }
 
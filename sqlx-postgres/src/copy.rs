@@ -3,6 +3,7 @@ use std::borrow::Cow;
 use std::ops::{Deref, DerefMut};
 
 use futures_core::stream::BoxStream;
+use futures_util::{future, TryStreamExt};
 use sqlx_core::bytes::{BufMut, Bytes};
 
 use crate::connection::PgConnection;
@@ -59,6 +60,50 @@ impl PgConnection {
     ) -> Result<BoxStream<'c, Result<Bytes>>> {
         pg_begin_copy_out(self, statement).await
     }
+
+    /// Issue a `COPY TO STDOUT` statement like [`copy_out_raw()`](Self::copy_out_raw), passing
+    /// each chunk of data through `transform` as it arrives.
+    ///
+    /// In the text and CSV formats, Postgres sends each row in its own chunk, so `transform`
+    /// receives one row at a time, with its trailing newline. It returns the bytes to yield in
+    /// place of the row, or `None` to skip it, e.g. to filter or redact the rows of an export
+    /// without buffering them. In the binary format, the first chunk also starts with the header
+    /// of the file, and the last one is the trailer.
+    ///
+    /// The same caveats as for [`copy_out_raw()`](Self::copy_out_raw) apply if the stream isn't
+    /// read to completion.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use futures::TryStreamExt;
+    ///
+    /// // skip the rows of internal users
+    /// let mut rows = conn
+    ///     .copy_out_with("COPY users (email, name) TO STDOUT WITH (FORMAT CSV)", |row| {
+    ///         (!row.starts_with(b"admin@")).then_some(row)
+    ///     })
+    ///     .await?;
+    ///
+    /// while let Some(row) = rows.try_next().await? {
+    ///     // write `row` to the export
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn copy_out_with<'c, F>(
+        &'c mut self,
+        statement: &str,
+        transform: F,
+    ) -> Result<BoxStream<'c, Result<Bytes>>>
+    where
+        F: FnMut(Bytes) -> Option<Bytes> + Send + 'c,
+    {
+        let stream = pg_begin_copy_out(self, statement).await?;
+
+        Ok(transform_copy_out(stream, transform))
+    }
 }
 
 /// Implements methods for directly executing `COPY FROM/TO STDOUT` on a [`PgPool`].
@@ -109,6 +154,18 @@ pub trait PgPoolCopyExt {
         &'a self,
         statement: &'a str,
     ) -> BoxFuture<'a, Result<BoxStream<'static, Result<Bytes>>>>;
+
+    /// Issue a `COPY TO STDOUT` statement and begin streaming data from Postgres, passing each
+    /// chunk of data through `transform` as it arrives.
+    ///
+    /// See [`PgConnection::copy_out_with()`].
+    fn copy_out_with<'a, F>(
+        &'a self,
+        statement: &'a str,
+        transform: F,
+    ) -> BoxFuture<'a, Result<BoxStream<'static, Result<Bytes>>>>
+    where
+        F: FnMut(Bytes) -> Option<Bytes> + Send + 'static;
 }
 
 impl PgPoolCopyExt for Pool<Postgres> {
@@ -125,6 +182,21 @@ impl PgPoolCopyExt for Pool<Postgres> {
     ) -> BoxFuture<'a, Result<BoxStream<'static, Result<Bytes>>>> {
         Box::pin(async { pg_begin_copy_out(self.acquire().await?, statement).await })
     }
+
+    fn copy_out_with<'a, F>(
+        &'a self,
+        statement: &'a str,
+        transform: F,
+    ) -> BoxFuture<'a, Result<BoxStream<'static, Result<Bytes>>>>
+    where
+        F: FnMut(Bytes) -> Option<Bytes> + Send + 'static,
+    {
+        Box::pin(async {
+            let stream = pg_begin_copy_out(self.acquire().await?, statement).await?;
+
+            Ok(transform_copy_out(stream, transform))
+        })
+    }
 }
 
 /// A connection in streaming `COPY FROM STDIN` mode.
@@ -362,3 +434,13 @@ async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
 
     Ok(Box::pin(stream))
 }
+
+fn transform_copy_out<'c, F>(
+    stream: BoxStream<'c, Result<Bytes>>,
+    mut transform: F,
+) -> BoxStream<'c, Result<Bytes>>
+where
+    F: FnMut(Bytes) -> Option<Bytes> + Send + 'c,
+{
+    Box::pin(stream.try_filter_map(move |chunk| future::ok(transform(chunk))))
+}
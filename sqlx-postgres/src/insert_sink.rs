@@ -0,0 +1,302 @@
+use std::cmp;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use futures_core::future::BoxFuture;
+use futures_util::{ready, FutureExt, Sink};
+use sqlx_core::query_builder::{QueryBuilder, Separated};
+
+use crate::encode::Encode;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::quote::quote_table_name;
+use crate::types::Type;
+use crate::{PgConnection, Postgres};
+
+// the number of bind parameters of a statement is sent as an `i16` in `Bind`
+const MAX_BIND_PARAMS: usize = u16::MAX as usize;
+
+/// A row to insert with a [`PgInsertSink`], whose values are bound in order.
+///
+/// This is implemented for tuples of up to 16 values. For an other type, push each of its
+/// values with [`Separated::push_bind()`], in the order of the columns of the sink.
+pub trait PgInsertRow<'args>: Send {
+    /// Push the values of the row.
+    fn push_binds(self, row: &mut Separated<'_, 'args, Postgres, &'static str>);
+}
+
+macro_rules! impl_insert_row_for_tuple {
+    ($( ($idx:tt) -> $T:ident );+;) => {
+        impl<'args, $($T,)+> PgInsertRow<'args> for ($($T,)+)
+        where
+            $($T: 'args + Encode<'args, Postgres> + Type<Postgres> + Send,)+
+        {
+            fn push_binds(self, row: &mut Separated<'_, 'args, Postgres, &'static str>) {
+                $(row.push_bind(self.$idx);)+
+            }
+        }
+    };
+}
+
+impl_insert_row_for_tuple!((0) -> T1;);
+impl_insert_row_for_tuple!((0) -> T1; (1) -> T2;);
+impl_insert_row_for_tuple!((0) -> T1; (1) -> T2; (2) -> T3;);
+impl_insert_row_for_tuple!((0) -> T1; (1) -> T2; (2) -> T3; (3) -> T4;);
+impl_insert_row_for_tuple!((0) -> T1; (1) -> T2; (2) -> T3; (3) -> T4; (4) -> T5;);
+impl_insert_row_for_tuple!((0) -> T1; (1) -> T2; (2) -> T3; (3) -> T4; (4) -> T5; (5) -> T6;);
+impl_insert_row_for_tuple!(
+    (0) -> T1; (1) -> T2; (2) -> T3; (3) -> T4; (4) -> T5; (5) -> T6; (6) -> T7;
+);
+impl_insert_row_for_tuple!(
+    (0) -> T1; (1) -> T2; (2) -> T3; (3) -> T4; (4) -> T5; (5) -> T6; (6) -> T7; (7) -> T8;
+);
+impl_insert_row_for_tuple!(
+    (0) -> T1; (1) -> T2; (2) -> T3; (3) -> T4; (4) -> T5; (5) -> T6; (6) -> T7; (7) -> T8;
+    (8) -> T9;
+);
+impl_insert_row_for_tuple!(
+    (0) -> T1; (1) -> T2; (2) -> T3; (3) -> T4; (4) -> T5; (5) -> T6; (6) -> T7; (7) -> T8;
+    (8) -> T9; (9) -> T10;
+);
+impl_insert_row_for_tuple!(
+    (0) -> T1; (1) -> T2; (2) -> T3; (3) -> T4; (4) -> T5; (5) -> T6; (6) -> T7; (7) -> T8;
+    (8) -> T9; (9) -> T10; (10) -> T11;
+);
+impl_insert_row_for_tuple!(
+    (0) -> T1; (1) -> T2; (2) -> T3; (3) -> T4; (4) -> T5; (5) -> T6; (6) -> T7; (7) -> T8;
+    (8) -> T9; (9) -> T10; (10) -> T11; (11) -> T12;
+);
+impl_insert_row_for_tuple!(
+    (0) -> T1; (1) -> T2; (2) -> T3; (3) -> T4; (4) -> T5; (5) -> T6; (6) -> T7; (7) -> T8;
+    (8) -> T9; (9) -> T10; (10) -> T11; (11) -> T12; (12) -> T13;
+);
+impl_insert_row_for_tuple!(
+    (0) -> T1; (1) -> T2; (2) -> T3; (3) -> T4; (4) -> T5; (5) -> T6; (6) -> T7; (7) -> T8;
+    (8) -> T9; (9) -> T10; (10) -> T11; (11) -> T12; (12) -> T13; (13) -> T14;
+);
+impl_insert_row_for_tuple!(
+    (0) -> T1; (1) -> T2; (2) -> T3; (3) -> T4; (4) -> T5; (5) -> T6; (6) -> T7; (7) -> T8;
+    (8) -> T9; (9) -> T10; (10) -> T11; (11) -> T12; (12) -> T13; (13) -> T14; (14) -> T15;
+);
+impl_insert_row_for_tuple!(
+    (0) -> T1; (1) -> T2; (2) -> T3; (3) -> T4; (4) -> T5; (5) -> T6; (6) -> T7; (7) -> T8;
+    (8) -> T9; (9) -> T10; (10) -> T11; (11) -> T12; (12) -> T13; (13) -> T14; (14) -> T15;
+    (15) -> T16;
+);
+
+/// A [`Sink`] inserting the rows sent to it into a table, in batches, returned by
+/// [`PgConnection::insert_sink()`].
+///
+/// The rows are buffered until there are `batch_size` of them, or until the first of them was
+/// buffered for longer than [`max_delay()`](Self::max_delay) when another one is sent, and
+/// then inserted with a multi-row `INSERT .. VALUES`. A batch with more values than the
+/// maximum number of bind parameters of a statement (65535) is inserted with several
+/// statements.
+///
+/// The buffered rows are inserted by [`SinkExt::flush()`](futures_util::SinkExt::flush) and
+/// [`SinkExt::close()`](futures_util::SinkExt::close), which must be called before dropping the
+/// sink, or they are lost. When a batch fails to be inserted, the error is returned and its
+/// rows are dropped.
+///
+/// Each statement is committed on its own, unless the sink is created on a transaction.
+pub struct PgInsertSink<'c, T> {
+    // `None` while a batch is being inserted, by `flush`
+    conn: Option<&'c mut PgConnection>,
+    flush: Option<BoxFuture<'c, (&'c mut PgConnection, Result<u64, Error>)>>,
+    insert: String,
+    rows_per_statement: usize,
+    batch_size: usize,
+    max_delay: Option<Duration>,
+    rows: Vec<T>,
+    first_row_at: Option<Instant>,
+    rows_inserted: u64,
+}
+
+impl PgConnection {
+    /// Returns a [`Sink`] inserting the rows sent to it into the `columns` of `table`, in
+    /// batches of `batch_size` rows, see [`PgInsertSink`].
+    ///
+    /// The names are quoted as with [`QueryBuilder::push_identifier()`], and the table can be
+    /// qualified with the name of its schema, e.g. `public.users`. The values of each row are
+    /// bound in the order of `columns`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use futures::{stream, StreamExt};
+    ///
+    /// let users = stream::iter(0..10_000).map(|id| Ok((id, format!("user {id}"))));
+    ///
+    /// let mut sink = conn.insert_sink("users", &["id", "name"], 1000);
+    ///
+    /// // `forward()` closes the sink, inserting the last rows
+    /// users.forward(&mut sink).await?;
+    ///
+    /// assert_eq!(sink.rows_inserted(), 10_000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn insert_sink<'c, T>(
+        &'c mut self,
+        table: &str,
+        columns: &[&str],
+        batch_size: usize,
+    ) -> PgInsertSink<'c, T>
+    where
+        T: PgInsertRow<'c> + 'c,
+    {
+        let mut insert = QueryBuilder::<Postgres>::new("INSERT INTO ");
+
+        insert.push(quote_table_name(table)).push(" (");
+
+        for (i, column) in columns.iter().enumerate() {
+            if i > 0 {
+                insert.push(", ");
+            }
+
+            insert.push_identifier(column);
+        }
+
+        insert.push(") ");
+
+        PgInsertSink {
+            conn: Some(self),
+            flush: None,
+            insert: insert.into_sql(),
+            rows_per_statement: cmp::max(1, MAX_BIND_PARAMS / cmp::max(1, columns.len())),
+            batch_size: cmp::max(1, batch_size),
+            max_delay: None,
+            rows: Vec::new(),
+            first_row_at: None,
+            rows_inserted: 0,
+        }
+    }
+}
+
+impl<'c, T> PgInsertSink<'c, T>
+where
+    T: PgInsertRow<'c> + 'c,
+{
+    /// Insert the buffered rows when another one is sent after the first of them was buffered
+    /// for `max_delay`, even if there are fewer than `batch_size` of them.
+    ///
+    /// This does not insert the rows by itself when no row is sent: flush the sink for that.
+    /// Note that [`StreamExt::forward()`](futures_util::StreamExt::forward) flushes the sink
+    /// whenever the stream has no row ready.
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Returns the number of rows inserted so far.
+    pub fn rows_inserted(&self) -> u64 {
+        self.rows_inserted
+    }
+
+    fn start_flush(&mut self) {
+        if self.rows.is_empty() {
+            return;
+        }
+
+        let Some(conn) = self.conn.take() else {
+            return;
+        };
+
+        let rows = mem::take(&mut self.rows);
+        let insert = self.insert.clone();
+        let rows_per_statement = self.rows_per_statement;
+
+        self.first_row_at = None;
+        self.flush = Some(
+            async move {
+                let result = insert_rows(&mut *conn, insert, rows, rows_per_statement).await;
+                (conn, result)
+            }
+            .boxed(),
+        );
+    }
+
+    fn poll_flushing(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        let Some(flush) = &mut self.flush else {
+            return Poll::Ready(Ok(()));
+        };
+
+        let (conn, result) = ready!(flush.as_mut().poll(cx));
+
+        self.flush = None;
+        self.conn = Some(conn);
+        self.rows_inserted += result?;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+async fn insert_rows<'c, T>(
+    conn: &mut PgConnection,
+    insert: String,
+    rows: Vec<T>,
+    rows_per_statement: usize,
+) -> Result<u64, Error>
+where
+    T: PgInsertRow<'c> + 'c,
+{
+    let mut rows = rows.into_iter().peekable();
+    let mut rows_inserted = 0;
+
+    while rows.peek().is_some() {
+        let mut query_builder = QueryBuilder::<'c, Postgres>::new(&insert);
+
+        query_builder.push_values(rows.by_ref().take(rows_per_statement), |mut b, row| {
+            row.push_binds(&mut b);
+        });
+
+        rows_inserted += conn.execute(query_builder.build()).await?.rows_affected();
+    }
+
+    Ok(rows_inserted)
+}
+
+// the rows are never pinned
+impl<T> Unpin for PgInsertSink<'_, T> {}
+
+impl<'c, T> Sink<T> for PgInsertSink<'c, T>
+where
+    T: PgInsertRow<'c> + 'c,
+{
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        self.get_mut().poll_flushing(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, row: T) -> Result<(), Error> {
+        let this = self.get_mut();
+
+        let first_row_at = *this.first_row_at.get_or_insert_with(Instant::now);
+        this.rows.push(row);
+
+        let delayed =
+            matches!(this.max_delay, Some(max_delay) if first_row_at.elapsed() >= max_delay);
+
+        if this.rows.len() >= this.batch_size || delayed {
+            this.start_flush();
+        }
+
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        let this = self.get_mut();
+
+        ready!(this.poll_flushing(cx))?;
+        this.start_flush();
+        this.poll_flushing(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        self.poll_flush(cx)
+    }
+}
@@ -0,0 +1,130 @@
+use sqlx_core::row::Row;
+
+use crate::error::Error;
+use crate::query::query;
+use crate::query_as::query_as;
+use crate::{PgConnection, PgRow};
+
+/// Planner statistics for a table, as returned by [`PgConnection::table_estimate()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PgTableStats {
+    /// The estimated number of rows in the table.
+    ///
+    /// This is the same estimate used by the query planner. It's only updated by `VACUUM`,
+    /// `ANALYZE` and a few DDL commands such as `CREATE INDEX`, so it may be outdated.
+    pub estimated_rows: i64,
+
+    /// The total disk space used by the table in bytes, including its indexes and TOAST data.
+    pub size_bytes: i64,
+}
+
+/// An index of a table, as returned by [`PgConnection::indexes()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PgIndexInfo {
+    /// The name of the index.
+    pub name: String,
+
+    /// The key columns of the index, in order.
+    ///
+    /// For an expression index, this is the text of the expression instead of a column name.
+    /// Non-key columns added with `INCLUDE` are not listed.
+    pub columns: Vec<String>,
+
+    /// The index access method, e.g. `btree`, `hash` or `gin`.
+    pub method: String,
+
+    /// `true` if this is a unique index.
+    pub is_unique: bool,
+
+    /// `true` if this index backs the primary key of the table.
+    pub is_primary: bool,
+
+    /// `true` if this is a partial index, i.e. it was created with a `WHERE` clause.
+    pub is_partial: bool,
+
+    /// The disk space used by the index in bytes.
+    pub size_bytes: i64,
+}
+
+impl PgConnection {
+    /// Get the planner's estimate of the number of rows in a table, and its size on disk.
+    ///
+    /// `table` is resolved like a table name in a query, so it may be schema-qualified and
+    /// is subject to the `search_path`. Use double quotes for names which are case-sensitive,
+    /// e.g. `"public"."MyTable"`.
+    ///
+    /// This reads `pg_class` and is much cheaper than `SELECT count(*)`, but only as accurate
+    /// as the statistics gathered by the last `VACUUM` or `ANALYZE`. If the table has never been
+    /// analyzed, the number of live rows tracked by `pg_stat_user_tables` is used instead.
+    ///
+    /// Returns an error if the table does not exist.
+    pub async fn table_estimate(&mut self, table: &str) -> Result<PgTableStats, Error> {
+        // `reltuples` is `-1` for tables which have never been analyzed since Postgres 14,
+        // and `0` in earlier versions (which is indistinguishable from an empty table).
+        let (reltuples, n_live_tup, size_bytes): (f64, Option<i64>, i64) = query_as(
+            "SELECT c.reltuples::float8, s.n_live_tup, pg_catalog.pg_total_relation_size(c.oid) \
+             FROM pg_catalog.pg_class c \
+             LEFT JOIN pg_catalog.pg_stat_user_tables s ON s.relid = c.oid \
+             WHERE c.oid = $1::regclass",
+        )
+        .bind(table)
+        .fetch_one(&mut *self)
+        .await?;
+
+        let estimated_rows = if reltuples > 0.0 {
+            reltuples as i64
+        } else {
+            n_live_tup.unwrap_or(0)
+        };
+
+        Ok(PgTableStats {
+            estimated_rows,
+            size_bytes,
+        })
+    }
+
+    /// List the indexes of a table, ordered by name.
+    ///
+    /// `table` is resolved the same way as in [`table_estimate()`][Self::table_estimate].
+    ///
+    /// Returns an error if the table does not exist.
+    pub async fn indexes(&mut self, table: &str) -> Result<Vec<PgIndexInfo>, Error> {
+        // `INCLUDE` columns and `indnkeyatts` were added in Postgres 11
+        let key_columns = match self.server_version_num() {
+            Some(version) if version < 110000 => "ix.indnatts",
+            _ => "ix.indnkeyatts",
+        };
+
+        let rows: Vec<PgRow> = query(&format!(
+            "SELECT i.relname::text, \
+                 ARRAY(SELECT pg_catalog.pg_get_indexdef(ix.indexrelid, k, true) \
+                     FROM generate_series(1, {key_columns}) k ORDER BY k), \
+                 am.amname::text, ix.indisunique, ix.indisprimary, ix.indpred IS NOT NULL, \
+                 pg_catalog.pg_relation_size(i.oid) \
+             FROM pg_catalog.pg_index ix \
+             JOIN pg_catalog.pg_class i ON i.oid = ix.indexrelid \
+             JOIN pg_catalog.pg_am am ON am.oid = i.relam \
+             WHERE ix.indrelid = $1::regclass \
+             ORDER BY i.relname"
+        ))
+        .bind(table)
+        .fetch_all(&mut *self)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(PgIndexInfo {
+                    name: row.try_get(0)?,
+                    columns: row.try_get(1)?,
+                    method: row.try_get(2)?,
+                    is_unique: row.try_get(3)?,
+                    is_primary: row.try_get(4)?,
+                    is_partial: row.try_get(5)?,
+                    size_bytes: row.try_get(6)?,
+                })
+            })
+            .collect()
+    }
+}
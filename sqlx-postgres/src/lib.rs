@@ -12,17 +12,25 @@ mod connection;
 mod copy;
 mod database;
 mod error;
+mod insert_sink;
+mod introspect;
 mod io;
 mod listener;
+mod maintenance;
 mod message;
 mod options;
+mod query_builder;
 mod query_result;
+mod quote;
+mod replication;
+mod role;
 mod row;
 mod statement;
 mod transaction;
 mod type_info;
 pub mod types;
 mod value;
+mod wal;
 
 #[cfg(feature = "any")]
 pub mod any;
@@ -33,24 +41,38 @@ mod migrate;
 #[cfg(feature = "migrate")]
 mod testing;
 
+#[cfg(feature = "recording")]
+mod recording;
+
 pub(crate) use sqlx_core::driver_prelude::*;
 
 pub use advisory_lock::{PgAdvisoryLock, PgAdvisoryLockGuard, PgAdvisoryLockKey};
 pub use arguments::{PgArgumentBuffer, PgArguments};
 pub use column::PgColumn;
-pub use connection::PgConnection;
-pub use copy::PgCopyIn;
+pub use connection::{PgConnection, PgDeferredFlush, PgFetchBatchExt};
+pub use copy::{PgCopyIn, PgPoolCopyExt};
 pub use database::Postgres;
 pub use error::{PgDatabaseError, PgErrorPosition};
+pub use insert_sink::{PgInsertRow, PgInsertSink};
+pub use introspect::{PgIndexInfo, PgTableStats};
 pub use listener::{PgListener, PgNotification};
-pub use message::PgSeverity;
-pub use options::{PgConnectOptions, PgSslMode};
+pub use message::{PgSeverity, PgTransactionStatus};
+pub use options::{
+    PgConnectOptions, PgLoadBalanceHosts, PgSslMode, PgTargetSessionAttrs, PgTypeCache,
+};
+pub use query_builder::PgQueryBuilderExt;
 pub use query_result::PgQueryResult;
+#[cfg(feature = "recording")]
+pub use recording::PgRecordedArgument;
+pub use replication::{
+    PgLsn, PgRelation, PgRelationColumn, PgReplication, PgReplicationMessage, PgReplicationStream,
+    PgTupleData, PgTupleValue,
+};
 pub use row::PgRow;
 pub use statement::PgStatement;
 pub use transaction::PgTransactionManager;
 pub use type_info::{PgTypeInfo, PgTypeKind};
-pub use types::PgHasArrayType;
+pub use types::{PgHasArrayType, PgHasRangeType};
 pub use value::{PgValue, PgValueFormat, PgValueRef};
 
 /// An alias for [`Pool`][crate::pool::Pool], specialized for Postgres.
@@ -29,6 +29,8 @@ pub struct PgListener {
     buffer_tx: Option<mpsc::UnboundedSender<Notification>>,
     channels: Vec<String>,
     ignore_close_event: bool,
+    coalesce: bool,
+    pending: Option<Notification>,
 }
 
 /// An asynchronous notification from Postgres.
@@ -67,6 +69,8 @@ impl PgListener {
             buffer_tx: None,
             channels: Vec::new(),
             ignore_close_event: false,
+            coalesce: false,
+            pending: None,
         })
     }
 
@@ -93,6 +97,24 @@ impl PgListener {
         self.ignore_close_event = val;
     }
 
+    /// Set whether or not to coalesce duplicate notifications. Defaults to `false`.
+    ///
+    /// When enabled, a notification that has already been received is only handed out once
+    /// if it is immediately followed by identical notifications (same channel and payload).
+    /// This is useful when a busy table fires the same `NOTIFY` repeatedly and the work
+    /// it triggers only needs to happen once, such as invalidating a cache entry.
+    ///
+    /// Only notifications that have already arrived when one is returned are coalesced with it;
+    /// this never waits for more. Duplicates arriving afterwards are delivered again as usual,
+    /// so acting on every notification returned still observes all changes.
+    ///
+    /// However, this makes delivery at-most-once for each individual notification: coalesced
+    /// duplicates are discarded and cannot be recovered. Don't enable this if each
+    /// notification represents a distinct event that must be counted or handled separately.
+    pub fn coalesce(&mut self, val: bool) {
+        self.coalesce = val;
+    }
+
     /// Starts listening for notifications on a channel.
     /// The channel name is quoted here to ensure case sensitivity.
     pub async fn listen(&mut self, channel: &str) -> Result<(), Error> {
@@ -238,9 +260,8 @@ impl PgListener {
     /// ```
     pub async fn try_recv(&mut self) -> Result<Option<PgNotification>, Error> {
         // Flush the buffer first, if anything
-        // This would only fill up if this listener is used as a connection
-        if let Ok(Some(notification)) = self.buffer_rx.try_next() {
-            return Ok(Some(PgNotification(notification)));
+        if let Some(notification) = self.next_buffered()? {
+            return Ok(Some(notification));
         }
 
         // Fetch our `CloseEvent` listener, if applicable.
@@ -282,7 +303,7 @@ impl PgListener {
             match message.format {
                 // We've received an async notification, return it.
                 MessageFormat::NotificationResponse => {
-                    return Ok(Some(PgNotification(message.decode()?)));
+                    return self.coalesce_with(message.decode()?).map(Some);
                 }
 
                 // Mark the connection as ready for another query
@@ -296,6 +317,116 @@ impl PgListener {
         }
     }
 
+    /// Receives a batch of up to `max` notifications from any of the subscribed channels.
+    ///
+    /// This waits for the next notification like [`recv()`](Self::recv), then returns it along
+    /// with any further notifications that have already been received, without waiting
+    /// for more. Notifications are returned in the order they were received.
+    ///
+    /// This is useful to process a burst of notifications at once, such as coalescing
+    /// many cache invalidations into a single refresh.
+    ///
+    /// Returns an empty `Vec` immediately if `max` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core::postgres::PgListener;
+    /// # use sqlx_core::error::Error;
+    /// #
+    /// # #[cfg(feature = "_rt")]
+    /// # sqlx::__rt::test_block_on(async move {
+    /// # let mut listener = PgListener::connect("postgres:// ...").await?;
+    /// loop {
+    ///     let notifications = listener.recv_batch(100).await?;
+    ///
+    ///     // handle all of the notifications at once
+    /// }
+    /// # Result::<(), Error>::Ok(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn recv_batch(&mut self, max: usize) -> Result<Vec<PgNotification>, Error> {
+        if max == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut batch = vec![self.recv().await?];
+
+        while batch.len() < max {
+            match self.next_buffered()? {
+                Some(notification) => batch.push(notification),
+                None => break,
+            }
+        }
+
+        Ok(batch)
+    }
+
+    /// Returns the next notification that has already been received, without waiting.
+    ///
+    /// Returns `None` if no notification is immediately available. Unlike
+    /// [`try_recv()`](Self::try_recv), this never waits on the server or reconnects.
+    pub fn next_buffered(&mut self) -> Result<Option<PgNotification>, Error> {
+        match self.next_buffered_raw()? {
+            Some(notification) => self.coalesce_with(notification).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_buffered_raw(&mut self) -> Result<Option<Notification>, Error> {
+        // A notification held back while coalescing the previous one comes first
+        if let Some(notification) = self.pending.take() {
+            return Ok(Some(notification));
+        }
+
+        // Then notifications received while this listener was used as a connection
+        if let Ok(Some(notification)) = self.buffer_rx.try_next() {
+            return Ok(Some(notification));
+        }
+
+        let Some(connection) = self.connection.as_mut() else {
+            return Ok(None);
+        };
+
+        while connection.stream.has_buffered_message() {
+            // The message is already fully buffered, so this is guaranteed to complete
+            // without waiting on the socket.
+            let message = match connection.stream.recv_unchecked().now_or_never() {
+                Some(res) => res?,
+                None => break,
+            };
+
+            match message.format {
+                MessageFormat::NotificationResponse => {
+                    return Ok(Some(message.decode()?));
+                }
+
+                MessageFormat::ReadyForQuery => {
+                    connection.pending_ready_for_query_count -= 1;
+                }
+
+                _ => {}
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn coalesce_with(&mut self, notification: Notification) -> Result<PgNotification, Error> {
+        if self.coalesce {
+            // Skip over identical notifications which have already been received, holding back
+            // the first one which differs so it is returned next
+            while let Some(next) = self.next_buffered_raw()? {
+                if next.channel != notification.channel || next.payload != notification.payload {
+                    self.pending = Some(next);
+                    break;
+                }
+            }
+        }
+
+        Ok(PgNotification(notification))
+    }
+
     /// Consume this listener, returning a `Stream` of notifications.
     ///
     /// The backing connection will be automatically reconnected should it be lost.
@@ -311,6 +442,35 @@ impl PgListener {
     }
 }
 
+impl PgConnection {
+    /// Send a notification to all listeners of `channel` with the given `payload`.
+    ///
+    /// This uses `pg_notify()` with the channel and payload bound as parameters, so neither
+    /// needs to be escaped. Like [`PgListener::listen()`], the channel name is case-sensitive.
+    ///
+    /// As with `NOTIFY`, the notification is only delivered once the current transaction
+    /// commits, and the payload must be shorter than 8000 bytes in the default server
+    /// configuration; longer payloads are rejected by the server with an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// conn.notify("cache_invalidation", r#"{"table": "users", "id": 42}"#).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn notify(&mut self, channel: &str, payload: &str) -> Result<(), Error> {
+        crate::query::query("SELECT pg_notify($1, $2)")
+            .bind(channel)
+            .bind(payload)
+            .execute(self)
+            .await?;
+
+        Ok(())
+    }
+}
+
 impl Drop for PgListener {
     fn drop(&mut self) {
         if let Some(mut conn) = self.connection.take() {
@@ -0,0 +1,35 @@
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::quote::quote_table_name;
+use crate::PgConnection;
+
+impl PgConnection {
+    /// Collect statistics about the contents of `table` with `ANALYZE`, for the query planner to
+    /// choose better plans, e.g. after loading or deleting many rows.
+    ///
+    /// The table can be qualified with the name of its schema, e.g. `public.users`. Each part of
+    /// the name is quoted, so it is case-sensitive (`"Users"` is not `users`) and cannot contain
+    /// a `.` itself.
+    ///
+    /// Unlike `VACUUM`, this can be run within a transaction, and only takes a lock which does
+    /// not conflict with reads and writes to the table.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// sqlx::query("COPY users FROM '/tmp/users.csv' WITH (FORMAT csv)")
+    ///     .execute(&mut *conn)
+    ///     .await?;
+    ///
+    /// conn.analyze("public.users").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn analyze(&mut self, table: &str) -> Result<(), Error> {
+        self.execute(&*format!("ANALYZE {}", quote_table_name(table)))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use crate::io::Encode;
+
+// Sent instead of a startup message on a new connection, to cancel the query executing on the
+// connection of the given backend
+pub struct CancelRequest {
+    pub process_id: u32,
+    pub secret_key: u32,
+}
+
+impl Encode<'_> for CancelRequest {
+    fn encode_with(&self, buf: &mut Vec<u8>, _: ()) {
+        buf.extend(&16_u32.to_be_bytes());
+        buf.extend(&(((1234 << 16) | 5678) as u32).to_be_bytes());
+        buf.extend(&self.process_id.to_be_bytes());
+        buf.extend(&self.secret_key.to_be_bytes());
+    }
+}
+
+#[test]
+fn test_encode_cancel_request() {
+    let mut buf = Vec::new();
+    CancelRequest {
+        process_id: 0x0102_0304,
+        secret_key: 0x0a0b_0c0d,
+    }
+    .encode(&mut buf);
+
+    assert_eq!(
+        buf,
+        b"\x00\x00\x00\x10\x04\xd2\x16\x2e\x01\x02\x03\x04\x0a\x0b\x0c\x0d"
+    );
+}
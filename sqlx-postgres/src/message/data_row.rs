@@ -2,6 +2,7 @@ use std::ops::Range;
 
 use byteorder::{BigEndian, ByteOrder};
 use sqlx_core::bytes::Bytes;
+use sqlx_core::column::ColumnSelection;
 
 use crate::error::Error;
 use crate::io::Decode;
@@ -26,6 +27,36 @@ impl DataRow {
     }
 }
 
+impl DataRow {
+    // Only keeps the ranges of the selected columns, at their position among them.
+    pub(crate) fn decode_selected(buf: Bytes, selection: &ColumnSelection) -> Result<Self, Error> {
+        let cnt = BigEndian::read_u16(&buf) as usize;
+
+        let mut values = vec![None; selection.len()];
+        let mut offset = 2;
+
+        for index in 0..cnt {
+            let length = BigEndian::read_i32(&buf[(offset as usize)..]);
+            offset += 4;
+
+            if length < 0 {
+                continue;
+            }
+
+            if let Some(slot) = selection.slot(index) {
+                values[slot] = Some(offset..(offset + length as u32));
+            }
+
+            offset += length as u32;
+        }
+
+        Ok(Self {
+            storage: buf,
+            values,
+        })
+    }
+}
+
 impl Decode<'_> for DataRow {
     fn decode_with(buf: Bytes, _: ()) -> Result<Self, Error> {
         let cnt = BigEndian::read_u16(&buf) as usize;
@@ -73,6 +104,29 @@ fn test_decode_data_row() {
     assert_eq!(row.get(7).unwrap(), &[0_u8, 0, 0, 80][..]);
 }
 
+#[test]
+fn test_decode_selected_data_row() {
+    use sqlx_core::ext::ustr::UStr;
+    use sqlx_core::HashMap;
+
+    const DATA: &[u8] = b"\x00\x08\xff\xff\xff\xff\x00\x00\x00\x04\x00\x00\x00\n\xff\xff\xff\xff\x00\x00\x00\x04\x00\x00\x00\x14\xff\xff\xff\xff\x00\x00\x00\x04\x00\x00\x00(\xff\xff\xff\xff\x00\x00\x00\x04\x00\x00\x00P";
+
+    let column_names: HashMap<UStr, usize> = ["a", "b", "c", "d", "e", "f", "g", "h"]
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| (UStr::Static(name), index))
+        .collect();
+
+    let selection = ColumnSelection::new(&["h", "c", "b"], &column_names, 8).unwrap();
+    let row = DataRow::decode_selected(DATA.into(), &selection).unwrap();
+
+    assert_eq!(row.values.len(), 3);
+
+    assert_eq!(row.get(0).unwrap(), &[0_u8, 0, 0, 80][..]);
+    assert!(row.get(1).is_none());
+    assert_eq!(row.get(2).unwrap(), &[0_u8, 0, 0, 10][..]);
+}
+
 #[cfg(all(test, not(debug_assertions)))]
 #[bench]
 fn bench_data_row_get(b: &mut test::Bencher) {
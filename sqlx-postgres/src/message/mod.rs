@@ -6,6 +6,7 @@ use crate::io::Decode;
 mod authentication;
 mod backend_key_data;
 mod bind;
+mod cancel_request;
 mod close;
 mod command_complete;
 mod copy;
@@ -31,6 +32,7 @@ mod terminate;
 pub use authentication::{Authentication, AuthenticationSasl};
 pub use backend_key_data::BackendKeyData;
 pub use bind::Bind;
+pub use cancel_request::CancelRequest;
 pub use close::Close;
 pub use command_complete::CommandComplete;
 pub use copy::{CopyData, CopyDone, CopyFail, CopyResponse};
@@ -44,7 +46,7 @@ pub use parameter_status::ParameterStatus;
 pub use parse::Parse;
 pub use password::Password;
 pub use query::Query;
-pub use ready_for_query::{ReadyForQuery, TransactionStatus};
+pub use ready_for_query::{PgTransactionStatus, ReadyForQuery};
 pub use response::{Notice, PgSeverity};
 pub use row_description::RowDescription;
 pub use sasl::{SaslInitialResponse, SaslResponse};
@@ -61,6 +63,7 @@ pub enum MessageFormat {
     BindComplete,
     CloseComplete,
     CommandComplete,
+    CopyBothResponse,
     CopyData,
     CopyDone,
     CopyInResponse,
@@ -108,6 +111,7 @@ impl MessageFormat {
             b'c' => MessageFormat::CopyDone,
             b'G' => MessageFormat::CopyInResponse,
             b'H' => MessageFormat::CopyOutResponse,
+            b'W' => MessageFormat::CopyBothResponse,
             b'D' => MessageFormat::DataRow,
             b'E' => MessageFormat::ErrorResponse,
             b'I' => MessageFormat::EmptyQueryResponse,
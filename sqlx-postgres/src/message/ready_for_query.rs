@@ -3,30 +3,32 @@ use sqlx_core::bytes::Bytes;
 use crate::error::Error;
 use crate::io::Decode;
 
-#[derive(Debug)]
+/// The transaction status of a connection, as reported by the server when it is ready for the
+/// next query, see [`PgConnection::transaction_status()`](crate::PgConnection::transaction_status).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(u8)]
-pub enum TransactionStatus {
+pub enum PgTransactionStatus {
     /// Not in a transaction block.
     Idle = b'I',
 
     /// In a transaction block.
-    Transaction = b'T',
+    InTransaction = b'T',
 
     /// In a _failed_ transaction block. Queries will be rejected until block is ended.
-    Error = b'E',
+    InFailedTransaction = b'E',
 }
 
 #[derive(Debug)]
 pub struct ReadyForQuery {
-    pub transaction_status: TransactionStatus,
+    pub transaction_status: PgTransactionStatus,
 }
 
 impl Decode<'_> for ReadyForQuery {
     fn decode_with(buf: Bytes, _: ()) -> Result<Self, Error> {
         let status = match buf[0] {
-            b'I' => TransactionStatus::Idle,
-            b'T' => TransactionStatus::Transaction,
-            b'E' => TransactionStatus::Error,
+            b'I' => PgTransactionStatus::Idle,
+            b'T' => PgTransactionStatus::InTransaction,
+            b'E' => PgTransactionStatus::InFailedTransaction,
 
             status => {
                 return Err(err_protocol!(
@@ -48,7 +50,10 @@ fn test_decode_ready_for_query() -> Result<(), Error> {
 
     let m = ReadyForQuery::decode(Bytes::from_static(DATA))?;
 
-    assert!(matches!(m.transaction_status, TransactionStatus::Error));
+    assert_eq!(
+        m.transaction_status,
+        PgTransactionStatus::InFailedTransaction
+    );
 
     Ok(())
 }
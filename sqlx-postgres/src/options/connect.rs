@@ -1,4 +1,4 @@
-use crate::connection::ConnectOptions;
+use crate::connection::{ConnectOptions, SqlRewriter, StatementLogHandler};
 use crate::error::Error;
 use crate::{PgConnectOptions, PgConnection};
 use futures_core::future::BoxFuture;
@@ -29,4 +29,24 @@ impl ConnectOptions for PgConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn log_with(mut self, handler: StatementLogHandler) -> Self {
+        self.log_settings.log_with(handler);
+        self
+    }
+
+    fn log_parameter_values(mut self, enabled: bool) -> Self {
+        self.log_settings.log_parameter_values(enabled);
+        self
+    }
+
+    fn sql_rewriter(mut self, rewriter: SqlRewriter) -> Self {
+        self.sql_rewriter.set(rewriter);
+        self
+    }
+
+    fn disable_statement_logging(mut self) -> Self {
+        self.log_settings.disable();
+        self
+    }
 }
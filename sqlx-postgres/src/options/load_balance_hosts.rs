@@ -0,0 +1,35 @@
+use crate::error::Error;
+use std::str::FromStr;
+
+/// The order in which the hosts of a connection are tried.
+///
+/// It is used by the [`load_balance_hosts`](super::PgConnectOptions::load_balance_hosts)
+/// method, the same way as with libpq's
+/// [`load_balance_hosts`](https://www.postgresql.org/docs/current/libpq-connect.html#LIBPQ-CONNECT-LOAD-BALANCE-HOSTS).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PgLoadBalanceHosts {
+    /// Try the hosts in the order they are given, for failover. This is the default.
+    #[default]
+    Disable,
+
+    /// Try the hosts in a random order for each connection, to balance the connections
+    /// between the servers.
+    Random,
+}
+
+impl FromStr for PgLoadBalanceHosts {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "disable" => PgLoadBalanceHosts::Disable,
+            "random" => PgLoadBalanceHosts::Random,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!("unknown value {s:?} for `load_balance_hosts`").into(),
+                ));
+            }
+        })
+    }
+}
@@ -1,16 +1,30 @@
 use std::borrow::Cow;
 use std::env::var;
 use std::fmt::{Display, Write};
+use std::mem;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
+pub use load_balance_hosts::PgLoadBalanceHosts;
 pub use ssl_mode::PgSslMode;
+pub use target_session_attrs::PgTargetSessionAttrs;
+pub use type_cache::PgTypeCache;
 
-use crate::{connection::LogSettings, net::tls::CertificateInput};
+use sqlx_core::transaction::IsolationLevel;
+
+use crate::{
+    connection::{LogSettings, SqlRewriterSettings},
+    net::tls::CertificateInput,
+    PgTypeInfo,
+};
 
 mod connect;
+mod load_balance_hosts;
 mod parse;
 mod pgpass;
 mod ssl_mode;
+mod target_session_attrs;
+mod type_cache;
 
 /// Options and flags which can be used to configure a PostgreSQL connection.
 ///
@@ -41,6 +55,9 @@ mod ssl_mode;
 /// | `port` | `5432` | Port number to connect to at the server host, or socket file name extension for Unix-domain connections. |
 /// | `dbname` | `None` | The database name. |
 /// | `options` | `None` | The runtime parameters to send to the server at connection start. |
+/// | `target_session_attrs` | `any` | The properties the server must have for the connection to be accepted. See [`PgTargetSessionAttrs`]. |
+/// | `load_balance_hosts` | `disable` | The order in which the hosts are tried. See [`PgLoadBalanceHosts`]. |
+/// | `connect_timeout` | `None` | The maximum time to connect to each host, in seconds. `0` means no timeout. |
 ///
 /// The URL scheme designator can be either `postgresql://` or `postgres://`.
 /// Each of the URL parts is optional.
@@ -55,6 +72,15 @@ mod ssl_mode;
 /// postgresql://localhost?dbname=mydb&user=postgres&password=postgres
 /// ```
 ///
+/// Several hosts can be given as a comma-separated list, which are tried in order until
+/// a connection succeeds (see [`hosts()`][Self::hosts]). As a URL can't have a port for
+/// each host, different ports are given as a list with the `port` parameter instead:
+///
+/// ```text
+/// postgresql://host1,host2:5432/mydb?target_session_attrs=read-write
+/// postgresql:///mydb?host=host1,host2&port=5432,5433
+/// ```
+///
 /// # Example
 ///
 /// ```rust,no_run
@@ -89,6 +115,10 @@ mod ssl_mode;
 pub struct PgConnectOptions {
     pub(crate) host: String,
     pub(crate) port: u16,
+    pub(crate) fallback_hosts: Vec<(String, u16)>,
+    pub(crate) target_session_attrs: PgTargetSessionAttrs,
+    pub(crate) load_balance_hosts: PgLoadBalanceHosts,
+    pub(crate) connect_timeout: Option<Duration>,
     pub(crate) socket: Option<PathBuf>,
     pub(crate) username: String,
     pub(crate) password: Option<String>,
@@ -98,10 +128,17 @@ pub struct PgConnectOptions {
     pub(crate) ssl_client_cert: Option<CertificateInput>,
     pub(crate) ssl_client_key: Option<CertificateInput>,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) normalize_statement_cache_key: bool,
     pub(crate) application_name: Option<String>,
     pub(crate) log_settings: LogSettings,
+    pub(crate) sql_rewriter: SqlRewriterSettings,
     pub(crate) extra_float_digits: Option<Cow<'static, str>>,
     pub(crate) options: Option<String>,
+    pub(crate) default_transaction_isolation: Option<IsolationLevel>,
+    pub(crate) default_transaction_read_only: Option<bool>,
+    pub(crate) replication: bool,
+    pub(crate) type_cache: Option<PgTypeCache>,
+    pub(crate) text_result_types: Vec<PgTypeInfo>,
 }
 
 impl Default for PgConnectOptions {
@@ -152,6 +189,10 @@ impl PgConnectOptions {
         PgConnectOptions {
             port,
             host,
+            fallback_hosts: Vec::new(),
+            target_session_attrs: PgTargetSessionAttrs::Any,
+            load_balance_hosts: PgLoadBalanceHosts::Disable,
+            connect_timeout: None,
             socket: None,
             username,
             password: var("PGPASSWORD").ok(),
@@ -164,10 +205,17 @@ impl PgConnectOptions {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or_default(),
             statement_cache_capacity: 100,
+            normalize_statement_cache_key: false,
             application_name: var("PGAPPNAME").ok(),
             extra_float_digits: Some("2".into()),
             log_settings: Default::default(),
+            sql_rewriter: Default::default(),
             options: var("PGOPTIONS").ok(),
+            default_transaction_isolation: None,
+            default_transaction_read_only: None,
+            replication: false,
+            type_cache: None,
+            text_result_types: Vec::new(),
         }
     }
 
@@ -235,6 +283,117 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets the hosts to connect to, with their port, replacing [`host()`][Self::host] and
+    /// [`port()`][Self::port].
+    ///
+    /// The hosts are tried in order when connecting, until a connection succeeds and its session
+    /// matches the [`target_session_attrs()`][Self::target_session_attrs]. If none does, the
+    /// error of the last host is returned. This allows to fail over to another server of a
+    /// cluster without an external proxy. The connections can also be spread over the hosts
+    /// with [`load_balance_hosts()`][Self::load_balance_hosts].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::{PgConnectOptions, PgTargetSessionAttrs};
+    /// let options = PgConnectOptions::new()
+    ///     .hosts([("db1.example.com", 5432), ("db2.example.com", 5433)])
+    ///     .target_session_attrs(PgTargetSessionAttrs::ReadWrite);
+    /// ```
+    ///
+    /// # Panics
+    /// If `hosts` is empty.
+    pub fn hosts<'a>(mut self, hosts: impl IntoIterator<Item = (&'a str, u16)>) -> Self {
+        let mut hosts = hosts.into_iter();
+        let (host, port) = hosts.next().expect("at least one host is required");
+
+        self.host = host.to_owned();
+        self.port = port;
+        self.fallback_hosts = hosts.map(|(host, port)| (host.to_owned(), port)).collect();
+        self
+    }
+
+    /// Get the hosts to connect to, with their port, in order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .host("db1.example.com")
+    ///     .port(5432);
+    /// assert_eq!(options.get_hosts().collect::<Vec<_>>(), [("db1.example.com", 5432)]);
+    /// ```
+    pub fn get_hosts(&self) -> impl Iterator<Item = (&str, u16)> {
+        std::iter::once((self.host.as_str(), self.port)).chain(
+            self.fallback_hosts
+                .iter()
+                .map(|(host, port)| (host.as_str(), *port)),
+        )
+    }
+
+    /// Sets the properties the server must have for a connection to it to be accepted.
+    ///
+    /// Unless it is [`Any`](PgTargetSessionAttrs::Any) (the default), the session is checked
+    /// once the connection is established, and the next one of the [`hosts()`][Self::hosts]
+    /// is tried if it doesn't match. For example, [`ReadWrite`](PgTargetSessionAttrs::ReadWrite)
+    /// finds the primary of a cluster whichever server it currently is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::{PgConnectOptions, PgTargetSessionAttrs};
+    /// let options = PgConnectOptions::new()
+    ///     .target_session_attrs(PgTargetSessionAttrs::Standby);
+    /// ```
+    pub fn target_session_attrs(mut self, attrs: PgTargetSessionAttrs) -> Self {
+        self.target_session_attrs = attrs;
+        self
+    }
+
+    /// Sets the order in which the [`hosts()`][Self::hosts] are tried when connecting.
+    ///
+    /// By default, they are tried in the order they are given, so the first one that is
+    /// available is always used. With [`Random`](PgLoadBalanceHosts::Random), they are tried
+    /// in a random order for each connection instead, which spreads the connections of a pool
+    /// over the servers of a cluster.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::{PgConnectOptions, PgLoadBalanceHosts};
+    /// let options = PgConnectOptions::new()
+    ///     .hosts([("replica1.example.com", 5432), ("replica2.example.com", 5432)])
+    ///     .load_balance_hosts(PgLoadBalanceHosts::Random);
+    /// ```
+    pub fn load_balance_hosts(mut self, mode: PgLoadBalanceHosts) -> Self {
+        self.load_balance_hosts = mode;
+        self
+    }
+
+    /// Sets the maximum time to establish a connection to each host, including the
+    /// authentication and the check of [`target_session_attrs()`][Self::target_session_attrs].
+    ///
+    /// Once it elapses, the connection attempt fails with an I/O error of kind
+    /// [`TimedOut`][std::io::ErrorKind::TimedOut], and the next one of the
+    /// [`hosts()`][Self::hosts] is tried. This prevents an unresponsive host from
+    /// delaying the failover to the others.
+    ///
+    /// By default, there is no timeout besides the ones of the operating system.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .connect_timeout(Duration::from_secs(5));
+    /// ```
+    pub fn connect_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+        self.connect_timeout = timeout.into();
+        self
+    }
+
     /// Sets a custom path to a directory containing a unix domain socket,
     /// switching the connection method from TCP to the corresponding socket.
     ///
@@ -453,6 +612,30 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets whether the SQL of statements is normalized before being used as their key in the
+    /// statement cache, so that statements which only differ by their formatting are prepared
+    /// once.
+    ///
+    /// The normalization is conservative: runs of whitespace are collapsed into a single space
+    /// and leading and trailing whitespace is removed, but string literals, quoted identifiers
+    /// and comments are left as-is, and a SQL string containing a backslash is not normalized.
+    /// Statements are still prepared with their SQL as given.
+    ///
+    /// This is useful for SQL generated dynamically, e.g. by a query builder. It is disabled by
+    /// default, as it adds some work to the execution of each statement.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .normalize_statement_cache_key(true);
+    /// ```
+    pub fn normalize_statement_cache_key(mut self, normalize: bool) -> Self {
+        self.normalize_statement_cache_key = normalize;
+        self
+    }
+
     /// Sets the application name. Defaults to None
     ///
     /// # Example
@@ -522,12 +705,20 @@ impl PgConnectOptions {
 
     /// Set additional startup options for the connection as a list of key-value pairs.
     ///
+    /// These are run-time parameters which are set for the whole session, as if by `SET`.
+    /// Values are sent as-is, so spaces and backslashes in them must be escaped with
+    /// a backslash; [`date_style()`][Self::date_style] and
+    /// [`interval_style()`][Self::interval_style] escape their value automatically.
+    ///
     /// # Example
     ///
     /// ```rust
     /// # use sqlx_core::postgres::PgConnectOptions;
     /// let options = PgConnectOptions::new()
     ///     .options([("geqo", "off"), ("statement_timeout", "5min")]);
+    ///
+    /// let options = PgConnectOptions::new()
+    ///     .options([("DateStyle", r"ISO,\ DMY"), ("IntervalStyle", "iso_8601")]);
     /// ```
     pub fn options<K, V, I>(mut self, options: I) -> Self
     where
@@ -547,6 +738,205 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets the `DateStyle` run-time parameter, which controls how date/time values are
+    /// formatted in the text format and how ambiguous date input is interpreted.
+    ///
+    /// The text format is used by queries without bind parameters (e.g. [`Executor::execute()`]
+    /// with a plain string) and by some tooling.
+    ///
+    /// SQLx defaults `DateStyle` to `ISO, MDY` unless it is set here or with
+    /// [`options()`][Self::options]; date/time types can only be decoded from the text format
+    /// with the `ISO` output style, though the second component (`MDY`, `DMY` or `YMD`) can be
+    /// freely changed, as it only affects the interpretation of ambiguous input such as
+    /// `'01/02/2024'`.
+    ///
+    /// [`Executor::execute()`]: crate::executor::Executor::execute
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .date_style("ISO, DMY");
+    /// ```
+    pub fn date_style(self, date_style: &str) -> Self {
+        self.options([("DateStyle", escape_option_value(date_style))])
+    }
+
+    /// Sets the `IntervalStyle` run-time parameter, which controls how interval values are
+    /// formatted in the text format.
+    ///
+    /// [`PgInterval`][crate::types::PgInterval] can only be decoded from the text format
+    /// with `IntervalStyle` set to `iso_8601`, which, unlike the server's default of `postgres`,
+    /// is unambiguous and unaffected by `DateStyle`. Values are always decoded
+    /// from the binary format regardless of the setting.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .interval_style("iso_8601");
+    /// ```
+    pub fn interval_style(self, interval_style: &str) -> Self {
+        self.options([("IntervalStyle", escape_option_value(interval_style))])
+    }
+
+    /// Sets the isolation level of the transactions on the connection, unless specified otherwise
+    /// with `SET TRANSACTION` or `BEGIN ISOLATION LEVEL ...`.
+    ///
+    /// This has the same effect as `SET SESSION CHARACTERISTICS AS TRANSACTION ISOLATION LEVEL ...`
+    /// for the whole session. As it is sent when connecting rather than as a `SET` command, it
+    /// is also the value restored by `RESET ALL` or `DISCARD ALL`, so it survives any reset
+    /// of the session.
+    ///
+    /// By default, the server's setting is used, which is [`IsolationLevel::ReadCommitted`]
+    /// unless configured otherwise. See [the Postgres manual] for the guarantees of each level.
+    ///
+    /// [the Postgres manual]: https://www.postgresql.org/docs/current/transaction-iso.html
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// # use sqlx_core::transaction::IsolationLevel;
+    /// let options = PgConnectOptions::new()
+    ///     .default_transaction_isolation(IsolationLevel::Serializable);
+    /// ```
+    pub fn default_transaction_isolation(mut self, level: IsolationLevel) -> Self {
+        self.default_transaction_isolation = Some(level);
+        self
+    }
+
+    /// Sets whether the transactions on the connection are read-only, unless specified otherwise
+    /// with `SET TRANSACTION` or `BEGIN READ WRITE`.
+    ///
+    /// This has the same effect as `SET SESSION CHARACTERISTICS AS TRANSACTION READ ONLY`
+    /// (or `READ WRITE`) for the whole session, and survives any reset of the session, see
+    /// [`default_transaction_isolation()`][Self::default_transaction_isolation]. Statements
+    /// executed outside of an explicit transaction are affected as well.
+    ///
+    /// This is useful for connections to a replica, or to guard against accidental writes.
+    /// Note that this is not a security measure, as it can be changed by any query.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .default_transaction_read_only(true);
+    /// ```
+    pub fn default_transaction_read_only(mut self, read_only: bool) -> Self {
+        self.default_transaction_read_only = Some(read_only);
+        self
+    }
+
+    /// Sets a cache of the user-defined types of the database, shared by all the connections
+    /// using these options.
+    ///
+    /// The connections of a pool all share the same cache, so the types are only resolved with
+    /// queries to the catalog once for the whole pool, instead of once for each connection.
+    /// See [`PgTypeCache`] for details.
+    ///
+    /// By default, each connection has its own cache.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::{PgConnectOptions, PgTypeCache};
+    /// let options = PgConnectOptions::new()
+    ///     .type_cache(PgTypeCache::new());
+    /// ```
+    pub fn type_cache(mut self, cache: PgTypeCache) -> Self {
+        self.type_cache = Some(cache);
+        self
+    }
+
+    /// Get the shared cache of user-defined types, if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::{PgConnectOptions, PgTypeCache};
+    /// let options = PgConnectOptions::new()
+    ///     .type_cache(PgTypeCache::new());
+    /// assert!(options.get_type_cache().is_some());
+    /// ```
+    pub fn get_type_cache(&self) -> Option<&PgTypeCache> {
+        self.type_cache.as_ref()
+    }
+
+    /// Request the values of the columns of `type_info` in text format, instead of binary, in
+    /// the results of prepared statements.
+    ///
+    /// This is useful to decode types which have no binary representation, e.g. some types added
+    /// by extensions, or to get the text output of the server for a type, e.g. as a `String`
+    /// with [`Text`](sqlx_core::types::Text). The results of queries without bind parameters
+    /// are always in text format.
+    ///
+    /// A type declared with [`PgTypeInfo::with_name()`] matches the columns with this name,
+    /// e.g. `_ltree` for `ltree[]`, and one declared with [`PgTypeInfo::with_oid()`] the columns
+    /// with this OID. This can be called multiple times to add more types.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::postgres::{PgConnectOptions, PgTypeInfo};
+    /// let options = PgConnectOptions::new()
+    ///     .text_result_format(PgTypeInfo::with_name("ltree"));
+    /// ```
+    pub fn text_result_format(mut self, type_info: PgTypeInfo) -> Self {
+        self.text_result_types.push(type_info);
+        self
+    }
+
+    /// Returns `true` if the run-time parameter `name` is set by [`options()`][Self::options].
+    pub(crate) fn has_option(&self, name: &str) -> bool {
+        let Some(options) = &self.options else {
+            return false;
+        };
+
+        // Split on unescaped whitespace, the same way as the server parses these arguments
+        let mut args = Vec::new();
+        let mut arg = String::new();
+        let mut chars = options.chars();
+
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' => arg.extend(chars.next()),
+                _ if ch.is_ascii_whitespace() => args.push(mem::take(&mut arg)),
+                _ => arg.push(ch),
+            }
+        }
+
+        args.push(arg);
+
+        // Arguments are either `-c name=value`, `-cname=value` or `--name=value`
+        let mut args = args.iter().filter(|arg| !arg.is_empty());
+
+        while let Some(arg) = args.next() {
+            let setting = match arg.as_str() {
+                "-c" => match args.next() {
+                    Some(setting) => setting.as_str(),
+                    None => break,
+                },
+                arg => match arg.strip_prefix("-c").or_else(|| arg.strip_prefix("--")) {
+                    Some(setting) => setting,
+                    None => continue,
+                },
+            };
+
+            // Parameter names are case-insensitive, and `-` can be used in place of `_`
+            if let Some((key, _)) = setting.split_once('=') {
+                if key.replace('-', "_").eq_ignore_ascii_case(name) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
     /// We try using a socket if hostname starts with `/` or if socket parameter
     /// is specified.
     pub(crate) fn fetch_socket(&self) -> Option<String> {
@@ -583,6 +973,12 @@ fn default_host(port: u16) -> String {
     "localhost".to_owned()
 }
 
+/// Escapes a value of a run-time parameter for [`PgConnectOptions::options()`]: spaces separate
+/// arguments unless escaped with a backslash (and so do backslashes).
+fn escape_option_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(' ', "\\ ")
+}
+
 #[test]
 fn test_options_formatting() {
     let options = PgConnectOptions::new().options([("geqo", "off")]);
@@ -600,3 +996,33 @@ fn test_options_formatting() {
     let options = PgConnectOptions::new();
     assert_eq!(options.options, None);
 }
+
+#[test]
+fn test_options_escaping() {
+    let options = PgConnectOptions::new().options([("DateStyle", r"ISO,\ DMY")]);
+    assert_eq!(options.options, Some(r"-c DateStyle=ISO,\ DMY".to_string()));
+    let options = PgConnectOptions::new()
+        .date_style("ISO, DMY")
+        .interval_style(r"a\b");
+    assert_eq!(
+        options.options,
+        Some(r"-c DateStyle=ISO,\ DMY -c IntervalStyle=a\\b".to_string())
+    );
+}
+
+#[test]
+fn test_has_option() {
+    let options = PgConnectOptions::new().date_style("ISO, DMY");
+    assert!(options.has_option("datestyle"));
+    assert!(!options.has_option("DMY"));
+    assert!(!options.has_option("IntervalStyle"));
+
+    let mut options = PgConnectOptions::new();
+    assert!(!options.has_option("DateStyle"));
+
+    options.options = Some(r"-cgeqo=off --intervalstyle=iso_8601 -c x=a\ -c\ DateStyle=1".into());
+    assert!(options.has_option("geqo"));
+    assert!(options.has_option("IntervalStyle"));
+    assert!(options.has_option("x"));
+    assert!(!options.has_option("DateStyle"));
+}
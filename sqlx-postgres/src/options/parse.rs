@@ -4,12 +4,21 @@ use sqlx_core::percent_encoding::percent_decode_str;
 use sqlx_core::Url;
 use std::net::IpAddr;
 use std::str::FromStr;
+use std::time::Duration;
 
 impl PgConnectOptions {
     pub(crate) fn parse_from_url(url: &Url) -> Result<Self, Error> {
         let mut options = Self::new_without_pgpass();
 
-        if let Some(host) = url.host_str() {
+        // Further hosts and ports of a comma-separated list, which are paired at the end
+        let mut fallback_hosts = Vec::new();
+        let mut fallback_ports = Vec::new();
+
+        if let Some(hosts) = url.host_str() {
+            let mut hosts = hosts.split(',');
+            let host = hosts.next().unwrap_or_default();
+            fallback_hosts = hosts.map(str::to_owned).collect();
+
             let host_decoded = percent_decode_str(host);
             options = match host_decoded.clone().next() {
                 Some(b'/') => options.socket(&*host_decoded.decode_utf8().map_err(Error::config)?),
@@ -63,10 +72,14 @@ impl PgConnectOptions {
                 }
 
                 "host" => {
-                    if value.starts_with("/") {
-                        options = options.socket(&*value);
+                    let mut hosts = value.split(',');
+                    let host = hosts.next().unwrap_or_default();
+                    fallback_hosts = hosts.map(str::to_owned).collect();
+
+                    if host.starts_with("/") {
+                        options = options.socket(host);
                     } else {
-                        options = options.host(&*value);
+                        options = options.host(host);
                     }
                 }
 
@@ -75,7 +88,28 @@ impl PgConnectOptions {
                     options = options.host(&*value)
                 }
 
-                "port" => options = options.port(value.parse().map_err(Error::config)?),
+                "port" => {
+                    let mut ports = value.split(',');
+                    let port = ports.next().unwrap_or_default();
+                    options = options.port(port.parse().map_err(Error::config)?);
+                    fallback_ports = ports
+                        .map(|port| port.parse().map_err(Error::config))
+                        .collect::<Result<_, _>>()?;
+                }
+
+                "target_session_attrs" => {
+                    options = options.target_session_attrs(value.parse()?);
+                }
+
+                "load_balance_hosts" => {
+                    options = options.load_balance_hosts(value.parse()?);
+                }
+
+                "connect_timeout" => {
+                    let secs: u64 = value.parse().map_err(Error::config)?;
+                    options =
+                        options.connect_timeout((secs > 0).then(|| Duration::from_secs(secs)));
+                }
 
                 "dbname" => options = options.database(&*value),
 
@@ -104,6 +138,27 @@ impl PgConnectOptions {
             }
         }
 
+        // Like libpq, either a single port is used for all the hosts, or each one has its own
+        options.fallback_hosts = match fallback_ports.len() {
+            0 => fallback_hosts
+                .into_iter()
+                .map(|host| (host, options.port))
+                .collect(),
+            n if n == fallback_hosts.len() => {
+                fallback_hosts.into_iter().zip(fallback_ports).collect()
+            }
+            n => {
+                return Err(Error::Configuration(
+                    format!(
+                        "could not match {} port numbers to {} hosts",
+                        n + 1,
+                        fallback_hosts.len() + 1
+                    )
+                    .into(),
+                ))
+            }
+        };
+
         let options = options.apply_pgpass();
 
         Ok(options)
@@ -242,3 +297,51 @@ fn it_parses_sqlx_options_correctly() {
         opts.options
     );
 }
+
+#[test]
+fn it_parses_multiple_hosts_correctly() {
+    let url = "postgres://primary,standby:5433/database?target_session_attrs=read-write";
+    let opts = PgConnectOptions::from_str(url).unwrap();
+
+    assert_eq!(
+        opts.get_hosts().collect::<Vec<_>>(),
+        [("primary", 5433), ("standby", 5433)]
+    );
+    assert_eq!(
+        opts.target_session_attrs,
+        crate::PgTargetSessionAttrs::ReadWrite
+    );
+
+    let url = "postgres:///database?host=a,b,c&port=5432,5433,5434";
+    let opts = PgConnectOptions::from_str(url).unwrap();
+
+    assert_eq!(
+        opts.get_hosts().collect::<Vec<_>>(),
+        [("a", 5432), ("b", 5433), ("c", 5434)]
+    );
+    assert_eq!(opts.target_session_attrs, crate::PgTargetSessionAttrs::Any);
+}
+
+#[test]
+fn it_rejects_mismatched_hosts_and_ports() {
+    let url = "postgres:///database?host=a,b,c&port=5432,5433";
+    assert!(PgConnectOptions::from_str(url).is_err());
+
+    let url = "postgres:///database?target_session_attrs=prefer-primary";
+    assert!(PgConnectOptions::from_str(url).is_err());
+}
+
+#[test]
+fn it_parses_load_balancing_correctly() {
+    let url = "postgres://a,b/database?load_balance_hosts=random&connect_timeout=10";
+    let opts = PgConnectOptions::from_str(url).unwrap();
+
+    assert_eq!(opts.load_balance_hosts, crate::PgLoadBalanceHosts::Random);
+    assert_eq!(opts.connect_timeout, Some(Duration::from_secs(10)));
+
+    let url = "postgres://a,b/database?connect_timeout=0";
+    let opts = PgConnectOptions::from_str(url).unwrap();
+
+    assert_eq!(opts.load_balance_hosts, crate::PgLoadBalanceHosts::Disable);
+    assert_eq!(opts.connect_timeout, None);
+}
@@ -0,0 +1,68 @@
+use crate::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// The properties a server must have for a connection to it to be accepted.
+///
+/// It is used by the [`target_session_attrs`](super::PgConnectOptions::target_session_attrs)
+/// method. The hosts are tried in order, and the first one with a matching session is used,
+/// the same way as with libpq's
+/// [`target_session_attrs`](https://www.postgresql.org/docs/current/libpq-connect.html#LIBPQ-CONNECT-TARGET-SESSION-ATTRS).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PgTargetSessionAttrs {
+    /// Any successful connection is acceptable. This is the default.
+    #[default]
+    Any,
+
+    /// The session must accept read-write transactions by default, i.e.
+    /// `SHOW transaction_read_only` returns `off`.
+    ReadWrite,
+
+    /// The session must not accept read-write transactions by default, i.e.
+    /// `SHOW transaction_read_only` returns `on`.
+    ReadOnly,
+
+    /// The server must not be in hot standby mode, i.e. `pg_is_in_recovery()` returns `false`.
+    Primary,
+
+    /// The server must be in hot standby mode, i.e. `pg_is_in_recovery()` returns `true`.
+    Standby,
+}
+
+impl PgTargetSessionAttrs {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            PgTargetSessionAttrs::Any => "any",
+            PgTargetSessionAttrs::ReadWrite => "read-write",
+            PgTargetSessionAttrs::ReadOnly => "read-only",
+            PgTargetSessionAttrs::Primary => "primary",
+            PgTargetSessionAttrs::Standby => "standby",
+        }
+    }
+}
+
+impl Display for PgTargetSessionAttrs {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PgTargetSessionAttrs {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "any" => PgTargetSessionAttrs::Any,
+            "read-write" => PgTargetSessionAttrs::ReadWrite,
+            "read-only" => PgTargetSessionAttrs::ReadOnly,
+            "primary" => PgTargetSessionAttrs::Primary,
+            "standby" => PgTargetSessionAttrs::Standby,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!("unknown value {s:?} for `target_session_attrs`").into(),
+                ));
+            }
+        })
+    }
+}
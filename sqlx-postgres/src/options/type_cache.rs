@@ -0,0 +1,121 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use sqlx_core::sync::lock_poisoned;
+
+use crate::ext::ustr::UStr;
+use crate::types::Oid;
+use crate::{HashMap, PgTypeInfo};
+
+/// A cache of the user-defined types of a database, which is shared by the connections using it.
+///
+/// Each connection resolves the OIDs and definitions of the types it encounters which are not
+/// built-in, e.g. enums, composite types and domains, with queries to the catalog. As these
+/// are stable for a given database, resolving them once for all the connections of a pool saves
+/// these queries on every new connection.
+///
+/// It is used by the [`type_cache`](super::PgConnectOptions::type_cache) method. As the options
+/// of a pool are cloned for each of its connections, they all share the same cache:
+///
+/// ```rust,no_run
+/// # async fn example() -> sqlx::Result<()> {
+/// use sqlx::postgres::{PgConnectOptions, PgPoolOptions, PgTypeCache};
+///
+/// let type_cache = PgTypeCache::new();
+///
+/// let pool = PgPoolOptions::new()
+///     .connect_with(
+///         "postgres://localhost/mydb"
+///             .parse::<PgConnectOptions>()?
+///             .type_cache(type_cache.clone()),
+///     )
+///     .await?;
+///
+/// // after the types have been recreated, e.g. by a migration
+/// type_cache.clear();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct PgTypeCache {
+    inner: Arc<TypeCacheInner>,
+}
+
+#[derive(Default)]
+struct TypeCacheInner {
+    types: Mutex<Types>,
+    // incremented by `clear()`, for the connections to drop their own cached types as well
+    generation: AtomicU64,
+}
+
+#[derive(Default)]
+struct Types {
+    info: HashMap<Oid, PgTypeInfo>,
+    oids: HashMap<UStr, Oid>,
+}
+
+impl PgTypeCache {
+    /// Creates a new, empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes all the types from the cache, e.g. after they have been recreated with a
+    /// different OID.
+    ///
+    /// The connections using the cache also drop the types they cached themselves the next time
+    /// they resolve a type. Note that their prepared statements are not affected, see
+    /// [`Connection::clear_cached_statements()`](sqlx_core::connection::Connection::clear_cached_statements).
+    pub fn clear(&self) {
+        let mut types = self.lock();
+        types.info.clear();
+        types.oids.clear();
+
+        self.inner.generation.fetch_add(1, Ordering::AcqRel);
+    }
+
+    /// Returns the number of types in the cache.
+    pub fn len(&self) -> usize {
+        self.lock().info.len()
+    }
+
+    /// Returns `true` if there are no types in the cache.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn generation(&self) -> u64 {
+        self.inner.generation.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn get_info(&self, oid: Oid) -> Option<PgTypeInfo> {
+        self.lock().info.get(&oid).cloned()
+    }
+
+    pub(crate) fn get_oid(&self, name: &str) -> Option<Oid> {
+        self.lock().oids.get(name).copied()
+    }
+
+    pub(crate) fn insert_info(&self, oid: Oid, info: &PgTypeInfo) {
+        let mut types = self.lock();
+        types.info.insert(oid, info.clone());
+        types.oids.insert(info.0.name().to_string().into(), oid);
+    }
+
+    pub(crate) fn insert_oid(&self, name: &str, oid: Oid) {
+        self.lock().oids.insert(name.to_string().into(), oid);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Types> {
+        lock_poisoned(&self.inner.types)
+    }
+}
+
+impl Debug for PgTypeCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgTypeCache")
+            .field("len", &self.len())
+            .finish()
+    }
+}
@@ -0,0 +1,167 @@
+use sqlx_core::query_builder::QueryBuilder;
+
+use crate::Postgres;
+
+/// An extension trait to build upserts with a [`QueryBuilder`], which tell which rows were
+/// inserted.
+///
+/// An `INSERT .. ON CONFLICT DO UPDATE` reports the rows it inserted and the ones it updated
+/// together in [`rows_affected()`](crate::PgQueryResult::rows_affected). With
+/// [`push_returning_inserted()`](Self::push_returning_inserted), each returned row has an
+/// `inserted` column telling whether it was inserted or updated, using the standard trick
+/// of checking the `xmax` system column of the new row version: it is only `0` if it was just
+/// inserted.
+///
+/// With `ON CONFLICT DO NOTHING`, the skipped rows are not returned at all.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::postgres::PgQueryBuilderExt;
+/// use sqlx::QueryBuilder;
+///
+/// let users = [(1, "alice"), (2, "bob")];
+///
+/// let mut query_builder = QueryBuilder::new("INSERT INTO users (id, name) ");
+///
+/// query_builder
+///     .push_values(users, |mut b, (id, name)| {
+///         b.push_bind(id).push_bind(name);
+///     })
+///     .push_on_conflict_do_update(&["id"], &["name"])
+///     .push_returning_inserted(&["id"]);
+///
+/// let rows: Vec<(i32, bool)> = query_builder.build_query_as().fetch_all(conn).await?;
+///
+/// for (id, inserted) in rows {
+///     println!("user {id} was {}", if inserted { "inserted" } else { "updated" });
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub trait PgQueryBuilderExt {
+    /// Push ` ON CONFLICT (conflict_target..) DO UPDATE SET column = EXCLUDED.column, ..`, to
+    /// update the `columns` of the existing rows with the values which were to be inserted.
+    ///
+    /// The names are quoted as with [`QueryBuilder::push_identifier()`]. Postgres requires a
+    /// conflict target and at least one column to update: use
+    /// [`push_on_conflict_do_nothing()`](Self::push_on_conflict_do_nothing) to keep the existing
+    /// rows as they are.
+    fn push_on_conflict_do_update(
+        &mut self,
+        conflict_target: &[&str],
+        columns: &[&str],
+    ) -> &mut Self;
+
+    /// Push ` ON CONFLICT (conflict_target..) DO NOTHING`, or ` ON CONFLICT DO NOTHING` if
+    /// `conflict_target` is empty, to skip the rows conflicting with existing ones.
+    ///
+    /// The names are quoted as with [`QueryBuilder::push_identifier()`].
+    fn push_on_conflict_do_nothing(&mut self, conflict_target: &[&str]) -> &mut Self;
+
+    /// Push ` RETURNING column, .., (xmax = 0) AS inserted`, to return the `columns` of each
+    /// inserted or updated row, followed by a boolean telling whether it was inserted.
+    ///
+    /// The names are quoted as with [`QueryBuilder::push_identifier()`].
+    fn push_returning_inserted(&mut self, columns: &[&str]) -> &mut Self;
+}
+
+impl<'args> PgQueryBuilderExt for QueryBuilder<'args, Postgres> {
+    fn push_on_conflict_do_update(
+        &mut self,
+        conflict_target: &[&str],
+        columns: &[&str],
+    ) -> &mut Self {
+        self.push(" ON CONFLICT ");
+        push_conflict_target(self, conflict_target);
+        self.push(" DO UPDATE SET ");
+
+        for (i, column) in columns.iter().enumerate() {
+            if i > 0 {
+                self.push(", ");
+            }
+
+            self.push_identifier(column)
+                .push(" = EXCLUDED.")
+                .push_identifier(column);
+        }
+
+        self
+    }
+
+    fn push_on_conflict_do_nothing(&mut self, conflict_target: &[&str]) -> &mut Self {
+        self.push(" ON CONFLICT ");
+
+        if !conflict_target.is_empty() {
+            push_conflict_target(self, conflict_target);
+            self.push(" ");
+        }
+
+        self.push("DO NOTHING")
+    }
+
+    fn push_returning_inserted(&mut self, columns: &[&str]) -> &mut Self {
+        self.push(" RETURNING ");
+
+        for column in columns {
+            self.push_identifier(column).push(", ");
+        }
+
+        self.push("(xmax = 0) AS inserted")
+    }
+}
+
+fn push_conflict_target(query_builder: &mut QueryBuilder<'_, Postgres>, columns: &[&str]) {
+    query_builder.push("(");
+
+    for (i, column) in columns.iter().enumerate() {
+        if i > 0 {
+            query_builder.push(", ");
+        }
+
+        query_builder.push_identifier(column);
+    }
+
+    query_builder.push(")");
+}
+
+#[test]
+fn test_push_upserts() {
+    let mut query_builder: QueryBuilder<'_, Postgres> =
+        QueryBuilder::new("INSERT INTO users (id, name, email) VALUES ($1, $2, $3)");
+
+    query_builder
+        .push_on_conflict_do_update(&["id"], &["name", "email"])
+        .push_returning_inserted(&["id"]);
+
+    assert_eq!(
+        query_builder.sql(),
+        "INSERT INTO users (id, name, email) VALUES ($1, $2, $3) \
+         ON CONFLICT (\"id\") DO UPDATE SET \"name\" = EXCLUDED.\"name\", \
+         \"email\" = EXCLUDED.\"email\" RETURNING \"id\", (xmax = 0) AS inserted"
+    );
+
+    let mut query_builder: QueryBuilder<'_, Postgres> =
+        QueryBuilder::new("INSERT INTO users (id) VALUES ($1)");
+
+    query_builder
+        .push_on_conflict_do_nothing(&["id", "org_id"])
+        .push_returning_inserted(&[]);
+
+    assert_eq!(
+        query_builder.sql(),
+        "INSERT INTO users (id) VALUES ($1) ON CONFLICT (\"id\", \"org_id\") DO NOTHING \
+         RETURNING (xmax = 0) AS inserted"
+    );
+
+    let mut query_builder: QueryBuilder<'_, Postgres> =
+        QueryBuilder::new("INSERT INTO users (id) VALUES ($1)");
+
+    query_builder.push_on_conflict_do_nothing(&[]);
+
+    assert_eq!(
+        query_builder.sql(),
+        "INSERT INTO users (id) VALUES ($1) ON CONFLICT DO NOTHING"
+    );
+}
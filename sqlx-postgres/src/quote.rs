@@ -0,0 +1,21 @@
+/// Quote `ident` as an identifier, so it can be interpolated in SQL as-is.
+pub(crate) fn quote_identifier(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+/// Quote the name of a table, optionally qualified with the name of its schema.
+pub(crate) fn quote_table_name(table: &str) -> String {
+    match table.split_once('.') {
+        Some((schema, table)) => {
+            format!("{}.{}", quote_identifier(schema), quote_identifier(table))
+        }
+        None => quote_identifier(table),
+    }
+}
+
+#[test]
+fn test_quote_table_name() {
+    assert_eq!(quote_table_name("users"), "\"users\"");
+    assert_eq!(quote_table_name("public.Users"), "\"public\".\"Users\"");
+    assert_eq!(quote_table_name("a\"b"), "\"a\"\"b\"");
+}
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use sqlx_core::database::HasArguments;
+use sqlx_core::recording::RecordDatabase;
+
+use crate::arguments::PgArgumentBuffer;
+use crate::error::Error;
+use crate::ext::ustr::UStr;
+use crate::type_info::PgType;
+use crate::types::Oid;
+use crate::{PgArguments, PgQueryResult, PgTypeInfo, Postgres};
+
+/// The recorded form of an argument of a query, with its value in the binary format.
+///
+/// See the `sqlx::recording` module for details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PgRecordedArgument {
+    #[serde(rename = "type")]
+    ty: RecordedType,
+
+    // the hex-encoded value, without its length
+    value: Option<String>,
+
+    // the OIDs of named types in the value, which are resolved again on replay as they differ
+    // between databases: `{ offset in the value, type name }`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    type_holes: Vec<(usize, String)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RecordedType {
+    Oid(u32),
+    Name(String),
+}
+
+impl RecordedType {
+    fn new(ty: &PgTypeInfo) -> Self {
+        match &ty.0 {
+            PgType::DeclareWithName(name) => RecordedType::Name(name.to_string()),
+            // the OIDs of custom types are not stable, unlike the ones of built-in types
+            PgType::Custom(custom) => RecordedType::Name(custom.name.to_string()),
+            ty => RecordedType::Oid(ty.oid().0),
+        }
+    }
+
+    fn type_info(self) -> PgTypeInfo {
+        match self {
+            RecordedType::Oid(oid) => {
+                PgTypeInfo::try_from_oid(Oid(oid)).unwrap_or(PgTypeInfo::with_oid(Oid(oid)))
+            }
+            RecordedType::Name(name) => PgTypeInfo(PgType::DeclareWithName(UStr::from(name))),
+        }
+    }
+}
+
+impl RecordDatabase for Postgres {
+    type RecordedArguments = Vec<PgRecordedArgument>;
+
+    fn record_arguments(arguments: &PgArguments) -> Vec<PgRecordedArgument> {
+        let PgArgumentBuffer {
+            buffer,
+            patches,
+            type_holes,
+            ..
+        } = &arguments.buffer;
+
+        // As the declared types of the parameters are what Postgres uses for them, the patches
+        // can already be applied
+        let mut buffer = buffer.clone();
+
+        for (offset, index, callback) in patches {
+            callback(&mut buffer[*offset..], &arguments.types[*index]);
+        }
+
+        let mut recorded = Vec::with_capacity(arguments.types.len());
+        let mut offset = 0;
+
+        for ty in &arguments.types {
+            let len = i32::from_be_bytes(buffer[offset..offset + 4].try_into().unwrap());
+            let start = offset + 4;
+            let end = start + usize::try_from(len).unwrap_or(0);
+
+            recorded.push(PgRecordedArgument {
+                ty: RecordedType::new(ty),
+                value: (len >= 0).then(|| hex::encode(&buffer[start..end])),
+                type_holes: type_holes
+                    .iter()
+                    .filter(|(hole, _)| (start..end).contains(hole))
+                    .map(|(hole, name)| (hole - start, name.to_string()))
+                    .collect(),
+            });
+
+            offset = end;
+        }
+
+        recorded
+    }
+
+    fn replay_arguments<'q>(
+        recorded: Vec<PgRecordedArgument>,
+    ) -> Result<<Postgres as HasArguments<'q>>::Arguments, Error> {
+        let mut arguments = PgArguments::default();
+
+        for argument in recorded {
+            let buffer = &mut arguments.buffer;
+
+            match argument.value {
+                Some(value) => {
+                    let value = hex::decode(value).map_err(|e| Error::Decode(e.into()))?;
+                    let start = buffer.len() + 4;
+
+                    buffer.extend(&(value.len() as i32).to_be_bytes());
+                    buffer.extend(&value);
+
+                    for (hole, name) in argument.type_holes {
+                        if hole + 4 > value.len() {
+                            return Err(Error::Decode(
+                                format!("type hole at {hole} is out of the recorded value").into(),
+                            ));
+                        }
+
+                        buffer.type_holes.push((start + hole, UStr::from(name)));
+                    }
+                }
+                None => buffer.extend(&(-1_i32).to_be_bytes()),
+            }
+
+            buffer.count += 1;
+            arguments.types.push(argument.ty.type_info());
+        }
+
+        Ok(arguments)
+    }
+
+    fn rows_affected(result: &PgQueryResult) -> u64 {
+        result.rows_affected()
+    }
+}
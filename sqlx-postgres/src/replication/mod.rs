@@ -0,0 +1,343 @@
+use std::cmp;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use futures_core::stream::BoxStream;
+use sqlx_core::bytes::{Buf, BufMut, Bytes};
+
+use crate::connection::{ConnectOptions, Connection};
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::message::{CopyData, CopyResponse, MessageFormat, Query};
+use crate::quote::quote_identifier;
+use crate::row::PgRow;
+use crate::types::Oid;
+use crate::{HashMap, PgConnectOptions, PgConnection};
+use sqlx_core::row::Row;
+
+pub use crate::types::PgLsn;
+pub use pgoutput::{PgRelation, PgRelationColumn, PgReplicationMessage, PgTupleData, PgTupleValue};
+
+mod pgoutput;
+
+/// A connection to Postgres in logical replication mode.
+///
+/// This is used to manage replication slots and to start streaming changes decoded by
+/// the built-in `pgoutput` plugin, which requires a
+/// [publication](https://www.postgresql.org/docs/current/logical-replication-publication.html)
+/// to be created beforehand for the tables of interest:
+///
+/// ```sql
+/// CREATE PUBLICATION my_publication FOR TABLE users, orders;
+/// ```
+///
+/// The connecting role must have the `REPLICATION` attribute and the server must run with
+/// `wal_level = logical`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example() -> sqlx::Result<()> {
+/// use futures::TryStreamExt;
+/// use sqlx::postgres::{PgLsn, PgReplication, PgReplicationMessage};
+///
+/// let mut replication = PgReplication::connect("postgres://localhost/mydb").await?;
+///
+/// replication.create_slot("my_slot", false).await?;
+///
+/// let mut stream = replication
+///     .start("my_slot", &["my_publication"], PgLsn::default())
+///     .await?
+///     .into_stream();
+///
+/// while let Some(message) = stream.try_next().await? {
+///     if let PgReplicationMessage::Insert { relation, new } = message {
+///         println!("insert into {}: {:?}", relation.name, new);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct PgReplication {
+    conn: PgConnection,
+}
+
+/// A connection streaming changes from a logical replication slot.
+///
+/// Created by [`PgReplication::start()`].
+///
+/// The server does not discard WAL for the slot until it is told that the changes have been
+/// processed, by calling [`ack()`][Self::ack]. Keepalive requests from the server are answered
+/// automatically while calling [`recv()`][Self::recv].
+pub struct PgReplicationStream {
+    conn: PgConnection,
+    relations: HashMap<Oid, Arc<PgRelation>>,
+    received_lsn: PgLsn,
+    flushed_lsn: PgLsn,
+}
+
+impl PgReplication {
+    /// Connect in replication mode using a connection URL.
+    pub async fn connect(url: &str) -> Result<Self, Error> {
+        Self::connect_with(&url.parse()?).await
+    }
+
+    /// Connect in replication mode using the given options.
+    pub async fn connect_with(options: &PgConnectOptions) -> Result<Self, Error> {
+        let mut options = options.clone();
+        options.replication = true;
+
+        Ok(Self {
+            conn: options.connect().await?,
+        })
+    }
+
+    /// Create a logical replication slot using the `pgoutput` plugin.
+    ///
+    /// If `temporary` is `true`, the slot is dropped when this connection is closed.
+    ///
+    /// Returns the LSN from which the slot starts to retain changes.
+    pub async fn create_slot(&mut self, slot: &str, temporary: bool) -> Result<PgLsn, Error> {
+        let row: PgRow = self
+            .conn
+            .fetch_one(&*format!(
+                "CREATE_REPLICATION_SLOT {}{} LOGICAL pgoutput",
+                quote_identifier(slot),
+                if temporary { " TEMPORARY" } else { "" }
+            ))
+            .await?;
+
+        row.try_get::<&str, _>("consistent_point")?.parse()
+    }
+
+    /// Drop a replication slot, freeing any WAL retained for it.
+    pub async fn drop_slot(&mut self, slot: &str) -> Result<(), Error> {
+        self.conn
+            .execute(&*format!(
+                "DROP_REPLICATION_SLOT {}",
+                quote_identifier(slot)
+            ))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Start streaming changes from `slot` for the given publications.
+    ///
+    /// Changes are streamed from `start_lsn`, or from the slot's confirmed position
+    /// if that is later (such as when passing [`PgLsn::default()`]).
+    pub async fn start(
+        mut self,
+        slot: &str,
+        publications: &[&str],
+        start_lsn: PgLsn,
+    ) -> Result<PgReplicationStream, Error> {
+        let publication_names = publications
+            .iter()
+            .map(|name| quote_identifier(name))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let query = format!(
+            "START_REPLICATION SLOT {} LOGICAL {} (proto_version '1', publication_names '{}')",
+            quote_identifier(slot),
+            start_lsn,
+            publication_names.replace('\'', "''")
+        );
+
+        self.conn.wait_until_ready().await?;
+        self.conn.stream.send(Query(&query)).await?;
+
+        let _: CopyResponse = match self
+            .conn
+            .stream
+            .recv_expect(MessageFormat::CopyBothResponse)
+            .await
+        {
+            Ok(res) => res,
+            Err(e) => {
+                self.conn.stream.recv().await?;
+                return Err(e);
+            }
+        };
+
+        Ok(PgReplicationStream {
+            conn: self.conn,
+            relations: HashMap::new(),
+            received_lsn: start_lsn,
+            flushed_lsn: start_lsn,
+        })
+    }
+
+    /// Close the connection.
+    ///
+    /// Temporary replication slots created on this connection are dropped.
+    pub async fn close(self) -> Result<(), Error> {
+        self.conn.close().await
+    }
+}
+
+impl PgReplicationStream {
+    /// Receive the next change from the replication slot.
+    ///
+    /// Keepalive messages sent by the server are handled transparently, replying with
+    /// a status update if the server asks for one.
+    pub async fn recv(&mut self) -> Result<PgReplicationMessage, Error> {
+        loop {
+            let message = self.conn.stream.recv().await?;
+
+            let mut data = match message.format {
+                MessageFormat::CopyData => message.decode::<CopyData<Bytes>>()?.0,
+                MessageFormat::CopyDone => {
+                    return Err(err_protocol!("replication stream ended by the server"));
+                }
+                format => {
+                    return Err(err_protocol!(
+                        "unexpected message format during replication: {:?}",
+                        format
+                    ));
+                }
+            };
+
+            if data.is_empty() {
+                return Err(err_protocol!("received empty replication message"));
+            }
+
+            match data.get_u8() {
+                // XLogData
+                b'w' => {
+                    if data.remaining() < 24 {
+                        return Err(err_protocol!("XLogData message too short"));
+                    }
+
+                    let _wal_start = data.get_u64();
+                    let wal_end = PgLsn(data.get_u64());
+                    let _send_time = data.get_i64();
+
+                    self.received_lsn = cmp::max(self.received_lsn, wal_end);
+
+                    return pgoutput::decode(data, &mut self.relations);
+                }
+
+                // Primary keepalive message
+                b'k' => {
+                    if data.remaining() < 17 {
+                        return Err(err_protocol!("keepalive message too short"));
+                    }
+
+                    let wal_end = PgLsn(data.get_u64());
+                    let _send_time = data.get_i64();
+                    let reply_requested = data.get_u8() == 1;
+
+                    self.received_lsn = cmp::max(self.received_lsn, wal_end);
+
+                    if reply_requested {
+                        self.send_status_update().await?;
+                    }
+                }
+
+                other => {
+                    return Err(err_protocol!(
+                        "unknown replication message type: {:?}",
+                        other as char
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Acknowledge that all changes up to and including `lsn` have been processed,
+    /// allowing the server to discard the WAL retained for them.
+    ///
+    /// For a transaction, this is usually the `end_lsn` of its
+    /// [`Commit`][PgReplicationMessage::Commit] message.
+    pub async fn ack(&mut self, lsn: PgLsn) -> Result<(), Error> {
+        self.flushed_lsn = cmp::max(self.flushed_lsn, lsn);
+        self.send_status_update().await
+    }
+
+    /// The latest WAL position received from the server.
+    pub fn received_lsn(&self) -> PgLsn {
+        self.received_lsn
+    }
+
+    /// The latest WAL position acknowledged with [`ack()`][Self::ack].
+    pub fn flushed_lsn(&self) -> PgLsn {
+        self.flushed_lsn
+    }
+
+    /// Send a standby status update reporting the current positions to the server.
+    pub async fn send_status_update(&mut self) -> Result<(), Error> {
+        let mut buf = Vec::with_capacity(34);
+
+        buf.put_u8(b'r');
+        // last WAL position received and written
+        buf.put_u64(self.received_lsn.0);
+        // last WAL position flushed; this is what advances the slot
+        buf.put_u64(self.flushed_lsn.0);
+        // last WAL position applied
+        buf.put_u64(self.flushed_lsn.0);
+        buf.put_i64(pgoutput::encode_timestamp(SystemTime::now()));
+        // don't request a reply
+        buf.put_u8(0);
+
+        self.conn.stream.send(CopyData(buf)).await
+    }
+
+    /// Convert this into a [`Stream`][futures_core::Stream] of changes.
+    ///
+    /// Each time the next change is requested, all changes yielded before it are considered
+    /// processed, and they are acknowledged to the server at the end of each transaction.
+    pub fn into_stream(mut self) -> BoxStream<'static, Result<PgReplicationMessage, Error>> {
+        Box::pin(try_stream! {
+            let mut pending_ack = None;
+
+            loop {
+                if let Some(lsn) = pending_ack.take() {
+                    self.ack(lsn).await?;
+                }
+
+                let message = self.recv().await?;
+
+                if let PgReplicationMessage::Commit { end_lsn, .. } = &message {
+                    pending_ack = Some(*end_lsn);
+                }
+
+                r#yield!(message);
+            }
+        })
+    }
+
+    /// Close the connection.
+    pub async fn close(self) -> Result<(), Error> {
+        self.conn.close().await
+    }
+}
+
+impl Debug for PgReplication {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgReplication").finish()
+    }
+}
+
+impl Debug for PgReplicationStream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgReplicationStream")
+            .field("received_lsn", &self.received_lsn)
+            .field("flushed_lsn", &self.flushed_lsn)
+            .finish()
+    }
+}
+
+#[test]
+fn test_lsn_formatting() {
+    let lsn: PgLsn = "16/B374D848".parse().unwrap();
+
+    assert_eq!(lsn, PgLsn(0x16_B374_D848));
+    assert_eq!(lsn.to_string(), "16/B374D848");
+    assert_eq!(PgLsn::default().to_string(), "0/0");
+
+    assert!("16B374D848".parse::<PgLsn>().is_err());
+    assert!("16/XYZ".parse::<PgLsn>().is_err());
+}
@@ -0,0 +1,531 @@
+//! Decoding of the `pgoutput` logical decoding plugin's output.
+//!
+//! <https://www.postgresql.org/docs/current/protocol-logicalrep-message-formats.html>
+
+use std::cmp;
+use std::str::from_utf8;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use sqlx_core::bytes::{Buf, Bytes};
+
+use crate::error::Error;
+use crate::io::BufExt;
+use crate::types::Oid;
+use crate::HashMap;
+
+use super::PgLsn;
+
+/// A change event decoded from the `pgoutput` logical replication stream.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum PgReplicationMessage {
+    /// The start of a transaction.
+    Begin {
+        /// The LSN of the commit record of the transaction.
+        final_lsn: PgLsn,
+        /// The commit timestamp of the transaction.
+        commit_time: SystemTime,
+        /// The ID of the transaction.
+        xid: u32,
+    },
+
+    /// The end of a transaction.
+    Commit {
+        /// The LSN of the commit record.
+        commit_lsn: PgLsn,
+        /// The end LSN of the transaction.
+        end_lsn: PgLsn,
+        /// The commit timestamp of the transaction.
+        commit_time: SystemTime,
+    },
+
+    /// Describes a table. Always sent before the first change to that table in a session,
+    /// and again whenever its definition changes.
+    Relation(Arc<PgRelation>),
+
+    /// A row was inserted.
+    Insert {
+        relation: Arc<PgRelation>,
+        new: PgTupleData,
+    },
+
+    /// A row was updated.
+    Update {
+        relation: Arc<PgRelation>,
+        /// The old values of the replica identity (key) columns, or of the entire row
+        /// if the table uses `REPLICA IDENTITY FULL`.
+        ///
+        /// `None` if the key did not change and the replica identity is not `FULL`.
+        old: Option<PgTupleData>,
+        new: PgTupleData,
+    },
+
+    /// A row was deleted.
+    Delete {
+        relation: Arc<PgRelation>,
+        /// The values of the replica identity (key) columns, or of the entire row
+        /// if the table uses `REPLICA IDENTITY FULL`.
+        old: PgTupleData,
+    },
+
+    /// One or more tables were truncated.
+    Truncate {
+        relations: Vec<Arc<PgRelation>>,
+        cascade: bool,
+        restart_identity: bool,
+    },
+
+    /// A message which is not (yet) decoded by SQLx, such as `Origin` or `Type`.
+    ///
+    /// The first byte is the message tag.
+    Other(Bytes),
+}
+
+/// A table described by a `Relation` message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgRelation {
+    pub oid: Oid,
+    pub namespace: String,
+    pub name: String,
+    /// The `REPLICA IDENTITY` setting of the table (`d`efault, `n`othing, `f`ull or `i`ndex).
+    pub replica_identity: u8,
+    pub columns: Vec<PgRelationColumn>,
+}
+
+/// A column of a [`PgRelation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgRelationColumn {
+    pub name: String,
+    pub type_oid: Oid,
+    pub type_modifier: i32,
+    /// `true` if this column is part of the replica identity (key).
+    pub is_key: bool,
+}
+
+/// The column values of a single row.
+///
+/// Values are in the same order as [`PgRelation::columns`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgTupleData(pub Vec<PgTupleValue>);
+
+/// A single column value of a row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PgTupleValue {
+    Null,
+    /// A TOASTed value which did not change; the actual value is not sent.
+    Unchanged,
+    /// The value in Postgres' text format.
+    Text(Bytes),
+}
+
+impl PgTupleValue {
+    /// Get the value as a string, if it is not `NULL` or unchanged.
+    pub fn as_str(&self) -> Result<Option<&str>, Error> {
+        match self {
+            PgTupleValue::Text(text) => from_utf8(text).map(Some).map_err(Error::protocol),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl PgRelation {
+    /// Look up the value of the column named `name` in a row of this table.
+    pub fn get<'t>(&self, tuple: &'t PgTupleData, name: &str) -> Option<&'t PgTupleValue> {
+        let index = self.columns.iter().position(|col| col.name == name)?;
+        tuple.0.get(index)
+    }
+}
+
+// microseconds since 2000-01-01
+pub(super) fn decode_timestamp(micros: i64) -> SystemTime {
+    const POSTGRES_EPOCH_UNIX_SECS: u64 = 946_684_800;
+
+    let epoch = SystemTime::UNIX_EPOCH + Duration::from_secs(POSTGRES_EPOCH_UNIX_SECS);
+
+    if micros >= 0 {
+        epoch + Duration::from_micros(micros as u64)
+    } else {
+        epoch - Duration::from_micros(micros.unsigned_abs())
+    }
+}
+
+pub(super) fn encode_timestamp(time: SystemTime) -> i64 {
+    let epoch = decode_timestamp(0);
+
+    match time.duration_since(epoch) {
+        Ok(duration) => duration.as_micros() as i64,
+        Err(e) => -(e.duration().as_micros() as i64),
+    }
+}
+
+fn ensure_remaining(buf: &Bytes, len: usize) -> Result<(), Error> {
+    if buf.remaining() < len {
+        return Err(err_protocol!(
+            "pgoutput: expected at least {} more bytes, got {}",
+            len,
+            buf.remaining()
+        ));
+    }
+
+    Ok(())
+}
+
+fn get_relation(
+    relations: &HashMap<Oid, Arc<PgRelation>>,
+    oid: Oid,
+) -> Result<Arc<PgRelation>, Error> {
+    relations
+        .get(&oid)
+        .cloned()
+        .ok_or_else(|| err_protocol!("pgoutput: change for unknown relation {}", oid.0))
+}
+
+fn decode_tuple(buf: &mut Bytes) -> Result<PgTupleData, Error> {
+    ensure_remaining(buf, 2)?;
+    let num_columns = buf.get_i16();
+
+    let mut values = Vec::with_capacity(cmp::max(num_columns, 0) as usize);
+
+    for _ in 0..num_columns {
+        ensure_remaining(buf, 1)?;
+
+        values.push(match buf.get_u8() {
+            b'n' => PgTupleValue::Null,
+            b'u' => PgTupleValue::Unchanged,
+            b't' => {
+                ensure_remaining(buf, 4)?;
+                let len = buf.get_i32() as usize;
+                ensure_remaining(buf, len)?;
+                PgTupleValue::Text(buf.get_bytes(len))
+            }
+            other => {
+                return Err(err_protocol!(
+                    "pgoutput: unknown tuple value kind {:?}",
+                    other as char
+                ))
+            }
+        });
+    }
+
+    Ok(PgTupleData(values))
+}
+
+/// Decode a single `pgoutput` message, updating `relations` on `Relation` messages.
+pub(super) fn decode(
+    mut buf: Bytes,
+    relations: &mut HashMap<Oid, Arc<PgRelation>>,
+) -> Result<PgReplicationMessage, Error> {
+    let raw = buf.clone();
+
+    ensure_remaining(&buf, 1)?;
+
+    Ok(match buf.get_u8() {
+        b'B' => {
+            ensure_remaining(&buf, 20)?;
+
+            PgReplicationMessage::Begin {
+                final_lsn: PgLsn(buf.get_u64()),
+                commit_time: decode_timestamp(buf.get_i64()),
+                xid: buf.get_u32(),
+            }
+        }
+
+        b'C' => {
+            ensure_remaining(&buf, 25)?;
+
+            let _flags = buf.get_u8();
+
+            PgReplicationMessage::Commit {
+                commit_lsn: PgLsn(buf.get_u64()),
+                end_lsn: PgLsn(buf.get_u64()),
+                commit_time: decode_timestamp(buf.get_i64()),
+            }
+        }
+
+        b'R' => {
+            ensure_remaining(&buf, 4)?;
+            let oid = Oid(buf.get_u32());
+            let namespace = buf.get_str_nul()?;
+            let name = buf.get_str_nul()?;
+
+            ensure_remaining(&buf, 3)?;
+            let replica_identity = buf.get_u8();
+            let num_columns = buf.get_i16();
+
+            let mut columns = Vec::with_capacity(cmp::max(num_columns, 0) as usize);
+
+            for _ in 0..num_columns {
+                ensure_remaining(&buf, 1)?;
+                let flags = buf.get_u8();
+                let name = buf.get_str_nul()?;
+
+                ensure_remaining(&buf, 8)?;
+
+                columns.push(PgRelationColumn {
+                    name,
+                    type_oid: Oid(buf.get_u32()),
+                    type_modifier: buf.get_i32(),
+                    is_key: flags & 1 != 0,
+                });
+            }
+
+            let relation = Arc::new(PgRelation {
+                oid,
+                namespace,
+                name,
+                replica_identity,
+                columns,
+            });
+
+            relations.insert(oid, Arc::clone(&relation));
+
+            PgReplicationMessage::Relation(relation)
+        }
+
+        b'I' => {
+            ensure_remaining(&buf, 5)?;
+            let relation = get_relation(relations, Oid(buf.get_u32()))?;
+
+            match buf.get_u8() {
+                b'N' => PgReplicationMessage::Insert {
+                    relation,
+                    new: decode_tuple(&mut buf)?,
+                },
+                other => {
+                    return Err(err_protocol!(
+                        "pgoutput: expected new tuple in Insert, got {:?}",
+                        other as char
+                    ))
+                }
+            }
+        }
+
+        b'U' => {
+            ensure_remaining(&buf, 5)?;
+            let relation = get_relation(relations, Oid(buf.get_u32()))?;
+
+            let old = match buf.get_u8() {
+                b'K' | b'O' => {
+                    let old = decode_tuple(&mut buf)?;
+                    ensure_remaining(&buf, 1)?;
+
+                    match buf.get_u8() {
+                        b'N' => Some(old),
+                        other => {
+                            return Err(err_protocol!(
+                                "pgoutput: expected new tuple in Update, got {:?}",
+                                other as char
+                            ))
+                        }
+                    }
+                }
+                b'N' => None,
+                other => {
+                    return Err(err_protocol!(
+                        "pgoutput: unexpected tuple kind in Update: {:?}",
+                        other as char
+                    ))
+                }
+            };
+
+            PgReplicationMessage::Update {
+                relation,
+                old,
+                new: decode_tuple(&mut buf)?,
+            }
+        }
+
+        b'D' => {
+            ensure_remaining(&buf, 5)?;
+            let relation = get_relation(relations, Oid(buf.get_u32()))?;
+
+            match buf.get_u8() {
+                b'K' | b'O' => PgReplicationMessage::Delete {
+                    relation,
+                    old: decode_tuple(&mut buf)?,
+                },
+                other => {
+                    return Err(err_protocol!(
+                        "pgoutput: unexpected tuple kind in Delete: {:?}",
+                        other as char
+                    ))
+                }
+            }
+        }
+
+        b'T' => {
+            ensure_remaining(&buf, 5)?;
+            let num_relations = buf.get_i32();
+            let options = buf.get_u8();
+
+            let relations = (0..num_relations)
+                .map(|_| {
+                    ensure_remaining(&buf, 4)?;
+                    get_relation(relations, Oid(buf.get_u32()))
+                })
+                .collect::<Result<_, Error>>()?;
+
+            PgReplicationMessage::Truncate {
+                relations,
+                cascade: options & 1 != 0,
+                restart_identity: options & 2 != 0,
+            }
+        }
+
+        _ => PgReplicationMessage::Other(raw),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RELATION: &[u8] = b"R\0\0\x40\0public\0users\0d\0\x02\x01id\0\0\0\0\x17\xff\xff\xff\xff\x00name\0\0\0\0\x19\xff\xff\xff\xff";
+
+    fn relations() -> HashMap<Oid, Arc<PgRelation>> {
+        let mut relations = HashMap::new();
+        decode(Bytes::from_static(RELATION), &mut relations).unwrap();
+        relations
+    }
+
+    #[test]
+    fn it_decodes_relation() {
+        let mut relations = HashMap::new();
+        let message = decode(Bytes::from_static(RELATION), &mut relations).unwrap();
+
+        let PgReplicationMessage::Relation(relation) = message else {
+            panic!("expected Relation, got {message:?}");
+        };
+
+        assert_eq!(relation.oid, Oid(0x4000));
+        assert_eq!(relation.namespace, "public");
+        assert_eq!(relation.name, "users");
+        assert_eq!(relation.replica_identity, b'd');
+        assert_eq!(
+            relation.columns,
+            vec![
+                PgRelationColumn {
+                    name: "id".into(),
+                    type_oid: Oid(23),
+                    type_modifier: -1,
+                    is_key: true,
+                },
+                PgRelationColumn {
+                    name: "name".into(),
+                    type_oid: Oid(25),
+                    type_modifier: -1,
+                    is_key: false,
+                },
+            ]
+        );
+        assert!(relations.contains_key(&Oid(0x4000)));
+    }
+
+    #[test]
+    fn it_decodes_insert() {
+        const INSERT: &[u8] = b"I\0\0\x40\0N\0\x02t\0\0\0\x0242t\0\0\0\x05alice";
+
+        let message = decode(Bytes::from_static(INSERT), &mut relations()).unwrap();
+
+        let PgReplicationMessage::Insert { relation, new } = message else {
+            panic!("expected Insert, got {message:?}");
+        };
+
+        assert_eq!(relation.name, "users");
+        assert_eq!(
+            relation.get(&new, "id").unwrap().as_str().unwrap(),
+            Some("42")
+        );
+        assert_eq!(
+            relation.get(&new, "name").unwrap().as_str().unwrap(),
+            Some("alice")
+        );
+    }
+
+    #[test]
+    fn it_decodes_update_with_old_key() {
+        const UPDATE: &[u8] = b"U\0\0\x40\0K\0\x02t\0\0\0\x0242nN\0\x02t\0\0\0\x0243u";
+
+        let message = decode(Bytes::from_static(UPDATE), &mut relations()).unwrap();
+
+        let PgReplicationMessage::Update { old, new, .. } = message else {
+            panic!("expected Update, got {message:?}");
+        };
+
+        assert_eq!(
+            old,
+            Some(PgTupleData(vec![
+                PgTupleValue::Text(Bytes::from_static(b"42")),
+                PgTupleValue::Null
+            ]))
+        );
+        assert_eq!(
+            new,
+            PgTupleData(vec![
+                PgTupleValue::Text(Bytes::from_static(b"43")),
+                PgTupleValue::Unchanged
+            ])
+        );
+    }
+
+    #[test]
+    fn it_decodes_delete() {
+        const DELETE: &[u8] = b"D\0\0\x40\0K\0\x02t\0\0\0\x0242n";
+
+        let message = decode(Bytes::from_static(DELETE), &mut relations()).unwrap();
+
+        assert!(matches!(message, PgReplicationMessage::Delete { .. }));
+    }
+
+    #[test]
+    fn it_decodes_begin_and_commit() {
+        const BEGIN: &[u8] = b"B\0\0\0\x01\0\0\x10\0\0\0\0\0\0\0\0\0\0\0\x02\x2a";
+        const COMMIT: &[u8] = b"C\0\0\0\0\x01\0\0\x10\0\0\0\0\x01\0\0\x10\x08\0\0\0\0\0\0\0\0";
+
+        let message = decode(Bytes::from_static(BEGIN), &mut HashMap::new()).unwrap();
+
+        let PgReplicationMessage::Begin {
+            final_lsn,
+            commit_time,
+            xid,
+        } = message
+        else {
+            panic!("expected Begin, got {message:?}");
+        };
+
+        assert_eq!(final_lsn, "1/1000".parse().unwrap());
+        assert_eq!(commit_time, decode_timestamp(0));
+        assert_eq!(xid, 554);
+
+        let message = decode(Bytes::from_static(COMMIT), &mut HashMap::new()).unwrap();
+
+        let PgReplicationMessage::Commit {
+            commit_lsn,
+            end_lsn,
+            ..
+        } = message
+        else {
+            panic!("expected Commit, got {message:?}");
+        };
+
+        assert_eq!(commit_lsn, "1/1000".parse().unwrap());
+        assert_eq!(end_lsn, "1/1008".parse().unwrap());
+    }
+
+    #[test]
+    fn it_rejects_change_for_unknown_relation() {
+        const INSERT: &[u8] = b"I\0\0\x40\x01N\0\0";
+
+        assert!(decode(Bytes::from_static(INSERT), &mut relations()).is_err());
+    }
+
+    #[test]
+    fn it_round_trips_timestamps() {
+        let time = decode_timestamp(1_234_567_890);
+        assert_eq!(encode_timestamp(time), 1_234_567_890);
+
+        let time = decode_timestamp(-1_234_567_890);
+        assert_eq!(encode_timestamp(time), -1_234_567_890);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::quote::quote_identifier;
+use crate::PgConnection;
+
+impl PgConnection {
+    /// Switch to `role` until the end of the current transaction, with `SET LOCAL ROLE`.
+    ///
+    /// The statements of the transaction are then executed with the privileges of `role`,
+    /// and are subject to its row-level security policies, e.g. to isolate the data of tenants.
+    /// As the role is set with `LOCAL`, the previous role is restored when the transaction is
+    /// committed or rolled back, so the connection can be returned to a pool safely.
+    ///
+    /// The name of the role is quoted, so it is case-sensitive (`"Tenant"` is not `tenant`), and
+    /// the current user must be a member of it.
+    ///
+    /// Returns an error if no transaction is active, as the role would otherwise only be set
+    /// until the end of the statement.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool, tenant_role: &str) -> sqlx::Result<()> {
+    /// let mut tx = pool.begin().await?;
+    ///
+    /// tx.set_role(tenant_role).await?;
+    ///
+    /// // only the rows that `tenant_role` may see
+    /// let invoices: Vec<(i64,)> = sqlx::query_as("SELECT id FROM invoices")
+    ///     .fetch_all(&mut *tx)
+    ///     .await?;
+    ///
+    /// tx.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_role(&mut self, role: &str) -> Result<(), Error> {
+        if self.transaction_depth == 0 {
+            return Err(Error::Configuration(
+                "`set_role()` must be called in a transaction".into(),
+            ));
+        }
+
+        self.execute(&*format!("SET LOCAL ROLE {}", quote_identifier(role)))
+            .await?;
+
+        Ok(())
+    }
+}
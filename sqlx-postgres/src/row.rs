@@ -12,6 +12,9 @@ pub(crate) use sqlx_core::row::Row;
 pub struct PgRow {
     pub(crate) data: DataRow,
     pub(crate) format: PgValueFormat,
+    // the format of each column, if they are not all in `format`,
+    // see `PgConnectOptions::text_result_format()`
+    pub(crate) column_formats: Option<Arc<[PgValueFormat]>>,
     pub(crate) metadata: Arc<PgStatementMetadata>,
 }
 
@@ -31,7 +34,10 @@ impl Row for PgRow {
         let value = self.data.get(index);
 
         Ok(PgValueRef {
-            format: self.format,
+            format: self
+                .column_formats
+                .as_ref()
+                .map_or(self.format, |formats| formats[index]),
             row: Some(&self.data.storage),
             type_info: column.type_info.clone(),
             value,
@@ -3,6 +3,7 @@ use crate::column::ColumnIndex;
 use crate::error::Error;
 use crate::ext::ustr::UStr;
 use crate::{PgArguments, Postgres};
+use sqlx_core::column::ColumnSelection;
 use std::borrow::Cow;
 use std::sync::Arc;
 
@@ -24,6 +25,32 @@ pub(crate) struct PgStatementMetadata {
     pub(crate) parameters: Vec<PgTypeInfo>,
 }
 
+impl PgStatementMetadata {
+    // The metadata of the rows which only have the selected columns
+    pub(crate) fn select(&self, selection: &ColumnSelection) -> Self {
+        let columns: Vec<PgColumn> = selection
+            .indices()
+            .iter()
+            .enumerate()
+            .map(|(ordinal, &index)| PgColumn {
+                ordinal,
+                ..self.columns[index].clone()
+            })
+            .collect();
+
+        let column_names = columns
+            .iter()
+            .map(|column| (column.name.clone(), column.ordinal))
+            .collect();
+
+        Self {
+            columns,
+            column_names: Arc::new(column_names),
+            parameters: self.parameters.clone(),
+        }
+    }
+}
+
 impl<'q> Statement<'q> for PgStatement<'q> {
     type Database = Postgres;
 
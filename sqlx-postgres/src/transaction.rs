@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use futures_core::future::BoxFuture;
 
 use crate::error::Error;
@@ -14,9 +16,16 @@ impl TransactionManager for PgTransactionManager {
     type Database = Postgres;
 
     fn begin(conn: &mut PgConnection) -> BoxFuture<'_, Result<(), Error>> {
+        Self::begin_with(conn, TransactionOptions::default())
+    }
+
+    fn begin_with(
+        conn: &mut PgConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
+            let query = begin_transaction_sql(conn.transaction_depth, &options)?;
             let rollback = Rollback::new(conn);
-            let query = begin_ansi_transaction_sql(rollback.conn.transaction_depth);
             rollback.conn.queue_simple_query(&query);
             rollback.conn.transaction_depth += 1;
             rollback.conn.wait_until_ready().await?;
@@ -59,6 +68,38 @@ impl TransactionManager for PgTransactionManager {
             conn.transaction_depth -= 1;
         }
     }
+
+    fn get_transaction_depth(conn: &PgConnection) -> usize {
+        conn.transaction_depth
+    }
+}
+
+fn begin_transaction_sql(
+    depth: usize,
+    options: &TransactionOptions,
+) -> Result<Cow<'static, str>, Error> {
+    check_savepoint_options(depth, options)?;
+
+    if depth > 0 || options.is_default() {
+        return Ok(begin_ansi_transaction_sql(depth));
+    }
+
+    let mut sql = String::from("BEGIN");
+
+    if let Some(isolation_level) = options.get_isolation_level() {
+        sql.push_str(" ISOLATION LEVEL ");
+        sql.push_str(isolation_level.as_sql());
+    }
+
+    if options.is_read_only() {
+        sql.push_str(" READ ONLY");
+    }
+
+    if options.is_deferrable() {
+        sql.push_str(" DEFERRABLE");
+    }
+
+    Ok(Cow::Owned(sql))
 }
 
 struct Rollback<'c> {
@@ -85,3 +126,33 @@ impl<'c> Rollback<'c> {
         self.defuse = true;
     }
 }
+
+#[test]
+fn test_begin_transaction_sql() {
+    let options = TransactionOptions::new();
+    assert_eq!(begin_transaction_sql(0, &options).unwrap(), "BEGIN");
+    assert_eq!(
+        begin_transaction_sql(1, &options).unwrap(),
+        "SAVEPOINT _sqlx_savepoint_1"
+    );
+
+    let options = options.isolation_level(IsolationLevel::RepeatableRead);
+    assert_eq!(
+        begin_transaction_sql(0, &options).unwrap(),
+        "BEGIN ISOLATION LEVEL REPEATABLE READ"
+    );
+
+    let options = options
+        .isolation_level(IsolationLevel::Serializable)
+        .read_only(true)
+        .deferrable(true);
+    assert_eq!(
+        begin_transaction_sql(0, &options).unwrap(),
+        "BEGIN ISOLATION LEVEL SERIALIZABLE READ ONLY DEFERRABLE"
+    );
+
+    assert!(matches!(
+        begin_transaction_sql(1, &options),
+        Err(Error::Configuration(_))
+    ));
+}
@@ -118,6 +118,22 @@ pub enum PgType {
     JsonpathArray,
     Money,
     MoneyArray,
+    Int2Vector,
+    Int2VectorArray,
+    OidVector,
+    OidVectorArray,
+    Xid,
+    XidArray,
+    Xid8,
+    Xid8Array,
+    TxidSnapshot,
+    TxidSnapshotArray,
+    PgSnapshot,
+    PgSnapshotArray,
+    Tid,
+    TidArray,
+    PgLsn,
+    PgLsnArray,
 
     // https://www.postgresql.org/docs/9.3/datatype-pseudo.html
     Void,
@@ -263,9 +279,11 @@ impl PgType {
             19 => PgType::Name,
             20 => PgType::Int8,
             21 => PgType::Int2,
+            22 => PgType::Int2Vector,
             23 => PgType::Int4,
             25 => PgType::Text,
             26 => PgType::Oid,
+            30 => PgType::OidVector,
             114 => PgType::Json,
             199 => PgType::JsonArray,
             600 => PgType::Point,
@@ -293,8 +311,22 @@ impl PgType {
             1002 => PgType::CharArray,
             1003 => PgType::NameArray,
             1005 => PgType::Int2Array,
+            1006 => PgType::Int2VectorArray,
             1007 => PgType::Int4Array,
             1009 => PgType::TextArray,
+            1013 => PgType::OidVectorArray,
+            28 => PgType::Xid,
+            1011 => PgType::XidArray,
+            5069 => PgType::Xid8,
+            271 => PgType::Xid8Array,
+            2970 => PgType::TxidSnapshot,
+            2949 => PgType::TxidSnapshotArray,
+            5038 => PgType::PgSnapshot,
+            5039 => PgType::PgSnapshotArray,
+            27 => PgType::Tid,
+            1010 => PgType::TidArray,
+            3220 => PgType::PgLsn,
+            3221 => PgType::PgLsnArray,
             1014 => PgType::BpcharArray,
             1015 => PgType::VarcharArray,
             1016 => PgType::Int8Array,
@@ -394,6 +426,22 @@ impl PgType {
             PgType::Macaddr8Array => Oid(775),
             PgType::Money => Oid(790),
             PgType::MoneyArray => Oid(791),
+            PgType::Int2Vector => Oid(22),
+            PgType::Int2VectorArray => Oid(1006),
+            PgType::OidVector => Oid(30),
+            PgType::OidVectorArray => Oid(1013),
+            PgType::Xid => Oid(28),
+            PgType::XidArray => Oid(1011),
+            PgType::Xid8 => Oid(5069),
+            PgType::Xid8Array => Oid(271),
+            PgType::TxidSnapshot => Oid(2970),
+            PgType::TxidSnapshotArray => Oid(2949),
+            PgType::PgSnapshot => Oid(5038),
+            PgType::PgSnapshotArray => Oid(5039),
+            PgType::Tid => Oid(27),
+            PgType::TidArray => Oid(1010),
+            PgType::PgLsn => Oid(3220),
+            PgType::PgLsnArray => Oid(3221),
             PgType::Macaddr => Oid(829),
             PgType::Inet => Oid(869),
             PgType::BoolArray => Oid(1000),
@@ -560,6 +608,22 @@ impl PgType {
             PgType::JsonpathArray => "JSONPATH[]",
             PgType::Money => "MONEY",
             PgType::MoneyArray => "MONEY[]",
+            PgType::Int2Vector => "INT2VECTOR",
+            PgType::Int2VectorArray => "INT2VECTOR[]",
+            PgType::OidVector => "OIDVECTOR",
+            PgType::OidVectorArray => "OIDVECTOR[]",
+            PgType::Xid => "XID",
+            PgType::XidArray => "XID[]",
+            PgType::Xid8 => "XID8",
+            PgType::Xid8Array => "XID8[]",
+            PgType::TxidSnapshot => "TXID_SNAPSHOT",
+            PgType::TxidSnapshotArray => "TXID_SNAPSHOT[]",
+            PgType::PgSnapshot => "PG_SNAPSHOT",
+            PgType::PgSnapshotArray => "PG_SNAPSHOT[]",
+            PgType::Tid => "TID",
+            PgType::TidArray => "TID[]",
+            PgType::PgLsn => "PG_LSN",
+            PgType::PgLsnArray => "PG_LSN[]",
             PgType::Void => "VOID",
             PgType::Custom(ty) => &*ty.name,
             PgType::DeclareWithOid(_) => "?",
@@ -660,6 +724,22 @@ impl PgType {
             PgType::JsonpathArray => "_jsonpath",
             PgType::Money => "money",
             PgType::MoneyArray => "_money",
+            PgType::Int2Vector => "int2vector",
+            PgType::Int2VectorArray => "_int2vector",
+            PgType::OidVector => "oidvector",
+            PgType::OidVectorArray => "_oidvector",
+            PgType::Xid => "xid",
+            PgType::XidArray => "_xid",
+            PgType::Xid8 => "xid8",
+            PgType::Xid8Array => "_xid8",
+            PgType::TxidSnapshot => "txid_snapshot",
+            PgType::TxidSnapshotArray => "_txid_snapshot",
+            PgType::PgSnapshot => "pg_snapshot",
+            PgType::PgSnapshotArray => "_pg_snapshot",
+            PgType::Tid => "tid",
+            PgType::TidArray => "_tid",
+            PgType::PgLsn => "pg_lsn",
+            PgType::PgLsnArray => "_pg_lsn",
             PgType::Void => "void",
             PgType::Custom(ty) => &*ty.name,
             PgType::DeclareWithOid(_) => "?",
@@ -760,6 +840,22 @@ impl PgType {
             PgType::JsonpathArray => &PgTypeKind::Array(PgTypeInfo(PgType::Jsonpath)),
             PgType::Money => &PgTypeKind::Simple,
             PgType::MoneyArray => &PgTypeKind::Array(PgTypeInfo(PgType::Money)),
+            PgType::Int2Vector => &PgTypeKind::Array(PgTypeInfo(PgType::Int2)),
+            PgType::Int2VectorArray => &PgTypeKind::Array(PgTypeInfo(PgType::Int2Vector)),
+            PgType::OidVector => &PgTypeKind::Array(PgTypeInfo(PgType::Oid)),
+            PgType::OidVectorArray => &PgTypeKind::Array(PgTypeInfo(PgType::OidVector)),
+            PgType::Xid => &PgTypeKind::Simple,
+            PgType::XidArray => &PgTypeKind::Array(PgTypeInfo(PgType::Xid)),
+            PgType::Xid8 => &PgTypeKind::Simple,
+            PgType::Xid8Array => &PgTypeKind::Array(PgTypeInfo(PgType::Xid8)),
+            PgType::TxidSnapshot => &PgTypeKind::Simple,
+            PgType::TxidSnapshotArray => &PgTypeKind::Array(PgTypeInfo(PgType::TxidSnapshot)),
+            PgType::PgSnapshot => &PgTypeKind::Simple,
+            PgType::PgSnapshotArray => &PgTypeKind::Array(PgTypeInfo(PgType::PgSnapshot)),
+            PgType::Tid => &PgTypeKind::Simple,
+            PgType::TidArray => &PgTypeKind::Array(PgTypeInfo(PgType::Tid)),
+            PgType::PgLsn => &PgTypeKind::Simple,
+            PgType::PgLsnArray => &PgTypeKind::Array(PgTypeInfo(PgType::PgLsn)),
 
             PgType::Void => &PgTypeKind::Pseudo,
 
@@ -825,6 +921,22 @@ impl PgType {
             PgType::Macaddr8Array => Some(Cow::Owned(PgTypeInfo(PgType::Macaddr8))),
             PgType::Money => None,
             PgType::MoneyArray => Some(Cow::Owned(PgTypeInfo(PgType::Money))),
+            PgType::Int2Vector => Some(Cow::Owned(PgTypeInfo(PgType::Int2))),
+            PgType::Int2VectorArray => Some(Cow::Owned(PgTypeInfo(PgType::Int2Vector))),
+            PgType::OidVector => Some(Cow::Owned(PgTypeInfo(PgType::Oid))),
+            PgType::OidVectorArray => Some(Cow::Owned(PgTypeInfo(PgType::OidVector))),
+            PgType::Xid => None,
+            PgType::XidArray => Some(Cow::Owned(PgTypeInfo(PgType::Xid))),
+            PgType::Xid8 => None,
+            PgType::Xid8Array => Some(Cow::Owned(PgTypeInfo(PgType::Xid8))),
+            PgType::TxidSnapshot => None,
+            PgType::TxidSnapshotArray => Some(Cow::Owned(PgTypeInfo(PgType::TxidSnapshot))),
+            PgType::PgSnapshot => None,
+            PgType::PgSnapshotArray => Some(Cow::Owned(PgTypeInfo(PgType::PgSnapshot))),
+            PgType::Tid => None,
+            PgType::TidArray => Some(Cow::Owned(PgTypeInfo(PgType::Tid))),
+            PgType::PgLsn => None,
+            PgType::PgLsnArray => Some(Cow::Owned(PgTypeInfo(PgType::PgLsn))),
             PgType::Macaddr => None,
             PgType::MacaddrArray => Some(Cow::Owned(PgTypeInfo(PgType::Macaddr))),
             PgType::Inet => None,
@@ -1028,6 +1140,43 @@ impl PgTypeInfo {
     pub(crate) const MONEY: Self = Self(PgType::Money);
     pub(crate) const MONEY_ARRAY: Self = Self(PgType::MoneyArray);
 
+    //
+    // legacy vector types used in the system catalogs
+    //
+
+    pub(crate) const INT2_VECTOR: Self = Self(PgType::Int2Vector);
+    pub(crate) const OID_VECTOR: Self = Self(PgType::OidVector);
+
+    //
+    // transaction ids and snapshots
+    // https://www.postgresql.org/docs/current/functions-info.html#FUNCTIONS-PG-SNAPSHOT
+    //
+
+    pub(crate) const XID: Self = Self(PgType::Xid);
+    pub(crate) const XID_ARRAY: Self = Self(PgType::XidArray);
+
+    pub(crate) const XID8: Self = Self(PgType::Xid8);
+    pub(crate) const XID8_ARRAY: Self = Self(PgType::Xid8Array);
+
+    // `txid_snapshot` is the deprecated equivalent of `pg_snapshot`
+    pub(crate) const TXID_SNAPSHOT: Self = Self(PgType::TxidSnapshot);
+    pub(crate) const TXID_SNAPSHOT_ARRAY: Self = Self(PgType::TxidSnapshotArray);
+
+    pub(crate) const PG_SNAPSHOT: Self = Self(PgType::PgSnapshot);
+    pub(crate) const PG_SNAPSHOT_ARRAY: Self = Self(PgType::PgSnapshotArray);
+
+    //
+    // tuple identifiers
+    // https://www.postgresql.org/docs/current/datatype-oid.html
+    //
+
+    pub(crate) const TID: Self = Self(PgType::Tid);
+    pub(crate) const TID_ARRAY: Self = Self(PgType::TidArray);
+
+    // position in the write-ahead log
+    pub(crate) const PG_LSN: Self = Self(PgType::PgLsn);
+    pub(crate) const PG_LSN_ARRAY: Self = Self(PgType::PgLsnArray);
+
     //
     // date/time types
     // https://www.postgresql.org/docs/current/datatype-datetime.html
@@ -1,13 +1,10 @@
-use sqlx_core::bytes::Buf;
 use sqlx_core::types::Text;
-use std::borrow::Cow;
 
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::type_info::PgType;
-use crate::types::Oid;
-use crate::types::Type;
+use crate::types::{PgArrayIter, Type};
 use crate::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 
 /// Provides information necessary to encode and decode Postgres arrays as compatible Rust types.
@@ -104,6 +101,19 @@ where
     }
 }
 
+impl<T> Type<Postgres> for Box<[T]>
+where
+    T: PgHasArrayType,
+{
+    fn type_info() -> PgTypeInfo {
+        T::array_type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        T::array_compatible(ty)
+    }
+}
+
 impl<T, const N: usize> Type<Postgres> for [T; N]
 where
     T: PgHasArrayType,
@@ -128,6 +138,17 @@ where
     }
 }
 
+impl<'q, T> Encode<'q, Postgres> for Box<[T]>
+where
+    for<'a> &'a [T]: Encode<'q, Postgres>,
+    T: Encode<'q, Postgres>,
+{
+    #[inline]
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        (&**self).encode_by_ref(buf)
+    }
+}
+
 impl<'q, T, const N: usize> Encode<'q, Postgres> for [T; N]
 where
     for<'a> &'a [T]: Encode<'q, Postgres>,
@@ -185,6 +206,16 @@ where
     }
 }
 
+impl<'r, T> Decode<'r, Postgres> for Box<[T]>
+where
+    T: for<'a> Decode<'a, Postgres> + Type<Postgres>,
+{
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        // drops the spare capacity of the `Vec`, if any
+        Ok(Vec::<T>::decode(value)?.into_boxed_slice())
+    }
+}
+
 impl<'r, T> Decode<'r, Postgres> for Vec<T>
 where
     T: for<'a> Decode<'a, Postgres> + Type<Postgres>,
@@ -192,67 +223,33 @@ where
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
         let format = value.format();
 
-        match format {
-            PgValueFormat::Binary => {
-                // https://github.com/postgres/postgres/blob/a995b371ae29de2d38c4b7881cf414b1560e9746/src/backend/utils/adt/arrayfuncs.c#L1548
-
-                let mut buf = value.as_bytes()?;
-
-                // number of dimensions in the array
-                let ndim = buf.get_i32();
-
-                if ndim == 0 {
-                    // zero dimensions is an empty array
-                    return Ok(Vec::new());
-                }
-
-                if ndim != 1 {
-                    return Err(format!("encountered an array of {ndim} dimensions; only one-dimensional arrays are supported").into());
-                }
+        // these legacy types are array-like, but have their own text format and are zero-based
+        let is_vector =
+            [PgTypeInfo::INT2_VECTOR, PgTypeInfo::OID_VECTOR].contains(&value.type_info);
 
-                // appears to have been used in the past to communicate potential NULLS
-                // but reading source code back through our supported postgres versions (9.5+)
-                // this is never used for anything
-                let _flags = buf.get_i32();
-
-                // the OID of the element
-                let element_type_oid = Oid(buf.get_u32());
-                let element_type_info: PgTypeInfo = PgTypeInfo::try_from_oid(element_type_oid)
-                    .or_else(|| value.type_info.try_array_element().map(Cow::into_owned))
-                    .ok_or_else(|| {
-                        BoxDynError::from(format!(
-                            "failed to resolve array element type for oid {}",
-                            element_type_oid.0
-                        ))
-                    })?;
-
-                // length of the array axis
-                let len = buf.get_i32();
-
-                // the lower bound, we only support arrays starting from "1"
-                let lower = buf.get_i32();
-
-                if lower != 1 {
-                    return Err(format!("encountered an array with a lower bound of {lower} in the first dimension; only arrays starting at one are supported").into());
-                }
-
-                let mut elements = Vec::with_capacity(len as usize);
-
-                for _ in 0..len {
-                    elements.push(T::decode(PgValueRef::get(
-                        &mut buf,
-                        format,
-                        element_type_info.clone(),
-                    ))?)
-                }
-
-                Ok(elements)
-            }
+        match format {
+            PgValueFormat::Binary => PgArrayIter::<T>::decode(value)?.collect(),
 
             PgValueFormat::Text => {
                 // no type is provided from the database for the element
                 let element_type_info = T::type_info();
 
+                if is_vector {
+                    // elements are separated by spaces, without braces
+                    return value
+                        .as_str()?
+                        .split_whitespace()
+                        .map(|element| {
+                            T::decode(PgValueRef {
+                                value: Some(element.as_bytes()),
+                                row: None,
+                                type_info: element_type_info.clone(),
+                                format,
+                            })
+                        })
+                        .collect();
+                }
+
                 let s = value.as_str()?;
 
                 // https://github.com/postgres/postgres/blob/a995b371ae29de2d38c4b7881cf414b1560e9746/src/backend/utils/adt/arrayfuncs.c#L718
@@ -0,0 +1,178 @@
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Formatter};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+use sqlx_core::bytes::{Buf, Bytes};
+
+use crate::decode::Decode;
+use crate::error::BoxDynError;
+use crate::types::{Oid, Type};
+use crate::{PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// An iterator over the elements of a Postgres array, which are decoded as they are iterated
+/// over.
+///
+/// This borrows the array from the row instead of collecting its elements in a `Vec<T>`, for
+/// arrays which are large but only need to be iterated over once, e.g. to aggregate them.
+/// Each element is decoded by [`next()`](Iterator::next), which returns its decoding error
+/// if any.
+///
+/// Arrays are only sent by Postgres in a format which can be iterated over in prepared queries:
+/// decoding this from the result of a simple query (e.g. executed with a SQL string on its own)
+/// returns an error. Use a `Vec<T>` instead in that case.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::postgres::types::PgArrayIter;
+/// use sqlx::Row;
+///
+/// let row = sqlx::query("SELECT samples FROM measurements WHERE id = $1")
+///     .bind(1_i64)
+///     .fetch_one(conn)
+///     .await?;
+///
+/// let samples: PgArrayIter<'_, i32> = row.try_get("samples")?;
+/// let mut sum = 0_i64;
+///
+/// for sample in samples {
+///     sum += i64::from(sample.map_err(sqlx::Error::Decode)?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct PgArrayIter<'r, T> {
+    buf: &'r [u8],
+    row: Option<&'r Bytes>,
+    element_type_info: PgTypeInfo,
+    remaining: usize,
+    element_type: PhantomData<fn() -> T>,
+}
+
+impl<T> Type<Postgres> for PgArrayIter<'_, T>
+where
+    T: PgHasArrayType,
+{
+    fn type_info() -> PgTypeInfo {
+        T::array_type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        T::array_compatible(ty)
+    }
+}
+
+impl<'r, T> Decode<'r, Postgres> for PgArrayIter<'r, T> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        if value.format() == PgValueFormat::Text {
+            return Err("unsupported decode to `PgArrayIter` of an array in a simple query; use a prepared query or decode to `Vec<T>`".into());
+        }
+
+        // https://github.com/postgres/postgres/blob/a995b371ae29de2d38c4b7881cf414b1560e9746/src/backend/utils/adt/arrayfuncs.c#L1548
+
+        // these legacy types are array-like, but are zero-based
+        let is_vector =
+            [PgTypeInfo::INT2_VECTOR, PgTypeInfo::OID_VECTOR].contains(&value.type_info);
+
+        let mut buf = value.as_bytes()?;
+
+        // number of dimensions in the array
+        let ndim = buf.get_i32();
+
+        if ndim == 0 {
+            // zero dimensions is an empty array
+            return Ok(PgArrayIter {
+                buf,
+                row: value.row,
+                element_type_info: value.type_info,
+                remaining: 0,
+                element_type: PhantomData,
+            });
+        }
+
+        if ndim != 1 {
+            return Err(format!("encountered an array of {ndim} dimensions; only one-dimensional arrays are supported").into());
+        }
+
+        // appears to have been used in the past to communicate potential NULLS
+        // but reading source code back through our supported postgres versions (9.5+)
+        // this is never used for anything
+        let _flags = buf.get_i32();
+
+        // the OID of the element
+        let element_type_oid = Oid(buf.get_u32());
+        let element_type_info: PgTypeInfo = PgTypeInfo::try_from_oid(element_type_oid)
+            .or_else(|| value.type_info.try_array_element().map(Cow::into_owned))
+            .ok_or_else(|| {
+                BoxDynError::from(format!(
+                    "failed to resolve array element type for oid {}",
+                    element_type_oid.0
+                ))
+            })?;
+
+        // length of the array axis
+        let len = buf.get_i32();
+        let len = usize::try_from(len)
+            .map_err(|_| format!("encountered an array with a negative length of {len}"))?;
+
+        // the lower bound, we only support arrays starting from "1"
+        let lower = buf.get_i32();
+
+        if lower != 1 && !(is_vector && lower == 0) {
+            return Err(format!("encountered an array with a lower bound of {lower} in the first dimension; only arrays starting at one are supported").into());
+        }
+
+        Ok(PgArrayIter {
+            buf,
+            row: value.row,
+            element_type_info,
+            remaining: len,
+            element_type: PhantomData,
+        })
+    }
+}
+
+impl<'r, T> Iterator for PgArrayIter<'r, T>
+where
+    T: Decode<'r, Postgres>,
+{
+    type Item = Result<T, BoxDynError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+
+        let mut element = PgValueRef::get(
+            &mut self.buf,
+            PgValueFormat::Binary,
+            self.element_type_info.clone(),
+        );
+
+        // the elements are slices of the same buffer as the array
+        element.row = self.row;
+
+        Some(T::decode(element))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'r, T> ExactSizeIterator for PgArrayIter<'r, T> where T: Decode<'r, Postgres> {}
+
+impl<'r, T> FusedIterator for PgArrayIter<'r, T> where T: Decode<'r, Postgres> {}
+
+impl<T> Debug for PgArrayIter<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgArrayIter")
+            .field("element_type_info", &self.element_type_info)
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
@@ -1,3 +1,5 @@
+use sqlx_core::bytes::Bytes;
+
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
@@ -34,6 +36,18 @@ impl PgHasArrayType for Vec<u8> {
     }
 }
 
+impl PgHasArrayType for Bytes {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::BYTEA_ARRAY
+    }
+}
+
+impl Type<Postgres> for Bytes {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::BYTEA
+    }
+}
+
 impl<const N: usize> PgHasArrayType for [u8; N] {
     fn array_type_info() -> PgTypeInfo {
         <[&[u8]] as Type<Postgres>>::type_info()
@@ -60,6 +74,12 @@ impl Encode<'_, Postgres> for Vec<u8> {
     }
 }
 
+impl Encode<'_, Postgres> for Bytes {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        <&[u8] as Encode<Postgres>>::encode(self, buf)
+    }
+}
+
 impl<const N: usize> Encode<'_, Postgres> for [u8; N] {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
         <&[u8] as Encode<Postgres>>::encode(self.as_slice(), buf)
@@ -104,14 +124,41 @@ impl Decode<'_, Postgres> for Vec<u8> {
     }
 }
 
+/// Decoding as [`Bytes`] doesn't copy the value in the binary format (i.e. in prepared queries):
+/// the returned `Bytes` is a reference-counted slice of the buffer of the row, which is kept alive
+/// as long as the value is in use.
+impl Decode<'_, Postgres> for Bytes {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(match value.format() {
+            PgValueFormat::Binary => match value.row {
+                Some(row) => row.slice_ref(value.as_bytes()?),
+                None => Bytes::copy_from_slice(value.as_bytes()?),
+            },
+            PgValueFormat::Text => hex::decode(text_hex_decode_input(value)?)?.into(),
+        })
+    }
+}
+
 impl<const N: usize> Decode<'_, Postgres> for [u8; N] {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
         let mut bytes = [0u8; N];
         match value.format() {
             PgValueFormat::Binary => {
-                bytes = value.as_bytes()?.try_into()?;
+                let value = value.as_bytes()?;
+
+                bytes = value
+                    .try_into()
+                    .map_err(|_| format!("expected {N} bytes, got {}", value.len()))?;
+            }
+            PgValueFormat::Text => {
+                let hex = text_hex_decode_input(value)?;
+
+                if hex.len() != N * 2 {
+                    return Err(format!("expected {N} bytes, got {}", hex.len() / 2).into());
+                }
+
+                hex::decode_to_slice(hex, &mut bytes)?
             }
-            PgValueFormat::Text => hex::decode_to_slice(text_hex_decode_input(value)?, &mut bytes)?,
         };
         Ok(bytes)
     }
@@ -17,6 +17,24 @@ use std::str::FromStr;
 /// However, it may be needed, for example, when binding a `citext[]` array,
 /// as Postgres will generally not accept a `text[]` array (mapped from `Vec<String>`) in its place.
 ///
+/// ### Note: Comparisons with Bound `String`s are Case-Sensitive
+/// A bound `String` or `&str` is sent as `text`, and comparing a `citext` with a `text` uses
+/// the operators of `text`, so `WHERE email = $1` is case-sensitive regardless of the type of
+/// `email`. Bind a `PgCiText` instead, or cast the parameter with `$1::citext`:
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::postgres::types::PgCiText;
+///
+/// // matches `Alice@Example.com`
+/// let email: String = sqlx::query_scalar("SELECT email FROM users WHERE email = $1")
+///     .bind(PgCiText("alice@example.com".into()))
+///     .fetch_one(conn)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
 /// See [the Postgres manual, Appendix F, Section 10][PG.F.10] for details on using `citext`.
 ///
 /// [PG.F.10]: https://www.postgresql.org/docs/current/citext.html
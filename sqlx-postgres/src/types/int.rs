@@ -1,3 +1,5 @@
+use std::any::type_name;
+
 use byteorder::{BigEndian, ByteOrder};
 
 use crate::decode::Decode;
@@ -33,16 +35,54 @@ impl Decode<'_, Postgres> for i8 {
     }
 }
 
+/// Integer types can be decoded from each other, checking that the value fits.
+const INT_TYPES: [PgTypeInfo; 3] = [PgTypeInfo::INT2, PgTypeInfo::INT4, PgTypeInfo::INT8];
+
+const INT_ARRAY_TYPES: [PgTypeInfo; 3] = [
+    PgTypeInfo::INT2_ARRAY,
+    PgTypeInfo::INT4_ARRAY,
+    PgTypeInfo::INT8_ARRAY,
+];
+
+/// Decode a `SMALLINT`, `INTEGER` or `BIGINT`, then convert it to the target type.
+fn int_decode<T: TryFrom<i64>>(value: PgValueRef<'_>) -> Result<T, BoxDynError> {
+    let int = match value.format() {
+        PgValueFormat::Binary => {
+            let buf = value.as_bytes()?;
+
+            match buf.len() {
+                2 => BigEndian::read_i16(buf).into(),
+                4 => BigEndian::read_i32(buf).into(),
+                8 => BigEndian::read_i64(buf),
+                len => return Err(format!("invalid length {len} for an integer").into()),
+            }
+        }
+        PgValueFormat::Text => value.as_str()?.parse()?,
+    };
+
+    T::try_from(int)
+        .map_err(|_| format!("value {int} is out of range for `{}`", type_name::<T>()).into())
+}
+
 impl Type<Postgres> for i16 {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::INT2
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        INT_TYPES.contains(ty)
+    }
 }
 
 impl PgHasArrayType for i16 {
     fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::INT2_ARRAY
     }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        // `int2vector` (e.g. `pg_index.indkey`) can be decoded like an array
+        INT_ARRAY_TYPES.contains(ty) || *ty == PgTypeInfo::INT2_VECTOR
+    }
 }
 
 impl Encode<'_, Postgres> for i16 {
@@ -55,10 +95,7 @@ impl Encode<'_, Postgres> for i16 {
 
 impl Decode<'_, Postgres> for i16 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
-        Ok(match value.format() {
-            PgValueFormat::Binary => BigEndian::read_i16(value.as_bytes()?),
-            PgValueFormat::Text => value.as_str()?.parse()?,
-        })
+        int_decode(value)
     }
 }
 
@@ -66,12 +103,20 @@ impl Type<Postgres> for i32 {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::INT4
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        INT_TYPES.contains(ty)
+    }
 }
 
 impl PgHasArrayType for i32 {
     fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::INT4_ARRAY
     }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        INT_ARRAY_TYPES.contains(ty)
+    }
 }
 
 impl Encode<'_, Postgres> for i32 {
@@ -84,10 +129,7 @@ impl Encode<'_, Postgres> for i32 {
 
 impl Decode<'_, Postgres> for i32 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
-        Ok(match value.format() {
-            PgValueFormat::Binary => BigEndian::read_i32(value.as_bytes()?),
-            PgValueFormat::Text => value.as_str()?.parse()?,
-        })
+        int_decode(value)
     }
 }
 
@@ -95,12 +137,20 @@ impl Type<Postgres> for i64 {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::INT8
     }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        INT_TYPES.contains(ty)
+    }
 }
 
 impl PgHasArrayType for i64 {
     fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::INT8_ARRAY
     }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        INT_ARRAY_TYPES.contains(ty)
+    }
 }
 
 impl Encode<'_, Postgres> for i64 {
@@ -113,9 +163,6 @@ impl Encode<'_, Postgres> for i64 {
 
 impl Decode<'_, Postgres> for i64 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
-        Ok(match value.format() {
-            PgValueFormat::Binary => BigEndian::read_i64(value.as_bytes()?),
-            PgValueFormat::Text => value.as_str()?.parse()?,
-        })
+        int_decode(value)
     }
 }
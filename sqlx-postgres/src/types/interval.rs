@@ -45,14 +45,104 @@ impl<'de> Decode<'de, Postgres> for PgInterval {
                 })
             }
 
-            // TODO: Implement parsing of text mode
-            PgValueFormat::Text => {
-                Err("not implemented: decode `INTERVAL` in text mode (unprepared queries)".into())
-            }
+            // Only the unambiguous ISO 8601 style is supported in text mode
+            PgValueFormat::Text => parse_iso_8601(value.as_str()?),
         }
     }
 }
 
+// https://www.postgresql.org/docs/current/datatype-datetime.html#DATATYPE-INTERVAL-OUTPUT
+// e.g. `P1Y2M3DT4H5M6.789S` or `P-1Y-2M3DT-4H-5M-6.5S`
+fn parse_iso_8601(s: &str) -> Result<PgInterval, BoxDynError> {
+    let Some(rest) = s.strip_prefix('P') else {
+        return Err(format!(
+            "unsupported INTERVAL text format {s:?}; \
+             set `IntervalStyle` to `iso_8601` to decode intervals in text mode"
+        )
+        .into());
+    };
+
+    let (date, time) = rest.split_once('T').unwrap_or((rest, ""));
+
+    let mut interval = PgInterval {
+        months: 0,
+        days: 0,
+        microseconds: 0,
+    };
+
+    for (value, unit) in iso_8601_components(date)? {
+        let value: i32 = value.parse()?;
+
+        let (field, factor) = match unit {
+            'Y' => (&mut interval.months, 12),
+            'M' => (&mut interval.months, 1),
+            'W' => (&mut interval.days, 7),
+            'D' => (&mut interval.days, 1),
+            _ => return Err(format!("unexpected unit {unit:?} in INTERVAL {s:?}").into()),
+        };
+
+        *field = value
+            .checked_mul(factor)
+            .and_then(|value| field.checked_add(value))
+            .ok_or("INTERVAL out of range")?;
+    }
+
+    for (value, unit) in iso_8601_components(time)? {
+        let microseconds = match unit {
+            'H' => value.parse::<i64>()?.checked_mul(3_600_000_000),
+            'M' => value.parse::<i64>()?.checked_mul(60_000_000),
+            'S' => Some(parse_seconds(value)?),
+            _ => return Err(format!("unexpected unit {unit:?} in INTERVAL {s:?}").into()),
+        };
+
+        interval.microseconds = microseconds
+            .and_then(|value| interval.microseconds.checked_add(value))
+            .ok_or("INTERVAL out of range")?;
+    }
+
+    Ok(interval)
+}
+
+// Splits e.g. `1Y-2M` into `[("1", 'Y'), ("-2", 'M')]`
+fn iso_8601_components(mut s: &str) -> Result<Vec<(&str, char)>, BoxDynError> {
+    let mut components = Vec::new();
+
+    while !s.is_empty() {
+        let end = s
+            .find(|c: char| c.is_ascii_alphabetic())
+            .ok_or_else(|| format!("missing unit after {s:?} in INTERVAL"))?;
+
+        components.push((&s[..end], s[end..].chars().next().unwrap()));
+        s = &s[end + 1..];
+    }
+
+    Ok(components)
+}
+
+// Parses e.g. `-6.5` as `-6_500_000` microseconds
+fn parse_seconds(s: &str) -> Result<i64, BoxDynError> {
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+
+    if fraction.len() > 6 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("invalid seconds {s:?} in INTERVAL").into());
+    }
+
+    let negative = whole.starts_with('-');
+    let whole: i64 = whole.parse()?;
+    let fraction: i64 = format!("{fraction:0<6}").parse()?;
+
+    whole
+        .checked_mul(1_000_000)
+        .and_then(|whole| {
+            if negative {
+                whole.checked_sub(fraction)
+            } else {
+                whole.checked_add(fraction)
+            }
+        })
+        .ok_or_else(|| "INTERVAL out of range".into())
+}
+
 impl Encode<'_, Postgres> for PgInterval {
     fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
         buf.extend(&self.microseconds.to_be_bytes());
@@ -223,6 +313,32 @@ impl TryFrom<time::Duration> for PgInterval {
     }
 }
 
+#[test]
+fn test_parse_iso_8601_interval() {
+    let interval = |months, days, microseconds| PgInterval {
+        months,
+        days,
+        microseconds,
+    };
+
+    assert_eq!(parse_iso_8601("PT0S").unwrap(), interval(0, 0, 0));
+    assert_eq!(
+        parse_iso_8601("P1Y2M3DT4H5M6.789S").unwrap(),
+        interval(14, 3, 14_706_789_000)
+    );
+    assert_eq!(
+        parse_iso_8601("P-1Y-2M3DT-4H-5M-6.5S").unwrap(),
+        interval(-14, 3, -14_706_500_000)
+    );
+    assert_eq!(parse_iso_8601("P2W").unwrap(), interval(0, 14, 0));
+    assert_eq!(parse_iso_8601("PT-0.000001S").unwrap(), interval(0, 0, -1));
+
+    // `IntervalStyle = postgres`
+    assert!(parse_iso_8601("1 year 2 mons").is_err());
+    assert!(parse_iso_8601("P1").is_err());
+    assert!(parse_iso_8601("PT1.1234567S").is_err());
+}
+
 #[test]
 fn test_encode_interval() {
     let mut buf = PgArgumentBuffer::default();
@@ -0,0 +1,47 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::jiff::PG_EPOCH;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use jiff::civil::Date;
+use jiff::SignedDuration;
+use std::mem;
+
+impl Type<Postgres> for Date {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::DATE
+    }
+}
+
+impl PgHasArrayType for Date {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::DATE_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for Date {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        // DATE is encoded as the days since epoch
+        let days = (self.duration_since(PG_EPOCH).as_secs() / 86_400) as i32;
+        Encode::<Postgres>::encode(days, buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        mem::size_of::<i32>()
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Date {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(match value.format() {
+            PgValueFormat::Binary => {
+                // DATE is encoded as the days since epoch
+                let days: i32 = Decode::<Postgres>::decode(value)?;
+                PG_EPOCH.checked_add(SignedDuration::from_hours(i64::from(days) * 24))?
+            }
+
+            PgValueFormat::Text => value.as_str()?.parse()?,
+        })
+    }
+}
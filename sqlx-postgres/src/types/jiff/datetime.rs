@@ -0,0 +1,84 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::jiff::PG_EPOCH;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use jiff::civil::DateTime;
+use jiff::tz::Offset;
+use jiff::{SignedDuration, Timestamp};
+use std::mem;
+
+impl Type<Postgres> for DateTime {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TIMESTAMP
+    }
+}
+
+impl Type<Postgres> for Timestamp {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TIMESTAMPTZ
+    }
+}
+
+impl PgHasArrayType for DateTime {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TIMESTAMP_ARRAY
+    }
+}
+
+impl PgHasArrayType for Timestamp {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TIMESTAMPTZ_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for DateTime {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        // TIMESTAMP is encoded as the microseconds since the epoch
+        let us = self.duration_since(PG_EPOCH.into()).as_micros() as i64;
+        Encode::<Postgres>::encode(us, buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        mem::size_of::<i64>()
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for DateTime {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(match value.format() {
+            PgValueFormat::Binary => {
+                // TIMESTAMP is encoded as the microseconds since the epoch
+                let us = Decode::<Postgres>::decode(value)?;
+                DateTime::from(PG_EPOCH).checked_add(SignedDuration::from_micros(us))?
+            }
+
+            PgValueFormat::Text => value.as_str()?.parse()?,
+        })
+    }
+}
+
+impl Encode<'_, Postgres> for Timestamp {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        Encode::<Postgres>::encode(Offset::UTC.to_datetime(*self), buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        mem::size_of::<i64>()
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Timestamp {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(match value.format() {
+            PgValueFormat::Binary => {
+                let datetime: DateTime = Decode::<Postgres>::decode(value)?;
+                Offset::UTC.to_timestamp(datetime)?
+            }
+
+            // The offset is included, e.g. `2019-01-02 05:10:20.1151+00`
+            PgValueFormat::Text => value.as_str()?.parse()?,
+        })
+    }
+}
@@ -0,0 +1,6 @@
+mod date;
+mod datetime;
+mod time;
+
+#[rustfmt::skip]
+const PG_EPOCH: ::jiff::civil::Date = ::jiff::civil::date(2000, 1, 1);
@@ -0,0 +1,47 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use jiff::civil::Time;
+use jiff::SignedDuration;
+use std::mem;
+
+impl Type<Postgres> for Time {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TIME
+    }
+}
+
+impl PgHasArrayType for Time {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TIME_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for Time {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        // TIME is encoded as the microseconds since midnight
+        let us = self.duration_since(Time::midnight()).as_micros() as i64;
+        Encode::<Postgres>::encode(us, buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        mem::size_of::<u64>()
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for Time {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(match value.format() {
+            PgValueFormat::Binary => {
+                // TIME is encoded as the microseconds since midnight
+                let us = Decode::<Postgres>::decode(value)?;
+                Time::midnight().checked_add(SignedDuration::from_micros(us))?
+            }
+
+            // Postgres will not include the subsecond part if it's zero.
+            PgValueFormat::Text => value.as_str()?.parse()?,
+        })
+    }
+}
@@ -98,3 +98,89 @@ where
         serde_json::from_slice(buf).map(Json).map_err(Into::into)
     }
 }
+
+/// The raw bytes of a `JSON` or `JSONB` value, to parse it incrementally.
+///
+/// Decoding a [`JsonValue`] or a [`Json<T>`] builds the whole document in memory while parsing
+/// it, which can take a lot more memory than its text for large documents. This borrows the
+/// text of the document from the row instead, e.g. to deserialize it into types which borrow
+/// from it, or to feed it to a streaming JSON parser: `&[u8]` implements [`std::io::Read`] and
+/// `futures_io::AsyncRead`. Note that the row still holds the whole text of the document.
+///
+/// For a `JSONB` value received in the binary format, its format version byte is removed, so the
+/// bytes are always the JSON text.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> Result<(), Box<dyn std::error::Error>> {
+/// use serde::Deserialize;
+/// use sqlx::postgres::types::PgRawJson;
+/// use sqlx::Row;
+///
+/// #[derive(Deserialize)]
+/// struct Event<'a> {
+///     // borrowed from the row, if it has no escape sequences
+///     kind: &'a str,
+/// }
+///
+/// let row = sqlx::query("SELECT events FROM logs WHERE id = $1")
+///     .bind(1)
+///     .fetch_one(conn)
+///     .await?;
+///
+/// let raw: PgRawJson<'_> = row.try_get("events")?;
+///
+/// // the events borrow their strings from the row, instead of allocating them
+/// let events: Vec<Event<'_>> = serde_json::from_slice(raw.as_bytes())?;
+///
+/// // a document with several whitespace-separated values can be parsed lazily
+/// for value in serde_json::Deserializer::from_slice(raw.as_bytes()).into_iter::<Event<'_>>() {
+///     println!("{}", value?.kind);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PgRawJson<'r>(&'r [u8]);
+
+impl<'r> PgRawJson<'r> {
+    /// The JSON text of the value.
+    pub fn as_bytes(&self) -> &'r [u8] {
+        self.0
+    }
+}
+
+impl AsRef<[u8]> for PgRawJson<'_> {
+    fn as_ref(&self) -> &[u8] {
+        self.0
+    }
+}
+
+impl Type<Postgres> for PgRawJson<'_> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::JSONB
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::JSON || *ty == PgTypeInfo::JSONB
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for PgRawJson<'r> {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let buf = value.as_bytes()?;
+
+        if value.format() == PgValueFormat::Binary && value.type_info == PgTypeInfo::JSONB {
+            return match buf.split_first() {
+                Some((1, text)) => Ok(PgRawJson(text)),
+                Some((version, _)) => {
+                    Err(format!("unsupported JSONB format version {version}").into())
+                }
+                None => Err("empty JSONB value".into()),
+            };
+        }
+
+        Ok(PgRawJson(buf))
+    }
+}
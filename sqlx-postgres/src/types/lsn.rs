@@ -0,0 +1,80 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::{BoxDynError, Error};
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// A position in the Postgres write-ahead log (WAL), also known as a Log Sequence Number.
+///
+/// Formatted and parsed in the same `XXX/XXX` form used by Postgres' [`PG_LSN`] type,
+/// which it is encoded and decoded as.
+///
+/// [`PG_LSN`]: https://www.postgresql.org/docs/current/datatype-pg-lsn.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PgLsn(pub u64);
+
+impl Display for PgLsn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}/{:X}", self.0 >> 32, self.0 as u32)
+    }
+}
+
+impl FromStr for PgLsn {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let (hi, lo) = s
+            .split_once('/')
+            .ok_or_else(|| err_protocol!("invalid LSN {:?}: expected `XXX/XXX`", s))?;
+
+        let hi =
+            u32::from_str_radix(hi, 16).map_err(|e| err_protocol!("invalid LSN {:?}: {}", s, e))?;
+        let lo =
+            u32::from_str_radix(lo, 16).map_err(|e| err_protocol!("invalid LSN {:?}: {}", s, e))?;
+
+        Ok(PgLsn(((hi as u64) << 32) | lo as u64))
+    }
+}
+
+impl Type<Postgres> for PgLsn {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::PG_LSN
+    }
+}
+
+impl PgHasArrayType for PgLsn {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::PG_LSN_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgLsn {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        buf.extend(&self.0.to_be_bytes());
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, Postgres> for PgLsn {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(match value.format() {
+            PgValueFormat::Binary => PgLsn(BigEndian::read_u64(value.as_bytes()?)),
+            PgValueFormat::Text => value.as_str()?.parse()?,
+        })
+    }
+}
+
+#[test]
+fn test_lsn_formatting() {
+    let lsn: PgLsn = "16/B374D848".parse().unwrap();
+
+    assert_eq!(lsn, PgLsn(0x16_B374_D848));
+    assert_eq!(lsn.to_string(), "16/B374D848");
+    assert!("16B374D848".parse::<PgLsn>().is_err());
+}
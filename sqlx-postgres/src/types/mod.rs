@@ -11,8 +11,8 @@
 //! | `i64`                                 | BIGINT, BIGSERIAL, INT8                              |
 //! | `f32`                                 | REAL, FLOAT4                                         |
 //! | `f64`                                 | DOUBLE PRECISION, FLOAT8                             |
-//! | `&str`, [`String`]                    | VARCHAR, CHAR(N), TEXT, NAME, CITEXT                 |
-//! | `&[u8]`, `Vec<u8>`                    | BYTEA                                                |
+//! | `&str`, [`String`], `Cow<str>`<sup>3</sup> | VARCHAR, CHAR(N), TEXT, NAME, CITEXT            |
+//! | `&[u8]`, `Vec<u8>`, `[u8; N]`, [`Bytes`]<sup>2</sup> | BYTEA                                 |
 //! | `()`                                  | VOID                                                 |
 //! | [`PgInterval`]                        | INTERVAL                                             |
 //! | [`PgRange<T>`](PgRange)               | INT8RANGE, INT4RANGE, TSRANGE, TSTZRANGE, DATERANGE, NUMRANGE |
@@ -20,11 +20,31 @@
 //! | [`PgLTree`]                           | LTREE                                                |
 //! | [`PgLQuery`]                          | LQUERY                                               |
 //! | [`PgCiText`]                          | CITEXT<sup>1</sup>                                   |
+//! | [`PgRecord`]                          | RECORD, composite types (decode only)                |
+//! | [`PgLsn`]                             | PG_LSN                                               |
+//! | [`PgXid`]                             | XID                                                  |
+//! | [`PgXid8`]                            | XID8                                                 |
+//! | [`PgTxidSnapshot`]                    | PG_SNAPSHOT, TXID_SNAPSHOT (decode only)             |
+//! | [`PgTid`]                             | TID (decode only)                                    |
+//! | `std::net::SocketAddr`                | TEXT, VARCHAR<sup>4</sup>                            |
 //!
 //! <sup>1</sup> SQLx generally considers `CITEXT` to be compatible with `String`, `&str`, etc.,
 //! but this wrapper type is available for edge cases, such as `CITEXT[]` which Postgres
 //! does not consider to be compatible with `TEXT[]`.
 //!
+//! <sup>2</sup> [`Bytes`] is decoded without copying in prepared queries, by sharing the buffer
+//! of the row.
+//!
+//! <sup>3</sup> `&str` and `Cow<str>` borrow the buffer of the row, in both text and binary
+//! formats; `Cow<str>` is always decoded as `Cow::Borrowed`.
+//!
+//! <sup>4</sup> Postgres has no type for an address with a port, so `SocketAddr` is stored as
+//! text, e.g. `192.0.2.1:8080` or `[2001:db8::1]:8080`. To query the address itself, e.g. with
+//! the operators of `INET`, store it in an `INET` column (as an `IpAddr`) and the port in an
+//! `INT4` column instead.
+//!
+//! [`Bytes`]: sqlx_core::bytes::Bytes
+//!
 //! ### [`bigdecimal`](https://crates.io/crates/bigdecimal)
 //! Requires the `bigdecimal` Cargo feature flag.
 //!
@@ -51,6 +71,7 @@
 //! | `chrono::NaiveDate`                   | DATE                                                 |
 //! | `chrono::NaiveTime`                   | TIME                                                 |
 //! | [`PgTimeTz`]                          | TIMETZ                                               |
+//! | [`PgPrecision<T, DIGITS>`]            | TIMESTAMPTZ(DIGITS), TIMESTAMP(DIGITS), TIME(DIGITS) |
 //!
 //! ### [`time`](https://crates.io/crates/time)
 //!
@@ -63,6 +84,22 @@
 //! | `time::Date`                          | DATE                                                 |
 //! | `time::Time`                          | TIME                                                 |
 //! | [`PgTimeTz`]                          | TIMETZ                                               |
+//! | [`PgPrecision<T, DIGITS>`]            | TIMESTAMPTZ(DIGITS), TIMESTAMP(DIGITS), TIME(DIGITS) |
+//!
+//! [`PgPrecision<T, DIGITS>`] wraps one of the types above to truncate its fractional seconds
+//! to the precision of a column declared with fewer than 6 digits, instead of letting Postgres
+//! round them.
+//!
+//! ### [`jiff`](https://crates.io/crates/jiff)
+//!
+//! Requires the `jiff` Cargo feature flag.
+//!
+//! | Rust type                             | Postgres type(s)                                     |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `jiff::civil::DateTime`               | TIMESTAMP                                            |
+//! | `jiff::Timestamp`                     | TIMESTAMPTZ                                          |
+//! | `jiff::civil::Date`                   | DATE                                                 |
+//! | `jiff::civil::Time`                   | TIME                                                 |
 //!
 //! ### [`uuid`](https://crates.io/crates/uuid)
 //!
@@ -112,12 +149,15 @@
 //! | [`Json<T>`]                           | JSON, JSONB                                          |
 //! | `serde_json::Value`                   | JSON, JSONB                                          |
 //! | `&serde_json::value::RawValue`        | JSON, JSONB                                          |
+//! | [`PgRawJson<'_>`]                     | JSON, JSONB                                          |
 //!
 //! `Value` and `RawValue` from `serde_json` can be used for unstructured JSON data with
 //! Postgres.
 //!
 //! [`Json<T>`](crate::types::Json) can be used for structured JSON data with Postgres.
 //!
+//! [`PgRawJson`] borrows the JSON text of a value, to parse large documents incrementally.
+//!
 //! # [Composite types](https://www.postgresql.org/docs/current/rowtypes.html)
 //!
 //! User-defined composite types are supported through a derive for `Type`.
@@ -143,9 +183,21 @@
 //! Anonymous composite types are represented as tuples. Note that anonymous composites may only
 //! be returned and not sent to Postgres (this is a limitation of postgres).
 //!
+//! Any record or composite value can also be decoded as a [`PgRecord`], which gives access to its
+//! fields by position, e.g. for functions returning `record` or `SETOF record`.
+//!
 //! # Arrays
 //!
-//! One-dimensional arrays are supported as `Vec<T>` or `&[T]` where `T` implements `Type`.
+//! One-dimensional arrays are supported as `Vec<T>`, `Box<[T]>` or `&[T]` where `T` implements
+//! `Type`. Decoding to a `Box<[T]>` drops the spare capacity of the `Vec<T>` it is decoded from,
+//! for arrays which are kept in memory without being modified.
+//!
+//! A [`PgArrayIter<T>`](PgArrayIter) borrows an array from the row and decodes its elements as
+//! it is iterated over, without collecting them.
+//!
+//! The legacy `INT2VECTOR` and `OIDVECTOR` types found in the system catalogs
+//! (e.g. `pg_index.indkey` and `pg_proc.proargtypes`) can be decoded as `Vec<i16>`
+//! and `Vec<Oid>` respectively, but not encoded.
 //!
 //! # [Enumerations](https://www.postgresql.org/docs/current/datatype-enum.html)
 //!
@@ -178,6 +230,7 @@ use crate::{PgTypeInfo, Postgres};
 pub(crate) use sqlx_core::types::{Json, Type};
 
 mod array;
+mod array_iter;
 mod bool;
 mod bytes;
 mod citext;
@@ -185,6 +238,7 @@ mod float;
 mod int;
 mod interval;
 mod lquery;
+mod lsn;
 mod ltree;
 // Not behind a Cargo feature because we require JSON in the driver implementation.
 mod json;
@@ -192,10 +246,16 @@ mod money;
 mod oid;
 mod range;
 mod record;
+mod socket_addr;
 mod str;
 mod text;
+mod tid;
 mod tuple;
 mod void;
+mod xid;
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod precision;
 
 #[cfg(any(feature = "chrono", feature = "time"))]
 mod time_tz;
@@ -215,6 +275,9 @@ mod chrono;
 #[cfg(feature = "time")]
 mod time;
 
+#[cfg(feature = "jiff")]
+mod jiff;
+
 #[cfg(feature = "uuid")]
 mod uuid;
 
@@ -231,18 +294,27 @@ mod mac_address;
 mod bit_vec;
 
 pub use array::PgHasArrayType;
+pub use array_iter::PgArrayIter;
 pub use citext::PgCiText;
 pub use interval::PgInterval;
+pub use json::PgRawJson;
 pub use lquery::PgLQuery;
 pub use lquery::PgLQueryLevel;
 pub use lquery::PgLQueryVariant;
 pub use lquery::PgLQueryVariantFlag;
+pub use lsn::PgLsn;
 pub use ltree::PgLTree;
 pub use ltree::PgLTreeLabel;
 pub use ltree::PgLTreeParseError;
 pub use money::PgMoney;
 pub use oid::Oid;
-pub use range::PgRange;
+pub use range::{PgHasRangeType, PgRange};
+pub use record::PgRecord;
+pub use tid::PgTid;
+pub use xid::{PgTxidSnapshot, PgXid, PgXid8};
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use precision::{PgFractionalSeconds, PgPrecision};
 
 #[cfg(any(feature = "chrono", feature = "time"))]
 pub use time_tz::PgTimeTz;
@@ -33,6 +33,11 @@ impl PgHasArrayType for Oid {
     fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::OID_ARRAY
     }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        // `oidvector` (e.g. `pg_proc.proargtypes`) can be decoded like an array
+        *ty == PgTypeInfo::OID_ARRAY || *ty == PgTypeInfo::OID_VECTOR
+    }
 }
 
 impl Encode<'_, Postgres> for Oid {
@@ -0,0 +1,185 @@
+use std::cmp;
+use std::ops::Deref;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+
+/// A timestamp or time of day with its fractional seconds truncated to `DIGITS` digits.
+///
+/// Postgres stores timestamps and times with microsecond precision, unless a lower precision is
+/// declared for the column, e.g. `TIMESTAMPTZ(0)` or `TIME(3)`. In that case, the server
+/// _rounds_ the values it receives to the nearest representable value, so a value read back
+/// may not be equal to the value written, and may even be later than it.
+///
+/// Wrapping a value in `PgPrecision` makes this predictable by truncating it on the client
+/// instead: the fractional seconds beyond `DIGITS` digits are dropped (rounding towards the past),
+/// so the value is stored as-is and reads back equal to [`PgPrecision::new()`] of the original.
+/// Values decoded as `PgPrecision` are truncated the same way, which is a no-op for columns
+/// declared with the same precision.
+///
+/// `DIGITS` must be between `0` and `6`; greater values are treated as `6`, the maximum precision
+/// supported by Postgres.
+///
+/// ### Supported Types
+/// With the `time` feature: `time::OffsetDateTime`, `time::PrimitiveDateTime` and `time::Time`.
+///
+/// With the `chrono` feature: `chrono::DateTime<Utc>`, `chrono::NaiveDateTime` and
+/// `chrono::NaiveTime`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use sqlx::postgres::types::PgPrecision;
+/// use time::OffsetDateTime;
+///
+/// // for a `created_at TIMESTAMPTZ(0)` column
+/// let created_at = PgPrecision::<_, 0>::new(OffsetDateTime::now_utc());
+///
+/// sqlx::query("INSERT INTO events (created_at) VALUES ($1)")
+///     .bind(created_at)
+///     .execute(&mut conn)
+///     .await?;
+///
+/// let read: PgPrecision<OffsetDateTime, 0> = sqlx::query_scalar("SELECT created_at FROM events")
+///     .fetch_one(&mut conn)
+///     .await?;
+///
+/// assert_eq!(read, created_at);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PgPrecision<T, const DIGITS: u8>(T);
+
+impl<T, const DIGITS: u8> PgPrecision<T, DIGITS> {
+    /// Returns the wrapped value, with its fractional seconds truncated to `DIGITS` digits.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const DIGITS: u8> Deref for PgPrecision<T, DIGITS> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T, const DIGITS: u8> Type<Postgres> for PgPrecision<T, DIGITS>
+where
+    T: Type<Postgres>,
+{
+    fn type_info() -> PgTypeInfo {
+        T::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        T::compatible(ty)
+    }
+}
+
+impl<T, const DIGITS: u8> PgHasArrayType for PgPrecision<T, DIGITS>
+where
+    T: PgHasArrayType,
+{
+    fn array_type_info() -> PgTypeInfo {
+        T::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        T::array_compatible(ty)
+    }
+}
+
+impl<'q, T, const DIGITS: u8> Encode<'q, Postgres> for PgPrecision<T, DIGITS>
+where
+    T: Encode<'q, Postgres>,
+{
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        self.0.encode_by_ref(buf)
+    }
+
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+}
+
+/// Truncate nanoseconds to the given number of fractional digits.
+fn truncate_nanos(nanos: u32, digits: u8) -> u32 {
+    let step = 10_u32.pow(9 - u32::from(cmp::min(digits, 6)));
+
+    nanos - nanos % step
+}
+
+/// A timestamp or time of day which can be wrapped in [`PgPrecision`].
+pub trait PgFractionalSeconds: Sized {
+    /// Returns the value with its fractional seconds truncated to `digits` digits.
+    ///
+    /// `digits` greater than `6` are treated as `6`.
+    fn truncate_fractional_seconds(self, digits: u8) -> Self;
+}
+
+impl<T: PgFractionalSeconds, const DIGITS: u8> PgPrecision<T, DIGITS> {
+    /// Wrap a value, truncating its fractional seconds to `DIGITS` digits.
+    pub fn new(value: T) -> Self {
+        Self(value.truncate_fractional_seconds(DIGITS))
+    }
+}
+
+impl<T: PgFractionalSeconds, const DIGITS: u8> From<T> for PgPrecision<T, DIGITS> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<'r, T, const DIGITS: u8> Decode<'r, Postgres> for PgPrecision<T, DIGITS>
+where
+    T: PgFractionalSeconds + Decode<'r, Postgres>,
+{
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(Self::new(T::decode(value)?))
+    }
+}
+
+macro_rules! impl_fractional_seconds {
+    ($($ty:ty => $with_nanosecond:ident),* $(,)?) => {$(
+        impl PgFractionalSeconds for $ty {
+            fn truncate_fractional_seconds(self, digits: u8) -> Self {
+                let nanos = truncate_nanos(self.nanosecond(), digits);
+
+                // This can't fail as the nanoseconds were truncated from valid ones
+                self.$with_nanosecond(nanos).unwrap()
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "time")]
+impl_fractional_seconds!(
+    ::time::OffsetDateTime => replace_nanosecond,
+    ::time::PrimitiveDateTime => replace_nanosecond,
+    ::time::Time => replace_nanosecond,
+);
+
+#[cfg(feature = "chrono")]
+mod chrono {
+    use super::*;
+    use ::chrono::Timelike;
+
+    impl_fractional_seconds!(
+        ::chrono::DateTime<::chrono::Utc> => with_nanosecond,
+        ::chrono::NaiveDateTime => with_nanosecond,
+        ::chrono::NaiveTime => with_nanosecond,
+    );
+}
+
+#[test]
+fn test_truncate_nanos() {
+    assert_eq!(truncate_nanos(123_456_789, 0), 0);
+    assert_eq!(truncate_nanos(123_456_789, 3), 123_000_000);
+    assert_eq!(truncate_nanos(999_999_999, 5), 999_990_000);
+    assert_eq!(truncate_nanos(123_456_789, 6), 123_456_000);
+    assert_eq!(truncate_nanos(123_456_789, 9), 123_456_000);
+}
@@ -112,178 +112,98 @@ impl<T> RangeBounds<T> for PgRange<T> {
     }
 }
 
-impl Type<Postgres> for PgRange<i32> {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::INT4_RANGE
-    }
-
-    fn compatible(ty: &PgTypeInfo) -> bool {
-        range_compatible::<i32>(ty)
-    }
-}
-
-impl Type<Postgres> for PgRange<i64> {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::INT8_RANGE
-    }
-
-    fn compatible(ty: &PgTypeInfo) -> bool {
-        range_compatible::<i64>(ty)
-    }
-}
-
-#[cfg(feature = "bigdecimal")]
-impl Type<Postgres> for PgRange<bigdecimal::BigDecimal> {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::NUM_RANGE
-    }
-
-    fn compatible(ty: &PgTypeInfo) -> bool {
-        range_compatible::<bigdecimal::BigDecimal>(ty)
-    }
-}
-
-#[cfg(feature = "rust_decimal")]
-impl Type<Postgres> for PgRange<rust_decimal::Decimal> {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::NUM_RANGE
-    }
-
-    fn compatible(ty: &PgTypeInfo) -> bool {
-        range_compatible::<rust_decimal::Decimal>(ty)
-    }
-}
-
-#[cfg(feature = "chrono")]
-impl Type<Postgres> for PgRange<chrono::NaiveDate> {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::DATE_RANGE
-    }
-
-    fn compatible(ty: &PgTypeInfo) -> bool {
-        range_compatible::<chrono::NaiveDate>(ty)
-    }
-}
-
-#[cfg(feature = "chrono")]
-impl Type<Postgres> for PgRange<chrono::NaiveDateTime> {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::TS_RANGE
-    }
-
-    fn compatible(ty: &PgTypeInfo) -> bool {
-        range_compatible::<chrono::NaiveDateTime>(ty)
-    }
-}
-
-#[cfg(feature = "chrono")]
-impl<Tz: chrono::TimeZone> Type<Postgres> for PgRange<chrono::DateTime<Tz>> {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::TSTZ_RANGE
-    }
-
-    fn compatible(ty: &PgTypeInfo) -> bool {
-        range_compatible::<chrono::DateTime<Tz>>(ty)
-    }
-}
-
-#[cfg(feature = "time")]
-impl Type<Postgres> for PgRange<time::Date> {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::DATE_RANGE
-    }
-
-    fn compatible(ty: &PgTypeInfo) -> bool {
-        range_compatible::<time::Date>(ty)
-    }
-}
-
-#[cfg(feature = "time")]
-impl Type<Postgres> for PgRange<time::PrimitiveDateTime> {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::TS_RANGE
-    }
-
-    fn compatible(ty: &PgTypeInfo) -> bool {
-        range_compatible::<time::PrimitiveDateTime>(ty)
-    }
-}
-
-#[cfg(feature = "time")]
-impl Type<Postgres> for PgRange<time::OffsetDateTime> {
+/// A type which can be the element of a [`PgRange`], providing the type of the range.
+///
+/// [`PgRange<T>`] implements [`Type`] and [`PgHasArrayType`] for every `T` implementing this trait.
+/// As the binary format of ranges doesn't depend on the element type, this is all that is needed
+/// to use `PgRange` over any type which can be encoded and decoded, including custom ones.
+///
+/// For the types supported by SQLx, this is implemented to map to the built-in range types
+/// (e.g. `PgRange<i32>` is an `INT4RANGE`). For custom types, the `Type` derive can implement it
+/// with `#[sqlx(range_type_name = "...")]`, naming the range type over the (custom) element type:
+///
+/// ```rust,ignore
+/// // CREATE DOMAIN slot_time AS TIMESTAMPTZ CHECK (date_trunc('minute', VALUE) = VALUE);
+/// // CREATE TYPE slot_range AS RANGE (SUBTYPE = slot_time);
+/// #[derive(sqlx::Type)]
+/// #[sqlx(type_name = "slot_time", range_type_name = "slot_range")]
+/// struct SlotTime(time::OffsetDateTime);
+///
+/// let slot: PgRange<SlotTime> = sqlx::query_scalar("SELECT slot FROM bookings")
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
+/// The array type of the range is then assumed to be named as the range type prefixed by an
+/// underscore, which is what Postgres names it by default (e.g. `_slot_range`).
+pub trait PgHasRangeType {
+    fn range_type_info() -> PgTypeInfo;
+    fn range_array_type_info() -> PgTypeInfo;
+}
+
+impl<T> Type<Postgres> for PgRange<T>
+where
+    T: PgHasRangeType + Type<Postgres>,
+{
     fn type_info() -> PgTypeInfo {
-        PgTypeInfo::TSTZ_RANGE
+        T::range_type_info()
     }
 
     fn compatible(ty: &PgTypeInfo) -> bool {
-        range_compatible::<time::OffsetDateTime>(ty)
+        *ty == T::range_type_info() || range_compatible::<T>(ty)
     }
 }
 
-impl PgHasArrayType for PgRange<i32> {
-    fn array_type_info() -> PgTypeInfo {
-        PgTypeInfo::INT4_RANGE_ARRAY
-    }
-}
-
-impl PgHasArrayType for PgRange<i64> {
-    fn array_type_info() -> PgTypeInfo {
-        PgTypeInfo::INT8_RANGE_ARRAY
-    }
-}
-
-#[cfg(feature = "bigdecimal")]
-impl PgHasArrayType for PgRange<bigdecimal::BigDecimal> {
-    fn array_type_info() -> PgTypeInfo {
-        PgTypeInfo::NUM_RANGE_ARRAY
-    }
-}
-
-#[cfg(feature = "rust_decimal")]
-impl PgHasArrayType for PgRange<rust_decimal::Decimal> {
+impl<T> PgHasArrayType for PgRange<T>
+where
+    T: PgHasRangeType,
+{
     fn array_type_info() -> PgTypeInfo {
-        PgTypeInfo::NUM_RANGE_ARRAY
+        T::range_array_type_info()
     }
 }
 
-#[cfg(feature = "chrono")]
-impl PgHasArrayType for PgRange<chrono::NaiveDate> {
-    fn array_type_info() -> PgTypeInfo {
-        PgTypeInfo::DATE_RANGE_ARRAY
-    }
-}
+macro_rules! impl_range_type {
+    ($($(#[$meta:meta])* $ty:ty => $range:ident, $range_array:ident;)*) => {$(
+        $(#[$meta])*
+        impl PgHasRangeType for $ty {
+            fn range_type_info() -> PgTypeInfo {
+                PgTypeInfo::$range
+            }
 
-#[cfg(feature = "chrono")]
-impl PgHasArrayType for PgRange<chrono::NaiveDateTime> {
-    fn array_type_info() -> PgTypeInfo {
-        PgTypeInfo::TS_RANGE_ARRAY
-    }
+            fn range_array_type_info() -> PgTypeInfo {
+                PgTypeInfo::$range_array
+            }
+        }
+    )*};
+}
+
+impl_range_type! {
+    i32 => INT4_RANGE, INT4_RANGE_ARRAY;
+    i64 => INT8_RANGE, INT8_RANGE_ARRAY;
+    #[cfg(feature = "bigdecimal")]
+    bigdecimal::BigDecimal => NUM_RANGE, NUM_RANGE_ARRAY;
+    #[cfg(feature = "rust_decimal")]
+    rust_decimal::Decimal => NUM_RANGE, NUM_RANGE_ARRAY;
+    #[cfg(feature = "chrono")]
+    chrono::NaiveDate => DATE_RANGE, DATE_RANGE_ARRAY;
+    #[cfg(feature = "chrono")]
+    chrono::NaiveDateTime => TS_RANGE, TS_RANGE_ARRAY;
+    #[cfg(feature = "time")]
+    time::Date => DATE_RANGE, DATE_RANGE_ARRAY;
+    #[cfg(feature = "time")]
+    time::PrimitiveDateTime => TS_RANGE, TS_RANGE_ARRAY;
+    #[cfg(feature = "time")]
+    time::OffsetDateTime => TSTZ_RANGE, TSTZ_RANGE_ARRAY;
 }
 
 #[cfg(feature = "chrono")]
-impl<Tz: chrono::TimeZone> PgHasArrayType for PgRange<chrono::DateTime<Tz>> {
-    fn array_type_info() -> PgTypeInfo {
-        PgTypeInfo::TSTZ_RANGE_ARRAY
-    }
-}
-
-#[cfg(feature = "time")]
-impl PgHasArrayType for PgRange<time::Date> {
-    fn array_type_info() -> PgTypeInfo {
-        PgTypeInfo::DATE_RANGE_ARRAY
-    }
-}
-
-#[cfg(feature = "time")]
-impl PgHasArrayType for PgRange<time::PrimitiveDateTime> {
-    fn array_type_info() -> PgTypeInfo {
-        PgTypeInfo::TS_RANGE_ARRAY
+impl<Tz: chrono::TimeZone> PgHasRangeType for chrono::DateTime<Tz> {
+    fn range_type_info() -> PgTypeInfo {
+        PgTypeInfo::TSTZ_RANGE
     }
-}
 
-#[cfg(feature = "time")]
-impl PgHasArrayType for PgRange<time::OffsetDateTime> {
-    fn array_type_info() -> PgTypeInfo {
+    fn range_array_type_info() -> PgTypeInfo {
         PgTypeInfo::TSTZ_RANGE_ARRAY
     }
 }
@@ -1,13 +1,17 @@
-use sqlx_core::bytes::Buf;
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Formatter};
+
+use sqlx_core::bytes::{Buf, Bytes};
+use sqlx_core::value::Value;
 
 use crate::decode::Decode;
 use crate::encode::Encode;
-use crate::error::{mismatched_types, BoxDynError};
+use crate::error::{mismatched_types, BoxDynError, Error};
 use crate::type_info::TypeInfo;
 use crate::type_info::{PgType, PgTypeKind};
 use crate::types::Oid;
-use crate::types::Type;
-use crate::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use crate::types::{array_compatible, PgHasArrayType, Type};
+use crate::{PgArgumentBuffer, PgTypeInfo, PgValue, PgValueFormat, PgValueRef, Postgres};
 
 #[doc(hidden)]
 pub struct PgRecordEncoder<'a> {
@@ -63,6 +67,8 @@ pub struct PgRecordDecoder<'r> {
     typ: PgTypeInfo,
     fmt: PgValueFormat,
     ind: usize,
+    // whether a field follows in the text format, which is empty if it's `NULL`
+    has_next_text_field: bool,
 }
 
 impl<'r> PgRecordDecoder<'r> {
@@ -88,6 +94,7 @@ impl<'r> PgRecordDecoder<'r> {
             fmt,
             typ,
             ind: 0,
+            has_next_text_field: true,
         })
     }
 
@@ -96,15 +103,72 @@ impl<'r> PgRecordDecoder<'r> {
     where
         T: for<'a> Decode<'a, Postgres> + Type<Postgres>,
     {
-        if self.buf.is_empty() {
-            return Err(format!("no field `{0}` found on record", self.ind).into());
+        let field = self.next_field()?;
+
+        match self.fmt {
+            PgValueFormat::Binary => {
+                if let Some(ty) = &field.type_info {
+                    if !ty.is_null() && !T::compatible(ty) {
+                        return Err(mismatched_types::<Postgres, T>(ty));
+                    }
+                }
+
+                let element_type =
+                    field.type_info
+                        .ok_or_else(|| BoxDynError::from(format!("custom types in records are not fully supported yet: failed to retrieve type info for field {} with type oid {}", self.ind - 1, field.oid.0)))?;
+
+                T::decode(PgValueRef {
+                    value: field.value.as_deref(),
+                    row: None,
+                    type_info: element_type,
+                    format: self.fmt,
+                })
+            }
+
+            PgValueFormat::Text => {
+                // NOTE: we do not call [`accepts`] or give a chance to from a user as
+                //       TEXT sequences are not strongly typed
+
+                T::decode(PgValueRef {
+                    // NOTE: We pass `0` as the type ID because we don't have a reasonable value
+                    //       we could use.
+                    type_info: PgTypeInfo::with_oid(Oid(0)),
+                    format: self.fmt,
+                    value: field.value.as_deref(),
+                    row: None,
+                })
+            }
         }
+    }
 
+    fn has_next_field(&self) -> bool {
         match self.fmt {
+            PgValueFormat::Binary => !self.buf.is_empty(),
+            PgValueFormat::Text => self.has_next_text_field,
+        }
+    }
+
+    // Read the next field of the record, with its type if it is known
+    fn next_field(&mut self) -> Result<RecordField<'r>, BoxDynError> {
+        if !self.has_next_field() {
+            return Err(format!("no field `{0}` found on record", self.ind).into());
+        }
+
+        let field = match self.fmt {
             PgValueFormat::Binary => {
                 let element_type_oid = Oid(self.buf.get_u32());
-                let element_type_opt = match self.typ.0.kind() {
-                    PgTypeKind::Simple if self.typ.0 == PgType::Record => {
+
+                // a composite value of a custom type nested in an anonymous record has an
+                // unresolved type, and is decoded like an anonymous record
+                let kind = match &self.typ.0 {
+                    PgType::DeclareWithOid(_) => &PgTypeKind::Simple,
+                    ty => ty.kind(),
+                };
+
+                let element_type_opt = match kind {
+                    PgTypeKind::Simple
+                        if matches!(self.typ.0, PgType::Record | PgType::DeclareWithOid(_)) =>
+                    {
                         PgTypeInfo::try_from_oid(element_type_oid)
                     }
 
@@ -125,19 +189,13 @@ impl<'r> PgRecordDecoder<'r> {
                     }
                 };
 
-                if let Some(ty) = &element_type_opt {
-                    if !ty.is_null() && !T::compatible(ty) {
-                        return Err(mismatched_types::<Postgres, T>(ty));
-                    }
-                }
-
-                let element_type =
-                    element_type_opt
-                        .ok_or_else(|| BoxDynError::from(format!("custom types in records are not fully supported yet: failed to retrieve type info for field {} with type oid {}", self.ind, element_type_oid.0)))?;
+                let value = PgValueRef::get(&mut self.buf, self.fmt, PgTypeInfo::RECORD).value;
 
-                self.ind += 1;
-
-                T::decode(PgValueRef::get(&mut self.buf, self.fmt, element_type))
+                RecordField {
+                    oid: element_type_oid,
+                    type_info: element_type_opt,
+                    value: value.map(Cow::Borrowed),
+                }
             }
 
             PgValueFormat::Text => {
@@ -147,6 +205,8 @@ impl<'r> PgRecordDecoder<'r> {
                 let mut in_escape = false;
                 let mut prev_ch = '\0';
 
+                self.has_next_text_field = false;
+
                 while !self.buf.is_empty() {
                     let ch = self.buf.get_u8() as char;
                     match ch {
@@ -172,7 +232,10 @@ impl<'r> PgRecordDecoder<'r> {
                             in_escape = true;
                         }
 
-                        ',' if !in_quotes => break,
+                        ',' if !in_quotes => {
+                            self.has_next_text_field = true;
+                            break;
+                        }
 
                         _ => {
                             element.push(ch);
@@ -181,25 +244,203 @@ impl<'r> PgRecordDecoder<'r> {
                     prev_ch = ch;
                 }
 
-                let buf = if element.is_empty() && !quoted {
+                let value = if element.is_empty() && !quoted {
                     // completely empty input means NULL
                     None
                 } else {
-                    Some(element.as_bytes())
+                    Some(Cow::Owned(element.into_bytes()))
                 };
 
-                // NOTE: we do not call [`accepts`] or give a chance to from a user as
-                //       TEXT sequences are not strongly typed
+                // the types of the fields are only known for named composite types, and nested
+                // values have no type at all
+                let type_info = match &self.typ.0 {
+                    PgType::Custom(custom) => match &custom.kind {
+                        PgTypeKind::Composite(fields) => fields.get(self.ind).map(|f| f.1.clone()),
+                        _ => None,
+                    },
+                    _ => None,
+                };
 
-                T::decode(PgValueRef {
-                    // NOTE: We pass `0` as the type ID because we don't have a reasonable value
-                    //       we could use.
-                    type_info: PgTypeInfo::with_oid(Oid(0)),
-                    format: self.fmt,
-                    value: buf,
-                    row: None,
-                })
+                RecordField {
+                    oid: type_info.as_ref().and_then(|ty| ty.oid()).unwrap_or(Oid(0)),
+                    type_info,
+                    value,
+                }
             }
+        };
+
+        self.ind += 1;
+
+        Ok(field)
+    }
+}
+
+struct RecordField<'r> {
+    oid: Oid,
+    type_info: Option<PgTypeInfo>,
+    value: Option<Cow<'r, [u8]>>,
+}
+
+/// A record or composite value, with its fields accessed by position.
+///
+/// This decodes any record, including the anonymous records built with `ROW(..)` or returned
+/// by functions declared as returning `record`, which have no type to derive
+/// [`Type`][derive@sqlx_core::types::Type] for. Unlike tuples, which also decode anonymous
+/// records, the number of fields doesn't have to be known in advance, and fields of custom
+/// types (e.g. enums) can be decoded.
+///
+/// In the binary format (used by queries with [`query()`][sqlx_core::query::query] and
+/// the macros), the type of each field is sent along with its value and checked when decoding
+/// it, unless it is a custom type whose OID isn't known to SQLx. In the text format (used by
+/// the simple query protocol, e.g. with [`Executor::fetch()`][sqlx_core::executor::Executor::fetch]
+/// on a string), the types of the fields of anonymous records are unknown and not checked.
+///
+/// Names of fields are not sent by Postgres and must be looked up in the catalog if needed.
+///
+/// ```rust,no_run
+/// # async fn example(mut conn: sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::postgres::types::PgRecord;
+///
+/// let record: PgRecord = sqlx::query_scalar("SELECT ROW(1, 'foo', NULL::int8)")
+///     .fetch_one(&mut conn)
+///     .await?;
+///
+/// assert_eq!(record.len(), 3);
+/// assert_eq!(record.try_get::<i32>(0)?, 1);
+/// assert_eq!(record.try_get::<&str>(1)?, "foo");
+/// assert_eq!(record.try_get::<Option<i64>>(2)?, None);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct PgRecord {
+    fields: Vec<PgValue>,
+}
+
+impl PgRecord {
+    /// Returns the number of fields of the record.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Returns `true` if the record has no fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Returns the OID of the type of the field at `index`.
+    ///
+    /// Returns `None` if there is no such field, or if the type of the field is unknown, as for
+    /// anonymous records in the text format.
+    pub fn field_oid(&self, index: usize) -> Option<Oid> {
+        self.fields
+            .get(index)?
+            .type_info
+            .oid()
+            .filter(|oid| oid.0 != 0)
+    }
+
+    /// Returns the raw value of the field at `index`, if there is one.
+    pub fn field(&self, index: usize) -> Option<&PgValue> {
+        self.fields.get(index)
+    }
+
+    /// Decode the field at `index`.
+    ///
+    /// Returns an error if there is no such field, if its type is known and not compatible with
+    /// `T`, or if it cannot be decoded.
+    pub fn try_get<'r, T>(&'r self, index: usize) -> Result<T, Error>
+    where
+        T: Decode<'r, Postgres> + Type<Postgres>,
+    {
+        let value = self
+            .fields
+            .get(index)
+            .ok_or(Error::ColumnIndexOutOfBounds {
+                index,
+                len: self.fields.len(),
+            })?;
+
+        let ty = &value.type_info;
+
+        // the types of custom types in anonymous records are only known by their OIDs
+        let known = !matches!(ty.0, PgType::DeclareWithOid(_));
+
+        if known && !value.is_null() && !ty.is_null() && !T::compatible(ty) {
+            return Err(Error::ColumnDecode {
+                index: format!("{index:?}"),
+                source: mismatched_types::<Postgres, T>(ty),
+            });
+        }
+
+        T::decode(value.as_ref()).map_err(|source| Error::ColumnDecode {
+            index: format!("{index:?}"),
+            source,
+        })
+    }
+
+    /// Decode the field at `index`.
+    ///
+    /// # Panics
+    /// If [`try_get()`][Self::try_get] returns an error.
+    #[track_caller]
+    pub fn get<'r, T>(&'r self, index: usize) -> T
+    where
+        T: Decode<'r, Postgres> + Type<Postgres>,
+    {
+        self.try_get(index).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl Debug for PgRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PgRecord")
+            .field(
+                "types",
+                &self.fields.iter().map(|v| &v.type_info).collect::<Vec<_>>(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl Type<Postgres> for PgRecord {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::RECORD
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        ty.0 == PgType::Record || matches!(ty.kind(), PgTypeKind::Composite(_))
+    }
+}
+
+impl PgHasArrayType for PgRecord {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::RECORD_ARRAY
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        array_compatible::<PgRecord>(ty)
+    }
+}
+
+impl Decode<'_, Postgres> for PgRecord {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        let format = value.format;
+        let mut decoder = PgRecordDecoder::new(value)?;
+        let mut fields = Vec::new();
+
+        while decoder.has_next_field() {
+            let field = decoder.next_field()?;
+
+            fields.push(PgValue {
+                value: field.value.map(|v| Bytes::from(v.into_owned())),
+                type_info: field
+                    .type_info
+                    .unwrap_or_else(|| PgTypeInfo::with_oid(field.oid)),
+                format,
+            });
         }
+
+        Ok(PgRecord { fields })
     }
 }
@@ -0,0 +1,45 @@
+use std::net::SocketAddr;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+
+// Postgres has no type for an address with a port, so it is stored as text,
+// e.g. `192.0.2.1:8080` or `[2001:db8::1]:8080`
+
+impl Type<Postgres> for SocketAddr {
+    fn type_info() -> PgTypeInfo {
+        <&str as Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <&str as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl PgHasArrayType for SocketAddr {
+    fn array_type_info() -> PgTypeInfo {
+        <&str as PgHasArrayType>::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        <&str as PgHasArrayType>::array_compatible(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for SocketAddr {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        <&str as Encode<Postgres>>::encode(&*self.to_string(), buf)
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for SocketAddr {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let s = value.as_str()?;
+
+        s.parse()
+            .map_err(|_| format!("invalid socket address: {s:?}").into())
+    }
+}
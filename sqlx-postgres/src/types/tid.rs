@@ -0,0 +1,75 @@
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::decode::Decode;
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// The PostgreSQL [`TID`] type identifies the physical location of a row version in its table,
+/// as the number of its block and its index in the block.
+///
+/// This is the type of the `ctid` system column. It orders rows by their location, e.g. to scan
+/// a table incrementally, but the location of a row changes when it is updated, or when the
+/// table is vacuumed with `VACUUM FULL`.
+///
+/// Its text representation is `(block,offset)`, e.g. `(0,1)`.
+///
+/// This type is decode-only.
+///
+/// [`TID`]: https://www.postgresql.org/docs/current/datatype-oid.html
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct PgTid {
+    /// The number of the block of the row version, starting at 0.
+    pub block: u32,
+
+    /// The index of the row version in its block, starting at 1.
+    pub offset: u16,
+}
+
+impl Type<Postgres> for PgTid {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TID
+    }
+}
+
+impl PgHasArrayType for PgTid {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TID_ARRAY
+    }
+}
+
+impl Decode<'_, Postgres> for PgTid {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let buf = value.as_bytes()?;
+
+                if buf.len() != 6 {
+                    return Err(format!("expected 6 bytes, got {}", buf.len()).into());
+                }
+
+                Ok(PgTid {
+                    block: BigEndian::read_u32(&buf[..4]),
+                    offset: BigEndian::read_u16(&buf[4..]),
+                })
+            }
+
+            PgValueFormat::Text => {
+                let s = value.as_str()?;
+
+                let Some((block, offset)) = s
+                    .strip_prefix('(')
+                    .and_then(|s| s.strip_suffix(')'))
+                    .and_then(|s| s.split_once(','))
+                else {
+                    return Err(format!("invalid tuple identifier: {s:?}").into());
+                };
+
+                Ok(PgTid {
+                    block: block.parse()?,
+                    offset: offset.parse()?,
+                })
+            }
+        }
+    }
+}
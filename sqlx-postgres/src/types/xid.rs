@@ -0,0 +1,220 @@
+use byteorder::{BigEndian, ByteOrder};
+use sqlx_core::bytes::Buf;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// The PostgreSQL [`XID`] type stores a 32-bit transaction identifier, which wraps around.
+///
+/// This is the type of the `xmin` and `xmax` system columns, for example. See [`PgXid8`] for
+/// the 64-bit identifiers returned by e.g. `pg_current_xact_id()`, which don't.
+///
+/// [`XID`]: https://www.postgresql.org/docs/current/datatype-oid.html
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Default)]
+pub struct PgXid(
+    /// The raw unsigned integer value sent over the wire
+    pub u32,
+);
+
+/// The PostgreSQL [`XID8`] type stores a 64-bit transaction identifier, which never wraps
+/// around.
+///
+/// This is the type returned by `pg_current_xact_id()`, and of the transaction ids in a
+/// [`PgTxidSnapshot`]. The `txid_*` functions which predate it (e.g. `txid_current()`) return
+/// the same identifiers as a `BIGINT` instead, which can be converted with
+/// [`PgXid8::from_txid()`].
+///
+/// [`XID8`]: https://www.postgresql.org/docs/current/datatype-oid.html
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct PgXid8(
+    /// The raw unsigned integer value sent over the wire
+    pub u64,
+);
+
+impl PgXid8 {
+    /// Convert a transaction id returned as a `BIGINT` by the `txid_*` functions.
+    pub fn from_txid(txid: i64) -> Self {
+        Self(txid as u64)
+    }
+}
+
+/// The PostgreSQL [`PG_SNAPSHOT`] and `TXID_SNAPSHOT` types store a snapshot of the
+/// transactions visible at some point in time, as returned by e.g. `pg_current_snapshot()`.
+///
+/// Its text representation is `xmin:xmax:xip_list`, e.g. `10:20:10,14,15`.
+///
+/// This type is decode-only.
+///
+/// [`PG_SNAPSHOT`]: https://www.postgresql.org/docs/current/functions-info.html#FUNCTIONS-PG-SNAPSHOT-PARTS
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PgTxidSnapshot {
+    /// The lowest transaction id that was still active; all the ones before it are either
+    /// committed and visible, or rolled back.
+    pub xmin: PgXid8,
+
+    /// One past the highest completed transaction id; all the ones from it on were not
+    /// completed yet, and are not visible.
+    pub xmax: PgXid8,
+
+    /// The transaction ids between `xmin` and `xmax` which were still active, in ascending
+    /// order.
+    pub xip: Vec<PgXid8>,
+}
+
+impl PgTxidSnapshot {
+    /// Returns `true` if the transaction `xid` is visible in this snapshot, i.e. it was completed
+    /// before the snapshot was taken, like `pg_visible_in_snapshot()`.
+    ///
+    /// Note that a completed transaction may have been rolled back instead of committed.
+    pub fn is_visible(&self, xid: PgXid8) -> bool {
+        if xid < self.xmin {
+            return true;
+        }
+
+        xid < self.xmax && self.xip.binary_search(&xid).is_err()
+    }
+}
+
+impl Type<Postgres> for PgXid {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::XID
+    }
+}
+
+impl PgHasArrayType for PgXid {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::XID_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgXid {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        buf.extend(&self.0.to_be_bytes());
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, Postgres> for PgXid {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(Self(match value.format() {
+            PgValueFormat::Binary => BigEndian::read_u32(value.as_bytes()?),
+            PgValueFormat::Text => value.as_str()?.parse()?,
+        }))
+    }
+}
+
+impl Type<Postgres> for PgXid8 {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::XID8
+    }
+}
+
+impl PgHasArrayType for PgXid8 {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::XID8_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgXid8 {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        buf.extend(&self.0.to_be_bytes());
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, Postgres> for PgXid8 {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(Self(match value.format() {
+            PgValueFormat::Binary => BigEndian::read_u64(value.as_bytes()?),
+            PgValueFormat::Text => value.as_str()?.parse()?,
+        }))
+    }
+}
+
+impl Type<Postgres> for PgTxidSnapshot {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::PG_SNAPSHOT
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::PG_SNAPSHOT || *ty == PgTypeInfo::TXID_SNAPSHOT
+    }
+}
+
+impl PgHasArrayType for PgTxidSnapshot {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::PG_SNAPSHOT_ARRAY
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::PG_SNAPSHOT_ARRAY || *ty == PgTypeInfo::TXID_SNAPSHOT_ARRAY
+    }
+}
+
+impl Decode<'_, Postgres> for PgTxidSnapshot {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let mut buf = value.as_bytes()?;
+
+                if buf.len() < 20 {
+                    return Err(format!("expected at least 20 bytes, got {}", buf.len()).into());
+                }
+
+                let nxip = buf.get_i32();
+                let xmin = PgXid8(buf.get_u64());
+                let xmax = PgXid8(buf.get_u64());
+
+                if nxip < 0 || buf.len() != nxip as usize * 8 {
+                    return Err(format!("invalid number of in-progress ids: {nxip}").into());
+                }
+
+                let xip = (0..nxip).map(|_| PgXid8(buf.get_u64())).collect();
+
+                Ok(PgTxidSnapshot { xmin, xmax, xip })
+            }
+
+            PgValueFormat::Text => {
+                let s = value.as_str()?;
+                let mut parts = s.splitn(3, ':');
+
+                let (Some(xmin), Some(xmax), Some(xip)) =
+                    (parts.next(), parts.next(), parts.next())
+                else {
+                    return Err(format!("invalid snapshot: {s:?}").into());
+                };
+
+                Ok(PgTxidSnapshot {
+                    xmin: PgXid8(xmin.parse()?),
+                    xmax: PgXid8(xmax.parse()?),
+                    xip: xip
+                        .split(',')
+                        .filter(|xid| !xid.is_empty())
+                        .map(|xid| xid.parse().map(PgXid8))
+                        .collect::<Result<_, _>>()?,
+                })
+            }
+        }
+    }
+}
+
+#[test]
+fn test_snapshot_visibility() {
+    let snapshot = PgTxidSnapshot {
+        xmin: PgXid8(10),
+        xmax: PgXid8(20),
+        xip: vec![PgXid8(10), PgXid8(14)],
+    };
+
+    assert!(snapshot.is_visible(PgXid8(9)));
+    assert!(!snapshot.is_visible(PgXid8(10)));
+    assert!(snapshot.is_visible(PgXid8(11)));
+    assert!(!snapshot.is_visible(PgXid8(14)));
+    assert!(snapshot.is_visible(PgXid8(19)));
+    assert!(!snapshot.is_visible(PgXid8(20)));
+}
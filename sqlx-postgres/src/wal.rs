@@ -0,0 +1,106 @@
+use std::cmp;
+use std::time::{Duration, Instant};
+
+use sqlx_core::rt;
+
+use crate::error::Error;
+use crate::query_scalar::query_scalar;
+use crate::types::PgLsn;
+use crate::PgConnection;
+
+// The longest delay between two checks of the replayed position
+const MAX_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+impl PgConnection {
+    /// Get the current position in the write-ahead log (WAL) of the server, i.e. the result of
+    /// `pg_current_wal_lsn()`.
+    ///
+    /// Taken on the primary right after a write is committed, this is the position which a
+    /// replica must have replayed to see that write, see [`wait_for_lsn()`][Self::wait_for_lsn].
+    ///
+    /// Returns an error if the server is a replica.
+    pub async fn current_wal_lsn(&mut self) -> Result<PgLsn, Error> {
+        query_scalar("SELECT pg_current_wal_lsn()")
+            .fetch_one(&mut *self)
+            .await
+    }
+
+    /// Get the position in the write-ahead log up to which a replica has replayed the changes
+    /// of the primary, i.e. the result of `pg_last_wal_replay_lsn()`.
+    ///
+    /// Returns `None` if the server is not a replica.
+    pub async fn last_wal_replay_lsn(&mut self) -> Result<Option<PgLsn>, Error> {
+        query_scalar("SELECT pg_last_wal_replay_lsn()")
+            .fetch_one(&mut *self)
+            .await
+    }
+
+    /// Wait until the server has replayed the write-ahead log up to `target`, for at most
+    /// `timeout`.
+    ///
+    /// This allows reading your own writes from a replica: once a write has been committed
+    /// on the primary, get its position with [`current_wal_lsn()`][Self::current_wal_lsn]
+    /// there, then wait for it on the connection to the replica before reading.
+    ///
+    /// The replayed position is polled with an increasing delay, up to 100 milliseconds.
+    /// If the server is the primary, its current position is used instead, which is always
+    /// reached for positions taken from itself.
+    ///
+    /// Returns `false` if the position was not reached before the timeout, e.g. because the
+    /// replica lags behind and the read should go to the primary instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(primary: &mut sqlx::PgConnection, replica: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use std::time::Duration;
+    ///
+    /// sqlx::query("UPDATE users SET name = 'Alice' WHERE id = 1")
+    ///     .execute(&mut *primary)
+    ///     .await?;
+    ///
+    /// let lsn = primary.current_wal_lsn().await?;
+    ///
+    /// let conn = if replica.wait_for_lsn(lsn, Duration::from_secs(1)).await? {
+    ///     replica
+    /// } else {
+    ///     primary
+    /// };
+    ///
+    /// let name: String = sqlx::query_scalar("SELECT name FROM users WHERE id = 1")
+    ///     .fetch_one(conn)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_lsn(&mut self, target: PgLsn, timeout: Duration) -> Result<bool, Error> {
+        let deadline = Instant::now() + timeout;
+        let mut interval = Duration::from_millis(1);
+
+        loop {
+            // `pg_last_wal_replay_lsn()` is `NULL` on a primary, where `pg_current_wal_lsn()`
+            // fails on a replica
+            let lsn: Option<PgLsn> = query_scalar(
+                "SELECT CASE WHEN pg_is_in_recovery() \
+                     THEN pg_last_wal_replay_lsn() \
+                     ELSE pg_current_wal_lsn() \
+                 END",
+            )
+            .fetch_one(&mut *self)
+            .await?;
+
+            if lsn >= Some(target) {
+                return Ok(true);
+            }
+
+            let now = Instant::now();
+
+            if now >= deadline {
+                return Ok(false);
+            }
+
+            rt::sleep(cmp::min(interval, deadline - now)).await;
+            interval = cmp::min(interval * 2, MAX_POLL_INTERVAL);
+        }
+    }
+}
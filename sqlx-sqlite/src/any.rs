@@ -12,11 +12,11 @@ use sqlx_core::any::{
 };
 
 use crate::type_info::DataType;
-use sqlx_core::connection::{ConnectOptions, Connection};
+use sqlx_core::connection::{CancelToken, ConnectOptions, Connection};
 use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
-use sqlx_core::transaction::TransactionManager;
+use sqlx_core::transaction::{TransactionManager, TransactionOptions};
 
 sqlx_core::declare_driver_with_optional_migrate!(DRIVER = Sqlite);
 
@@ -41,6 +41,10 @@ impl AnyConnectionBackend for SqliteConnection {
         SqliteTransactionManager::begin(self)
     }
 
+    fn begin_with(&mut self, options: TransactionOptions) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        SqliteTransactionManager::begin_with(self, options)
+    }
+
     fn commit(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
         SqliteTransactionManager::commit(self)
     }
@@ -53,6 +57,14 @@ impl AnyConnectionBackend for SqliteConnection {
         SqliteTransactionManager::start_rollback(self)
     }
 
+    fn get_transaction_depth(&self) -> usize {
+        SqliteTransactionManager::get_transaction_depth(self)
+    }
+
+    fn cached_statements(&mut self) -> BoxFuture<'_, sqlx_core::Result<Vec<String>>> {
+        Connection::cached_statements(self)
+    }
+
     fn shrink_buffers(&mut self) {
         // NO-OP.
     }
@@ -65,6 +77,14 @@ impl AnyConnectionBackend for SqliteConnection {
         Connection::should_flush(self)
     }
 
+    fn rollback_leaked_transaction(&mut self) -> BoxFuture<'_, sqlx_core::Result<bool>> {
+        Connection::rollback_leaked_transaction(self)
+    }
+
+    fn cancel_token(&self) -> Option<CancelToken> {
+        Connection::cancel_token(self)
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(
         &mut self,
@@ -79,18 +99,22 @@ impl AnyConnectionBackend for SqliteConnection {
     ) -> BoxStream<'q, sqlx_core::Result<Either<AnyQueryResult, AnyRow>>> {
         let persistent = arguments.is_some();
         let args = arguments.map(map_arguments);
+        let query = self.sql_rewriter.rewrite(query);
 
         Box::pin(
-            self.worker
-                .execute(query, args, self.row_channel_size, persistent)
-                .map_ok(flume::Receiver::into_stream)
-                .try_flatten_stream()
-                .map(
-                    move |res: sqlx_core::Result<Either<SqliteQueryResult, SqliteRow>>| match res? {
-                        Either::Left(result) => Ok(Either::Left(map_result(result))),
-                        Either::Right(row) => Ok(Either::Right(AnyRow::try_from(&row)?)),
-                    },
-                ),
+            async move {
+                self.worker
+                    .execute(&query, args, self.row_channel_size, persistent, None)
+                    .await
+            }
+            .map_ok(flume::Receiver::into_stream)
+            .try_flatten_stream()
+            .map(
+                move |res: sqlx_core::Result<Either<SqliteQueryResult, SqliteRow>>| match res? {
+                    Either::Left(result) => Ok(Either::Left(map_result(result))),
+                    Either::Right(row) => Ok(Either::Right(AnyRow::try_from(&row)?)),
+                },
+            ),
         )
     }
 
@@ -103,9 +127,11 @@ impl AnyConnectionBackend for SqliteConnection {
         let args = arguments.map(map_arguments);
 
         Box::pin(async move {
+            let query = self.sql_rewriter.rewrite(query);
+
             let stream = self
                 .worker
-                .execute(query, args, self.row_channel_size, persistent)
+                .execute(&query, args, self.row_channel_size, persistent, None)
                 .map_ok(flume::Receiver::into_stream)
                 .await?;
             futures_util::pin_mut!(stream);
@@ -189,6 +215,7 @@ impl<'a> TryFrom<&'a AnyConnectOptions> for SqliteConnectOptions {
     fn try_from(opts: &'a AnyConnectOptions) -> Result<Self, Self::Error> {
         let mut opts_out = SqliteConnectOptions::from_url(&opts.database_url)?;
         opts_out.log_settings = opts.log_settings.clone();
+        opts_out.sql_rewriter = opts.sql_rewriter.clone();
         Ok(opts_out)
     }
 }
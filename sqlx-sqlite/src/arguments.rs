@@ -5,6 +5,7 @@ use crate::Sqlite;
 use atoi::atoi;
 use libsqlite3_sys::SQLITE_OK;
 use std::borrow::Cow;
+use std::fmt::Write;
 
 pub(crate) use sqlx_core::arguments::*;
 
@@ -33,6 +34,38 @@ impl<'q> SqliteArguments<'q> {
         }
     }
 
+    // The values of the arguments formatted for logging, see
+    // `ConnectOptions::log_parameter_values()`.
+    pub(crate) fn format_values(&self) -> String {
+        let mut formatted = String::from("[");
+
+        for (index, value) in self.values.iter().enumerate() {
+            if index > 0 {
+                formatted.push_str(", ");
+            }
+
+            let _ = match value {
+                SqliteArgumentValue::Null => write!(formatted, "NULL"),
+                SqliteArgumentValue::Text(text) => write!(formatted, "{text:?}"),
+                SqliteArgumentValue::Blob(blob) => {
+                    formatted.push_str("x'");
+
+                    for byte in blob.iter() {
+                        let _ = write!(formatted, "{byte:02x}");
+                    }
+
+                    write!(formatted, "'")
+                }
+                SqliteArgumentValue::Double(double) => write!(formatted, "{double}"),
+                SqliteArgumentValue::Int(int) => write!(formatted, "{int}"),
+                SqliteArgumentValue::Int64(int) => write!(formatted, "{int}"),
+            };
+        }
+
+        formatted.push(']');
+        formatted
+    }
+
     pub(crate) fn into_static(self) -> SqliteArguments<'static> {
         SqliteArguments {
             values: self
@@ -57,6 +90,9 @@ impl<'q> Arguments<'q> for SqliteArguments<'q> {
     {
         self.add(value)
     }
+    fn try_clone(&self) -> Option<Self> {
+        Some(self.clone())
+    }
 }
 
 impl SqliteArguments<'_> {
@@ -1,8 +1,8 @@
-use crate::connection::handle::ConnectionHandle;
+use crate::connection::handle::{ConnectionHandle, InterruptHandle};
 use crate::connection::LogSettings;
-use crate::connection::{ConnectionState, Statements};
+use crate::connection::{ConnectionState, SqliteWorkerPool, Statements};
 use crate::error::Error;
-use crate::{SqliteConnectOptions, SqliteError};
+use crate::{SqliteConnectOptions, SqliteDbConfig, SqliteError};
 use libsqlite3_sys::{
     sqlite3, sqlite3_busy_timeout, sqlite3_db_config, sqlite3_extended_result_codes, sqlite3_free,
     sqlite3_load_extension, sqlite3_open_v2, SQLITE_DBCONFIG_ENABLE_LOAD_EXTENSION, SQLITE_OK,
@@ -45,7 +45,10 @@ pub struct EstablishParams {
     statement_cache_capacity: usize,
     log_settings: LogSettings,
     extensions: IndexMap<CString, Option<CString>>,
+    db_config: IndexMap<SqliteDbConfig, bool>,
     pub(crate) thread_name: String,
+    pub(crate) thread_stack_size: Option<usize>,
+    pub(crate) worker_pool: Option<SqliteWorkerPool>,
     pub(crate) command_channel_size: usize,
     #[cfg(feature = "regexp")]
     register_regexp_function: bool,
@@ -154,7 +157,10 @@ impl EstablishParams {
             statement_cache_capacity: options.statement_cache_capacity,
             log_settings: options.log_settings.clone(),
             extensions,
+            db_config: options.db_config.clone(),
             thread_name: (options.thread_name)(thread_id as u64),
+            thread_stack_size: options.thread_stack_size,
+            worker_pool: options.worker_pool.clone(),
             command_channel_size: options.command_channel_size,
             #[cfg(feature = "regexp")]
             register_regexp_function: options.register_regexp_function,
@@ -214,6 +220,22 @@ impl EstablishParams {
             sqlite3_extended_result_codes(handle.as_ptr(), 1);
         }
 
+        for (config, enabled) in &self.db_config {
+            // <https://www.sqlite.org/c3ref/db_config.html>
+            status = unsafe {
+                sqlite3_db_config(
+                    handle.as_ptr(),
+                    config.as_int(),
+                    c_int::from(*enabled),
+                    null::<c_int>(),
+                )
+            };
+
+            if status != SQLITE_OK {
+                return Err(Error::Database(Box::new(SqliteError::new(handle.as_ptr()))));
+            }
+        }
+
         if !self.extensions.is_empty() {
             // Enable loading extensions
             unsafe {
@@ -287,6 +309,7 @@ impl EstablishParams {
         }
 
         Ok(ConnectionState {
+            interrupt: InterruptHandle::new(&handle),
             handle,
             statements: Statements::new(self.statement_cache_capacity),
             transaction_depth: 0,
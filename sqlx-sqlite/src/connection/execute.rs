@@ -1,6 +1,7 @@
 use crate::connection::{ConnectionHandle, ConnectionState};
 use crate::error::Error;
 use crate::logger::QueryLogger;
+use crate::row::SelectedColumns;
 use crate::statement::{StatementHandle, VirtualStatement};
 use crate::{SqliteArguments, SqliteQueryResult, SqliteRow};
 use sqlx_core::Either;
@@ -16,6 +17,15 @@ pub struct ExecuteIter<'a> {
     args_used: usize,
 
     goto_next: bool,
+
+    /// the total number of changes on the connection before the current statement was executed
+    total_changes: u64,
+
+    /// the only columns to keep in the rows, see `Query::select_columns()`
+    selected_columns: Option<&'a [Box<str>]>,
+
+    /// the columns kept in the rows of the current statement, if only some are selected
+    selected: Option<SelectedColumns>,
 }
 
 pub(crate) fn iter<'a>(
@@ -27,7 +37,11 @@ pub(crate) fn iter<'a>(
     // fetch the cached statement or allocate a new one
     let statement = conn.statements.get(query, persistent)?;
 
-    let logger = QueryLogger::new(query, conn.log_settings.clone());
+    let mut logger = QueryLogger::new(query, conn.log_settings.clone());
+
+    if let Some(args) = &args {
+        logger.log_parameter_values(|| args.format_values());
+    }
 
     Ok(ExecuteIter {
         handle: &mut conn.handle,
@@ -36,6 +50,9 @@ pub(crate) fn iter<'a>(
         args,
         args_used: 0,
         goto_next: true,
+        total_changes: 0,
+        selected_columns: None,
+        selected: None,
     })
 }
 
@@ -53,7 +70,12 @@ fn bind(
     Ok(n)
 }
 
-impl ExecuteIter<'_> {
+impl<'a> ExecuteIter<'a> {
+    pub(crate) fn select_columns(mut self, selected_columns: Option<&'a [Box<str>]>) -> Self {
+        self.selected_columns = selected_columns;
+        self
+    }
+
     pub fn finish(&mut self) -> Result<(), Error> {
         for res in self {
             let _ = res?;
@@ -83,11 +105,28 @@ impl Iterator for ExecuteIter<'_> {
 
             statement.handle.clear_bindings();
 
+            self.total_changes = self.handle.total_changes();
+
             match bind(&mut statement.handle, &self.args, self.args_used) {
                 Ok(args_used) => self.args_used += args_used,
                 Err(e) => return Some(Err(e)),
             }
 
+            self.selected = match self.selected_columns {
+                // statements which don't return rows are left as they are
+                Some(selected_columns) if !statement.columns.is_empty() => {
+                    match SelectedColumns::new(
+                        selected_columns,
+                        statement.columns,
+                        statement.column_names,
+                    ) {
+                        Ok(selected) => Some(selected),
+                        Err(e) => return Some(Err(e)),
+                    }
+                }
+                _ => None,
+            };
+
             statement
         } else {
             self.statement.current()?
@@ -97,16 +136,28 @@ impl Iterator for ExecuteIter<'_> {
             Ok(true) => {
                 self.logger.increment_rows_returned();
 
-                Some(Ok(Either::Right(SqliteRow::current(
-                    &statement.handle,
-                    &statement.columns,
-                    &statement.column_names,
-                ))))
+                let row = match &self.selected {
+                    Some(selected) => SqliteRow::current_selected(&statement.handle, selected),
+                    None => SqliteRow::current(
+                        &statement.handle,
+                        &statement.columns,
+                        &statement.column_names,
+                    ),
+                };
+
+                Some(Ok(Either::Right(row)))
             }
             Ok(false) => {
                 let last_insert_rowid = self.handle.last_insert_rowid();
 
-                let changes = statement.handle.changes();
+                // `sqlite3_changes()` is only updated by `INSERT`, `UPDATE` and `DELETE`
+                // statements, so it would report the changes of a previous statement for
+                // any other one; checking whether any row was changed at all avoids that
+                let changes = if self.handle.total_changes() != self.total_changes {
+                    statement.handle.changes()
+                } else {
+                    0
+                };
                 self.logger.increase_rows_affected(changes);
 
                 let done = SqliteQueryResult {
@@ -6,7 +6,7 @@ use futures_core::stream::BoxStream;
 use futures_util::{TryFutureExt, TryStreamExt};
 use sqlx_core::describe::Describe;
 use sqlx_core::error::Error;
-use sqlx_core::executor::{Execute, Executor};
+use sqlx_core::executor::{sql_with_comment, Execute, Executor};
 use sqlx_core::Either;
 
 impl<'c> Executor<'c> for &'c mut SqliteConnection {
@@ -20,15 +20,26 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
-        let sql = query.sql();
+        let comment = query.comment();
+        let sql = sql_with_comment(self.sql_rewriter.rewrite(query.sql()), comment);
         let arguments = query.take_arguments();
         let persistent = query.persistent() && arguments.is_some();
+        let selected_columns = query.selected_columns();
 
         Box::pin(
-            self.worker
-                .execute(sql, arguments, self.row_channel_size, persistent)
-                .map_ok(flume::Receiver::into_stream)
-                .try_flatten_stream(),
+            async move {
+                self.worker
+                    .execute(
+                        &sql,
+                        arguments,
+                        self.row_channel_size,
+                        persistent,
+                        selected_columns,
+                    )
+                    .await
+            }
+            .map_ok(flume::Receiver::into_stream)
+            .try_flatten_stream(),
         )
     }
 
@@ -40,14 +51,22 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
-        let sql = query.sql();
+        let comment = query.comment();
+        let sql = sql_with_comment(self.sql_rewriter.rewrite(query.sql()), comment);
         let arguments = query.take_arguments();
         let persistent = query.persistent() && arguments.is_some();
+        let selected_columns = query.selected_columns();
 
         Box::pin(async move {
             let stream = self
                 .worker
-                .execute(sql, arguments, self.row_channel_size, persistent)
+                .execute(
+                    &sql,
+                    arguments,
+                    self.row_channel_size,
+                    persistent,
+                    selected_columns,
+                )
                 .map_ok(flume::Receiver::into_stream)
                 .try_flatten_stream();
 
@@ -72,7 +91,8 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
         'c: 'e,
     {
         Box::pin(async move {
-            let statement = self.worker.prepare(sql).await?;
+            let rewritten = self.sql_rewriter.rewrite(sql);
+            let statement = self.worker.prepare(&rewritten).await?;
 
             Ok(SqliteStatement {
                 sql: sql.into(),
@@ -86,6 +106,10 @@ impl<'c> Executor<'c> for &'c mut SqliteConnection {
     where
         'c: 'e,
     {
-        Box::pin(self.worker.describe(sql))
+        Box::pin(async move {
+            let sql = self.sql_rewriter.rewrite(sql);
+
+            self.worker.describe(&sql).await
+        })
     }
 }
@@ -1,12 +1,15 @@
 use std::ffi::CString;
 use std::ptr;
 use std::ptr::NonNull;
+use std::sync::{Arc, Mutex};
 
 use crate::error::Error;
 use libsqlite3_sys::{
-    sqlite3, sqlite3_close, sqlite3_exec, sqlite3_last_insert_rowid, SQLITE_LOCKED_SHAREDCACHE,
+    sqlite3, sqlite3_changes, sqlite3_close, sqlite3_exec, sqlite3_get_autocommit,
+    sqlite3_interrupt, sqlite3_last_insert_rowid, sqlite3_total_changes, SQLITE_LOCKED_SHAREDCACHE,
     SQLITE_OK,
 };
+use sqlx_core::sync::lock_poisoned;
 
 use crate::{statement::unlock_notify, SqliteError};
 
@@ -33,6 +36,33 @@ unsafe impl Send for ConnectionHandle {}
 // SAFETY: this type does nothing but provide access to the DB handle pointer.
 unsafe impl Send for ConnectionHandleRaw {}
 
+/// A handle to interrupt the statements running on a connection from any thread.
+///
+/// It must be [released][Self::release] before the connection is closed, after which
+/// interrupting does nothing.
+#[derive(Clone, Debug)]
+pub(crate) struct InterruptHandle(Arc<Mutex<Option<ConnectionHandleRaw>>>);
+
+impl InterruptHandle {
+    pub(crate) fn new(handle: &ConnectionHandle) -> Self {
+        InterruptHandle(Arc::new(Mutex::new(Some(handle.to_raw()))))
+    }
+
+    pub(crate) fn interrupt(&self) {
+        let handle = lock_poisoned(&self.0);
+
+        if let Some(handle) = &*handle {
+            // SAFETY: `sqlite3_interrupt()` may be called from any thread while the connection
+            // is open, which the lock ensures
+            unsafe { sqlite3_interrupt(handle.0.as_ptr()) }
+        }
+    }
+
+    pub(crate) fn release(&self) {
+        *lock_poisoned(&self.0) = None;
+    }
+}
+
 impl ConnectionHandle {
     #[inline]
     pub(super) unsafe fn new(ptr: *mut sqlite3) -> Self {
@@ -58,6 +88,22 @@ impl ConnectionHandle {
         unsafe { sqlite3_last_insert_rowid(self.as_ptr()) }
     }
 
+    pub(crate) fn changes(&mut self) -> u64 {
+        // SAFETY: we have exclusive access to the database handle
+        unsafe { sqlite3_changes(self.as_ptr()) as u64 }
+    }
+
+    pub(crate) fn total_changes(&mut self) -> u64 {
+        // SAFETY: we have exclusive access to the database handle
+        unsafe { sqlite3_total_changes(self.as_ptr()) as u64 }
+    }
+
+    /// Returns `true` if a transaction is open, i.e. the connection is not in autocommit mode.
+    pub(crate) fn in_transaction(&mut self) -> bool {
+        // SAFETY: we have exclusive access to the database handle
+        unsafe { sqlite3_get_autocommit(self.as_ptr()) == 0 }
+    }
+
     pub(crate) fn exec(&mut self, query: impl Into<String>) -> Result<(), Error> {
         let query = query.into();
         let query = CString::new(query).map_err(|_| err_protocol!("query contains nul bytes"))?;
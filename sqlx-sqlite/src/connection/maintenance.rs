@@ -0,0 +1,61 @@
+use std::path::Path;
+
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::query::query;
+use crate::SqliteConnection;
+
+impl SqliteConnection {
+    /// Rebuild the main database file with `VACUUM`, to reclaim the space of deleted rows and
+    /// defragment it.
+    ///
+    /// This rewrites the whole database and holds an exclusive lock on it until it is done, which
+    /// can take a long time for a large database. Its progress can be followed by setting a
+    /// [progress handler](crate::LockedSqliteHandle::set_progress_handler) beforehand, which can
+    /// also stop it by returning `false`.
+    ///
+    /// Returns an error if a transaction is open on the connection, as SQLite does not support
+    /// vacuuming within one.
+    pub async fn vacuum(&mut self) -> Result<(), Error> {
+        self.execute("VACUUM").await?;
+
+        Ok(())
+    }
+
+    /// Write a vacuumed copy of the main database to a new file at `path`, with `VACUUM INTO`.
+    ///
+    /// The copy is consistent and compacted, as with [`vacuum()`](Self::vacuum), and the database
+    /// itself is left as-is, which makes this a simple way to back up a database which is in use.
+    /// The path is bound as a parameter, so it does not need to be quoted.
+    ///
+    /// The copy is written with the same VFS as the database, so no file is written for an
+    /// in-memory database, e.g. one opened with `sqlite::memory:`.
+    ///
+    /// Returns an error if the file at `path` already exists and is not empty, if `path` is not
+    /// valid UTF-8, or if a transaction is open on the connection.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::SqliteConnection) -> sqlx::Result<()> {
+    /// conn.vacuum_into("backups/app-2024-01-01.db").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn vacuum_into(&mut self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+
+        let Some(path) = path.to_str() else {
+            return Err(Error::Configuration(
+                format!("path to vacuum into is not valid UTF-8: {}", path.display()).into(),
+            ));
+        };
+
+        query("VACUUM INTO ?1")
+            .bind(path)
+            .execute(&mut *self)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@ use futures_util::future;
 use libsqlite3_sys::{sqlite3, sqlite3_progress_handler};
 use sqlx_core::common::StatementCache;
 use sqlx_core::error::Error;
-use sqlx_core::transaction::Transaction;
+use sqlx_core::transaction::{Transaction, TransactionOptions};
 use std::cmp::Ordering;
 use std::fmt::{self, Debug, Formatter};
 use std::os::raw::{c_int, c_void};
@@ -21,7 +21,9 @@ use std::fmt::Write;
 
 pub(crate) use sqlx_core::connection::*;
 
-pub(crate) use handle::{ConnectionHandle, ConnectionHandleRaw};
+pub(crate) use handle::{ConnectionHandle, ConnectionHandleRaw, InterruptHandle};
+pub use pragma::{SqliteColumnInfo, SqliteForeignKeyInfo, SqliteIndexInfo, SqliteIndexOrigin};
+pub use worker_pool::SqliteWorkerPool;
 
 pub(crate) mod collation;
 pub(crate) mod describe;
@@ -31,8 +33,11 @@ mod executor;
 mod explain;
 mod handle;
 mod intmap;
+mod maintenance;
+mod pragma;
 
 mod worker;
+mod worker_pool;
 
 /// A connection to an open [Sqlite] database.
 ///
@@ -48,6 +53,7 @@ pub struct SqliteConnection {
     optimize_on_close: OptimizeOnClose,
     pub(crate) worker: ConnectionWorker,
     pub(crate) row_channel_size: usize,
+    pub(crate) sql_rewriter: SqlRewriterSettings,
 }
 
 pub struct LockedSqliteHandle<'a> {
@@ -61,6 +67,9 @@ unsafe impl Send for Handler {}
 pub(crate) struct ConnectionState {
     pub(crate) handle: ConnectionHandle,
 
+    // used to interrupt the statements running on the connection from other threads
+    pub(crate) interrupt: InterruptHandle,
+
     // transaction status
     pub(crate) transaction_depth: usize,
 
@@ -100,6 +109,7 @@ impl SqliteConnection {
             optimize_on_close: options.optimize_on_close.clone(),
             worker,
             row_channel_size: options.row_channel_size,
+            sql_rewriter: options.sql_rewriter.clone(),
         })
     }
 
@@ -112,6 +122,20 @@ impl SqliteConnection {
 
         Ok(LockedSqliteHandle { guard })
     }
+
+    /// Returns the rowid of the last row inserted on this connection, or `0` if none was.
+    ///
+    /// This is the value of [`sqlite3_last_insert_rowid()`] once the statements executed so far
+    /// have completed, e.g. after the stream of an `INSERT ... RETURNING` query is exhausted. It is
+    /// also available for each statement from [`SqliteQueryResult::last_insert_rowid()`].
+    ///
+    /// Returns an error if the worker thread crashed.
+    ///
+    /// [`sqlite3_last_insert_rowid()`]: https://www.sqlite.org/c3ref/last_insert_rowid.html
+    /// [`SqliteQueryResult::last_insert_rowid()`]: crate::SqliteQueryResult::last_insert_rowid
+    pub async fn last_insert_rowid(&mut self) -> Result<i64, Error> {
+        Ok(self.lock_handle().await?.last_insert_rowid())
+    }
 }
 
 impl Debug for SqliteConnection {
@@ -166,6 +190,16 @@ impl Connection for SqliteConnection {
         Transaction::begin(self)
     }
 
+    fn begin_with(
+        &mut self,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
+    where
+        Self: Sized,
+    {
+        Transaction::begin_with(self, options)
+    }
+
     fn cached_statements_size(&self) -> usize {
         self.worker
             .shared
@@ -173,6 +207,10 @@ impl Connection for SqliteConnection {
             .load(std::sync::atomic::Ordering::Acquire)
     }
 
+    fn cached_statements(&mut self) -> BoxFuture<'_, Result<Vec<String>, Error>> {
+        Box::pin(self.worker.cached_statements())
+    }
+
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             self.worker.clear_cache().await?;
@@ -198,6 +236,20 @@ impl Connection for SqliteConnection {
     fn should_flush(&self) -> bool {
         false
     }
+
+    #[doc(hidden)]
+    fn rollback_leaked_transaction(&mut self) -> BoxFuture<'_, Result<bool, Error>> {
+        Box::pin(self.worker.rollback_leaked_transaction())
+    }
+
+    fn cancel_token(&self) -> Option<CancelToken> {
+        let interrupt = self.worker.interrupt.clone();
+
+        Some(CancelToken::new(async move {
+            interrupt.interrupt();
+            Ok(())
+        }))
+    }
 }
 
 /// Implements a C binding to a progress callback. The function returns `0` if the
@@ -234,6 +286,23 @@ impl LockedSqliteHandle<'_> {
         self.guard.handle.as_non_null_ptr()
     }
 
+    /// Returns the rowid of the last row inserted on this connection, or `0` if none was.
+    ///
+    /// See [`sqlite3_last_insert_rowid()`](https://www.sqlite.org/c3ref/last_insert_rowid.html).
+    pub fn last_insert_rowid(&mut self) -> i64 {
+        self.guard.handle.last_insert_rowid()
+    }
+
+    /// Returns the number of rows modified by the last `INSERT`, `UPDATE` or `DELETE` statement
+    /// completed on this connection.
+    ///
+    /// Unlike [`SqliteQueryResult::rows_affected()`][crate::SqliteQueryResult::rows_affected],
+    /// this is not reset by other kinds of statements.
+    /// See [`sqlite3_changes()`](https://www.sqlite.org/c3ref/changes.html).
+    pub fn changes(&mut self) -> u64 {
+        self.guard.handle.changes()
+    }
+
     /// Apply a collation to the open database.
     ///
     /// See [`SqliteConnectOptions::collation()`] for details.
@@ -289,6 +358,7 @@ impl Drop for ConnectionState {
     fn drop(&mut self) {
         // explicitly drop statements before the connection handle is dropped
         self.statements.clear();
+        self.interrupt.release();
         self.remove_progress_handler();
     }
 }
@@ -327,6 +397,10 @@ impl Statements {
         self.cached.len()
     }
 
+    fn keys(&self) -> Vec<String> {
+        self.cached.keys().map(str::to_owned).collect()
+    }
+
     fn clear(&mut self) {
         self.cached.clear();
         self.temp = None;
@@ -0,0 +1,218 @@
+use crate::error::Error;
+use crate::query_as::query_as;
+use crate::SqliteConnection;
+
+/// A column of a table, as returned by [`SqliteConnection::pragma_table_info()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SqliteColumnInfo {
+    /// The position of the column in the table, starting at 0.
+    pub cid: i64,
+
+    /// The name of the column.
+    pub name: String,
+
+    /// The declared type of the column, empty if it has none.
+    pub declared_type: String,
+
+    /// Whether the column has a `NOT NULL` constraint.
+    pub not_null: bool,
+
+    /// The SQL expression of the default value of the column, if it has one.
+    pub default_value: Option<String>,
+
+    /// The position of the column in the primary key of the table, starting at 1, or 0 if the
+    /// column is not part of it.
+    pub primary_key: i64,
+}
+
+/// A foreign key of a table, as returned by [`SqliteConnection::pragma_foreign_key_list()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SqliteForeignKeyInfo {
+    /// The identifier of the foreign key in the table.
+    pub id: i64,
+
+    /// The name of the referenced table.
+    pub referenced_table: String,
+
+    /// The columns of the table which reference the other table, in order.
+    pub columns: Vec<String>,
+
+    /// The referenced columns, in the same order as [`columns`](Self::columns).
+    ///
+    /// A column is `None` if the foreign key references the primary key of the table
+    /// implicitly, e.g. `REFERENCES users` instead of `REFERENCES users(id)`.
+    pub referenced_columns: Vec<Option<String>>,
+
+    /// The action on update of the referenced rows, e.g. `CASCADE` or `NO ACTION`.
+    pub on_update: String,
+
+    /// The action on deletion of the referenced rows, e.g. `CASCADE` or `NO ACTION`.
+    pub on_delete: String,
+}
+
+/// An index of a table, as returned by [`SqliteConnection::pragma_index_list()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SqliteIndexInfo {
+    /// The name of the index.
+    ///
+    /// Indexes created for constraints are named automatically, e.g.
+    /// `sqlite_autoindex_users_1`.
+    pub name: String,
+
+    /// Whether the index is `UNIQUE`.
+    pub unique: bool,
+
+    /// How the index was created.
+    pub origin: SqliteIndexOrigin,
+
+    /// Whether the index is partial, i.e. has a `WHERE` clause.
+    pub partial: bool,
+}
+
+/// How an index was created, see [`SqliteIndexInfo::origin`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SqliteIndexOrigin {
+    /// With `CREATE INDEX`.
+    CreateIndex,
+
+    /// For a `UNIQUE` constraint.
+    Unique,
+
+    /// For a `PRIMARY KEY` constraint.
+    PrimaryKey,
+}
+
+impl SqliteIndexOrigin {
+    fn from_code(code: &str) -> Result<Self, Error> {
+        match code {
+            "c" => Ok(SqliteIndexOrigin::CreateIndex),
+            "u" => Ok(SqliteIndexOrigin::Unique),
+            "pk" => Ok(SqliteIndexOrigin::PrimaryKey),
+            _ => Err(err_protocol!("unknown index origin: {:?}", code)),
+        }
+    }
+}
+
+// The pragmas are queried with their table-valued functions, which accept the name of the table as
+// a bound parameter, and return the columns of each pragma in the order they are documented:
+// https://www.sqlite.org/pragma.html#pragfunc
+impl SqliteConnection {
+    /// Returns the columns of a table or a view, in order, with `PRAGMA table_info`.
+    ///
+    /// The table is looked up in all the attached databases, and can be qualified with the name
+    /// of one, e.g. `main.users`. An empty list is returned if there is no such table.
+    ///
+    /// Returns an error if the worker thread crashed.
+    pub async fn pragma_table_info(&mut self, table: &str) -> Result<Vec<SqliteColumnInfo>, Error> {
+        let (schema, table) = split_table_name(table);
+
+        // language=SQLite
+        let rows: Vec<(i64, String, String, bool, Option<String>, i64)> = query_as(
+            "SELECT cid, name, type, \"notnull\", dflt_value, pk FROM pragma_table_info(?1, ?2)",
+        )
+        .bind(table)
+        .bind(schema)
+        .fetch_all(&mut *self)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(cid, name, declared_type, not_null, default_value, primary_key)| {
+                    SqliteColumnInfo {
+                        cid,
+                        name,
+                        declared_type,
+                        not_null,
+                        default_value,
+                        primary_key,
+                    }
+                },
+            )
+            .collect())
+    }
+
+    /// Returns the foreign keys of a table, with `PRAGMA foreign_key_list`.
+    ///
+    /// The foreign keys referencing multiple columns are returned as one, with their columns in
+    /// order. An empty list is returned if there is no such table.
+    ///
+    /// See [`pragma_table_info()`](Self::pragma_table_info) for how the table is looked up.
+    pub async fn pragma_foreign_key_list(
+        &mut self,
+        table: &str,
+    ) -> Result<Vec<SqliteForeignKeyInfo>, Error> {
+        let (schema, table) = split_table_name(table);
+
+        // language=SQLite
+        let rows: Vec<(i64, String, String, Option<String>, String, String)> = query_as(
+            "SELECT id, \"table\", \"from\", \"to\", on_update, on_delete \
+             FROM pragma_foreign_key_list(?1, ?2) ORDER BY id, seq",
+        )
+        .bind(table)
+        .bind(schema)
+        .fetch_all(&mut *self)
+        .await?;
+
+        let mut foreign_keys: Vec<SqliteForeignKeyInfo> = Vec::new();
+
+        for (id, referenced_table, column, referenced_column, on_update, on_delete) in rows {
+            match foreign_keys.last_mut() {
+                Some(foreign_key) if foreign_key.id == id => {
+                    foreign_key.columns.push(column);
+                    foreign_key.referenced_columns.push(referenced_column);
+                }
+                _ => foreign_keys.push(SqliteForeignKeyInfo {
+                    id,
+                    referenced_table,
+                    columns: vec![column],
+                    referenced_columns: vec![referenced_column],
+                    on_update,
+                    on_delete,
+                }),
+            }
+        }
+
+        Ok(foreign_keys)
+    }
+
+    /// Returns the indexes of a table, with `PRAGMA index_list`.
+    ///
+    /// An empty list is returned if there is no such table.
+    ///
+    /// See [`pragma_table_info()`](Self::pragma_table_info) for how the table is looked up.
+    pub async fn pragma_index_list(&mut self, table: &str) -> Result<Vec<SqliteIndexInfo>, Error> {
+        let (schema, table) = split_table_name(table);
+
+        // language=SQLite
+        let rows: Vec<(String, bool, String, bool)> = query_as(
+            "SELECT name, \"unique\", origin, partial FROM pragma_index_list(?1, ?2) ORDER BY seq",
+        )
+        .bind(table)
+        .bind(schema)
+        .fetch_all(&mut *self)
+        .await?;
+
+        rows.into_iter()
+            .map(|(name, unique, origin, partial)| {
+                Ok(SqliteIndexInfo {
+                    name,
+                    unique,
+                    origin: SqliteIndexOrigin::from_code(&origin)?,
+                    partial,
+                })
+            })
+            .collect()
+    }
+}
+
+// `main.users` is the table `users` of the `main` schema, a `NULL` schema searches all of them
+fn split_table_name(table: &str) -> (Option<&str>, &str) {
+    match table.split_once('.') {
+        Some((schema, table)) => (Some(schema), table),
+        None => (None, table),
+    }
+}
@@ -1,44 +1,66 @@
 use std::borrow::Cow;
 use std::future::Future;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::thread;
 
 use futures_intrusive::sync::{Mutex, MutexGuard};
 
 use futures_channel::oneshot;
+use futures_core::future::BoxFuture;
+use futures_util::task::{waker_ref, ArcWake};
 use sqlx_core::describe::Describe;
 use sqlx_core::error::Error;
+use sqlx_core::sync::lock_poisoned;
 use sqlx_core::transaction::{
-    begin_ansi_transaction_sql, commit_ansi_transaction_sql, rollback_ansi_transaction_sql,
+    commit_ansi_transaction_sql, rollback_ansi_transaction_sql, TransactionOptions,
 };
 use sqlx_core::Either;
 
 use crate::connection::describe::describe;
 use crate::connection::establish::EstablishParams;
 use crate::connection::ConnectionState;
-use crate::connection::{execute, ConnectionHandleRaw};
+use crate::connection::{execute, ConnectionHandleRaw, InterruptHandle, SqliteWorkerPool};
+use crate::transaction::begin_transaction_sql;
 use crate::{Sqlite, SqliteArguments, SqliteQueryResult, SqliteRow, SqliteStatement};
 
-// Each SQLite connection has a dedicated thread.
-
-// TODO: Tweak this so that we can use a thread pool per pool of SQLite3 connections to reduce
-//       OS resource usage. Low priority because a high concurrent load for SQLite3 is very
-//       unlikely.
+// Each SQLite connection has a dedicated thread, unless a shared `SqliteWorkerPool` is used.
 
 pub(crate) struct ConnectionWorker {
     command_tx: flume::Sender<Command>,
     /// The `sqlite3` pointer. NOTE: access is unsynchronized!
     pub(crate) _handle_raw: ConnectionHandleRaw,
+    /// Used to interrupt the statements running on this connection.
+    pub(crate) interrupt: InterruptHandle,
     /// Mutex for locking access to the database.
     pub(crate) shared: Arc<WorkerSharedState>,
 }
 
 pub(crate) struct WorkerSharedState {
+    transaction_depth: AtomicUsize,
     pub(crate) cached_statements_size: AtomicUsize,
     pub(crate) conn: Mutex<ConnectionState>,
 }
 
+impl WorkerSharedState {
+    fn new(conn: ConnectionState) -> Self {
+        WorkerSharedState {
+            transaction_depth: AtomicUsize::new(0),
+            cached_statements_size: AtomicUsize::new(0),
+            // note: must be fair because in `Command::UnlockDb` we unlock the mutex
+            // and then immediately try to relock it; an unfair mutex would immediately
+            // grant us the lock even if another task is waiting.
+            conn: Mutex::new(conn, true),
+        }
+    }
+
+    pub(crate) fn get_transaction_depth(&self) -> usize {
+        self.transaction_depth.load(Ordering::Acquire)
+    }
+}
+
 enum Command {
     Prepare {
         query: Box<str>,
@@ -52,9 +74,11 @@ enum Command {
         query: Box<str>,
         arguments: Option<SqliteArguments<'static>>,
         persistent: bool,
+        selected_columns: Option<Box<[Box<str>]>>,
         tx: flume::Sender<Result<Either<SqliteQueryResult, SqliteRow>, Error>>,
     },
     Begin {
+        options: TransactionOptions,
         tx: rendezvous_oneshot::Sender<Result<(), Error>>,
     },
     Commit {
@@ -63,7 +87,13 @@ enum Command {
     Rollback {
         tx: Option<rendezvous_oneshot::Sender<Result<(), Error>>>,
     },
+    RollbackLeakedTransaction {
+        tx: oneshot::Sender<Result<bool, Error>>,
+    },
     UnlockDb,
+    CachedStatements {
+        tx: oneshot::Sender<Vec<String>>,
+    },
     ClearCache {
         tx: oneshot::Sender<()>,
     },
@@ -75,190 +105,380 @@ enum Command {
     },
 }
 
-impl ConnectionWorker {
-    pub(crate) async fn establish(params: EstablishParams) -> Result<Self, Error> {
-        let (establish_tx, establish_rx) = oneshot::channel();
+/// What the worker should do after handling a command.
+enum Flow {
+    Continue,
+    /// Release the lock on the connection until it can be locked again.
+    UnlockDb,
+    /// Stop processing commands.
+    Stop,
+    /// Drop the connection, then confirm the shutdown.
+    Shutdown(oneshot::Sender<()>),
+}
+
+/// The state of a worker, besides the connection itself.
+struct WorkerState {
+    shared: Arc<WorkerSharedState>,
+    // If COMMIT or ROLLBACK is processed but not acknowledged, there would be another
+    // ROLLBACK sent when the `Transaction` drops. We need to ignore it otherwise we
+    // would rollback an already completed transaction.
+    ignore_next_start_rollback: bool,
+}
 
-        thread::Builder::new()
-            .name(params.thread_name.clone())
-            .spawn(move || {
-                let (command_tx, command_rx) = flume::bounded(params.command_channel_size);
+impl WorkerState {
+    fn new(shared: Arc<WorkerSharedState>) -> Self {
+        WorkerState {
+            shared,
+            ignore_next_start_rollback: false,
+        }
+    }
 
-                let conn = match params.establish() {
-                    Ok(conn) => conn,
+    async fn handle(&mut self, conn: &mut ConnectionState, cmd: Command) -> Flow {
+        let shared = &*self.shared;
+
+        match cmd {
+            Command::Prepare { query, tx } => {
+                tx.send(prepare(conn, &query).map(|prepared| {
+                    update_cached_statements_size(conn, &shared.cached_statements_size);
+                    prepared
+                }))
+                .ok();
+            }
+            Command::Describe { query, tx } => {
+                tx.send(describe(conn, &query)).ok();
+            }
+            Command::Execute {
+                query,
+                arguments,
+                persistent,
+                selected_columns,
+                tx,
+            } => {
+                let iter = match execute::iter(conn, &query, arguments, persistent)
+                    .map(|iter| iter.select_columns(selected_columns.as_deref()))
+                {
+                    Ok(iter) => iter,
                     Err(e) => {
-                        establish_tx.send(Err(e)).ok();
-                        return;
+                        tx.send(Err(e)).ok();
+                        return Flow::Continue;
                     }
                 };
 
-                let shared = Arc::new(WorkerSharedState {
-                    cached_statements_size: AtomicUsize::new(0),
-                    // note: must be fair because in `Command::UnlockDb` we unlock the mutex
-                    // and then immediately try to relock it; an unfair mutex would immediately
-                    // grant us the lock even if another task is waiting.
-                    conn: Mutex::new(conn, true),
-                });
-                let mut conn = shared.conn.try_lock().unwrap();
-
-                if establish_tx
-                    .send(Ok(Self {
-                        command_tx,
-                        _handle_raw: conn.handle.to_raw(),
-                        shared: Arc::clone(&shared),
-                    }))
-                    .is_err()
-                {
+                for res in iter {
+                    if tx.send_async(res).await.is_err() {
+                        break;
+                    }
+                }
+
+                update_cached_statements_size(conn, &shared.cached_statements_size);
+            }
+            Command::Begin { options, tx } => {
+                let depth = conn.transaction_depth;
+                let res = begin_transaction_sql(depth, &options)
+                    .and_then(|sql| conn.handle.exec(sql))
+                    .map(|_| {
+                        conn.transaction_depth += 1;
+                    });
+                let res_ok = res.is_ok();
+                update_transaction_depth(conn, &shared.transaction_depth);
+
+                if tx.send(res).await.is_err() && res_ok {
+                    // The BEGIN was processed but not acknowledged. This means no
+                    // `Transaction` was created and so there is no way to commit /
+                    // rollback this transaction. We need to roll it back
+                    // immediately otherwise it would remain started forever.
+                    if let Err(error) = conn
+                        .handle
+                        .exec(rollback_ansi_transaction_sql(depth + 1))
+                        .map(|_| {
+                            conn.transaction_depth -= 1;
+                        })
+                    {
+                        // The rollback failed. To prevent leaving the connection
+                        // in an inconsistent state we shutdown this worker which
+                        // causes any subsequent operation on the connection to fail.
+                        tracing::error!(%error, "failed to rollback cancelled transaction");
+                        return Flow::Stop;
+                    }
+
+                    update_transaction_depth(conn, &shared.transaction_depth);
+                }
+            }
+            Command::Commit { tx } => {
+                let depth = conn.transaction_depth;
+
+                let res = if depth > 0 {
+                    conn.handle
+                        .exec(commit_ansi_transaction_sql(depth))
+                        .map(|_| {
+                            conn.transaction_depth -= 1;
+                        })
+                } else {
+                    Ok(())
+                };
+                let res_ok = res.is_ok();
+                update_transaction_depth(conn, &shared.transaction_depth);
+
+                if tx.send(res).await.is_err() && res_ok {
+                    // The COMMIT was processed but not acknowledged. This means that
+                    // the `Transaction` doesn't know it was committed and will try to
+                    // rollback on drop. We need to ignore that rollback.
+                    self.ignore_next_start_rollback = true;
+                }
+            }
+            Command::Rollback { tx } => {
+                if self.ignore_next_start_rollback && tx.is_none() {
+                    self.ignore_next_start_rollback = false;
+                    return Flow::Continue;
+                }
+
+                let depth = conn.transaction_depth;
+
+                let res = if depth > 0 {
+                    conn.handle
+                        .exec(rollback_ansi_transaction_sql(depth))
+                        .map(|_| {
+                            conn.transaction_depth -= 1;
+                        })
+                } else {
+                    Ok(())
+                };
+
+                let res_ok = res.is_ok();
+                update_transaction_depth(conn, &shared.transaction_depth);
+
+                if let Some(tx) = tx {
+                    if tx.send(res).await.is_err() && res_ok {
+                        // The ROLLBACK was processed but not acknowledged. This means
+                        // that the `Transaction` doesn't know it was rolled back and
+                        // will try to rollback again on drop. We need to ignore that
+                        // rollback.
+                        self.ignore_next_start_rollback = true;
+                    }
+                }
+            }
+            Command::RollbackLeakedTransaction { tx } => {
+                let res = if conn.handle.in_transaction() {
+                    // this also ends any savepoint of a transaction begun with `begin()`
+                    conn.handle.exec("ROLLBACK").map(|_| {
+                        conn.transaction_depth = 0;
+                        true
+                    })
+                } else {
+                    Ok(false)
+                };
+
+                update_transaction_depth(conn, &shared.transaction_depth);
+                tx.send(res).ok();
+            }
+            Command::CachedStatements { tx } => {
+                tx.send(conn.statements.keys()).ok();
+            }
+            Command::ClearCache { tx } => {
+                conn.statements.clear();
+                update_cached_statements_size(conn, &shared.cached_statements_size);
+                tx.send(()).ok();
+            }
+            Command::UnlockDb => return Flow::UnlockDb,
+            Command::Ping { tx } => {
+                tx.send(()).ok();
+            }
+            Command::Shutdown { tx } => return Flow::Shutdown(tx),
+        }
+
+        Flow::Continue
+    }
+}
+
+/// A worker running on the threads of a [`SqliteWorkerPool`].
+///
+/// Its commands are processed by a future which is polled on a thread of the pool whenever it
+/// is woken up. Unlike a dedicated worker thread, it doesn't block while waiting for the next
+/// command, the lock on the connection, or room in the buffer of rows of a query, so the thread
+/// is released for the other connections in the meantime.
+struct PooledWorker {
+    // `None` once the worker stopped, so the connection is dropped
+    task: std::sync::Mutex<Option<BoxFuture<'static, ()>>>,
+    // whether a job to poll the task was spawned on the pool and hasn't started yet
+    scheduled: AtomicBool,
+    pool: SqliteWorkerPool,
+}
+
+impl PooledWorker {
+    fn schedule(self: &Arc<Self>) {
+        if !self.scheduled.swap(true, Ordering::SeqCst) {
+            let worker = Arc::clone(self);
+            self.pool.spawn(move || worker.run());
+        }
+    }
+
+    fn run(self: Arc<Self>) {
+        // reset before polling, so that waking the task while it is polled schedules it again
+        self.scheduled.store(false, Ordering::SeqCst);
+
+        let waker = waker_ref(&self);
+        let mut cx = Context::from_waker(&waker);
+        let mut task = lock_poisoned(&self.task);
+
+        if let Some(future) = &mut *task {
+            // A panic stops the worker, as it would end a dedicated thread
+            let poll = panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(&mut cx)));
+
+            if !matches!(poll, Ok(Poll::Pending)) {
+                *task = None;
+            }
+        }
+    }
+
+    async fn process(command_rx: flume::Receiver<Command>, shared: Arc<WorkerSharedState>) {
+        let mut state = WorkerState::new(Arc::clone(&shared));
+
+        while let Ok(cmd) = command_rx.recv_async().await {
+            // The lock isn't held between commands, so there is nothing to unlock
+            if matches!(cmd, Command::UnlockDb) {
+                continue;
+            }
+
+            let mut conn = shared.conn.lock().await;
+
+            match state.handle(&mut conn, cmd).await {
+                Flow::Continue | Flow::UnlockDb => {}
+                // Commands received afterwards are dropped with `command_rx`, which fails them
+                // with `WorkerCrashed`
+                Flow::Stop => break,
+                Flow::Shutdown(tx) => {
+                    // drop the connection references before sending confirmation
+                    drop(conn);
+                    drop(state);
+                    drop(shared);
+                    let _ = tx.send(());
                     return;
                 }
+            }
+        }
+    }
+}
+
+impl ArcWake for PooledWorker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.schedule();
+    }
+}
+
+impl ConnectionWorker {
+    pub(crate) async fn establish(params: EstablishParams) -> Result<Self, Error> {
+        match params.worker_pool.clone() {
+            Some(pool) => Self::establish_pooled(params, pool).await,
+            None => Self::establish_dedicated(params).await,
+        }
+    }
+
+    async fn establish_dedicated(params: EstablishParams) -> Result<Self, Error> {
+        let (establish_tx, establish_rx) = oneshot::channel();
+
+        let mut builder = thread::Builder::new().name(params.thread_name.clone());
+
+        if let Some(size) = params.thread_stack_size {
+            builder = builder.stack_size(size);
+        }
+
+        builder.spawn(move || {
+            let (command_tx, command_rx) = flume::bounded(params.command_channel_size);
 
-                // If COMMIT or ROLLBACK is processed but not acknowledged, there would be another
-                // ROLLBACK sent when the `Transaction` drops. We need to ignore it otherwise we
-                // would rollback an already completed transaction.
-                let mut ignore_next_start_rollback = false;
-
-                for cmd in command_rx {
-                    match cmd {
-                        Command::Prepare { query, tx } => {
-                            tx.send(prepare(&mut conn, &query).map(|prepared| {
-                                update_cached_statements_size(
-                                    &conn,
-                                    &shared.cached_statements_size,
-                                );
-                                prepared
-                            }))
-                            .ok();
-                        }
-                        Command::Describe { query, tx } => {
-                            tx.send(describe(&mut conn, &query)).ok();
-                        }
-                        Command::Execute {
-                            query,
-                            arguments,
-                            persistent,
-                            tx,
-                        } => {
-                            let iter = match execute::iter(&mut conn, &query, arguments, persistent)
-                            {
-                                Ok(iter) => iter,
-                                Err(e) => {
-                                    tx.send(Err(e)).ok();
-                                    continue;
-                                }
-                            };
-
-                            for res in iter {
-                                if tx.send(res).is_err() {
-                                    break;
-                                }
-                            }
-
-                            update_cached_statements_size(&conn, &shared.cached_statements_size);
-                        }
-                        Command::Begin { tx } => {
-                            let depth = conn.transaction_depth;
-                            let res =
-                                conn.handle
-                                    .exec(begin_ansi_transaction_sql(depth))
-                                    .map(|_| {
-                                        conn.transaction_depth += 1;
-                                    });
-                            let res_ok = res.is_ok();
-
-                            if tx.blocking_send(res).is_err() && res_ok {
-                                // The BEGIN was processed but not acknowledged. This means no
-                                // `Transaction` was created and so there is no way to commit /
-                                // rollback this transaction. We need to roll it back
-                                // immediately otherwise it would remain started forever.
-                                if let Err(error) = conn
-                                    .handle
-                                    .exec(rollback_ansi_transaction_sql(depth + 1))
-                                    .map(|_| {
-                                        conn.transaction_depth -= 1;
-                                    })
-                                {
-                                    // The rollback failed. To prevent leaving the connection
-                                    // in an inconsistent state we shutdown this worker which
-                                    // causes any subsequent operation on the connection to fail.
-                                    tracing::error!(%error, "failed to rollback cancelled transaction");
-                                    break;
-                                }
-                            }
-                        }
-                        Command::Commit { tx } => {
-                            let depth = conn.transaction_depth;
-
-                            let res = if depth > 0 {
-                                conn.handle
-                                    .exec(commit_ansi_transaction_sql(depth))
-                                    .map(|_| {
-                                        conn.transaction_depth -= 1;
-                                    })
-                            } else {
-                                Ok(())
-                            };
-                            let res_ok = res.is_ok();
-
-                            if tx.blocking_send(res).is_err() && res_ok {
-                                // The COMMIT was processed but not acknowledged. This means that
-                                // the `Transaction` doesn't know it was committed and will try to
-                                // rollback on drop. We need to ignore that rollback.
-                                ignore_next_start_rollback = true;
-                            }
-                        }
-                        Command::Rollback { tx } => {
-                            if ignore_next_start_rollback && tx.is_none() {
-                                ignore_next_start_rollback = false;
-                                continue;
-                            }
-
-                            let depth = conn.transaction_depth;
-
-                            let res = if depth > 0 {
-                                conn.handle
-                                    .exec(rollback_ansi_transaction_sql(depth))
-                                    .map(|_| {
-                                        conn.transaction_depth -= 1;
-                                    })
-                            } else {
-                                Ok(())
-                            };
-
-                            let res_ok = res.is_ok();
-
-                            if let Some(tx) = tx {
-                                if tx.blocking_send(res).is_err() && res_ok {
-                                    // The ROLLBACK was processed but not acknowledged. This means
-                                    // that the `Transaction` doesn't know it was rolled back and
-                                    // will try to rollback again on drop. We need to ignore that
-                                    // rollback.
-                                    ignore_next_start_rollback = true;
-                                }
-                            }
-                        }
-                        Command::ClearCache { tx } => {
-                            conn.statements.clear();
-                            update_cached_statements_size(&conn, &shared.cached_statements_size);
-                            tx.send(()).ok();
-                        }
-                        Command::UnlockDb => {
-                            drop(conn);
-                            conn = futures_executor::block_on(shared.conn.lock());
-                        }
-                        Command::Ping { tx } => {
-                            tx.send(()).ok();
-                        }
-                        Command::Shutdown { tx } => {
-                            // drop the connection references before sending confirmation
-                            // and ending the command loop
-                            drop(conn);
-                            drop(shared);
-                            let _ = tx.send(());
-                            return;
-                        }
+            let conn = match params.establish() {
+                Ok(conn) => conn,
+                Err(e) => {
+                    establish_tx.send(Err(e)).ok();
+                    return;
+                }
+            };
+
+            let shared = Arc::new(WorkerSharedState::new(conn));
+            let mut state = WorkerState::new(Arc::clone(&shared));
+            let mut conn = shared.conn.try_lock().unwrap();
+
+            if establish_tx
+                .send(Ok(Self {
+                    command_tx,
+                    _handle_raw: conn.handle.to_raw(),
+                    interrupt: conn.interrupt.clone(),
+                    shared: Arc::clone(&shared),
+                }))
+                .is_err()
+            {
+                return;
+            }
+
+            for cmd in command_rx {
+                match futures_executor::block_on(state.handle(&mut conn, cmd)) {
+                    Flow::Continue => {}
+                    Flow::UnlockDb => {
+                        drop(conn);
+                        conn = futures_executor::block_on(shared.conn.lock());
+                    }
+                    Flow::Stop => break,
+                    Flow::Shutdown(tx) => {
+                        // drop the connection references before sending confirmation
+                        // and ending the command loop
+                        drop(conn);
+                        drop(state);
+                        drop(shared);
+                        let _ = tx.send(());
+                        return;
                     }
                 }
-            })?;
+            }
+        })?;
+
+        establish_rx.await.map_err(|_| Error::WorkerCrashed)?
+    }
+
+    async fn establish_pooled(
+        params: EstablishParams,
+        pool: SqliteWorkerPool,
+    ) -> Result<Self, Error> {
+        let (establish_tx, establish_rx) = oneshot::channel();
+
+        let worker_pool = pool.clone();
+
+        pool.spawn(move || {
+            let (command_tx, command_rx) = flume::bounded(params.command_channel_size);
+
+            let conn = match params.establish() {
+                Ok(conn) => conn,
+                Err(e) => {
+                    establish_tx.send(Err(e)).ok();
+                    return;
+                }
+            };
+
+            let shared = Arc::new(WorkerSharedState::new(conn));
+            let (handle_raw, interrupt) = {
+                let conn = shared.conn.try_lock().unwrap();
+                (conn.handle.to_raw(), conn.interrupt.clone())
+            };
+
+            let worker = Arc::new(PooledWorker {
+                task: std::sync::Mutex::new(Some(Box::pin(PooledWorker::process(
+                    command_rx,
+                    Arc::clone(&shared),
+                )))),
+                scheduled: AtomicBool::new(false),
+                pool: worker_pool,
+            });
+
+            // the task is then kept alive by the wakers it registers, e.g. with `command_rx`
+            worker.schedule();
+
+            establish_tx
+                .send(Ok(Self {
+                    command_tx,
+                    _handle_raw: handle_raw,
+                    interrupt,
+                    shared,
+                }))
+                .ok();
+        });
 
         establish_rx.await.map_err(|_| Error::WorkerCrashed)?
     }
@@ -285,6 +505,7 @@ impl ConnectionWorker {
         args: Option<SqliteArguments<'_>>,
         chan_size: usize,
         persistent: bool,
+        selected_columns: Option<&[&str]>,
     ) -> Result<flume::Receiver<Result<Either<SqliteQueryResult, SqliteRow>, Error>>, Error> {
         let (tx, rx) = flume::bounded(chan_size);
 
@@ -293,6 +514,8 @@ impl ConnectionWorker {
                 query: query.into(),
                 arguments: args.map(SqliteArguments::into_static),
                 persistent,
+                selected_columns: selected_columns
+                    .map(|columns| columns.iter().map(|&column| column.into()).collect()),
                 tx,
             })
             .await
@@ -301,8 +524,8 @@ impl ConnectionWorker {
         Ok(rx)
     }
 
-    pub(crate) async fn begin(&mut self) -> Result<(), Error> {
-        self.oneshot_cmd_with_ack(|tx| Command::Begin { tx })
+    pub(crate) async fn begin(&mut self, options: TransactionOptions) -> Result<(), Error> {
+        self.oneshot_cmd_with_ack(|tx| Command::Begin { options, tx })
             .await?
     }
 
@@ -326,6 +549,11 @@ impl ConnectionWorker {
         self.oneshot_cmd(|tx| Command::Ping { tx }).await
     }
 
+    pub(crate) async fn rollback_leaked_transaction(&mut self) -> Result<bool, Error> {
+        self.oneshot_cmd(|tx| Command::RollbackLeakedTransaction { tx })
+            .await?
+    }
+
     async fn oneshot_cmd<F, T>(&mut self, command: F) -> Result<T, Error>
     where
         F: FnOnce(oneshot::Sender<T>) -> Command,
@@ -354,6 +582,11 @@ impl ConnectionWorker {
         rx.recv().await.map_err(|_| Error::WorkerCrashed)
     }
 
+    pub(crate) async fn cached_statements(&mut self) -> Result<Vec<String>, Error> {
+        self.oneshot_cmd(|tx| Command::CachedStatements { tx })
+            .await
+    }
+
     pub(crate) async fn clear_cache(&mut self) -> Result<(), Error> {
         self.oneshot_cmd(|tx| Command::ClearCache { tx }).await
     }
@@ -421,6 +654,10 @@ fn update_cached_statements_size(conn: &ConnectionState, size: &AtomicUsize) {
     size.store(conn.statements.len(), Ordering::Release);
 }
 
+fn update_transaction_depth(conn: &ConnectionState, depth: &AtomicUsize) {
+    depth.store(conn.transaction_depth, Ordering::Release);
+}
+
 // A oneshot channel where send completes only after the receiver receives the value.
 mod rendezvous_oneshot {
     use super::oneshot::{self, Canceled};
@@ -440,10 +677,6 @@ mod rendezvous_oneshot {
             self.inner.send((value, ack_tx)).map_err(|_| Canceled)?;
             ack_rx.await
         }
-
-        pub fn blocking_send(self, value: T) -> Result<(), Canceled> {
-            futures_executor::block_on(self.send(value))
-        }
     }
 
     pub struct Receiver<T> {
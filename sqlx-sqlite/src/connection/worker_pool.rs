@@ -0,0 +1,94 @@
+use std::fmt::{self, Debug, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// A pool of threads shared by SQLite connections to run their commands.
+///
+/// By default, each SQLite connection has a dedicated background thread which executes its
+/// queries. With many connections (e.g. one database per tenant) this can exhaust the limit of
+/// threads of the process. Connections configured with
+/// [`SqliteConnectOptions::shared_worker_pool()`][crate::SqliteConnectOptions::shared_worker_pool]
+/// run their commands on the threads of a pool instead, at the cost of some latency when all
+/// the threads are busy.
+///
+/// A thread of the pool is only busy while a connection is running SQLite: it is released while
+/// the rows of a query wait to be received by the calling task (see
+/// [`SqliteConnectOptions::row_buffer_size()`][crate::SqliteConnectOptions::row_buffer_size]),
+/// or while the connection is locked elsewhere, so queries can be nested even with one thread.
+///
+/// The pool is cheap to clone, and its threads exit once it and all the connections using it
+/// are dropped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # async fn example() -> sqlx::Result<()> {
+/// use sqlx::ConnectOptions;
+/// use sqlx::sqlite::{SqliteConnectOptions, SqliteWorkerPool};
+///
+/// let workers = SqliteWorkerPool::new(4);
+///
+/// for tenant in ["alice", "bob"] {
+///     let conn = SqliteConnectOptions::new()
+///         .filename(format!("{tenant}.db"))
+///         .shared_worker_pool(workers.clone())
+///         .connect()
+///         .await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct SqliteWorkerPool {
+    jobs: flume::Sender<Job>,
+    threads: usize,
+}
+
+impl SqliteWorkerPool {
+    /// Start a pool of `threads` threads.
+    ///
+    /// # Panics
+    /// If `threads` is zero, or if the operating system fails to create a thread.
+    pub fn new(threads: usize) -> Self {
+        assert!(threads > 0, "a SqliteWorkerPool needs at least one thread");
+
+        let (jobs, jobs_rx) = flume::unbounded::<Job>();
+
+        for id in 0..threads {
+            let jobs_rx = jobs_rx.clone();
+
+            thread::Builder::new()
+                .name(format!("sqlx-sqlite-pool-worker-{id}"))
+                .spawn(move || {
+                    for job in jobs_rx {
+                        // Keep the thread for the other connections if establishing one panics
+                        let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                    }
+                })
+                .expect("failed to spawn a thread of the SqliteWorkerPool");
+        }
+
+        SqliteWorkerPool { jobs, threads }
+    }
+
+    /// Returns the number of threads of the pool.
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    pub(crate) fn spawn(&self, job: impl FnOnce() + Send + 'static) {
+        // The threads only exit once all the senders are dropped
+        let _ = self.jobs.send(Box::new(job));
+    }
+}
+
+impl Debug for SqliteWorkerPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqliteWorkerPool")
+            .field("threads", &self.threads)
+            .field("queued_jobs", &self.jobs.len())
+            .finish()
+    }
+}
@@ -33,13 +33,19 @@ use std::sync::atomic::AtomicBool;
 
 pub use arguments::{SqliteArgumentValue, SqliteArguments};
 pub use column::SqliteColumn;
-pub use connection::{LockedSqliteHandle, SqliteConnection};
+pub use connection::{
+    LockedSqliteHandle, SqliteColumnInfo, SqliteConnection, SqliteForeignKeyInfo, SqliteIndexInfo,
+    SqliteIndexOrigin, SqliteWorkerPool,
+};
 pub use database::Sqlite;
 pub use error::SqliteError;
 pub use options::{
-    SqliteAutoVacuum, SqliteConnectOptions, SqliteJournalMode, SqliteLockingMode, SqliteSynchronous,
+    SqliteAutoVacuum, SqliteConnectOptions, SqliteDbConfig, SqliteJournalMode, SqliteLockingMode,
+    SqliteSynchronous,
 };
 pub use query_result::SqliteQueryResult;
+#[cfg(feature = "recording")]
+pub use recording::SqliteRecordedArgument;
 pub use row::SqliteRow;
 pub use statement::SqliteStatement;
 pub use transaction::SqliteTransactionManager;
@@ -81,6 +87,9 @@ mod migrate;
 #[cfg(feature = "migrate")]
 mod testing;
 
+#[cfg(feature = "recording")]
+mod recording;
+
 /// An alias for [`Pool`][crate::pool::Pool], specialized for SQLite.
 pub type SqlitePool = crate::pool::Pool<Sqlite>;
 
@@ -1,7 +1,7 @@
 use crate::{SqliteConnectOptions, SqliteConnection};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
-use sqlx_core::connection::ConnectOptions;
+use sqlx_core::connection::{ConnectOptions, SqlRewriter, StatementLogHandler};
 use sqlx_core::error::Error;
 use sqlx_core::executor::Executor;
 use std::fmt::Write;
@@ -55,6 +55,26 @@ impl ConnectOptions for SqliteConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn log_with(mut self, handler: StatementLogHandler) -> Self {
+        self.log_settings.log_with(handler);
+        self
+    }
+
+    fn log_parameter_values(mut self, enabled: bool) -> Self {
+        self.log_settings.log_parameter_values(enabled);
+        self
+    }
+
+    fn sql_rewriter(mut self, rewriter: SqlRewriter) -> Self {
+        self.sql_rewriter.set(rewriter);
+        self
+    }
+
+    fn disable_statement_logging(mut self) -> Self {
+        self.log_settings.disable();
+        self
+    }
 }
 
 impl SqliteConnectOptions {
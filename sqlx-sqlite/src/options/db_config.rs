@@ -0,0 +1,46 @@
+use libsqlite3_sys::{
+    SQLITE_DBCONFIG_DEFENSIVE, SQLITE_DBCONFIG_ENABLE_FKEY, SQLITE_DBCONFIG_ENABLE_TRIGGER,
+    SQLITE_DBCONFIG_ENABLE_VIEW, SQLITE_DBCONFIG_NO_CKPT_ON_CLOSE, SQLITE_DBCONFIG_TRUSTED_SCHEMA,
+};
+
+/// A boolean option of a database connection, set with
+/// [`sqlite3_db_config()`](https://www.sqlite.org/c3ref/db_config.html).
+///
+/// Refer to the [SQLite documentation] for the meaning of each option.
+///
+/// [SQLite documentation]: https://www.sqlite.org/c3ref/c_dbconfig_defensive.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SqliteDbConfig {
+    /// `SQLITE_DBCONFIG_DEFENSIVE`: disallow language features which can corrupt the database
+    /// file, e.g. writing to the schema with `PRAGMA writable_schema`.
+    Defensive,
+    /// `SQLITE_DBCONFIG_ENABLE_TRIGGER`: enable triggers.
+    EnableTrigger,
+    /// `SQLITE_DBCONFIG_ENABLE_VIEW`: enable views.
+    EnableView,
+    /// `SQLITE_DBCONFIG_ENABLE_FKEY`: enforce foreign key constraints.
+    ///
+    /// Prefer [`SqliteConnectOptions::foreign_keys()`][crate::SqliteConnectOptions::foreign_keys]:
+    /// the `foreign_keys` pragma it sets (enabled by default) is the same setting, and takes
+    /// precedence as it is applied afterwards.
+    EnableFkey,
+    /// `SQLITE_DBCONFIG_NO_CKPT_ON_CLOSE`: don't checkpoint the WAL when the connection is closed.
+    NoCkptOnClose,
+    /// `SQLITE_DBCONFIG_TRUSTED_SCHEMA`: allow SQL functions and virtual tables which are not
+    /// marked as safe to be used in the schema (views, triggers, defaults, indexes...).
+    TrustedSchema,
+}
+
+impl SqliteDbConfig {
+    pub(crate) fn as_int(&self) -> i32 {
+        match self {
+            SqliteDbConfig::Defensive => SQLITE_DBCONFIG_DEFENSIVE,
+            SqliteDbConfig::EnableTrigger => SQLITE_DBCONFIG_ENABLE_TRIGGER,
+            SqliteDbConfig::EnableView => SQLITE_DBCONFIG_ENABLE_VIEW,
+            SqliteDbConfig::EnableFkey => SQLITE_DBCONFIG_ENABLE_FKEY,
+            SqliteDbConfig::NoCkptOnClose => SQLITE_DBCONFIG_NO_CKPT_ON_CLOSE,
+            SqliteDbConfig::TrustedSchema => SQLITE_DBCONFIG_TRUSTED_SCHEMA,
+        }
+    }
+}
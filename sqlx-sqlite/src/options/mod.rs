@@ -2,13 +2,15 @@ use std::path::Path;
 
 mod auto_vacuum;
 mod connect;
+mod db_config;
 mod journal_mode;
 mod locking_mode;
 mod parse;
 mod synchronous;
 
-use crate::connection::LogSettings;
+use crate::connection::{LogSettings, SqlRewriterSettings, SqliteWorkerPool};
 pub use auto_vacuum::SqliteAutoVacuum;
+pub use db_config::SqliteDbConfig;
 pub use journal_mode::SqliteJournalMode;
 pub use locking_mode::SqliteLockingMode;
 use std::cmp::Ordering;
@@ -62,6 +64,7 @@ pub struct SqliteConnectOptions {
     pub(crate) statement_cache_capacity: usize,
     pub(crate) busy_timeout: Duration,
     pub(crate) log_settings: LogSettings,
+    pub(crate) sql_rewriter: SqlRewriterSettings,
     pub(crate) immutable: bool,
     pub(crate) vfs: Option<Cow<'static, str>>,
 
@@ -72,6 +75,8 @@ pub struct SqliteConnectOptions {
     /// <https://www.sqlite.org/loadext.html#loading_an_extension>
     pub(crate) extensions: IndexMap<Cow<'static, str>, Option<Cow<'static, str>>>,
 
+    pub(crate) db_config: IndexMap<SqliteDbConfig, bool>,
+
     pub(crate) command_channel_size: usize,
     pub(crate) row_channel_size: usize,
 
@@ -79,6 +84,8 @@ pub struct SqliteConnectOptions {
 
     pub(crate) serialized: bool,
     pub(crate) thread_name: Arc<DebugFn<dyn Fn(u64) -> String + Send + Sync + 'static>>,
+    pub(crate) thread_stack_size: Option<usize>,
+    pub(crate) worker_pool: Option<SqliteWorkerPool>,
 
     pub(crate) optimize_on_close: OptimizeOnClose,
 
@@ -190,13 +197,17 @@ impl SqliteConnectOptions {
             statement_cache_capacity: 100,
             busy_timeout: Duration::from_secs(5),
             log_settings: Default::default(),
+            sql_rewriter: Default::default(),
             immutable: false,
             vfs: None,
             pragmas,
             extensions: Default::default(),
+            db_config: Default::default(),
             collations: Default::default(),
             serialized: false,
             thread_name: Arc::new(DebugFn(|id| format!("sqlx-sqlite-worker-{id}"))),
+            thread_stack_size: None,
+            worker_pool: None,
             command_channel_size: 50,
             row_channel_size: 50,
             optimize_on_close: OptimizeOnClose::Disabled,
@@ -409,6 +420,54 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Set the size of the stack of the background worker thread, in bytes.
+    ///
+    /// The default is the default of [`std::thread::Builder`] (currently 2 MiB, or the value of
+    /// the `RUST_MIN_STACK` environment variable). SQLite itself runs on this stack, so it may
+    /// need to be increased for deeply recursive queries, e.g. with nested triggers.
+    pub fn thread_stack_size(mut self, size: usize) -> Self {
+        self.thread_stack_size = Some(size);
+        self
+    }
+
+    /// Run the connection on the threads of a shared [`SqliteWorkerPool`], instead of a dedicated
+    /// background worker thread.
+    ///
+    /// This bounds the number of threads used by many connections, see [`SqliteWorkerPool`] for
+    /// the trade-offs. [`thread_name()`][Self::thread_name] and
+    /// [`thread_stack_size()`][Self::thread_stack_size] are ignored for such connections.
+    pub fn shared_worker_pool(mut self, pool: SqliteWorkerPool) -> Self {
+        self.worker_pool = Some(pool);
+        self
+    }
+
+    /// Set a boolean option of the connection with
+    /// [`sqlite3_db_config()`](https://www.sqlite.org/c3ref/db_config.html).
+    ///
+    /// The options are set right after the database is opened, before any other operation on it.
+    /// Options which are not set keep the defaults of SQLite.
+    ///
+    /// # Example
+    ///
+    /// Open a database file from an untrusted source:
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core::error::Error;
+    /// # use std::str::FromStr;
+    /// # use sqlx_sqlite::{SqliteConnectOptions, SqliteDbConfig};
+    /// # fn options() -> Result<SqliteConnectOptions, Error> {
+    /// let options = SqliteConnectOptions::from_str("sqlite://uploaded.db")?
+    ///     .db_config(SqliteDbConfig::Defensive, true)
+    ///     .db_config(SqliteDbConfig::TrustedSchema, false)
+    ///     .db_config(SqliteDbConfig::EnableTrigger, false);
+    /// # Ok(options)
+    /// # }
+    /// ```
+    pub fn db_config(mut self, config: SqliteDbConfig, enabled: bool) -> Self {
+        self.db_config.insert(config, enabled);
+        self
+    }
+
     /// Set the maximum number of commands to buffer for the worker thread before backpressure is
     /// applied.
     ///
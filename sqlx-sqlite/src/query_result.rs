@@ -7,10 +7,19 @@ pub struct SqliteQueryResult {
 }
 
 impl SqliteQueryResult {
+    /// Returns the number of rows inserted, updated or deleted by the statement, or `0` for
+    /// any other kind of statement.
+    ///
+    /// Rows changed by triggers or foreign key actions are not counted, see
+    /// [`sqlite3_changes()`](https://www.sqlite.org/c3ref/changes.html).
     pub fn rows_affected(&self) -> u64 {
         self.changes
     }
 
+    /// Returns the rowid of the last row inserted on the connection once the statement completed.
+    ///
+    /// This is not reset by statements which don't insert any row, see
+    /// [`sqlite3_last_insert_rowid()`](https://www.sqlite.org/c3ref/last_insert_rowid.html).
     pub fn last_insert_rowid(&self) -> i64 {
         self.last_insert_rowid
     }
@@ -0,0 +1,67 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+use sqlx_core::recording::RecordDatabase;
+
+use crate::error::Error;
+use crate::{Sqlite, SqliteArgumentValue, SqliteArguments, SqliteQueryResult};
+
+/// The recorded form of an argument of a query.
+///
+/// See the `sqlx::recording` module for details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SqliteRecordedArgument {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    // hex-encoded
+    Blob(String),
+}
+
+impl RecordDatabase for Sqlite {
+    type RecordedArguments = Vec<SqliteRecordedArgument>;
+
+    fn record_arguments(arguments: &SqliteArguments<'_>) -> Vec<SqliteRecordedArgument> {
+        arguments
+            .values
+            .iter()
+            .map(|value| match value {
+                SqliteArgumentValue::Null => SqliteRecordedArgument::Null,
+                SqliteArgumentValue::Text(text) => SqliteRecordedArgument::Text(text.to_string()),
+                SqliteArgumentValue::Blob(blob) => SqliteRecordedArgument::Blob(hex::encode(blob)),
+                SqliteArgumentValue::Double(value) => SqliteRecordedArgument::Real(*value),
+                SqliteArgumentValue::Int(value) => SqliteRecordedArgument::Integer((*value).into()),
+                SqliteArgumentValue::Int64(value) => SqliteRecordedArgument::Integer(*value),
+            })
+            .collect()
+    }
+
+    fn replay_arguments<'q>(
+        recorded: Vec<SqliteRecordedArgument>,
+    ) -> Result<SqliteArguments<'q>, Error> {
+        let values = recorded
+            .into_iter()
+            .map(|argument| {
+                Ok(match argument {
+                    SqliteRecordedArgument::Null => SqliteArgumentValue::Null,
+                    SqliteRecordedArgument::Integer(value) => SqliteArgumentValue::Int64(value),
+                    SqliteRecordedArgument::Real(value) => SqliteArgumentValue::Double(value),
+                    SqliteRecordedArgument::Text(text) => {
+                        SqliteArgumentValue::Text(Cow::Owned(text))
+                    }
+                    SqliteRecordedArgument::Blob(blob) => SqliteArgumentValue::Blob(Cow::Owned(
+                        hex::decode(blob).map_err(|e| Error::Decode(e.into()))?,
+                    )),
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+
+        Ok(SqliteArguments { values })
+    }
+
+    fn rows_affected(result: &SqliteQueryResult) -> u64 {
+        result.rows_affected()
+    }
+}
@@ -2,7 +2,7 @@
 
 use std::sync::Arc;
 
-use sqlx_core::column::ColumnIndex;
+use sqlx_core::column::{ColumnIndex, ColumnSelection};
 use sqlx_core::error::Error;
 use sqlx_core::ext::ustr::UStr;
 use sqlx_core::row::Row;
@@ -52,6 +52,68 @@ impl SqliteRow {
     }
 }
 
+impl SqliteRow {
+    // Only reads the values of the selected columns of the current row
+    pub(crate) fn current_selected(
+        statement: &StatementHandle,
+        selected: &SelectedColumns,
+    ) -> Self {
+        let mut values = Vec::with_capacity(selected.selection.len());
+
+        for (slot, &i) in selected.selection.indices().iter().enumerate() {
+            values.push(unsafe {
+                let raw = statement.column_value(i);
+
+                SqliteValue::new(raw, selected.columns[slot].type_info.clone())
+            });
+        }
+
+        Self {
+            values: values.into_boxed_slice(),
+            columns: Arc::clone(&selected.columns),
+            column_names: Arc::clone(&selected.column_names),
+        }
+    }
+}
+
+/// The columns of the result of a statement which are kept in its rows.
+pub(crate) struct SelectedColumns {
+    pub(crate) selection: ColumnSelection,
+    pub(crate) columns: Arc<Vec<SqliteColumn>>,
+    pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+}
+
+impl SelectedColumns {
+    pub(crate) fn new(
+        selected_columns: &[Box<str>],
+        columns: &[SqliteColumn],
+        column_names: &HashMap<UStr, usize>,
+    ) -> Result<Self, Error> {
+        let selection = ColumnSelection::new(selected_columns, column_names, columns.len())?;
+
+        let columns: Vec<SqliteColumn> = selection
+            .indices()
+            .iter()
+            .enumerate()
+            .map(|(ordinal, &index)| SqliteColumn {
+                ordinal,
+                ..columns[index].clone()
+            })
+            .collect();
+
+        let column_names = columns
+            .iter()
+            .map(|column| (column.name.clone(), column.ordinal))
+            .collect();
+
+        Ok(Self {
+            selection,
+            columns: Arc::new(columns),
+            column_names: Arc::new(column_names),
+        })
+    }
+}
+
 impl Row for SqliteRow {
     type Database = Sqlite;
 
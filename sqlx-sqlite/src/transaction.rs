@@ -1,8 +1,13 @@
+use std::borrow::Cow;
+
 use futures_core::future::BoxFuture;
 
 use crate::{Sqlite, SqliteConnection};
 use sqlx_core::error::Error;
-use sqlx_core::transaction::TransactionManager;
+use sqlx_core::transaction::{
+    begin_ansi_transaction_sql, check_savepoint_options, IsolationLevel, TransactionManager,
+    TransactionOptions,
+};
 
 /// Implementation of [`TransactionManager`] for SQLite.
 pub struct SqliteTransactionManager;
@@ -11,7 +16,14 @@ impl TransactionManager for SqliteTransactionManager {
     type Database = Sqlite;
 
     fn begin(conn: &mut SqliteConnection) -> BoxFuture<'_, Result<(), Error>> {
-        Box::pin(conn.worker.begin())
+        Self::begin_with(conn, TransactionOptions::default())
+    }
+
+    fn begin_with(
+        conn: &mut SqliteConnection,
+        options: TransactionOptions,
+    ) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(conn.worker.begin(options))
     }
 
     fn commit(conn: &mut SqliteConnection) -> BoxFuture<'_, Result<(), Error>> {
@@ -25,4 +37,72 @@ impl TransactionManager for SqliteTransactionManager {
     fn start_rollback(conn: &mut SqliteConnection) {
         conn.worker.start_rollback().ok();
     }
+
+    fn get_transaction_depth(conn: &SqliteConnection) -> usize {
+        conn.worker.shared.get_transaction_depth()
+    }
+}
+
+// Transactions are always `SERIALIZABLE` in SQLite, which has no read-only transactions either:
+// there is nothing else to set.
+pub(crate) fn begin_transaction_sql(
+    depth: usize,
+    options: &TransactionOptions,
+) -> Result<Cow<'static, str>, Error> {
+    check_savepoint_options(depth, options)?;
+
+    match options.get_isolation_level() {
+        None | Some(IsolationLevel::Serializable) => {}
+        Some(isolation_level) => {
+            return Err(Error::Configuration(
+                format!(
+                    "SQLite does not support the {} isolation level",
+                    isolation_level.as_sql()
+                )
+                .into(),
+            ));
+        }
+    }
+
+    if options.is_read_only() {
+        return Err(Error::Configuration(
+            "SQLite does not support read-only transactions".into(),
+        ));
+    }
+
+    if options.is_deferrable() {
+        return Err(Error::Configuration(
+            "SQLite does not support deferrable transactions".into(),
+        ));
+    }
+
+    Ok(begin_ansi_transaction_sql(depth))
+}
+
+#[test]
+fn test_begin_transaction_sql() {
+    let options = TransactionOptions::new();
+    assert_eq!(begin_transaction_sql(0, &options).unwrap(), "BEGIN");
+    assert_eq!(
+        begin_transaction_sql(1, &options).unwrap(),
+        "SAVEPOINT _sqlx_savepoint_1"
+    );
+
+    let options = options.isolation_level(IsolationLevel::Serializable);
+    assert_eq!(begin_transaction_sql(0, &options).unwrap(), "BEGIN");
+    assert!(matches!(
+        begin_transaction_sql(1, &options),
+        Err(Error::Configuration(_))
+    ));
+
+    for options in [
+        TransactionOptions::new().isolation_level(IsolationLevel::ReadCommitted),
+        TransactionOptions::new().read_only(true),
+        TransactionOptions::new().deferrable(true),
+    ] {
+        assert!(matches!(
+            begin_transaction_sql(0, &options),
+            Err(Error::Configuration(_))
+        ));
+    }
 }
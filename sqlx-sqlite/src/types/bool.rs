@@ -25,6 +25,7 @@ impl<'q> Encode<'q, Sqlite> for bool {
 
 impl<'r> Decode<'r, Sqlite> for bool {
     fn decode(value: SqliteValueRef<'r>) -> Result<bool, BoxDynError> {
-        Ok(value.int() != 0)
+        // `int()` would truncate the value to 32 bits, so e.g. `1 << 32` would be `false`
+        Ok(value.int64() != 0)
     }
 }
@@ -92,3 +92,31 @@ impl<'r> Decode<'r, Sqlite> for Vec<u8> {
         Ok(value.blob().to_owned())
     }
 }
+
+impl<const N: usize> Type<Sqlite> for [u8; N] {
+    fn type_info() -> SqliteTypeInfo {
+        <&[u8] as Type<Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <&[u8] as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl<'q, const N: usize> Encode<'q, Sqlite> for [u8; N] {
+    fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        args.push(SqliteArgumentValue::Blob(Cow::Owned(self.to_vec())));
+
+        IsNull::No
+    }
+}
+
+impl<const N: usize> Decode<'_, Sqlite> for [u8; N] {
+    fn decode(value: SqliteValueRef<'_>) -> Result<Self, BoxDynError> {
+        let bytes = value.blob();
+
+        bytes
+            .try_into()
+            .map_err(|_| format!("expected {N} bytes, got {}", bytes.len()).into())
+    }
+}
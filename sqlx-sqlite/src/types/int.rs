@@ -1,3 +1,5 @@
+use std::any::type_name;
+
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
@@ -5,6 +7,16 @@ use crate::type_info::DataType;
 use crate::types::Type;
 use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
 
+/// Decode an integer, checking that it fits in the target type.
+///
+/// SQLite stores all integers as 64-bit, and would silently truncate them to 32-bit otherwise.
+fn int_decode<T: TryFrom<i64>>(value: SqliteValueRef<'_>) -> Result<T, BoxDynError> {
+    let int = value.int64();
+
+    T::try_from(int)
+        .map_err(|_| format!("value {int} is out of range for `{}`", type_name::<T>()).into())
+}
+
 impl Type<Sqlite> for i8 {
     fn type_info() -> SqliteTypeInfo {
         SqliteTypeInfo(DataType::Int)
@@ -25,7 +37,7 @@ impl<'q> Encode<'q, Sqlite> for i8 {
 
 impl<'r> Decode<'r, Sqlite> for i8 {
     fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(value.int().try_into()?)
+        int_decode(value)
     }
 }
 
@@ -49,7 +61,7 @@ impl<'q> Encode<'q, Sqlite> for i16 {
 
 impl<'r> Decode<'r, Sqlite> for i16 {
     fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(value.int().try_into()?)
+        int_decode(value)
     }
 }
 
@@ -73,7 +85,7 @@ impl<'q> Encode<'q, Sqlite> for i32 {
 
 impl<'r> Decode<'r, Sqlite> for i32 {
     fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(value.int())
+        int_decode(value)
     }
 }
 
@@ -0,0 +1,156 @@
+use crate::value::ValueRef;
+use crate::{
+    decode::Decode,
+    encode::{Encode, IsNull},
+    error::BoxDynError,
+    type_info::DataType,
+    types::Type,
+    Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef,
+};
+use jiff::civil::{Date, DateTime, Time};
+use jiff::tz::Offset;
+use jiff::Timestamp;
+
+impl Type<Sqlite> for Timestamp {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Datetime)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <DateTime as Type<Sqlite>>::compatible(ty)
+    }
+}
+
+impl Type<Sqlite> for DateTime {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Datetime)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        matches!(
+            ty.0,
+            DataType::Datetime | DataType::Text | DataType::Int64 | DataType::Int
+        )
+    }
+}
+
+impl Type<Sqlite> for Date {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Date)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        matches!(ty.0, DataType::Date | DataType::Text)
+    }
+}
+
+impl Type<Sqlite> for Time {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Time)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        matches!(ty.0, DataType::Time | DataType::Text)
+    }
+}
+
+impl Encode<'_, Sqlite> for Timestamp {
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'_>>) -> IsNull {
+        // RFC 3339 in UTC, e.g. `2024-06-01T12:00:00.5Z`
+        Encode::<Sqlite>::encode(self.to_string(), buf)
+    }
+}
+
+impl Encode<'_, Sqlite> for DateTime {
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'_>>) -> IsNull {
+        // The same format as SQLite's own `datetime()`, with the fractional seconds if any
+        Encode::<Sqlite>::encode(self.strftime("%Y-%m-%d %H:%M:%S%.f").to_string(), buf)
+    }
+}
+
+impl Encode<'_, Sqlite> for Date {
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'_>>) -> IsNull {
+        Encode::<Sqlite>::encode(self.to_string(), buf)
+    }
+}
+
+impl Encode<'_, Sqlite> for Time {
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'_>>) -> IsNull {
+        Encode::<Sqlite>::encode(self.to_string(), buf)
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for Timestamp {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        let ts = match value.type_info().0 {
+            DataType::Text => decode_timestamp_from_text(value.text()?),
+            DataType::Int | DataType::Int64 => Timestamp::from_second(value.int64()).ok(),
+
+            _ => None,
+        };
+
+        if let Some(ts) = ts {
+            Ok(ts)
+        } else {
+            Err(format!("invalid timestamp: {}", value.text()?).into())
+        }
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for DateTime {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        let dt = match value.type_info().0 {
+            DataType::Text => decode_datetime_from_text(value.text()?),
+            DataType::Int | DataType::Int64 => Timestamp::from_second(value.int64())
+                .ok()
+                .map(|ts| Offset::UTC.to_datetime(ts)),
+
+            _ => None,
+        };
+
+        if let Some(dt) = dt {
+            Ok(dt)
+        } else {
+            Err(format!("invalid datetime: {}", value.text()?).into())
+        }
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for Date {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(value.text()?.parse()?)
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for Time {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        // Accepts `HH:MM`, `HH:MM:SS` and `HH:MM:SS.SSS`, like SQLite's time functions
+        let value = value.text()?;
+
+        value
+            .parse()
+            .map_err(|_| format!("invalid time: {value}").into())
+    }
+}
+
+fn decode_timestamp_from_text(value: &str) -> Option<Timestamp> {
+    // Requires an offset, e.g. `2024-06-01T12:00:00Z` or `2024-06-01 14:00:00+02:00`
+    if let Ok(ts) = value.parse() {
+        return Some(ts);
+    }
+
+    // Otherwise assume UTC, which is what SQLite's own functions use
+    let dt: DateTime = value.parse().ok()?;
+    Offset::UTC.to_timestamp(dt).ok()
+}
+
+fn decode_datetime_from_text(value: &str) -> Option<DateTime> {
+    // Either a space or `T` may separate the date and time. An offset other than `Z` is ignored.
+    if let Ok(dt) = value.parse() {
+        return Some(dt);
+    }
+
+    // e.g. `2024-06-01T12:00:00Z`
+    let ts: Timestamp = value.parse().ok()?;
+    Some(Offset::UTC.to_datetime(ts))
+}
@@ -4,7 +4,7 @@
 //!
 //! | Rust type                             | SQLite type(s)                                       |
 //! |---------------------------------------|------------------------------------------------------|
-//! | `bool`                                | BOOLEAN                                              |
+//! | `bool`                                | BOOLEAN, INTEGER                                     |
 //! | `i8`                                  | INTEGER                                              |
 //! | `i16`                                 | INTEGER                                              |
 //! | `i32`                                 | INTEGER                                              |
@@ -14,8 +14,18 @@
 //! | `u32`                                 | INTEGER                                              |
 //! | `f32`                                 | REAL                                                 |
 //! | `f64`                                 | REAL                                                 |
-//! | `&str`, [`String`]                    | TEXT                                                 |
-//! | `&[u8]`, `Vec<u8>`                    | BLOB                                                 |
+//! | `&str`, [`String`], `Cow<str>`        | TEXT                                                 |
+//! | `&[u8]`, `Vec<u8>`, `[u8; N]`         | BLOB                                                 |
+//!
+//! #### Note: Borrowed Strings
+//! The values of a row are copied out of the SQLite statement by the worker thread, so that the
+//! statement can be stepped further while the row is used. `&str` and `Cow<str>` borrow this
+//! copy without allocating again, and `Cow<str>` is always decoded as `Cow::Borrowed`.
+//!
+//! #### Note: Booleans
+//! SQLite has no native boolean type; `TRUE` and `FALSE` are just aliases for `1` and `0`.
+//! Accordingly, `bool` is encoded as the integer `0` or `1`, and can be decoded from any
+//! integer value, where `0` is `false` and any other value is `true`.
 //!
 //! #### Note: Unsigned Integers
 //! The unsigned integer types `u8`, `u16` and `u32` are implemented by zero-extending to the
@@ -54,6 +64,17 @@
 //! | `time::Date`                          | DATE                                                 |
 //! | `time::Time`                          | TIME                                                 |
 //!
+//! ### [`jiff`](https://crates.io/crates/jiff)
+//!
+//! Requires the `jiff` Cargo feature flag.
+//!
+//! | Rust type                             | Sqlite type(s)                                       |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `jiff::civil::DateTime`               | DATETIME                                             |
+//! | `jiff::Timestamp`                     | DATETIME                                             |
+//! | `jiff::civil::Date`                   | DATE                                                 |
+//! | `jiff::civil::Time`                   | TIME                                                 |
+//!
 //! ### [`uuid`](https://crates.io/crates/uuid)
 //!
 //! Requires the `uuid` Cargo feature flag.
@@ -74,6 +95,45 @@
 //! | `serde_json::JsonValue`               | TEXT                                                 |
 //! | `&serde_json::value::RawValue`        | TEXT                                                 |
 //!
+//! #### Note: Arrays
+//! SQLite has no array type, so a collection of values is usually stored as a JSON array, e.g.
+//! with `Json<Vec<T>>`, and queried with the [JSON functions][json1] of SQLite.
+//!
+//! This includes temporal values, which SQLx doesn't serialize itself: enable the `serde`
+//! feature of `chrono`, or the `serde-human-readable` feature of `time`, in your own
+//! `Cargo.toml`. They are then serialized as strings the date and time functions of SQLite
+//! understand, so these can be applied to the elements of the array. Dates and times are the
+//! same strings as when they are stored outside of JSON (e.g. `"2002-06-04"` for a date), but
+//! the `chrono` date-times (`NaiveDateTime` and `DateTime`) are separated by a `T` (e.g.
+//! `"2019-01-02T05:10:20"`), while SQLx stores them with a space:
+//!
+//! ```rust,no_run
+//! # async fn example(conn: &mut sqlx::SqliteConnection) -> sqlx::Result<()> {
+//! use sqlx::types::chrono::NaiveDate;
+//! use sqlx::types::Json;
+//!
+//! let holidays = vec![
+//!     NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+//!     NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(),
+//! ];
+//!
+//! sqlx::query("INSERT INTO calendars (name, holidays) VALUES (?, ?)")
+//!     .bind("work")
+//!     .bind(Json(&holidays))
+//!     .execute(&mut *conn)
+//!     .await?;
+//!
+//! let holidays: Json<Vec<NaiveDate>> =
+//!     sqlx::query_scalar("SELECT holidays FROM calendars WHERE name = ?")
+//!         .bind("work")
+//!         .fetch_one(&mut *conn)
+//!         .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [json1]: https://www.sqlite.org/json1.html
+//!
 //! # Nullable
 //!
 //! In addition, `Option<T>` is supported where `T` implements `Type`. An `Option<T>` represents
@@ -141,6 +201,8 @@ mod bytes;
 mod chrono;
 mod float;
 mod int;
+#[cfg(feature = "jiff")]
+mod jiff;
 #[cfg(feature = "json")]
 mod json;
 mod str;
@@ -3,21 +3,28 @@
 
 pub use sqlx_core::acquire::Acquire;
 pub use sqlx_core::arguments::{Arguments, IntoArguments};
+pub use sqlx_core::batch;
 pub use sqlx_core::column::Column;
 pub use sqlx_core::column::ColumnIndex;
-pub use sqlx_core::connection::{ConnectOptions, Connection};
+pub use sqlx_core::connection::{
+    CancelToken, ConnectOptions, Connection, StatementLogContext, StatementLogHandler,
+};
 pub use sqlx_core::database::{self, Database};
 pub use sqlx_core::describe::Describe;
 pub use sqlx_core::executor::{Execute, Executor};
+pub use sqlx_core::export;
 pub use sqlx_core::from_row::FromRow;
 pub use sqlx_core::pool::{self, Pool};
 pub use sqlx_core::query::{query, query_with};
 pub use sqlx_core::query_as::{query_as, query_as_with};
 pub use sqlx_core::query_builder::{self, QueryBuilder};
 pub use sqlx_core::query_scalar::{query_scalar, query_scalar_with};
+pub use sqlx_core::reconnect::ReconnectingConnection;
 pub use sqlx_core::row::Row;
 pub use sqlx_core::statement::Statement;
-pub use sqlx_core::transaction::{Transaction, TransactionManager};
+pub use sqlx_core::transaction::{
+    IsolationLevel, Transaction, TransactionManager, TransactionOptions,
+};
 pub use sqlx_core::type_info::TypeInfo;
 pub use sqlx_core::types::Type;
 pub use sqlx_core::value::{Value, ValueRef};
@@ -29,6 +36,14 @@ pub use sqlx_core::error::{self, Error, Result};
 #[cfg(feature = "migrate")]
 pub use sqlx_core::migrate;
 
+#[cfg(feature = "fault-injection")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fault-injection")))]
+pub use sqlx_core::fault_injection;
+
+#[cfg(feature = "recording")]
+#[cfg_attr(docsrs, doc(cfg(feature = "recording")))]
+pub use sqlx_core::recording;
+
 #[cfg(feature = "mysql")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mysql")))]
 #[doc(inline)]
@@ -84,6 +99,9 @@ pub mod ty_match;
 #[doc(hidden)]
 pub use sqlx_core::rt as __rt;
 
+#[doc(hidden)]
+pub use sqlx_core::from_row::from_row_flatten_option as __from_row_flatten_option;
+
 /// Conversions between Rust and SQL types.
 ///
 /// To see how each SQL type maps to a Rust type, see the corresponding `types` module for each
@@ -132,7 +150,7 @@ pub use self::decode::Decode;
 /// Types and traits for the `query` family of functions and macros.
 pub mod query {
     pub use sqlx_core::query::{Map, Query};
-    pub use sqlx_core::query_as::QueryAs;
+    pub use sqlx_core::query_as::{OnDuplicateKey, QueryAs};
     pub use sqlx_core::query_scalar::QueryScalar;
 }
 
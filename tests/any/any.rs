@@ -31,6 +31,21 @@ async fn it_pings() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_counts_row_columns() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let mut conn = new::<Any>().await?;
+
+    let row = conn.fetch_one("SELECT 1, 2, 3").await?;
+
+    assert_eq!(row.len(), 3);
+    assert_eq!(row.len(), row.columns().len());
+    assert!(!row.is_empty());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_executes_with_pool() -> anyhow::Result<()> {
     sqlx::any::install_default_drivers();
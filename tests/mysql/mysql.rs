@@ -1,8 +1,15 @@
 use futures::TryStreamExt;
-use sqlx::mysql::{MySql, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow};
-use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
+use sqlx::mysql::{
+    MySql, MySqlConnectOptions, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow,
+};
+use sqlx::{
+    Column, ConnectOptions, Connection, Executor, IsolationLevel, QueryBuilder, Row, Statement,
+    TransactionOptions, TypeInfo,
+};
 use sqlx_test::{new, setup_if_needed};
 use std::env;
+use std::sync::Arc;
+use std::time::Duration;
 
 #[sqlx_macros::test]
 async fn it_connects() -> anyhow::Result<()> {
@@ -29,6 +36,62 @@ async fn it_maths() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_fetches_rows_from_a_cursor() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE cursor_test (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    for id in 1..=10_i32 {
+        sqlx::query("INSERT INTO cursor_test (id) VALUES (?)")
+            .bind(id)
+            .execute(&mut conn)
+            .await?;
+    }
+
+    conn.set_fetch_size(3);
+
+    // 10 rows are fetched in 4 batches, the last of which is incomplete
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM cursor_test WHERE id > ? ORDER BY id")
+        .bind(0_i32)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(ids, (1..=10).collect::<Vec<_>>());
+
+    // a batch ending exactly on the last row
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM cursor_test WHERE id > ? ORDER BY id")
+        .bind(4_i32)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(ids, (5..=10).collect::<Vec<_>>());
+
+    // an abandoned cursor doesn't break the connection
+    let first: i32 = sqlx::query_scalar("SELECT id FROM cursor_test ORDER BY id")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(first, 1);
+
+    // statements without a result set are unaffected
+    let done = sqlx::query("DELETE FROM cursor_test WHERE id > ?")
+        .bind(5_i32)
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(done.rows_affected(), 5);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM cursor_test")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 5);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_fail_at_querying() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -334,6 +397,36 @@ async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_rolls_back_leaked_transactions_on_release() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = MySqlPoolOptions::new()
+        .max_connections(1)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE leaked_transaction (id INT)")
+        .await?;
+
+    // a plain `BEGIN`, which is left open when the connection is released
+    conn.execute("BEGIN").await?;
+    conn.execute("INSERT INTO leaked_transaction (id) VALUES (1)")
+        .await?;
+    drop(conn);
+
+    let mut conn = pool.acquire().await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM leaked_transaction")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
 // repro is more reliable with the basic scheduler used by `#[tokio::test]`
 #[cfg(feature = "_rt-tokio")]
 #[tokio::test]
@@ -388,6 +481,56 @@ async fn test_issue_622() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_reset_cached_statements() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let sql = "SELECT ? + 1";
+    assert!(!conn.reset_cached_statement(sql).await?);
+
+    for i in 0..2_i64 {
+        let value: i64 = sqlx::query_scalar(sql).bind(i).fetch_one(&mut conn).await?;
+        assert_eq!(value, i + 1);
+
+        assert!(conn.reset_cached_statement(sql).await?);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_sends_long_parameters_in_chunks() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // 3 chunks, the last of which is incomplete
+    conn.set_long_data_threshold(1024);
+    let data = vec![7_u8; 2500];
+
+    // the second execution resets the cached statement before sending the data again
+    for _ in 0..2 {
+        let row = sqlx::query("SELECT LENGTH(?), ?, ? + 1")
+            .bind(&data)
+            .bind(&data)
+            .bind(1_i32)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(row.try_get::<i64, _>(0)?, 2500);
+        assert_eq!(row.try_get::<Vec<u8>, _>(1)?, data);
+        assert_eq!(row.try_get::<i64, _>(2)?, 2);
+    }
+
+    // short values are still sent along with the query
+    let text: String = sqlx::query_scalar("SELECT ?")
+        .bind("short")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(text, "short");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_work_with_transactions() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -510,3 +653,239 @@ async fn test_shrink_buffers() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_cancels_a_query_with_a_token() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+    let token = conn.cancel_token().unwrap();
+
+    sqlx_core::rt::spawn(async move {
+        sqlx_core::rt::sleep(std::time::Duration::from_millis(200)).await;
+        token.cancel().await
+    });
+
+    // `SLEEP()` returns 1 when it is interrupted
+    let interrupted: i64 = sqlx::query_scalar("SELECT SLEEP(30)")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(interrupted, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_quotes_identifiers_in_query_builder() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let mut create = QueryBuilder::<MySql>::new("CREATE TEMPORARY TABLE ");
+    create
+        .push_identifier("my `table`")
+        .push(" (")
+        .push_identifier("select")
+        .push(" TEXT)");
+    create.build().execute(&mut conn).await?;
+
+    let mut insert = QueryBuilder::<MySql>::new("INSERT INTO ");
+    insert.push_identifier("my `table`").push(" VALUES ('one')");
+    insert.build().execute(&mut conn).await?;
+
+    let mut select = QueryBuilder::<MySql>::new("SELECT ");
+    select
+        .push_identifier("my `table`.select")
+        .push(" FROM ")
+        .push_identifier("my `table`");
+
+    let value: String = select.build_query_scalar().fetch_one(&mut conn).await?;
+    assert_eq!(value, "one");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_only_keeps_the_selected_columns() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let row = sqlx::query("SELECT 1 AS a, 'two' AS b, 3 AS c, NULL AS d")
+        .select_columns(&["c", "d", "a"])
+        .fetch_one(&mut conn)
+        .await?;
+
+    let names: Vec<&str> = row.columns().iter().map(|c| c.name()).collect();
+    assert_eq!(names, ["c", "d", "a"]);
+
+    assert_eq!(row.try_get::<i64, _>(0)?, 3);
+    assert_eq!(row.try_get::<Option<String>, _>("d")?, None);
+    assert_eq!(row.try_get::<i64, _>("a")?, 1);
+    assert!(matches!(
+        row.try_get::<String, _>("b"),
+        Err(sqlx::Error::ColumnNotFound(_))
+    ));
+
+    let res = sqlx::query("SELECT 1 AS a")
+        .select_columns(&["b"])
+        .fetch_one(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::ColumnNotFound(name)) if name == "b"));
+
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[sqlx_macros::test]
+async fn it_builds_json_expressions() -> anyhow::Result<()> {
+    use sqlx::mysql::MySqlJsonExt;
+    use sqlx::types::Json;
+
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE products (id INT PRIMARY KEY, attributes JSON NOT NULL);
+
+INSERT INTO products (id, attributes) VALUES
+    (1, '{"color": "red", "size": 3, "tags": ["outdoor", "garden"]}'),
+    (2, '{"color": "blue", "tags": ["indoor"]}');
+        "#,
+    )
+    .await?;
+
+    let mut query = QueryBuilder::<MySql>::new("SELECT id, ");
+    query
+        .push_json_extract("attributes", "$.size")
+        .push(", ")
+        .push_json_extract_unquoted("attributes", "$.color")
+        .push(" FROM products WHERE ")
+        .push_json_contains("attributes", "outdoor", Some("$.tags"));
+
+    let rows: Vec<(i32, Option<Json<i64>>, Option<String>)> =
+        query.build_query_as().fetch_all(&mut conn).await?;
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].0, 1);
+    assert_eq!(rows[0].1.as_ref().map(|size| size.0), Some(3));
+    assert_eq!(rows[0].2.as_deref(), Some("red"));
+
+    // the path is bound and cannot inject SQL
+    let mut query = QueryBuilder::<MySql>::new("SELECT COUNT(*) FROM products WHERE ");
+    query
+        .push_json_extract("attributes", "$.size') OR ('1' = '1")
+        .push(" IS NOT NULL");
+
+    assert!(query.build().fetch_one(&mut conn).await.is_err());
+
+    let mut query = QueryBuilder::<MySql>::new("SELECT id FROM products WHERE ");
+    query.push_json_contains("attributes", serde_json::json!({ "color": "blue" }), None);
+
+    let ids: Vec<i32> = query.build_query_scalar().fetch_all(&mut conn).await?;
+    assert_eq!(ids, [2]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_begins_transactions_with_options() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let options = TransactionOptions::new()
+        .isolation_level(IsolationLevel::Serializable)
+        .read_only(true);
+
+    let mut tx = conn.begin_with(options).await?;
+
+    let res = tx
+        .execute("CREATE TABLE _sqlx_read_only_test (id INT)")
+        .await;
+    assert!(res.is_err());
+
+    // savepoints have no options
+    let res = tx.begin_with(options).await.map(|_| ());
+    assert!(matches!(res, Err(sqlx::Error::Configuration(_))));
+
+    tx.rollback().await?;
+
+    // MySQL doesn't have deferrable transactions
+    let res = conn
+        .begin_with(TransactionOptions::new().deferrable(true))
+        .await
+        .map(|_| ());
+    assert!(matches!(res, Err(sqlx::Error::Configuration(_))));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_calls_the_statement_log_handler() -> anyhow::Result<()> {
+    use std::sync::Mutex;
+
+    setup_if_needed();
+
+    let statements = Arc::new(Mutex::new(Vec::new()));
+    let handler = {
+        let statements = statements.clone();
+        Arc::new(move |statement: &sqlx::StatementLogContext<'_>| {
+            statements.lock().unwrap().push((
+                statement.sql.to_owned(),
+                statement.elapsed,
+                statement.rows_returned,
+                statement.rows_affected,
+                statement.was_slow,
+            ));
+        })
+    };
+    let logged = |sql: &str| -> Vec<_> {
+        statements
+            .lock()
+            .unwrap()
+            .drain(..)
+            .filter(|(logged, ..)| logged == sql)
+            .collect()
+    };
+
+    // the handler is called even if logging is disabled
+    let options = env::var("DATABASE_URL")?
+        .parse::<MySqlConnectOptions>()?
+        .disable_statement_logging()
+        .log_with(handler.clone());
+
+    let mut conn = MySqlConnection::connect_with(&options).await?;
+
+    let rows = sqlx::query("SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(rows.len(), 3);
+
+    let statement = logged("SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3");
+    assert_eq!(statement.len(), 1);
+    assert!(statement[0].1 > Duration::ZERO);
+    assert_eq!(statement[0].2, 3);
+    // the statements are never slow while slow statements aren't logged
+    assert!(!statement[0].4);
+
+    conn.execute("CREATE TEMPORARY TABLE logged (id INT)")
+        .await?;
+    conn.execute("INSERT INTO logged (id) VALUES (1), (2)")
+        .await?;
+
+    let statement = logged("INSERT INTO logged (id) VALUES (1), (2)");
+    assert_eq!(statement.len(), 1);
+    assert_eq!((statement[0].2, statement[0].3), (0, 2));
+
+    // every statement is slow with a threshold of zero
+    let options = options.log_slow_statements("warn".parse()?, Duration::ZERO);
+    let mut conn = MySqlConnection::connect_with(&options).await?;
+
+    conn.execute("SELECT 1").await?;
+
+    let statement = logged("SELECT 1");
+    assert_eq!(statement.len(), 1);
+    assert!(statement[0].4);
+
+    // but removed by `disable_statement_logging()`
+    let options = options.log_with(handler).disable_statement_logging();
+    let mut conn = MySqlConnection::connect_with(&options).await?;
+
+    conn.execute("SELECT 1").await?;
+    assert!(statements.lock().unwrap().is_empty());
+
+    Ok(())
+}
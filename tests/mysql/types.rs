@@ -1,5 +1,6 @@
 extern crate time_ as time;
 
+use std::borrow::Cow;
 use std::net::SocketAddr;
 #[cfg(feature = "rust_decimal")]
 use std::str::FromStr;
@@ -9,7 +10,7 @@ use sqlx::{Executor, Row};
 
 use sqlx::types::Text;
 
-use sqlx_test::{new, test_type};
+use sqlx_test::{new, test_decode_type, test_type};
 
 test_type!(bool(MySql, "false" == false, "true" == true));
 
@@ -37,6 +38,26 @@ test_type!(string<String>(MySql,
     "''" == ""
 ));
 
+#[sqlx_macros::test]
+async fn it_decodes_cow_str_borrowed() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // text protocol
+    let row = conn.fetch_one("SELECT 'helloworld'").await?;
+    let text: Cow<'_, str> = row.try_get(0)?;
+    assert!(matches!(text, Cow::Borrowed("helloworld")));
+
+    // binary protocol
+    let row = sqlx::query("SELECT ?")
+        .bind("helloworld")
+        .fetch_one(&mut conn)
+        .await?;
+    let text: Cow<'_, str> = row.try_get(0)?;
+    assert!(matches!(text, Cow::Borrowed("helloworld")));
+
+    Ok(())
+}
+
 test_type!(bytes<Vec<u8>>(MySql,
     "X'DEADBEEF'"
         == vec![0xDE_u8, 0xAD, 0xBE, 0xEF],
@@ -46,6 +67,24 @@ test_type!(bytes<Vec<u8>>(MySql,
         == vec![0_u8, 0, 0, 0, 0x52]
 ));
 
+test_type!(byte_array<[u8; 4]>(MySql,
+    "X'DEADBEEF'" == [0xDE_u8, 0xAD, 0xBE, 0xEF],
+));
+
+#[sqlx_macros::test]
+async fn it_rejects_byte_arrays_of_another_length() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let res = sqlx::query_scalar::<_, [u8; 32]>("SELECT X'DEADBEEF'")
+        .fetch_one(&mut conn)
+        .await;
+
+    let err = res.unwrap_err().to_string();
+    assert!(err.contains("expected 32 bytes, got 4"), "{err}");
+
+    Ok(())
+}
+
 #[cfg(feature = "uuid")]
 test_type!(uuid<sqlx::types::Uuid>(MySql,
     "x'b731678f636f4135bc6f19440c13bd19'"
@@ -70,6 +109,39 @@ test_type!(uuid_simple<sqlx::types::uuid::fmt::Simple>(MySql,
         == sqlx::types::Uuid::parse_str("00000000000000000000000000000000").unwrap().simple()
 ));
 
+#[cfg(feature = "uuid")]
+test_type!(uuid_binary<sqlx::mysql::types::MySqlUuidBinary>(MySql,
+    "x'b731678f636f4135bc6f19440c13bd19'"
+        == sqlx::mysql::types::MySqlUuidBinary(
+            sqlx::types::Uuid::parse_str("b731678f-636f-4135-bc6f-19440c13bd19").unwrap()
+        ),
+));
+
+#[cfg(feature = "uuid")]
+test_type!(uuid_binary_swapped<sqlx::mysql::types::MySqlUuidBinarySwapped>(MySql,
+    // `UUID_TO_BIN('b731678f-636f-4135-bc6f-19440c13bd19', 1)`
+    "x'4135636fb731678fbc6f19440c13bd19'"
+        == sqlx::mysql::types::MySqlUuidBinarySwapped(
+            sqlx::types::Uuid::parse_str("b731678f-636f-4135-bc6f-19440c13bd19").unwrap()
+        ),
+));
+
+#[cfg(feature = "uuid")]
+test_type!(uuid_text<sqlx::mysql::types::MySqlUuidText>(MySql,
+    "'b731678f-636f-4135-bc6f-19440c13bd19'"
+        == sqlx::mysql::types::MySqlUuidText(
+            sqlx::types::Uuid::parse_str("b731678f-636f-4135-bc6f-19440c13bd19").unwrap()
+        ),
+));
+
+#[cfg(feature = "uuid")]
+test_decode_type!(uuid_text_simple<sqlx::mysql::types::MySqlUuidText>(MySql,
+    "'b731678f636f4135bc6f19440c13bd19'"
+        == sqlx::mysql::types::MySqlUuidText(
+            sqlx::types::Uuid::parse_str("b731678f-636f-4135-bc6f-19440c13bd19").unwrap()
+        ),
+));
+
 #[cfg(feature = "chrono")]
 mod chrono {
     use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
@@ -52,6 +52,26 @@ enum Strong {
     Three,
 }
 
+// Values matching no other variant are held by the `#[sqlx(other)]` variant
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[repr(i32)]
+enum WeakWithOther {
+    One = 1,
+    Two = 2,
+    #[sqlx(other)]
+    Unknown(i32),
+}
+
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+enum StrongWithOther {
+    One,
+    #[sqlx(rename = "deux")]
+    Two,
+    #[sqlx(other)]
+    Unknown(String),
+}
+
 // rename_all variants
 #[derive(PartialEq, Debug, sqlx::Type)]
 #[sqlx(type_name = "color_lower")]
@@ -113,7 +133,7 @@ enum ColorPascalCase {
 
 // "Strong" enum can map to a custom type
 #[derive(PartialEq, Debug, sqlx::Type)]
-#[sqlx(type_name = "mood")]
+#[sqlx(type_name = "mood", array_type_name = "_mood")]
 #[sqlx(rename_all = "lowercase")]
 enum Mood {
     Ok,
@@ -121,10 +141,34 @@ enum Mood {
     Sad,
 }
 
+// The array type is named after the enum with #[sqlx(array)]
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(type_name = "status", rename_all = "lowercase", array)]
+enum Status {
+    New,
+    Open,
+    Closed,
+}
+
+// ...and can still be implemented manually without it
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(type_name = "status", rename_all = "lowercase")]
+enum StatusWithoutArray {
+    New,
+    Open,
+    Closed,
+}
+
+impl sqlx::postgres::PgHasArrayType for StatusWithoutArray {
+    fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+        sqlx::postgres::PgTypeInfo::with_name("_status")
+    }
+}
+
 // Records must map to a custom type
 // Note that all types are types in Postgres
 #[derive(PartialEq, Debug, sqlx::Type)]
-#[sqlx(type_name = "inventory_item")]
+#[sqlx(type_name = "inventory_item", array_type_name = "_inventory_item")]
 struct InventoryItem {
     name: String,
     supplier_id: Option<i32>,
@@ -136,6 +180,24 @@ struct InventoryItem {
 #[sqlx(type_name = "float_range")]
 struct FloatRange(PgRange<f64>);
 
+// Custom domain type used as the element of a custom range type
+#[derive(sqlx::Type, Debug, PartialEq)]
+#[sqlx(
+    type_name = "seat_number",
+    array_type_name = "_seat_number",
+    range_type_name = "seat_range"
+)]
+struct SeatNumber(i32);
+
+// Custom domain type used as the element of a custom range type in another schema
+#[derive(sqlx::Type, Debug, PartialEq)]
+#[sqlx(
+    type_name = "sched.slot_number",
+    array_type_name = "sched._slot_number",
+    range_type_name = "sched.slot_range"
+)]
+struct SlotNumber(i32);
+
 // Custom domain type
 #[derive(sqlx::Type, Debug)]
 #[sqlx(type_name = "int4rangeL0pC")]
@@ -163,6 +225,18 @@ test_type!(strong_enum<Strong>(Postgres,
     "'four'::text" == Strong::Three
 ));
 
+test_type!(weak_enum_with_other<WeakWithOther>(Postgres,
+    "1::int4" == WeakWithOther::One,
+    "2::int4" == WeakWithOther::Two,
+    "7::int4" == WeakWithOther::Unknown(7)
+));
+
+test_type!(strong_enum_with_other<StrongWithOther>(Postgres,
+    "'one'::text" == StrongWithOther::One,
+    "'deux'::text" == StrongWithOther::Two,
+    "'three'::text" == StrongWithOther::Unknown("three".into())
+));
+
 test_type!(floatrange<FloatRange>(Postgres,
     "'[1.234, 5.678]'::float_range" == FloatRange(PgRange::from((Bound::Included(1.234), Bound::Included(5.678)))),
 ));
@@ -361,6 +435,74 @@ SELECT id, mood FROM people WHERE id = $1
     Ok(())
 }
 
+test_type!(seat_range<PgRange<SeatNumber>>(Postgres,
+    "'[1, 5)'::seat_range" == PgRange::from(SeatNumber(1)..SeatNumber(5)),
+    "'[3,)'::seat_range" == PgRange::from(SeatNumber(3)..),
+));
+
+test_type!(seat_range_vec<Vec<PgRange<SeatNumber>>>(Postgres,
+    "array['[1, 3)', '[5, 7)']::seat_range[]" == vec![
+        PgRange::from(SeatNumber(1)..SeatNumber(3)),
+        PgRange::from(SeatNumber(5)..SeatNumber(7)),
+    ],
+));
+
+test_type!(slot_range_vec<Vec<PgRange<SlotNumber>>>(Postgres,
+    "array['[1, 3)', '[5, 7)']::sched.slot_range[]" == vec![
+        PgRange::from(SlotNumber(1)..SlotNumber(3)),
+        PgRange::from(SlotNumber(5)..SlotNumber(7)),
+    ],
+));
+
+test_type!(mood_vec<Vec<Mood>>(Postgres,
+    "'{}'::mood[]" == Vec::<Mood>::new(),
+    "'{ok, sad, happy}'::mood[]" == vec![Mood::Ok, Mood::Sad, Mood::Happy],
+));
+
+test_type!(status_vec<Vec<Status>>(Postgres,
+    "'{}'::status[]" == Vec::<Status>::new(),
+    "'{new, closed}'::status[]" == vec![Status::New, Status::Closed],
+));
+
+test_type!(status_without_array_vec<Vec<StatusWithoutArray>>(Postgres,
+    "'{open}'::status[]" == vec![StatusWithoutArray::Open],
+));
+
+#[sqlx_macros::test]
+async fn test_enum_array_with_any() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let statuses: Vec<Status> = sqlx::query_scalar(
+        "SELECT s FROM unnest('{new, open, closed}'::status[]) s WHERE s = ANY($1) ORDER BY s",
+    )
+    .bind(vec![Status::Closed, Status::New])
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(statuses, [Status::New, Status::Closed]);
+
+    Ok(())
+}
+
+test_type!(inventory_item_vec<Vec<InventoryItem>>(Postgres,
+    "array[('fuzzy dice', 42, 199), ('dice', null, null)]::inventory_item[]" == vec![
+        InventoryItem {
+            name: "fuzzy dice".to_owned(),
+            supplier_id: Some(42),
+            price: Some(199),
+        },
+        InventoryItem {
+            name: "dice".to_owned(),
+            supplier_id: None,
+            price: None,
+        },
+    ],
+));
+
+test_type!(seat_number_vec<Vec<SeatNumber>>(Postgres,
+    "'{1, 2, 3}'::seat_number[]" == vec![SeatNumber(1), SeatNumber(2), SeatNumber(3)],
+));
+
 #[sqlx_macros::test]
 async fn test_record_type() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -697,6 +839,92 @@ async fn test_flatten() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn test_flatten_option() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq, sqlx::FromRow)]
+    struct Address {
+        city: String,
+        road: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, sqlx::FromRow)]
+    #[sqlx(rename_all = "camelCase")]
+    struct Company {
+        company_id: i32,
+        company_name: Option<String>,
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct User {
+        id: i32,
+        #[sqlx(flatten)]
+        address: Option<Address>,
+        #[sqlx(flatten, skip_if_null = "companyId")]
+        company: Option<Company>,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let users: Vec<User> = sqlx::query_as(
+        r#"
+SELECT id, city, road, "companyId", "companyName"
+FROM (VALUES (1), (2), (3)) users (id)
+LEFT JOIN (VALUES (1, 'Paris', 'Rue de Rivoli'), (2, 'Lyon', NULL)) addresses (user_id, city, road)
+    ON addresses.user_id = users.id
+LEFT JOIN (VALUES (1, 10, 'Acme'), (3, 30, NULL)) companies (user_id, "companyId", "companyName")
+    ON companies.user_id = users.id
+ORDER BY id
+        "#,
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(
+        users[0].address,
+        Some(Address {
+            city: "Paris".into(),
+            road: Some("Rue de Rivoli".into())
+        })
+    );
+    assert_eq!(
+        users[0].company,
+        Some(Company {
+            company_id: 10,
+            company_name: Some("Acme".into())
+        })
+    );
+
+    // some columns are NULL, but not all of them
+    assert_eq!(
+        users[1].address,
+        Some(Address {
+            city: "Lyon".into(),
+            road: None
+        })
+    );
+    assert_eq!(users[1].company, None);
+
+    assert_eq!(users[2].address, None);
+    // only `companyId` tells whether there is a company
+    assert_eq!(
+        users[2].company,
+        Some(Company {
+            company_id: 30,
+            company_name: None
+        })
+    );
+
+    // the columns of the nested structs are checked
+    let err = sqlx::query_as::<_, User>(r#"SELECT 1 AS id, 'Paris' AS city"#)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::ColumnNotFound(_)), "{err:?}");
+
+    Ok(())
+}
+
 #[cfg(feature = "macros")]
 #[sqlx_macros::test]
 async fn test_skip() -> anyhow::Result<()> {
@@ -724,3 +952,52 @@ async fn test_skip() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn test_from_columns() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq)]
+    struct Money {
+        amount: i64,
+        currency: String,
+    }
+
+    impl From<(i64, String)> for Money {
+        fn from((amount, currency): (i64, String)) -> Self {
+            Money { amount, currency }
+        }
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    #[sqlx(rename_all = "camelCase")]
+    struct Product {
+        product_id: i32,
+        #[sqlx(from_columns("price", "currency"), try_from = "(i64, String)")]
+        price: Money,
+        #[sqlx(from_columns("width", "height"))]
+        size: (i32, Option<i32>),
+        #[sqlx(from_columns("discount", "discount_currency"), default)]
+        discount: (Option<i64>, Option<String>),
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let product: Product = sqlx::query_as(
+        r#"SELECT * FROM (VALUES (1, 1250::int8, 'EUR', 20, null::int4)) products("productId", price, currency, width, height)"#,
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(product.product_id, 1);
+    assert_eq!(
+        product.price,
+        Money {
+            amount: 1250,
+            currency: "EUR".to_owned()
+        }
+    );
+    assert_eq!(product.size, (20, None));
+    assert_eq!(product.discount, (None, None));
+
+    Ok(())
+}
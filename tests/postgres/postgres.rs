@@ -1,10 +1,17 @@
 use futures::{StreamExt, TryStreamExt};
-use sqlx::postgres::types::Oid;
+use sqlx::export::CsvOptions;
+use sqlx::pool::RetryPolicy;
+use sqlx::postgres::types::{Oid, PgCiText, PgInterval};
 use sqlx::postgres::{
     PgAdvisoryLock, PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgListener,
-    PgPoolOptions, PgRow, PgSeverity, Postgres,
+    PgLoadBalanceHosts, PgLsn, PgPoolCopyExt, PgPoolOptions, PgQueryBuilderExt, PgReplication,
+    PgReplicationMessage, PgRow, PgSeverity, PgTargetSessionAttrs, PgTransactionStatus,
+    PgTypeCache, Postgres,
+};
+use sqlx::{
+    Column, ConnectOptions, Connection, Executor, IsolationLevel, QueryBuilder, Row, Statement,
+    TransactionOptions, TypeInfo,
 };
-use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
 use sqlx_test::{new, pool, setup_if_needed};
 use std::env;
 use std::sync::Arc;
@@ -93,6 +100,154 @@ async fn it_maths() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_decodes_null_aggregates_with_a_default() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let row = sqlx::query(
+        "SELECT SUM(x), MAX(x), COUNT(x) FROM (VALUES (1::int4), (NULL)) t (x) WHERE x > 10",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(row.try_get_or::<i64, _>("sum", 0)?, 0);
+    assert_eq!(row.try_get_or::<i32, _>(1, -1)?, -1);
+    assert_eq!(row.try_get_or::<i64, _>("count", 7)?, 0);
+    assert!(row.try_get::<i64, _>("sum").is_err());
+
+    let row = sqlx::query("SELECT SUM(x) FROM (VALUES (1::int4), (2), (NULL)) t (x)")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.try_get_or::<i64, _>(0, 0)?, 3);
+    assert!(matches!(
+        row.try_get_or::<i64, _>("missing", 0),
+        Err(sqlx::Error::ColumnNotFound(_))
+    ));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_decodes_citext_as_strings() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (email CITEXT NOT NULL, aliases CITEXT[] NOT NULL)")
+        .await?;
+
+    sqlx::query("INSERT INTO users (email, aliases) VALUES ($1, $2)")
+        .bind("Alice@Example.com")
+        .bind(vec![PgCiText("Alice".into()), PgCiText("Ally".into())])
+        .execute(&mut conn)
+        .await?;
+
+    // bound as `citext`, the comparison is case-insensitive
+    let (email, aliases): (String, Vec<String>) =
+        sqlx::query_as("SELECT email, aliases FROM users WHERE email = $1")
+            .bind(PgCiText("alice@example.COM".into()))
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(email, "Alice@Example.com");
+    assert_eq!(aliases, ["Alice", "Ally"]);
+
+    // bound as `text`, the comparison is case-sensitive
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users WHERE email = $1")
+        .bind("alice@example.COM")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_analyzes_tables() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"CREATE TEMPORARY TABLE "Analyzed" (id INT4); INSERT INTO "Analyzed" SELECT generate_series(1, 100)"#,
+    )
+    .await?;
+
+    conn.analyze("Analyzed").await?;
+
+    let reltuples: f32 =
+        sqlx::query_scalar(r#"SELECT reltuples FROM pg_class WHERE oid = '"Analyzed"'::regclass"#)
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(reltuples, 100.0);
+
+    let schema: String = sqlx::query_scalar(
+        "SELECT nspname::text FROM pg_namespace WHERE oid = pg_my_temp_schema()",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    conn.analyze(&format!("{schema}.Analyzed")).await?;
+
+    assert!(conn.analyze("analyzed").await.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_batches() -> anyhow::Result<()> {
+    use sqlx::batch::{execute_batch, BatchMode};
+
+    let mut conn = new::<Postgres>().await?;
+
+    let statements = [
+        "CREATE TEMPORARY TABLE batch (id INT4 PRIMARY KEY)",
+        "INSERT INTO batch VALUES (1)",
+        "INSERT INTO batch VALUES (1)",
+        "INSERT INTO batch VALUES (2)",
+    ];
+
+    let results = execute_batch(&mut conn, statements, BatchMode::StopOnError).await;
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[1].as_ref().unwrap().rows_affected(), 1);
+    assert!(results[2].is_err());
+
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM batch ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(ids, [1]);
+
+    // the failed statements are rolled back to a savepoint, so that the transaction goes on
+    let mut tx = conn.begin().await?;
+
+    let results = execute_batch(
+        &mut *tx,
+        [
+            "INSERT INTO batch VALUES (1)",
+            "INSERT INTO batch VALUES (2)",
+            "SELECT does_not_exist",
+            "INSERT INTO batch VALUES (3)",
+        ],
+        BatchMode::ContinueOnError,
+    )
+    .await;
+
+    assert_eq!(results.len(), 4);
+    assert!(results[0].is_err());
+    assert!(results[1].is_ok());
+    assert!(results[2].is_err());
+    assert!(results[3].is_ok());
+
+    tx.commit().await?;
+
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM batch ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(ids, [1, 2, 3]);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_inspect_errors() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -334,6 +489,75 @@ async fn it_can_fail_and_recover_with_pool() -> anyhow::Result<()> {
     Ok(())
 }
 
+// kills the only connection of `pool` while it is idle
+async fn terminate_pooled_connection(
+    pool: &sqlx::PgPool,
+    conn: &mut PgConnection,
+) -> anyhow::Result<()> {
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(pool)
+        .await?;
+
+    // the connection is returned to the pool in the background
+    while pool.num_idle() == 0 {
+        sqlx_core::rt::sleep(Duration::from_millis(1)).await;
+    }
+
+    // waits for the backend to exit
+    sqlx::query("SELECT pg_terminate_backend($1, 5000)")
+        .bind(pid)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_retries_retryable_queries_with_pool() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .test_before_acquire(false)
+        .retry_policy(RetryPolicy::new().base_delay(Duration::from_millis(1)))
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = new::<Postgres>().await?;
+
+    terminate_pooled_connection(&pool, &mut conn).await?;
+
+    let val: i32 = sqlx::query_scalar("SELECT $1::int4")
+        .bind(42_i32)
+        .retryable(true)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(val, 42);
+
+    // the statement is kept when retrying a prepared query
+    let statement = pool.prepare("SELECT $1::int4").await?;
+    terminate_pooled_connection(&pool, &mut conn).await?;
+
+    let row = statement
+        .query()
+        .bind(43_i32)
+        .retryable(true)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(row.try_get::<i32, _>(0)?, 43);
+
+    terminate_pooled_connection(&pool, &mut conn).await?;
+
+    // other queries are never retried
+    let res = sqlx::query_scalar::<_, i32>("SELECT $1::int4")
+        .bind(42_i32)
+        .fetch_one(&pool)
+        .await;
+    assert!(res.is_err());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_query_scalar() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -496,6 +720,68 @@ async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_recover_with_savepoints() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE savepoint_users (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut tx = conn.begin().await?;
+
+    let id: i32 = tx
+        .with_savepoint(|sp| {
+            Box::pin(async move {
+                sqlx::query_scalar("INSERT INTO savepoint_users (id) VALUES (1) RETURNING id")
+                    .fetch_one(&mut **sp)
+                    .await
+            })
+        })
+        .await?;
+    assert_eq!(id, 1);
+
+    // the duplicate key aborts the savepoint, but not the transaction
+    let res = tx
+        .with_savepoint(|sp| {
+            Box::pin(async move {
+                sp.execute("INSERT INTO savepoint_users (id) VALUES (2)")
+                    .await?;
+                sp.execute("INSERT INTO savepoint_users (id) VALUES (1)")
+                    .await
+            })
+        })
+        .await;
+    assert!(res.is_err());
+
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM savepoint_users ORDER BY id")
+        .fetch_all(&mut *tx)
+        .await?;
+    assert_eq!(ids, [1]);
+
+    // savepoints can be nested
+    tx.with_savepoint(|sp| {
+        Box::pin(async move {
+            sp.execute("INSERT INTO savepoint_users (id) VALUES (3)")
+                .await?;
+            let res = sp
+                .with_savepoint(|sp| Box::pin(async move { sp.execute("SELECT 1 / 0").await }))
+                .await;
+            assert!(res.is_err());
+            Ok::<_, sqlx::Error>(())
+        })
+    })
+    .await?;
+
+    tx.commit().await?;
+
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM savepoint_users ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(ids, [1, 3]);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_drop_multiple_transactions() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -748,1068 +1034,2920 @@ async fn it_closes_statement_from_cache_issue_470() -> anyhow::Result<()> {
 }
 
 #[sqlx_macros::test]
-async fn it_sets_application_name() -> anyhow::Result<()> {
-    sqlx_test::setup_if_needed();
+async fn it_warms_new_connections_with_cached_statements() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
-    options = options.application_name("some-name");
+    sqlx::query("SELECT 1::int4").execute(&mut conn).await?;
+    sqlx::query("SELECT $1::text")
+        .bind("a")
+        .execute(&mut conn)
+        .await?;
+    // not cached
+    sqlx::query("SELECT 2::int4")
+        .persistent(false)
+        .execute(&mut conn)
+        .await?;
 
-    let mut conn = PgConnection::connect_with(&options).await?;
+    let statements = conn.cached_statements().await?;
+    assert_eq!(statements, ["SELECT $1::text", "SELECT 1::int4"]);
 
-    let row = sqlx::query("select current_setting('application_name') as app_name")
-        .fetch_one(&mut conn)
+    let statements = Arc::new(statements);
+    let pool = PgPoolOptions::new()
+        .after_connect(move |conn, _meta| {
+            let statements = statements.clone();
+
+            Box::pin(async move {
+                for sql in statements.iter() {
+                    conn.prepare(sql).await?;
+                }
+
+                Ok(())
+            })
+        })
+        .connect(&env::var("DATABASE_URL")?)
         .await?;
 
-    let val: String = row.get("app_name");
+    let mut conn = pool.acquire().await?;
+    assert_eq!(conn.cached_statements_size(), 2);
 
-    assert_eq!("some-name", &val);
+    let mut statements = conn.cached_statements().await?;
+    statements.sort();
+    assert_eq!(statements, ["SELECT $1::text", "SELECT 1::int4"]);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_handle_parameter_status_message_issue_484() -> anyhow::Result<()> {
-    new::<Postgres>().await?.execute("SET NAMES 'UTF8'").await?;
+async fn it_normalizes_statement_cache_keys() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    let mut conn = PgConnection::connect_with(&options.normalize_statement_cache_key(true)).await?;
+
+    for sql in [
+        "SELECT $1::int4 + 1, '  a  '",
+        "  SELECT $1::int4\n  +  1,\t'  a  '\n",
+    ] {
+        let (val, s): (i32, String) = sqlx::query_as(sql).bind(1).fetch_one(&mut conn).await?;
+        assert_eq!((val, &*s), (2, "  a  "));
+    }
+
+    // string literals are not normalized
+    let (_, s): (i32, String) = sqlx::query_as("SELECT $1::int4 + 1, 'a'")
+        .bind(1)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(s, "a");
+
+    assert_eq!(
+        conn.cached_statements().await?,
+        ["SELECT $1::int4 + 1, 'a'", "SELECT $1::int4 + 1, '  a  '"]
+    );
+
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
+async fn it_prepends_comments_to_queries() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
-    let mut tx = conn.begin().await?;
-
-    let tweet_id: i64 =
-        sqlx::query_scalar("INSERT INTO tweet ( text ) VALUES ( 'Hello, World' ) RETURNING id")
-            .fetch_one(&mut *tx)
-            .await?;
 
-    let statement = tx.prepare("SELECT * FROM tweet WHERE id = $1").await?;
+    for request_id in ["abc", "*/ def /*"] {
+        let comment = format!("request_id='{request_id}'");
 
-    assert_eq!(statement.column(0).name(), "id");
-    assert_eq!(statement.column(1).name(), "created_at");
-    assert_eq!(statement.column(2).name(), "text");
-    assert_eq!(statement.column(3).name(), "owner_id");
+        let (query, val): (String, i32) = sqlx::query_as("SELECT current_query(), $1::int4")
+            .bind(1)
+            .with_comment(&comment)
+            .fetch_one(&mut conn)
+            .await?;
 
-    assert_eq!(statement.column(0).type_info().name(), "INT8");
-    assert_eq!(statement.column(1).type_info().name(), "TIMESTAMPTZ");
-    assert_eq!(statement.column(2).type_info().name(), "TEXT");
-    assert_eq!(statement.column(3).type_info().name(), "INT8");
+        assert_eq!(val, 1);
+        assert_eq!(
+            query,
+            format!(
+                "/* {} */ SELECT current_query(), $1::int4",
+                comment.replace("*/", "* /").replace("/*", "/ *")
+            )
+        );
+    }
 
-    let row = statement.query().bind(tweet_id).fetch_one(&mut *tx).await?;
-    let tweet_text: &str = row.try_get("text")?;
+    let query: String = sqlx::query_scalar("SELECT current_query()")
+        .with_comment("simple")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(query, "/* simple */ SELECT current_query()");
 
-    assert_eq!(tweet_text, "Hello, World");
+    // queries with a comment are not cached
+    assert!(conn.cached_statements().await?.is_empty());
 
     Ok(())
 }
 
-// repro is more reliable with the basic scheduler used by `#[tokio::test]`
-#[cfg(feature = "_rt-tokio")]
-#[tokio::test]
-async fn test_issue_622() -> anyhow::Result<()> {
-    use std::time::Instant;
-
-    setup_if_needed();
+#[sqlx_macros::test]
+async fn it_rewrites_sql_before_executing_it() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
 
-    let pool = PgPoolOptions::new()
-        .max_connections(1) // also fails with higher counts, e.g. 5
-        .connect(&std::env::var("DATABASE_URL").unwrap())
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    let mut conn = options
+        .sql_rewriter(Arc::new(|sql| {
+            if sql.contains("{shard}") {
+                sql.replace("{shard}", "shard_1").into()
+            } else {
+                sql.into()
+            }
+        }))
+        .connect()
         .await?;
 
-    println!("pool state: {pool:?}");
-
-    let mut handles = vec![];
-
-    // given repro spawned 100 tasks but I found it reliably reproduced with 3
-    for i in 0..3 {
-        let pool = pool.clone();
-
-        handles.push(sqlx_core::rt::spawn(async move {
-            {
-                let mut conn = pool.acquire().await.unwrap();
-
-                let _ = sqlx::query("SELECT 1").fetch_one(&mut *conn).await.unwrap();
+    conn.execute("CREATE TEMPORARY TABLE {shard}_users (id INT4 PRIMARY KEY)")
+        .await?;
 
-                // conn gets dropped here and should be returned to the pool
-            }
+    sqlx::query("INSERT INTO {shard}_users (id) VALUES ($1)")
+        .bind(1)
+        .execute(&mut conn)
+        .await?;
 
-            // (do some other work here without holding on to a connection)
-            // this actually fixes the issue, depending on the timeout used
-            // sqlx_core::rt::sleep(Duration::from_millis(500)).await;
+    let statement = conn.prepare("SELECT id FROM {shard}_users").await?;
+    assert_eq!(statement.sql(), "SELECT id FROM {shard}_users");
 
-            {
-                let start = Instant::now();
-                match pool.acquire().await {
-                    Ok(conn) => {
-                        println!("{} acquire took {:?}", i, start.elapsed());
-                        drop(conn);
-                    }
-                    Err(e) => panic!("{i} acquire returned error: {e} pool state: {pool:?}"),
-                }
-            }
+    let ids: Vec<i32> = statement
+        .query_as::<(i32,)>()
+        .fetch_all(&mut conn)
+        .await?
+        .into_iter()
+        .map(|(id,)| id)
+        .collect();
+    assert_eq!(ids, [1]);
 
-            Result::<(), anyhow::Error>::Ok(())
-        }));
-    }
+    let id: i32 = sqlx::query_scalar("SELECT id FROM shard_1_users")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(id, 1);
 
-    futures::future::try_join_all(handles).await?;
+    // the statements are cached with the rewritten SQL
+    assert!(conn
+        .cached_statements()
+        .await?
+        .contains(&"INSERT INTO shard_1_users (id) VALUES ($1)".to_string()));
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_describe_outer_join_nullable() -> anyhow::Result<()> {
+async fn it_recovers_from_failed_transactions() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
-    // test nullability inference for various joins
+    assert_eq!(conn.transaction_status(), PgTransactionStatus::Idle);
 
-    // inner join, nullability should not be overridden
-    // language=PostgreSQL
-    let describe = conn
-        .describe(
-            "select tweet.id
-    from tweet
-    inner join products on products.name = tweet.text",
-        )
-        .await?;
+    // a plain `BEGIN`, which SQLx does not track as a transaction
+    conn.execute("BEGIN").await?;
+    assert_eq!(
+        conn.transaction_status(),
+        PgTransactionStatus::InTransaction
+    );
 
-    assert_eq!(describe.nullable(0), Some(false));
+    assert!(conn.execute("SELECT 1 / 0").await.is_err());
+    assert_eq!(
+        conn.transaction_status(),
+        PgTransactionStatus::InFailedTransaction
+    );
 
-    // language=PostgreSQL
-    let describe = conn
-        .describe(
-            "select tweet.id
-from (values (null)) vals(val)
-         left join tweet on false",
-        )
-        .await?;
+    let err = conn.execute("SELECT 1").await.unwrap_err();
+    assert_eq!(err.into_database_error().unwrap().code().unwrap(), "25P02");
 
-    // tweet.id is marked NOT NULL but it's brought in from a left-join here
-    // which should make it nullable
-    assert_eq!(describe.nullable(0), Some(true));
+    conn.recover().await?;
+    assert_eq!(conn.transaction_status(), PgTransactionStatus::Idle);
 
-    // make sure we don't mis-infer for the outer half of the join
-    // language=PostgreSQL
-    let describe = conn
-        .describe(
-            "select tweet1.id, tweet2.id
-    from tweet tweet1
-    left join tweet tweet2 on false",
-        )
-        .await?;
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
 
-    assert_eq!(describe.nullable(0), Some(false));
-    assert_eq!(describe.nullable(1), Some(true));
+    // does nothing if the connection is idle
+    conn.recover().await?;
+    assert_eq!(conn.transaction_status(), PgTransactionStatus::Idle);
 
-    // right join, nullability should be inverted
-    // language=PostgreSQL
-    let describe = conn
-        .describe(
-            "select tweet1.id, tweet2.id
-    from tweet tweet1
-    right join tweet tweet2 on false",
-        )
-        .await?;
+    let mut tx = conn.begin().await?;
+    let mut savepoint = tx.begin().await?;
+    assert!(savepoint.execute("SELECT 1 / 0").await.is_err());
 
-    assert_eq!(describe.nullable(0), Some(true));
-    assert_eq!(describe.nullable(1), Some(false));
+    savepoint.recover().await?;
+    assert_eq!(savepoint.transaction_status(), PgTransactionStatus::Idle);
 
-    // full outer join, both tables are nullable
-    // language=PostgreSQL
-    let describe = conn
-        .describe(
-            "select tweet1.id, tweet2.id
-    from tweet tweet1
-    full join tweet tweet2 on false",
-        )
-        .await?;
+    // the transactions were rolled back as a whole
+    savepoint.rollback().await?;
+    tx.commit().await?;
+    assert_eq!(conn.transaction_status(), PgTransactionStatus::Idle);
 
-    assert_eq!(describe.nullable(0), Some(true));
-    assert_eq!(describe.nullable(1), Some(true));
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_listener_cleanup() -> anyhow::Result<()> {
-    #[cfg(feature = "_rt-tokio")]
-    use tokio::time::timeout;
-
-    #[cfg(feature = "_rt-async-std")]
-    use async_std::future::timeout;
-
-    use sqlx::pool::PoolOptions;
-    use sqlx::postgres::PgListener;
-
-    // Create a connection on which to send notifications
-    let mut notify_conn = new::<Postgres>().await?;
+async fn it_rolls_back_leaked_transactions_on_release() -> anyhow::Result<()> {
+    setup_if_needed();
 
-    // Create a pool with exactly one connection so we can
-    // deterministically test the cleanup.
-    let pool = PoolOptions::<Postgres>::new()
-        .min_connections(1)
+    let pool = PgPoolOptions::new()
         .max_connections(1)
-        .test_before_acquire(true)
         .connect(&env::var("DATABASE_URL")?)
         .await?;
 
-    let mut listener = PgListener::connect_with(&pool).await?;
-    listener.listen("test_channel").await?;
-
-    // Checks for a notification on the test channel
-    async fn try_recv(listener: &mut PgListener) -> anyhow::Result<bool> {
-        match timeout(Duration::from_millis(100), listener.recv()).await {
-            Ok(res) => {
-                res?;
-                Ok(true)
-            }
-            Err(_) => Ok(false),
-        }
-    }
-
-    // Check no notification is received before one is sent
-    assert!(!try_recv(&mut listener).await?, "Notification not sent");
+    let mut conn = pool.acquire().await?;
 
-    // Check notification is sent and received
-    notify_conn.execute("NOTIFY test_channel").await?;
-    assert!(
-        try_recv(&mut listener).await?,
-        "Notification sent and received"
-    );
-    assert!(
-        !try_recv(&mut listener).await?,
-        "Notification is not duplicated"
-    );
+    // a plain `BEGIN`, which is left open when the connection is released
+    conn.execute("BEGIN").await?;
+    conn.execute("CREATE TEMPORARY TABLE leaked_transaction (id INT)")
+        .await?;
+    drop(conn);
 
-    // Test that cleanup stops listening on the channel
-    drop(listener);
-    let mut listener = PgListener::connect_with(&pool).await?;
+    let mut conn = pool.acquire().await?;
+    assert_eq!(conn.transaction_status(), PgTransactionStatus::Idle);
 
-    // Check notification is sent but not received
-    notify_conn.execute("NOTIFY test_channel").await?;
-    assert!(
-        !try_recv(&mut listener).await?,
-        "Notification is not received on fresh listener"
-    );
+    let exists: bool = sqlx::query_scalar("SELECT to_regclass('leaked_transaction') IS NOT NULL")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert!(!exists);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_pg_listener_allows_pool_to_close() -> anyhow::Result<()> {
-    let pool = pool::<Postgres>().await?;
+async fn it_tells_upserted_rows_which_were_inserted() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    // acquires and holds a connection which would normally prevent the pool from closing
-    let mut listener = PgListener::connect_with(&pool).await?;
+    conn.execute("CREATE TEMPORARY TABLE upserts (id INT PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+    conn.execute("INSERT INTO upserts VALUES (1, 'a')").await?;
 
-    sqlx_core::rt::spawn(async move {
-        listener.recv().await.unwrap();
-    });
+    let mut query_builder = QueryBuilder::new("INSERT INTO upserts (id, name) ");
+    query_builder
+        .push_values([(1, "b"), (2, "c")], |mut b, (id, name)| {
+            b.push_bind(id).push_bind(name);
+        })
+        .push_on_conflict_do_update(&["id"], &["name"])
+        .push_returning_inserted(&["id", "name"]);
 
-    // would previously hang forever since `PgListener` had no way to know the pool wanted to close
-    pool.close().await;
+    let mut rows: Vec<(i32, String, bool)> =
+        query_builder.build_query_as().fetch_all(&mut conn).await?;
+    rows.sort();
+
+    assert_eq!(
+        rows,
+        [(1, "b".to_owned(), false), (2, "c".to_owned(), true)]
+    );
+
+    let mut query_builder = QueryBuilder::new("INSERT INTO upserts (id, name) ");
+    query_builder
+        .push_values([(2, "d"), (3, "e")], |mut b, (id, name)| {
+            b.push_bind(id).push_bind(name);
+        })
+        .push_on_conflict_do_nothing(&["id"])
+        .push_returning_inserted(&["id"]);
+
+    let rows: Vec<(i32, bool)> = query_builder.build_query_as().fetch_all(&mut conn).await?;
+    assert_eq!(rows, [(3, true)]);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_supports_domain_types_in_composite_domain_types() -> anyhow::Result<()> {
-    // Only supported in Postgres 11+
+async fn it_sets_the_role_of_a_transaction() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
-    if matches!(conn.server_version_num(), Some(version) if version < 110000) {
-        return Ok(());
-    }
 
     conn.execute(
         r#"
-DROP TABLE IF EXISTS heating_bills;
-DROP DOMAIN IF EXISTS winter_year_month;
-DROP TYPE IF EXISTS year_month;
-DROP DOMAIN IF EXISTS month_id;
-
-CREATE DOMAIN month_id AS INT2 CHECK (1 <= value AND value <= 12);
-CREATE TYPE year_month AS (year INT4, month month_id);
-CREATE DOMAIN winter_year_month AS year_month CHECK ((value).month <= 3);
-CREATE TABLE heating_bills (
-  month winter_year_month NOT NULL PRIMARY KEY,
-  cost INT4 NOT NULL
-);
-    "#,
+DO $$
+BEGIN
+    CREATE ROLE "sqlx_Tenant";
+EXCEPTION WHEN duplicate_object THEN
+END
+$$;
+        "#,
     )
     .await?;
 
-    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct MonthId(i16);
+    let user: String = sqlx::query_scalar("SELECT current_user::text")
+        .fetch_one(&mut conn)
+        .await?;
 
-    impl sqlx::Type<Postgres> for MonthId {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            sqlx::postgres::PgTypeInfo::with_name("month_id")
-        }
+    assert!(matches!(
+        conn.set_role("sqlx_Tenant").await,
+        Err(sqlx::Error::Configuration(_))
+    ));
 
-        fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
-            *ty == Self::type_info()
-        }
-    }
+    let mut tx = conn.begin().await?;
+    tx.set_role("sqlx_Tenant").await?;
 
-    impl<'r> sqlx::Decode<'r, Postgres> for MonthId {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            Ok(Self(<i16 as sqlx::Decode<Postgres>>::decode(value)?))
-        }
-    }
+    let role: String = sqlx::query_scalar("SELECT current_user::text")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(role, "sqlx_Tenant");
 
-    impl<'q> sqlx::Encode<'q, Postgres> for MonthId {
-        fn encode_by_ref(
-            &self,
-            buf: &mut sqlx::postgres::PgArgumentBuffer,
-        ) -> sqlx::encode::IsNull {
-            <i16 as sqlx::Encode<Postgres>>::encode(self.0, buf)
-        }
-    }
+    tx.commit().await?;
 
-    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct WinterYearMonth {
-        year: i32,
-        month: MonthId,
-    }
+    // the role is reset at the end of the transaction
+    let role: String = sqlx::query_scalar("SELECT current_user::text")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(role, user);
 
-    impl sqlx::Type<Postgres> for WinterYearMonth {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            sqlx::postgres::PgTypeInfo::with_name("winter_year_month")
-        }
+    // the name is quoted
+    let mut tx = conn.begin().await?;
+    let res = tx.set_role("sqlx_Tenant\"; RESET ROLE; --").await;
+    assert!(matches!(res, Err(sqlx::Error::Database(_))), "{res:?}");
 
-        fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
-            *ty == Self::type_info()
-        }
-    }
+    Ok(())
+}
 
-    impl<'r> sqlx::Decode<'r, Postgres> for WinterYearMonth {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            let mut decoder = sqlx::postgres::types::PgRecordDecoder::new(value)?;
+#[sqlx_macros::test]
+async fn it_sets_application_name() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
 
-            let year = decoder.try_decode::<i32>()?;
-            let month = decoder.try_decode::<MonthId>()?;
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    options = options.application_name("some-name");
 
-            Ok(Self { year, month })
-        }
-    }
+    let mut conn = PgConnection::connect_with(&options).await?;
 
-    impl<'q> sqlx::Encode<'q, Postgres> for WinterYearMonth {
-        fn encode_by_ref(
-            &self,
-            buf: &mut sqlx::postgres::PgArgumentBuffer,
-        ) -> sqlx::encode::IsNull {
-            let mut encoder = sqlx::postgres::types::PgRecordEncoder::new(buf);
-            encoder.encode(self.year);
-            encoder.encode(self.month);
-            encoder.finish();
-            sqlx::encode::IsNull::No
-        }
-    }
-    let mut conn = new::<Postgres>().await?;
+    let row = sqlx::query("select current_setting('application_name') as app_name")
+        .fetch_one(&mut conn)
+        .await?;
 
-    let result = sqlx::query("DELETE FROM heating_bills;")
-        .execute(&mut conn)
-        .await;
+    let val: String = row.get("app_name");
 
-    let result = result.unwrap();
-    assert_eq!(result.rows_affected(), 0);
+    assert_eq!("some-name", &val);
 
-    let result =
-        sqlx::query("INSERT INTO heating_bills(month, cost) VALUES($1::winter_year_month, 100);")
-            .bind(WinterYearMonth {
-                year: 2021,
-                month: MonthId(1),
-            })
-            .execute(&mut conn)
-            .await;
+    Ok(())
+}
 
-    let result = result.unwrap();
-    assert_eq!(result.rows_affected(), 1);
+#[sqlx_macros::test]
+async fn it_tries_hosts_until_the_session_matches() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
 
-    let result = sqlx::query("DELETE FROM heating_bills;")
-        .execute(&mut conn)
-        .await;
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    let (host, port) = options.get_hosts().next().unwrap();
+    let (host, port) = (host.to_owned(), port);
 
-    let result = result.unwrap();
-    assert_eq!(result.rows_affected(), 1);
+    // nothing listens on the first host, so the second one is used
+    let options = options.hosts([("localhost", 1), (&*host, port)]);
+
+    let mut conn = PgConnection::connect_with(
+        &options
+            .clone()
+            .target_session_attrs(PgTargetSessionAttrs::ReadWrite),
+    )
+    .await?;
+
+    let read_only: String = sqlx::query_scalar("SHOW transaction_read_only")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(read_only, "off");
+
+    PgConnection::connect_with(
+        &options
+            .clone()
+            .target_session_attrs(PgTargetSessionAttrs::Primary),
+    )
+    .await?;
+
+    // the server is not a standby, so none of the hosts matches
+    let res = PgConnection::connect_with(
+        &options
+            .clone()
+            .target_session_attrs(PgTargetSessionAttrs::Standby),
+    )
+    .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Configuration(_))));
+
+    // the session of the second host is read-only, and the first one is skipped
+    let options = options.default_transaction_read_only(true);
+
+    PgConnection::connect_with(
+        &options
+            .clone()
+            .target_session_attrs(PgTargetSessionAttrs::ReadOnly),
+    )
+    .await?;
+
+    let res =
+        PgConnection::connect_with(&options.target_session_attrs(PgTargetSessionAttrs::ReadWrite))
+            .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Configuration(_))));
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_resolves_custom_type_in_array() -> anyhow::Result<()> {
-    // Only supported in Postgres 11+
+async fn it_fails_over_to_the_next_host_on_timeout() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    let (host, port) = options.get_hosts().next().unwrap();
+    let (host, port) = (host.to_owned(), port);
+
+    // the kernel accepts connections to this socket, but nothing ever responds
+    let unresponsive = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let unresponsive_port = unresponsive.local_addr()?.port();
+
+    let options = options
+        .hosts([("127.0.0.1", unresponsive_port), (&*host, port)])
+        .connect_timeout(Duration::from_millis(500));
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+    conn.ping().await?;
+
+    let res =
+        PgConnection::connect_with(&options.clone().hosts([("127.0.0.1", unresponsive_port)]))
+            .await;
+
+    assert!(
+        matches!(&res, Err(sqlx::Error::Io(e)) if e.kind() == std::io::ErrorKind::TimedOut),
+        "{res:?}"
+    );
+
+    // whichever host is tried first, the connection succeeds
+    let options = options.load_balance_hosts(PgLoadBalanceHosts::Random);
+
+    for _ in 0..4 {
+        PgConnection::connect_with(&options).await?.close().await?;
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_retries_transient_connect_errors() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+
+    let mut conn = options
+        .connect_with_retry(3, Duration::from_millis(10))
+        .await?;
+    conn.ping().await?;
+
+    // a server which closes every connection right away
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+    let accepted = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+    std::thread::spawn({
+        let accepted = accepted.clone();
+        move || {
+            for stream in listener.incoming() {
+                accepted.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                drop(stream);
+            }
+        }
+    });
+
+    let err = options
+        .clone()
+        .host("127.0.0.1")
+        .port(port)
+        .ssl_mode(sqlx::postgres::PgSslMode::Disable)
+        .connect_with_retry(3, Duration::from_millis(10))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::Io(_)), "{err:?}");
+    assert_eq!(accepted.load(std::sync::atomic::Ordering::SeqCst), 3);
+
+    // invalid credentials are not retried, or this would wait for an hour
+    let err = options
+        .clone()
+        .username("sqlx_no_such_user")
+        .connect_with_retry(2, Duration::from_secs(3600))
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("28000"),
+        "{err:?}"
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_times_out_pings_on_half_open_connections() -> anyhow::Result<()> {
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    let (host, port) = options.get_hosts().next().unwrap();
+    let server = TcpStream::connect((host, port))?;
+
+    // a proxy to the server, which stops forwarding anything once `half_open` is set
+    let half_open = Arc::new(AtomicBool::new(false));
+    let proxy = TcpListener::bind("127.0.0.1:0")?;
+    let proxy_port = proxy.local_addr()?.port();
+
+    let forward = |mut from: TcpStream, mut to: TcpStream, half_open: Arc<AtomicBool>| {
+        std::thread::spawn(move || {
+            let mut buf = [0; 8192];
+
+            while let Ok(n @ 1..) = from.read(&mut buf) {
+                if !half_open.load(Ordering::SeqCst) && to.write_all(&buf[..n]).is_err() {
+                    break;
+                }
+            }
+        });
+    };
+
+    std::thread::spawn({
+        let half_open = half_open.clone();
+
+        move || {
+            let (client, _) = proxy.accept().unwrap();
+            forward(
+                client.try_clone().unwrap(),
+                server.try_clone().unwrap(),
+                half_open.clone(),
+            );
+            forward(server, client, half_open);
+        }
+    });
+
+    let mut conn = PgConnection::connect_with(&options.host("127.0.0.1").port(proxy_port)).await?;
+    conn.ping_with_timeout(Duration::from_secs(5)).await?;
+
+    half_open.store(true, Ordering::SeqCst);
+
+    let start = std::time::Instant::now();
+    let res = conn.ping_with_timeout(Duration::from_millis(200)).await;
+
+    assert!(
+        matches!(&res, Err(sqlx::Error::Io(e)) if e.kind() == std::io::ErrorKind::TimedOut),
+        "{res:?}"
+    );
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_waits_for_a_wal_position() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
-    if matches!(conn.server_version_num(), Some(version) if version < 110000) {
+
+    // the server is a primary, which never replays the WAL of another server
+    assert_eq!(conn.last_wal_replay_lsn().await?, None);
+
+    conn.execute("CREATE TEMPORARY TABLE wal_test (id INT)")
+        .await?;
+
+    let lsn = conn.current_wal_lsn().await?;
+
+    assert!(lsn > PgLsn(0));
+    assert!(conn.wait_for_lsn(lsn, Duration::from_secs(1)).await?);
+
+    let start = std::time::Instant::now();
+    let reached = conn
+        .wait_for_lsn(PgLsn(u64::MAX), Duration::from_millis(50))
+        .await?;
+
+    assert!(!reached);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_streams_logical_replication_changes() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let wal_level: String = sqlx::query_scalar("SHOW wal_level")
+        .fetch_one(&mut conn)
+        .await?;
+
+    // logical replication requires the server to run with `wal_level = logical`
+    if wal_level != "logical" {
+        eprintln!("skipping: wal_level is {wal_level:?}, not \"logical\"");
         return Ok(());
     }
 
-    // language=PostgreSQL
     conn.execute(
         r#"
-DROP TABLE IF EXISTS pets;
-DROP TYPE IF EXISTS pet_name_and_race;
-
-CREATE TYPE pet_name_and_race AS (
-  name TEXT,
-  race TEXT
-);
-CREATE TABLE pets (
-  owner TEXT NOT NULL,
-  name TEXT NOT NULL,
-  race TEXT NOT NULL,
-  PRIMARY KEY (owner, name)
-);
-INSERT INTO pets(owner, name, race)
-VALUES
-  ('Alice', 'Foo', 'cat');
-INSERT INTO pets(owner, name, race)
-VALUES
-  ('Alice', 'Bar', 'dog');
+DROP PUBLICATION IF EXISTS replicated_pub;
+DROP TABLE IF EXISTS replicated;
+CREATE TABLE replicated (id INT PRIMARY KEY, name TEXT NOT NULL);
+CREATE PUBLICATION replicated_pub FOR TABLE replicated;
     "#,
     )
     .await?;
 
-    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct PetNameAndRace {
-        name: String,
-        race: String,
-    }
+    // the server asks for a status update after half of `wal_sender_timeout` without one, and
+    // disconnects if none arrives in time, so waiting for changes requires answering keepalives
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .options([("wal_sender_timeout", "1s")]);
 
-    impl sqlx::Type<Postgres> for PetNameAndRace {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            sqlx::postgres::PgTypeInfo::with_name("pet_name_and_race")
+    let mut replication = PgReplication::connect_with(&options).await?;
+    let start_lsn = replication.create_slot("sqlx_test_slot", true).await?;
+    assert!(start_lsn > PgLsn(0));
+
+    let mut stream = replication
+        .start("sqlx_test_slot", &["replicated_pub"], start_lsn)
+        .await?;
+
+    conn.execute("INSERT INTO replicated (id, name) VALUES (1, 'foo')")
+        .await?;
+
+    let mut writer = new::<Postgres>().await?;
+    let writes = sqlx_core::rt::spawn(async move {
+        sqlx_core::rt::sleep(Duration::from_secs(3)).await;
+
+        writer
+            .execute("UPDATE replicated SET name = 'bar' WHERE id = 1")
+            .await?;
+        writer
+            .execute("DELETE FROM replicated WHERE id = 1")
+            .await?;
+
+        Ok::<_, sqlx::Error>(())
+    });
+
+    let mut changes = Vec::new();
+    let mut end_lsn = PgLsn(0);
+
+    // receive the three transactions
+    for _ in 0..3 {
+        loop {
+            match stream.recv().await? {
+                PgReplicationMessage::Begin { .. } | PgReplicationMessage::Relation(_) => {}
+                PgReplicationMessage::Commit { end_lsn: lsn, .. } => {
+                    end_lsn = lsn;
+                    break;
+                }
+                change => changes.push(change),
+            }
         }
     }
 
-    impl<'r> sqlx::Decode<'r, Postgres> for PetNameAndRace {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            let mut decoder = sqlx::postgres::types::PgRecordDecoder::new(value)?;
-            let name = decoder.try_decode::<String>()?;
-            let race = decoder.try_decode::<String>()?;
-            Ok(Self { name, race })
+    writes.await?;
+
+    let value = |relation: &sqlx::postgres::PgRelation, tuple, name| {
+        relation
+            .get(tuple, name)
+            .and_then(|value| value.as_str().unwrap())
+            .map(str::to_owned)
+    };
+
+    assert_eq!(changes.len(), 3);
+
+    match &changes[0] {
+        PgReplicationMessage::Insert { relation, new } => {
+            assert_eq!(relation.namespace, "public");
+            assert_eq!(relation.name, "replicated");
+            assert_eq!(relation.columns.len(), 2);
+            assert!(relation.columns[0].is_key);
+            assert_eq!(value(relation, new, "id").as_deref(), Some("1"));
+            assert_eq!(value(relation, new, "name").as_deref(), Some("foo"));
         }
+        other => panic!("expected an insert, got {other:?}"),
     }
 
-    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct PetNameAndRaceArray(Vec<PetNameAndRace>);
-
-    impl sqlx::Type<Postgres> for PetNameAndRaceArray {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            // Array type name is the name of the element type prefixed with `_`
-            sqlx::postgres::PgTypeInfo::with_name("_pet_name_and_race")
+    match &changes[1] {
+        PgReplicationMessage::Update { relation, old, new } => {
+            // the key did not change
+            assert!(old.is_none());
+            assert_eq!(value(relation, new, "id").as_deref(), Some("1"));
+            assert_eq!(value(relation, new, "name").as_deref(), Some("bar"));
         }
+        other => panic!("expected an update, got {other:?}"),
     }
 
-    impl<'r> sqlx::Decode<'r, Postgres> for PetNameAndRaceArray {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            Ok(Self(Vec::<PetNameAndRace>::decode(value)?))
+    match &changes[2] {
+        PgReplicationMessage::Delete { relation, old } => {
+            assert_eq!(value(relation, old, "id").as_deref(), Some("1"));
         }
+        other => panic!("expected a delete, got {other:?}"),
     }
 
-    let mut conn = new::<Postgres>().await?;
+    assert!(stream.received_lsn() >= end_lsn);
 
-    let row = sqlx::query("select owner, array_agg(row(name, race)::pet_name_and_race) as pets from pets group by owner")
+    stream.ack(end_lsn).await?;
+    assert_eq!(stream.flushed_lsn(), end_lsn);
+
+    // the status update is processed asynchronously by the server
+    let mut confirmed = PgLsn(0);
+
+    for _ in 0..50 {
+        confirmed = sqlx::query_scalar(
+            "SELECT confirmed_flush_lsn FROM pg_replication_slots WHERE slot_name = $1",
+        )
+        .bind("sqlx_test_slot")
         .fetch_one(&mut conn)
         .await?;
 
-    let pets: PetNameAndRaceArray = row.get("pets");
+        if confirmed >= end_lsn {
+            break;
+        }
+
+        sqlx_core::rt::sleep(Duration::from_millis(100)).await;
+    }
+
+    assert_eq!(confirmed, end_lsn);
+
+    // the temporary slot is dropped with the connection
+    stream.close().await?;
+    conn.execute("DROP PUBLICATION replicated_pub; DROP TABLE replicated")
+        .await?;
 
-    assert_eq!(pets.0.len(), 2);
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_resolves_custom_types_in_anonymous_records() -> anyhow::Result<()> {
-    use sqlx_core::error::Error;
-    // This request involves nested records and array types.
+async fn it_sets_default_transaction_characteristics() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
 
-    // Only supported in Postgres 11+
-    let mut conn = new::<Postgres>().await?;
-    if matches!(conn.server_version_num(), Some(version) if version < 110000) {
-        return Ok(());
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    options = options
+        .default_transaction_isolation(IsolationLevel::Serializable)
+        .default_transaction_read_only(true);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    for _ in 0..2 {
+        let mut tx = conn.begin().await?;
+
+        let (isolation, read_only): (String, String) = sqlx::query_as(
+            "SELECT current_setting('transaction_isolation'), current_setting('transaction_read_only')",
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        assert_eq!(isolation, "serializable");
+        assert_eq!(read_only, "on");
+
+        let res = sqlx::query("CREATE TEMPORARY TABLE read_only_test (id INT)")
+            .execute(&mut *tx)
+            .await;
+
+        assert!(
+            matches!(res, Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some("25006"))
+        );
+
+        tx.rollback().await?;
+
+        // the settings are the session defaults, which survive a reset
+        conn.execute("RESET ALL").await?;
     }
 
-    // language=PostgreSQL
-    conn.execute(
-        r#"
-DROP TABLE IF EXISTS repo_users;
-DROP TABLE IF EXISTS repositories;
-DROP TABLE IF EXISTS repo_memberships;
-DROP TYPE IF EXISTS repo_member;
+    // they can still be overridden for a single transaction
+    let mut tx = conn.begin().await?;
+    tx.execute("SET TRANSACTION ISOLATION LEVEL READ COMMITTED READ WRITE")
+        .await?;
 
-CREATE TABLE repo_users (
-  user_id INT4 NOT NULL,
-  username TEXT NOT NULL,
-  PRIMARY KEY (user_id)
-);
-CREATE TABLE repositories (
-  repo_id INT4 NOT NULL,
-  repo_name TEXT NOT NULL,
-  PRIMARY KEY (repo_id)
-);
-CREATE TABLE repo_memberships (
-  repo_id INT4 NOT NULL,
-  user_id INT4 NOT NULL,
-  permission TEXT NOT NULL,
-  PRIMARY KEY (repo_id, user_id)
-);
-CREATE TYPE repo_member AS (
-  user_id INT4,
-  permission TEXT
-);
-INSERT INTO repo_users(user_id, username)
-VALUES
-  (101, 'alice'),
-  (102, 'bob'),
-  (103, 'charlie');
-INSERT INTO repositories(repo_id, repo_name)
-VALUES
-  (201, 'rust'),
-  (202, 'sqlx'),
-  (203, 'hello-world');
-INSERT INTO repo_memberships(repo_id, user_id, permission)
-VALUES
-  (201, 101, 'admin'),
-  (201, 102, 'write'),
+    let isolation: String = sqlx::query_scalar("SELECT current_setting('transaction_isolation')")
+        .fetch_one(&mut *tx)
+        .await?;
+
+    assert_eq!(isolation, "read committed");
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_sets_date_style_and_interval_style() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    options = options.date_style("ISO, DMY").interval_style("iso_8601");
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let date_style: String = sqlx::query_scalar("SHOW DateStyle")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(date_style, "ISO, DMY");
+
+    // unprepared queries return the interval in the text format
+    let row = conn
+        .fetch_one("SELECT INTERVAL '1 year 2 days 3.5 seconds'")
+        .await?;
+
+    let interval: PgInterval = row.try_get(0)?;
+
+    assert_eq!(
+        interval,
+        PgInterval {
+            months: 12,
+            days: 2,
+            microseconds: 3_500_000,
+        }
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_handle_parameter_status_message_issue_484() -> anyhow::Result<()> {
+    new::<Postgres>().await?.execute("SET NAMES 'UTF8'").await?;
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
+
+    let tweet_id: i64 =
+        sqlx::query_scalar("INSERT INTO tweet ( text ) VALUES ( 'Hello, World' ) RETURNING id")
+            .fetch_one(&mut *tx)
+            .await?;
+
+    let statement = tx.prepare("SELECT * FROM tweet WHERE id = $1").await?;
+
+    assert_eq!(statement.column(0).name(), "id");
+    assert_eq!(statement.column(1).name(), "created_at");
+    assert_eq!(statement.column(2).name(), "text");
+    assert_eq!(statement.column(3).name(), "owner_id");
+
+    assert_eq!(statement.column(0).type_info().name(), "INT8");
+    assert_eq!(statement.column(1).type_info().name(), "TIMESTAMPTZ");
+    assert_eq!(statement.column(2).type_info().name(), "TEXT");
+    assert_eq!(statement.column(3).type_info().name(), "INT8");
+
+    let row = statement.query().bind(tweet_id).fetch_one(&mut *tx).await?;
+    let tweet_text: &str = row.try_get("text")?;
+
+    assert_eq!(tweet_text, "Hello, World");
+
+    Ok(())
+}
+
+// repro is more reliable with the basic scheduler used by `#[tokio::test]`
+#[cfg(feature = "_rt-tokio")]
+#[tokio::test]
+async fn test_issue_622() -> anyhow::Result<()> {
+    use std::time::Instant;
+
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1) // also fails with higher counts, e.g. 5
+        .connect(&std::env::var("DATABASE_URL").unwrap())
+        .await?;
+
+    println!("pool state: {pool:?}");
+
+    let mut handles = vec![];
+
+    // given repro spawned 100 tasks but I found it reliably reproduced with 3
+    for i in 0..3 {
+        let pool = pool.clone();
+
+        handles.push(sqlx_core::rt::spawn(async move {
+            {
+                let mut conn = pool.acquire().await.unwrap();
+
+                let _ = sqlx::query("SELECT 1").fetch_one(&mut *conn).await.unwrap();
+
+                // conn gets dropped here and should be returned to the pool
+            }
+
+            // (do some other work here without holding on to a connection)
+            // this actually fixes the issue, depending on the timeout used
+            // sqlx_core::rt::sleep(Duration::from_millis(500)).await;
+
+            {
+                let start = Instant::now();
+                match pool.acquire().await {
+                    Ok(conn) => {
+                        println!("{} acquire took {:?}", i, start.elapsed());
+                        drop(conn);
+                    }
+                    Err(e) => panic!("{i} acquire returned error: {e} pool state: {pool:?}"),
+                }
+            }
+
+            Result::<(), anyhow::Error>::Ok(())
+        }));
+    }
+
+    futures::future::try_join_all(handles).await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_describe_outer_join_nullable() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // test nullability inference for various joins
+
+    // inner join, nullability should not be overridden
+    // language=PostgreSQL
+    let describe = conn
+        .describe(
+            "select tweet.id
+    from tweet
+    inner join products on products.name = tweet.text",
+        )
+        .await?;
+
+    assert_eq!(describe.nullable(0), Some(false));
+
+    // language=PostgreSQL
+    let describe = conn
+        .describe(
+            "select tweet.id
+from (values (null)) vals(val)
+         left join tweet on false",
+        )
+        .await?;
+
+    // tweet.id is marked NOT NULL but it's brought in from a left-join here
+    // which should make it nullable
+    assert_eq!(describe.nullable(0), Some(true));
+
+    // make sure we don't mis-infer for the outer half of the join
+    // language=PostgreSQL
+    let describe = conn
+        .describe(
+            "select tweet1.id, tweet2.id
+    from tweet tweet1
+    left join tweet tweet2 on false",
+        )
+        .await?;
+
+    assert_eq!(describe.nullable(0), Some(false));
+    assert_eq!(describe.nullable(1), Some(true));
+
+    // right join, nullability should be inverted
+    // language=PostgreSQL
+    let describe = conn
+        .describe(
+            "select tweet1.id, tweet2.id
+    from tweet tweet1
+    right join tweet tweet2 on false",
+        )
+        .await?;
+
+    assert_eq!(describe.nullable(0), Some(true));
+    assert_eq!(describe.nullable(1), Some(false));
+
+    // full outer join, both tables are nullable
+    // language=PostgreSQL
+    let describe = conn
+        .describe(
+            "select tweet1.id, tweet2.id
+    from tweet tweet1
+    full join tweet tweet2 on false",
+        )
+        .await?;
+
+    assert_eq!(describe.nullable(0), Some(true));
+    assert_eq!(describe.nullable(1), Some(true));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_listener_cleanup() -> anyhow::Result<()> {
+    #[cfg(feature = "_rt-tokio")]
+    use tokio::time::timeout;
+
+    #[cfg(feature = "_rt-async-std")]
+    use async_std::future::timeout;
+
+    use sqlx::pool::PoolOptions;
+    use sqlx::postgres::PgListener;
+
+    // Create a connection on which to send notifications
+    let mut notify_conn = new::<Postgres>().await?;
+
+    // Create a pool with exactly one connection so we can
+    // deterministically test the cleanup.
+    let pool = PoolOptions::<Postgres>::new()
+        .min_connections(1)
+        .max_connections(1)
+        .test_before_acquire(true)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut listener = PgListener::connect_with(&pool).await?;
+    listener.listen("test_channel").await?;
+
+    // Checks for a notification on the test channel
+    async fn try_recv(listener: &mut PgListener) -> anyhow::Result<bool> {
+        match timeout(Duration::from_millis(100), listener.recv()).await {
+            Ok(res) => {
+                res?;
+                Ok(true)
+            }
+            Err(_) => Ok(false),
+        }
+    }
+
+    // Check no notification is received before one is sent
+    assert!(!try_recv(&mut listener).await?, "Notification not sent");
+
+    // Check notification is sent and received
+    notify_conn.execute("NOTIFY test_channel").await?;
+    assert!(
+        try_recv(&mut listener).await?,
+        "Notification sent and received"
+    );
+    assert!(
+        !try_recv(&mut listener).await?,
+        "Notification is not duplicated"
+    );
+
+    // Test that cleanup stops listening on the channel
+    drop(listener);
+    let mut listener = PgListener::connect_with(&pool).await?;
+
+    // Check notification is sent but not received
+    notify_conn.execute("NOTIFY test_channel").await?;
+    assert!(
+        !try_recv(&mut listener).await?,
+        "Notification is not received on fresh listener"
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_pg_connection_notify() -> anyhow::Result<()> {
+    let mut notify_conn = new::<Postgres>().await?;
+
+    let mut listener = PgListener::connect(&env::var("DATABASE_URL")?).await?;
+    listener.listen("Test_Notify_Channel").await?;
+
+    // payloads containing quotes must arrive unchanged
+    notify_conn
+        .notify("Test_Notify_Channel", "it's a \"quoted\" payload")
+        .await?;
+
+    let notification = listener.recv().await?;
+    assert_eq!(notification.channel(), "Test_Notify_Channel");
+    assert_eq!(notification.payload(), "it's a \"quoted\" payload");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_pg_listener_recv_batch() -> anyhow::Result<()> {
+    let mut notify_conn = new::<Postgres>().await?;
+
+    let mut listener = PgListener::connect(&env::var("DATABASE_URL")?).await?;
+    listener.listen("test_recv_batch").await?;
+
+    assert!(listener.recv_batch(0).await?.is_empty());
+
+    // the notifications are all delivered on commit
+    let mut tx = notify_conn.begin().await?;
+    for i in 0..5 {
+        tx.notify("test_recv_batch", &i.to_string()).await?;
+    }
+    tx.commit().await?;
+
+    let mut payloads = Vec::new();
+
+    while payloads.len() < 5 {
+        let batch = listener.recv_batch(3).await?;
+        assert!(!batch.is_empty() && batch.len() <= 3);
+
+        payloads.extend(batch.iter().map(|n| n.payload().to_owned()));
+    }
+
+    assert_eq!(payloads, ["0", "1", "2", "3", "4"]);
+    assert!(listener.next_buffered()?.is_none());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_pg_listener_coalesce() -> anyhow::Result<()> {
+    let mut notify_conn = new::<Postgres>().await?;
+
+    let mut listener = PgListener::connect(&env::var("DATABASE_URL")?).await?;
+    listener.listen("test_coalesce").await?;
+    listener.coalesce(true);
+
+    // `NOTIFY` already folds duplicates within a transaction, so use separate ones
+    for payload in ["a", "a", "a", "b", "a"] {
+        notify_conn.notify("test_coalesce", payload).await?;
+    }
+
+    // give all of the notifications time to arrive
+    sqlx_core::rt::sleep(Duration::from_millis(100)).await;
+
+    let payloads: Vec<String> = listener
+        .recv_batch(10)
+        .await?
+        .iter()
+        .map(|n| n.payload().to_owned())
+        .collect();
+
+    assert_eq!(payloads, ["a", "b", "a"]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_pg_listener_allows_pool_to_close() -> anyhow::Result<()> {
+    let pool = pool::<Postgres>().await?;
+
+    // acquires and holds a connection which would normally prevent the pool from closing
+    let mut listener = PgListener::connect_with(&pool).await?;
+
+    sqlx_core::rt::spawn(async move {
+        listener.recv().await.unwrap();
+    });
+
+    // would previously hang forever since `PgListener` had no way to know the pool wanted to close
+    pool.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_supports_domain_types_in_composite_domain_types() -> anyhow::Result<()> {
+    // Only supported in Postgres 11+
+    let mut conn = new::<Postgres>().await?;
+    if matches!(conn.server_version_num(), Some(version) if version < 110000) {
+        return Ok(());
+    }
+
+    conn.execute(
+        r#"
+DROP TABLE IF EXISTS heating_bills;
+DROP DOMAIN IF EXISTS winter_year_month;
+DROP TYPE IF EXISTS year_month;
+DROP DOMAIN IF EXISTS month_id;
+
+CREATE DOMAIN month_id AS INT2 CHECK (1 <= value AND value <= 12);
+CREATE TYPE year_month AS (year INT4, month month_id);
+CREATE DOMAIN winter_year_month AS year_month CHECK ((value).month <= 3);
+CREATE TABLE heating_bills (
+  month winter_year_month NOT NULL PRIMARY KEY,
+  cost INT4 NOT NULL
+);
+    "#,
+    )
+    .await?;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct MonthId(i16);
+
+    impl sqlx::Type<Postgres> for MonthId {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            sqlx::postgres::PgTypeInfo::with_name("month_id")
+        }
+
+        fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+            *ty == Self::type_info()
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for MonthId {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            Ok(Self(<i16 as sqlx::Decode<Postgres>>::decode(value)?))
+        }
+    }
+
+    impl<'q> sqlx::Encode<'q, Postgres> for MonthId {
+        fn encode_by_ref(
+            &self,
+            buf: &mut sqlx::postgres::PgArgumentBuffer,
+        ) -> sqlx::encode::IsNull {
+            <i16 as sqlx::Encode<Postgres>>::encode(self.0, buf)
+        }
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct WinterYearMonth {
+        year: i32,
+        month: MonthId,
+    }
+
+    impl sqlx::Type<Postgres> for WinterYearMonth {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            sqlx::postgres::PgTypeInfo::with_name("winter_year_month")
+        }
+
+        fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+            *ty == Self::type_info()
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for WinterYearMonth {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            let mut decoder = sqlx::postgres::types::PgRecordDecoder::new(value)?;
+
+            let year = decoder.try_decode::<i32>()?;
+            let month = decoder.try_decode::<MonthId>()?;
+
+            Ok(Self { year, month })
+        }
+    }
+
+    impl<'q> sqlx::Encode<'q, Postgres> for WinterYearMonth {
+        fn encode_by_ref(
+            &self,
+            buf: &mut sqlx::postgres::PgArgumentBuffer,
+        ) -> sqlx::encode::IsNull {
+            let mut encoder = sqlx::postgres::types::PgRecordEncoder::new(buf);
+            encoder.encode(self.year);
+            encoder.encode(self.month);
+            encoder.finish();
+            sqlx::encode::IsNull::No
+        }
+    }
+    let mut conn = new::<Postgres>().await?;
+
+    let result = sqlx::query("DELETE FROM heating_bills;")
+        .execute(&mut conn)
+        .await;
+
+    let result = result.unwrap();
+    assert_eq!(result.rows_affected(), 0);
+
+    let result =
+        sqlx::query("INSERT INTO heating_bills(month, cost) VALUES($1::winter_year_month, 100);")
+            .bind(WinterYearMonth {
+                year: 2021,
+                month: MonthId(1),
+            })
+            .execute(&mut conn)
+            .await;
+
+    let result = result.unwrap();
+    assert_eq!(result.rows_affected(), 1);
+
+    let result = sqlx::query("DELETE FROM heating_bills;")
+        .execute(&mut conn)
+        .await;
+
+    let result = result.unwrap();
+    assert_eq!(result.rows_affected(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resolves_custom_type_in_array() -> anyhow::Result<()> {
+    // Only supported in Postgres 11+
+    let mut conn = new::<Postgres>().await?;
+    if matches!(conn.server_version_num(), Some(version) if version < 110000) {
+        return Ok(());
+    }
+
+    // language=PostgreSQL
+    conn.execute(
+        r#"
+DROP TABLE IF EXISTS pets;
+DROP TYPE IF EXISTS pet_name_and_race;
+
+CREATE TYPE pet_name_and_race AS (
+  name TEXT,
+  race TEXT
+);
+CREATE TABLE pets (
+  owner TEXT NOT NULL,
+  name TEXT NOT NULL,
+  race TEXT NOT NULL,
+  PRIMARY KEY (owner, name)
+);
+INSERT INTO pets(owner, name, race)
+VALUES
+  ('Alice', 'Foo', 'cat');
+INSERT INTO pets(owner, name, race)
+VALUES
+  ('Alice', 'Bar', 'dog');
+    "#,
+    )
+    .await?;
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct PetNameAndRace {
+        name: String,
+        race: String,
+    }
+
+    impl sqlx::Type<Postgres> for PetNameAndRace {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            sqlx::postgres::PgTypeInfo::with_name("pet_name_and_race")
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for PetNameAndRace {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            let mut decoder = sqlx::postgres::types::PgRecordDecoder::new(value)?;
+            let name = decoder.try_decode::<String>()?;
+            let race = decoder.try_decode::<String>()?;
+            Ok(Self { name, race })
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct PetNameAndRaceArray(Vec<PetNameAndRace>);
+
+    impl sqlx::Type<Postgres> for PetNameAndRaceArray {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            // Array type name is the name of the element type prefixed with `_`
+            sqlx::postgres::PgTypeInfo::with_name("_pet_name_and_race")
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for PetNameAndRaceArray {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            Ok(Self(Vec::<PetNameAndRace>::decode(value)?))
+        }
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let row = sqlx::query("select owner, array_agg(row(name, race)::pet_name_and_race) as pets from pets group by owner")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let pets: PetNameAndRaceArray = row.get("pets");
+
+    assert_eq!(pets.0.len(), 2);
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resolves_custom_types_in_anonymous_records() -> anyhow::Result<()> {
+    use sqlx_core::error::Error;
+    // This request involves nested records and array types.
+
+    // Only supported in Postgres 11+
+    let mut conn = new::<Postgres>().await?;
+    if matches!(conn.server_version_num(), Some(version) if version < 110000) {
+        return Ok(());
+    }
+
+    // language=PostgreSQL
+    conn.execute(
+        r#"
+DROP TABLE IF EXISTS repo_users;
+DROP TABLE IF EXISTS repositories;
+DROP TABLE IF EXISTS repo_memberships;
+DROP TYPE IF EXISTS repo_member;
+
+CREATE TABLE repo_users (
+  user_id INT4 NOT NULL,
+  username TEXT NOT NULL,
+  PRIMARY KEY (user_id)
+);
+CREATE TABLE repositories (
+  repo_id INT4 NOT NULL,
+  repo_name TEXT NOT NULL,
+  PRIMARY KEY (repo_id)
+);
+CREATE TABLE repo_memberships (
+  repo_id INT4 NOT NULL,
+  user_id INT4 NOT NULL,
+  permission TEXT NOT NULL,
+  PRIMARY KEY (repo_id, user_id)
+);
+CREATE TYPE repo_member AS (
+  user_id INT4,
+  permission TEXT
+);
+INSERT INTO repo_users(user_id, username)
+VALUES
+  (101, 'alice'),
+  (102, 'bob'),
+  (103, 'charlie');
+INSERT INTO repositories(repo_id, repo_name)
+VALUES
+  (201, 'rust'),
+  (202, 'sqlx'),
+  (203, 'hello-world');
+INSERT INTO repo_memberships(repo_id, user_id, permission)
+VALUES
+  (201, 101, 'admin'),
+  (201, 102, 'write'),
   (201, 103, 'read'),
   (202, 102, 'admin');
 "#,
     )
     .await?;
 
-    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct RepoMember {
-        user_id: i32,
-        permission: String,
-    }
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct RepoMember {
+        user_id: i32,
+        permission: String,
+    }
+
+    impl sqlx::Type<Postgres> for RepoMember {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            sqlx::postgres::PgTypeInfo::with_name("repo_member")
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for RepoMember {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            let mut decoder = sqlx::postgres::types::PgRecordDecoder::new(value)?;
+            let user_id = decoder.try_decode::<i32>()?;
+            let permission = decoder.try_decode::<String>()?;
+            Ok(Self {
+                user_id,
+                permission,
+            })
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct RepoMemberArray(Vec<RepoMember>);
+
+    impl sqlx::Type<Postgres> for RepoMemberArray {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            // Array type name is the name of the element type prefixed with `_`
+            sqlx::postgres::PgTypeInfo::with_name("_repo_member")
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for RepoMemberArray {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            Ok(Self(Vec::<RepoMember>::decode(value)?))
+        }
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    #[derive(Debug, sqlx::FromRow)]
+    #[allow(dead_code)] // We don't actually read these fields.
+    struct Row {
+        count: i64,
+        items: Vec<(i32, String, RepoMemberArray)>,
+    }
+    // language=PostgreSQL
+    let row: Result<Row, Error> = sqlx::query_as::<_, Row>(
+        r"
+        WITH
+          members_by_repo AS (
+            SELECT repo_id,
+              ARRAY_AGG(ROW (user_id, permission)::repo_member) AS members
+            FROM repo_memberships
+            GROUP BY repo_id
+          ),
+          repos AS (
+            SELECT repo_id, repo_name, COALESCE(members, '{}') AS members
+            FROM repositories
+              LEFT OUTER JOIN members_by_repo USING (repo_id)
+            ORDER BY repo_id
+          ),
+          repo_array AS (
+            SELECT COALESCE(ARRAY_AGG(repos.*), '{}') AS items
+            FROM repos
+          ),
+          repo_count AS (
+            SELECT COUNT(*) AS count
+            FROM repos
+          )
+        SELECT count, items
+        FROM repo_count, repo_array
+        ;
+    ",
+    )
+    .fetch_one(&mut conn)
+    .await;
+
+    // This test currently tests mitigations for `#1672` (use regular errors
+    // instead of panics). Once we fully support custom types, it should be
+    // updated accordingly.
+    match row {
+        Ok(_) => panic!("full support for custom types is not implemented yet"),
+        Err(e) => assert!(e
+            .to_string()
+            .contains("custom types in records are not fully supported yet")),
+    }
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_decodes_anonymous_records() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgRecord;
+
+    #[derive(sqlx::Type, Debug, PartialEq)]
+    #[sqlx(type_name = "status", rename_all = "lowercase")]
+    enum Status {
+        New,
+        Open,
+        Closed,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let record: PgRecord =
+        sqlx::query_scalar("SELECT ROW(1, 'foo'::text, NULL::int8, 'open'::status)")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(record.len(), 4);
+    assert_eq!(record.field_oid(0), Some(Oid(23)));
+    assert_eq!(record.get::<i32>(0), 1);
+    assert_eq!(record.get::<&str>(1), "foo");
+    assert_eq!(record.get::<Option<i64>>(2), None);
+    // a custom type, which tuples cannot decode
+    assert_eq!(record.get::<Status>(3), Status::Open);
+
+    assert!(matches!(
+        record.try_get::<String>(0),
+        Err(sqlx::Error::ColumnDecode { .. })
+    ));
+    assert!(matches!(
+        record.try_get::<i32>(4),
+        Err(sqlx::Error::ColumnIndexOutOfBounds { index: 4, len: 4 })
+    ));
+
+    // a function returning `SETOF record`
+    conn.execute(
+        "CREATE FUNCTION pg_temp.pairs() RETURNS SETOF record \
+         AS $$ VALUES (1, 'a'::text), (2, 'b') $$ LANGUAGE sql",
+    )
+    .await?;
+
+    let pairs: Vec<PgRecord> = sqlx::query_scalar("SELECT pg_temp.pairs()")
+        .fetch_all(&mut conn)
+        .await?;
+
+    let pairs: Vec<(i32, String)> = pairs.iter().map(|r| (r.get(0), r.get(1))).collect();
+    assert_eq!(pairs, [(1, "a".to_owned()), (2, "b".to_owned())]);
+
+    let records: Vec<PgRecord> = sqlx::query_scalar("SELECT ARRAY[ROW(1), ROW(2)]")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].get::<i32>(0), 2);
+
+    // the types of the fields of anonymous records are unknown in the text format
+    let row = conn.fetch_one("SELECT ROW(1, 'foo', NULL)").await?;
+    let record: PgRecord = row.try_get(0)?;
+    assert_eq!(record.field_oid(0), None);
+    assert_eq!(record.get::<i32>(0), 1);
+    assert_eq!(record.get::<&str>(1), "foo");
+    assert_eq!(record.get::<Option<i32>>(2), None);
+
+    // named composite types, in both formats
+    let sql = "SELECT ROW('fuzzy dice', 42, 199)::inventory_item";
+
+    let record: PgRecord = sqlx::query_scalar(sql).fetch_one(&mut conn).await?;
+    assert_eq!(record.get::<&str>(0), "fuzzy dice");
+    assert_eq!(record.get::<i64>(2), 199);
+
+    let record: PgRecord = conn.fetch_one(sql).await?.try_get(0)?;
+    assert_eq!(record.field_oid(1), Some(Oid(23)));
+    assert_eq!(record.get::<i32>(1), 42);
+
+    // a custom composite type nested in an anonymous record
+    let record: PgRecord =
+        sqlx::query_scalar("SELECT ROW(ROW('fuzzy dice', 42, 199)::inventory_item)")
+            .fetch_one(&mut conn)
+            .await?;
+    let item: PgRecord = record.get(0);
+    assert_eq!(item.get::<&str>(0), "fuzzy dice");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn custom_type_resolution_respects_search_path() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+DROP TYPE IF EXISTS some_enum_type;
+DROP SCHEMA IF EXISTS another CASCADE;
+
+CREATE SCHEMA another;
+CREATE TYPE some_enum_type AS ENUM ('a', 'b', 'c');
+CREATE TYPE another.some_enum_type AS ENUM ('d', 'e', 'f');
+    "#,
+    )
+    .await?;
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct SomeEnumType(String);
+
+    impl sqlx::Type<Postgres> for SomeEnumType {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            sqlx::postgres::PgTypeInfo::with_name("some_enum_type")
+        }
+
+        fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+            *ty == Self::type_info()
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for SomeEnumType {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            Ok(Self(<String as sqlx::Decode<Postgres>>::decode(value)?))
+        }
+    }
+
+    impl<'q> sqlx::Encode<'q, Postgres> for SomeEnumType {
+        fn encode_by_ref(
+            &self,
+            buf: &mut sqlx::postgres::PgArgumentBuffer,
+        ) -> sqlx::encode::IsNull {
+            <String as sqlx::Encode<Postgres>>::encode_by_ref(&self.0, buf)
+        }
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    sqlx::query("set search_path = 'another'")
+        .execute(&mut conn)
+        .await?;
+
+    let result = sqlx::query("SELECT 1 WHERE $1::some_enum_type = 'd'::some_enum_type;")
+        .bind(SomeEnumType("d".into()))
+        .fetch_all(&mut conn)
+        .await;
+
+    let result = result.unwrap();
+    assert_eq!(result.len(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_pg_server_num() -> anyhow::Result<()> {
+    let conn = new::<Postgres>().await?;
+
+    assert!(conn.server_version_num().is_some());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_copy_in() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    conn.execute(
+        r#"
+        CREATE TEMPORARY TABLE users (id INTEGER NOT NULL);
+    "#,
+    )
+    .await?;
+
+    let mut copy = conn
+        .copy_in_raw(
+            r#"
+        COPY users (id) FROM STDIN WITH (FORMAT CSV, HEADER);
+    "#,
+        )
+        .await?;
+
+    copy.send("id\n1\n2\n".as_bytes()).await?;
+    let rows = copy.finish().await?;
+    assert_eq!(rows, 2);
+
+    // conn is safe for reuse
+    let value = sqlx::query("select 1 + 1")
+        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(2i32, value);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_abort_copy_in() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    conn.execute(
+        r#"
+        CREATE TEMPORARY TABLE users (id INTEGER NOT NULL);
+    "#,
+    )
+    .await?;
+
+    let copy = conn
+        .copy_in_raw(
+            r#"
+        COPY users (id) FROM STDIN WITH (FORMAT CSV, HEADER);
+    "#,
+        )
+        .await?;
+
+    copy.abort("this is only a test").await?;
+
+    // conn is safe for reuse
+    let value = sqlx::query("select 1 + 1")
+        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(2i32, value);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_copy_out() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    {
+        let mut copy = conn
+            .copy_out_raw(
+                "
+            COPY (SELECT generate_series(1, 2) AS id) TO STDOUT WITH (FORMAT CSV, HEADER);
+        ",
+            )
+            .await?;
+
+        assert_eq!(copy.next().await.unwrap().unwrap(), "id\n");
+        assert_eq!(copy.next().await.unwrap().unwrap(), "1\n");
+        assert_eq!(copy.next().await.unwrap().unwrap(), "2\n");
+        if copy.next().await.is_some() {
+            anyhow::bail!("Unexpected data from COPY");
+        }
+    }
+
+    // conn is safe for reuse
+    let value = sqlx::query("select 1 + 1")
+        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(2i32, value);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_copy_out_with_a_transform() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    {
+        let copy = conn
+            .copy_out_with(
+                "COPY (SELECT id, 'secret-' || id AS token FROM generate_series(1, 4) AS id) \
+                 TO STDOUT WITH (FORMAT CSV)",
+                |row| {
+                    // skip the even rows and redact the tokens of the odd ones
+                    let (id, _) = std::str::from_utf8(&row).ok()?.split_once(',')?;
+                    let id: i32 = id.parse().ok()?;
+
+                    (id % 2 == 1).then(|| format!("{id},REDACTED\n").into())
+                },
+            )
+            .await?;
+
+        let rows: Vec<_> = copy.try_collect().await?;
+        assert_eq!(rows, ["1,REDACTED\n", "3,REDACTED\n"]);
+    }
+
+    // conn is safe for reuse
+    let value: i32 = sqlx::query_scalar("SELECT 1 + 1")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 2);
+
+    let rows: Vec<_> = pool::<Postgres>()
+        .await?
+        .copy_out_with("COPY (SELECT 'a') TO STDOUT", |row| Some(row.slice(..1)))
+        .await?
+        .try_collect()
+        .await?;
+    assert_eq!(rows, ["a"]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_encodes_custom_array_issue_1504() -> anyhow::Result<()> {
+    use sqlx::encode::IsNull;
+    use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo};
+    use sqlx::{Decode, Encode, Type, ValueRef};
+
+    #[derive(Debug, PartialEq)]
+    enum Value {
+        String(String),
+        Number(i32),
+        Array(Vec<Value>),
+    }
+
+    impl<'r> Decode<'r, Postgres> for Value {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> std::result::Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            let typ = value.type_info().into_owned();
+
+            if typ == PgTypeInfo::with_name("text") {
+                let s = <String as Decode<'_, Postgres>>::decode(value)?;
+
+                Ok(Self::String(s))
+            } else if typ == PgTypeInfo::with_name("int4") {
+                let n = <i32 as Decode<'_, Postgres>>::decode(value)?;
+
+                Ok(Self::Number(n))
+            } else if typ == PgTypeInfo::with_name("_text") {
+                let arr = Vec::<String>::decode(value)?;
+                let v = arr.into_iter().map(|s| Value::String(s)).collect();
+
+                Ok(Self::Array(v))
+            } else if typ == PgTypeInfo::with_name("_int4") {
+                let arr = Vec::<i32>::decode(value)?;
+                let v = arr.into_iter().map(|n| Value::Number(n)).collect();
+
+                Ok(Self::Array(v))
+            } else {
+                Err("unknown type".into())
+            }
+        }
+    }
+
+    impl Encode<'_, Postgres> for Value {
+        fn produces(&self) -> Option<PgTypeInfo> {
+            match self {
+                Self::Array(a) => {
+                    if a.len() < 1 {
+                        return Some(PgTypeInfo::with_name("_text"));
+                    }
+
+                    match a[0] {
+                        Self::String(_) => Some(PgTypeInfo::with_name("_text")),
+                        Self::Number(_) => Some(PgTypeInfo::with_name("_int4")),
+                        Self::Array(_) => None,
+                    }
+                }
+                Self::String(_) => Some(PgTypeInfo::with_name("text")),
+                Self::Number(_) => Some(PgTypeInfo::with_name("int4")),
+            }
+        }
+
+        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+            match self {
+                Value::String(s) => <String as Encode<'_, Postgres>>::encode_by_ref(s, buf),
+                Value::Number(n) => <i32 as Encode<'_, Postgres>>::encode_by_ref(n, buf),
+                Value::Array(arr) => arr.encode(buf),
+            }
+        }
+    }
+
+    impl Type<Postgres> for Value {
+        fn type_info() -> PgTypeInfo {
+            PgTypeInfo::with_name("unknown")
+        }
+
+        fn compatible(ty: &PgTypeInfo) -> bool {
+            [
+                PgTypeInfo::with_name("text"),
+                PgTypeInfo::with_name("_text"),
+                PgTypeInfo::with_name("int4"),
+                PgTypeInfo::with_name("_int4"),
+            ]
+            .contains(ty)
+        }
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let (row,): (Value,) = sqlx::query_as("SELECT $1::text[] as Dummy")
+        .bind(Value::Array(vec![
+            Value::String("Test 0".to_string()),
+            Value::String("Test 1".to_string()),
+        ]))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(
+        row,
+        Value::Array(vec![
+            Value::String("Test 0".to_string()),
+            Value::String("Test 1".to_string()),
+        ])
+    );
+
+    let (row,): (Value,) = sqlx::query_as("SELECT $1::int4[] as Dummy")
+        .bind(Value::Array(vec![
+            Value::Number(3),
+            Value::Number(2),
+            Value::Number(1),
+        ]))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(
+        row,
+        Value::Array(vec![Value::Number(3), Value::Number(2), Value::Number(1)])
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_issue_1254() -> anyhow::Result<()> {
+    #[derive(sqlx::Type)]
+    #[sqlx(type_name = "pair")]
+    struct Pair {
+        one: i32,
+        two: i32,
+    }
+
+    // array for custom type is not supported, use wrapper
+    #[derive(sqlx::Type)]
+    #[sqlx(type_name = "_pair")]
+    struct Pairs(Vec<Pair>);
+
+    let mut conn = new::<Postgres>().await?;
+    conn.execute(
+        "
+DROP TABLE IF EXISTS issue_1254;
+DROP TYPE IF EXISTS pair;
+
+CREATE TYPE pair AS (one INT4, two INT4);
+CREATE TABLE issue_1254 (id INT4 PRIMARY KEY, pairs PAIR[]);
+",
+    )
+    .await?;
+
+    let result = sqlx::query("INSERT INTO issue_1254 VALUES($1, $2)")
+        .bind(0)
+        .bind(Pairs(vec![Pair { one: 94, two: 87 }]))
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(result.rows_affected(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_advisory_locks() -> anyhow::Result<()> {
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let lock1 = Arc::new(PgAdvisoryLock::new("sqlx-postgres-tests-1"));
+    let lock2 = Arc::new(PgAdvisoryLock::new("sqlx-postgres-tests-2"));
+
+    let conn1 = pool.acquire().await?;
+    let mut conn1_lock1 = lock1.acquire(conn1).await?;
+
+    // try acquiring a recursive lock through a mutable reference then dropping
+    drop(lock1.acquire(&mut conn1_lock1).await?);
+
+    let conn2 = pool.acquire().await?;
+    // leak so we can take it across the task boundary
+    let conn2_lock2 = lock2.acquire(conn2).await?.leak();
+
+    sqlx_core::rt::spawn({
+        let lock1 = lock1.clone();
+        let lock2 = lock2.clone();
+
+        async move {
+            let conn2_lock2 = lock1.try_acquire(conn2_lock2).await?.right_or_else(|_| {
+                panic!(
+                    "acquired lock but wasn't supposed to! Key: {:?}",
+                    lock1.key()
+                )
+            });
+
+            let (conn2, released) = lock2.force_release(conn2_lock2).await?;
+            assert!(released);
+
+            // acquire both locks but let the pool release them
+            let conn2_lock1 = lock1.acquire(conn2).await?;
+            let _conn2_lock1and2 = lock2.acquire(conn2_lock1).await?;
+
+            anyhow::Ok(())
+        }
+    });
+
+    // acquire lock2 on conn1, we leak the lock1 guard so we can manually release it before lock2
+    let conn1_lock1and2 = lock2.acquire(conn1_lock1.leak()).await?;
+
+    // release lock1 while holding lock2
+    let (conn1_lock2, released) = lock1.force_release(conn1_lock1and2).await?;
+    assert!(released);
+
+    let conn1 = conn1_lock2.release_now().await?;
+
+    // acquire both locks to be sure they were released
+    {
+        let conn1_lock1 = lock1.acquire(conn1).await?;
+        let _conn1_lock1and2 = lock2.acquire(conn1_lock1).await?;
+    }
+
+    pool.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_postgres_bytea_hex_deserialization_errors() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    conn.execute("SET bytea_output = 'escape';").await?;
+    for value in ["", "DEADBEEF"] {
+        let query = format!("SELECT '\\x{value}'::bytea");
+        let res: sqlx::Result<Vec<u8>> = conn.fetch_one(query.as_str()).await?.try_get(0usize);
+        // Deserialization only supports hex format so this should error and definitely not panic.
+        res.unwrap_err();
+    }
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_shrink_buffers() -> anyhow::Result<()> {
+    // We don't really have a good way to test that `.shrink_buffers()` functions as expected
+    // without exposing a lot of internals, but we can at least be sure it doesn't
+    // materially affect the operation of the connection.
+
+    let mut conn = new::<Postgres>().await?;
+
+    // The connection buffer is only 8 KiB by default so this should definitely force it to grow.
+    let data = vec![0u8; 32 * 1024];
+
+    let ret: Vec<u8> = sqlx::query_scalar("SELECT $1::bytea")
+        .bind(&data)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(ret, data);
+
+    conn.shrink_buffers();
+
+    let ret: i64 = sqlx::query_scalar("SELECT $1::int8")
+        .bind(&12345678i64)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(ret, 12345678i64);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_table_estimate_and_indexes() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE "Estimated" (id INT PRIMARY KEY, email TEXT NOT NULL, deleted BOOLEAN);
+CREATE UNIQUE INDEX estimated_email ON "Estimated" (lower(email)) WHERE NOT deleted;
+INSERT INTO "Estimated" SELECT i, 'user' || i, false FROM generate_series(1, 1000) i;
+ANALYZE "Estimated";
+"#,
+    )
+    .await?;
+
+    let stats = conn.table_estimate(r#""Estimated""#).await?;
+    assert_eq!(stats.estimated_rows, 1000);
+    assert!(stats.size_bytes > 0);
+
+    let indexes = conn.indexes(r#""Estimated""#).await?;
+    assert_eq!(indexes.len(), 2);
+
+    assert_eq!(indexes[0].name, "Estimated_pkey");
+    assert_eq!(indexes[0].columns, ["id"]);
+    assert_eq!(indexes[0].method, "btree");
+    assert!(indexes[0].is_primary && indexes[0].is_unique && !indexes[0].is_partial);
+
+    assert_eq!(indexes[1].name, "estimated_email");
+    assert_eq!(indexes[1].columns, ["lower(email)"]);
+    assert!(!indexes[1].is_primary && indexes[1].is_unique && indexes[1].is_partial);
+    assert!(indexes[1].size_bytes > 0);
+
+    // case-sensitive names must be quoted
+    assert!(conn.table_estimate("Estimated").await.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_writes_query_results_as_csv() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut out = Vec::new();
+
+    let written = sqlx::query(
+        "SELECT * FROM (VALUES \
+            (1::int4, 'plain'::text, 1.5::float8, true, '\\xdead'::bytea), \
+            (2, 'with, comma', NULL, false, NULL), \
+            (3, 'say \"hi\"', -2, NULL, ''::bytea), \
+            (4, '', 0, true, NULL) \
+         ) AS t (id, name, score, active, data) \
+         WHERE id > $1",
+    )
+    .bind(0_i32)
+    .write_csv(&mut conn, &mut out, CsvOptions::new().flush_every(2))
+    .await?;
+
+    assert_eq!(written, 4);
+    assert_eq!(
+        String::from_utf8(out)?,
+        "id,name,score,active,data\n\
+         1,plain,1.5,true,dead\n\
+         2,\"with, comma\",,false,\n\
+         3,\"say \"\"hi\"\"\",-2,,\"\"\n\
+         4,\"\",0,true,\n"
+    );
+
+    // the header is known in advance for prepared statements
+    let statement = conn.prepare("SELECT 1 AS a, 2 AS b WHERE false").await?;
+    let mut out = Vec::new();
+
+    let written = statement
+        .query()
+        .write_csv(&mut conn, &mut out, CsvOptions::new().delimiter(b'\t'))
+        .await?;
+
+    assert_eq!(written, 0);
+    assert_eq!(out, b"a\tb\n");
+
+    // other types are not supported
+    let res = sqlx::query("SELECT 1.5::numeric")
+        .write_csv(&mut conn, &mut Vec::new(), CsvOptions::new())
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::ColumnDecode { .. })));
+
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[sqlx_macros::test]
+async fn it_writes_query_results_as_ndjson() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut out = Vec::new();
+
+    let written = sqlx::query(
+        "SELECT * FROM (VALUES \
+            (1::int8, 'plain'::text, 0.1::float4, true, '\\xdead'::bytea, '{\"a\": [1]}'::jsonb), \
+            (2, 'say \"hi\"', 'NaN', NULL, NULL, 'null') \
+         ) AS t (id, name, score, active, data, extra)",
+    )
+    .write_ndjson(&mut conn, &mut out)
+    .await?;
+
+    assert_eq!(written, 2);
+    assert_eq!(
+        String::from_utf8(out)?,
+        "{\"id\":1,\"name\":\"plain\",\"score\":0.1,\"active\":true,\"data\":\"dead\",\"extra\":{\"a\":[1]}}\n\
+         {\"id\":2,\"name\":\"say \\\"hi\\\"\",\"score\":null,\"active\":null,\"data\":null,\"extra\":null}\n"
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_rows_with_a_batch_size() -> anyhow::Result<()> {
+    use sqlx::postgres::PgFetchBatchExt;
+
+    let mut conn = new::<Postgres>().await?;
+
+    for (count, batch_size) in [(10, 3), (9, 3), (2, 5), (0, 3), (5, 0)] {
+        let rows: Vec<i32> = sqlx::query("SELECT i FROM generate_series(1, $1) i")
+            .bind(count)
+            .fetch_with_batch_size(&mut conn, batch_size)
+            .map_ok(|row| row.get::<i32, _>(0))
+            .try_collect()
+            .await?;
+
+        assert_eq!(rows, (1..=count).collect::<Vec<_>>());
+    }
+
+    // dropping the stream early leaves the connection usable
+    {
+        let mut rows = sqlx::query("SELECT i FROM generate_series(1, 100) i")
+            .fetch_with_batch_size(&mut conn, 10);
+
+        let row = rows.try_next().await?.unwrap();
+        assert_eq!(row.get::<i32, _>(0), 1);
+    }
+
+    let value: i32 = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
+    assert_eq!(value, 42);
+
+    // an error in a later batch is returned from the stream
+    let mut rows = sqlx::query("SELECT 1 / (5 - i) FROM generate_series(1, 10) i")
+        .fetch_with_batch_size(&mut conn, 2);
+
+    let mut fetched = 0;
+    let err = loop {
+        match rows.try_next().await {
+            Ok(Some(_)) => fetched += 1,
+            Ok(None) => panic!("expected an error"),
+            Err(e) => break e,
+        }
+    };
+
+    drop(rows);
+
+    assert_eq!(fetched, 4);
+    assert!(matches!(err, sqlx::Error::Database(_)));
+
+    let value: i32 = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
+    assert_eq!(value, 42);
+
+    // the portal survives between batches within a transaction as well
+    let mut tx = conn.begin().await?;
+
+    let rows: Vec<i32> = sqlx::query("SELECT i FROM generate_series(1, 7) i")
+        .fetch_with_batch_size(&mut *tx, 2)
+        .map_ok(|row| row.get::<i32, _>(0))
+        .try_collect()
+        .await?;
+
+    tx.rollback().await?;
+
+    assert_eq!(rows, (1..=7).collect::<Vec<_>>());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_sends_and_receives_raw_messages() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    impl sqlx::Type<Postgres> for RepoMember {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            sqlx::postgres::PgTypeInfo::with_name("repo_member")
+    // a simple `Query` message, which is answered with a `ReadyForQuery` after its results
+    conn.send_raw_message(b'Q', b"SELECT 'raw'\0").await?;
+
+    let mut formats = Vec::new();
+
+    loop {
+        let (format, contents) = conn.recv_raw_message().await?;
+        formats.push(format);
+
+        if format == b'D' {
+            // one column, with a length-prefixed value
+            assert_eq!(&contents[..], b"\0\x01\0\0\0\x03raw");
         }
-    }
 
-    impl<'r> sqlx::Decode<'r, Postgres> for RepoMember {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            let mut decoder = sqlx::postgres::types::PgRecordDecoder::new(value)?;
-            let user_id = decoder.try_decode::<i32>()?;
-            let permission = decoder.try_decode::<String>()?;
-            Ok(Self {
-                user_id,
-                permission,
-            })
+        if format == b'Z' {
+            break;
         }
     }
 
-    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct RepoMemberArray(Vec<RepoMember>);
+    assert_eq!(formats, b"TDCZ");
 
-    impl sqlx::Type<Postgres> for RepoMemberArray {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            // Array type name is the name of the element type prefixed with `_`
-            sqlx::postgres::PgTypeInfo::with_name("_repo_member")
-        }
+    // the connection is usable by SQLx afterwards
+    let value: String = sqlx::query_scalar("SELECT 'cooked'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, "cooked");
+
+    Ok(())
+}
+
+#[cfg(feature = "recording")]
+#[sqlx_macros::test]
+async fn it_records_and_replays_queries() -> anyhow::Result<()> {
+    use sqlx::recording::{replay, QueryRecorder};
+    use sqlx::types::Json;
+    use std::io::BufReader;
+
+    #[derive(Debug, PartialEq, sqlx::Type)]
+    #[sqlx(type_name = "status", rename_all = "lowercase")]
+    enum Status {
+        New,
+        Open,
+        Closed,
     }
 
-    impl<'r> sqlx::Decode<'r, Postgres> for RepoMemberArray {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            Ok(Self(Vec::<RepoMember>::decode(value)?))
+    impl sqlx::postgres::PgHasArrayType for Status {
+        fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+            sqlx::postgres::PgTypeInfo::with_name("_status")
         }
     }
 
+    let schema = "CREATE TEMPORARY TABLE recorded (id INT8, status status, statuses status[], data JSONB, note TEXT)";
+    let path = env::temp_dir().join(format!("sqlx-pg-recording-{}.jsonl", std::process::id()));
+    let recorder = QueryRecorder::create(&path)?.capture_arguments(true);
+
     let mut conn = new::<Postgres>().await?;
+    conn.execute(schema).await?;
 
-    #[derive(Debug, sqlx::FromRow)]
-    #[allow(dead_code)] // We don't actually read these fields.
-    struct Row {
-        count: i64,
-        items: Vec<(i32, String, RepoMemberArray)>,
-    }
-    // language=PostgreSQL
-    let row: Result<Row, Error> = sqlx::query_as::<_, Row>(
-        r"
-        WITH
-          members_by_repo AS (
-            SELECT repo_id,
-              ARRAY_AGG(ROW (user_id, permission)::repo_member) AS members
-            FROM repo_memberships
-            GROUP BY repo_id
-          ),
-          repos AS (
-            SELECT repo_id, repo_name, COALESCE(members, '{}') AS members
-            FROM repositories
-              LEFT OUTER JOIN members_by_repo USING (repo_id)
-            ORDER BY repo_id
-          ),
-          repo_array AS (
-            SELECT COALESCE(ARRAY_AGG(repos.*), '{}') AS items
-            FROM repos
-          ),
-          repo_count AS (
-            SELECT COUNT(*) AS count
-            FROM repos
-          )
-        SELECT count, items
-        FROM repo_count, repo_array
-        ;
-    ",
+    sqlx::query(
+        "INSERT INTO recorded (id, status, statuses, data, note) VALUES ($1, $2, $3, $4, $5)",
     )
-    .fetch_one(&mut conn)
-    .await;
+    .bind(1_i64)
+    .bind(Status::Open)
+    .bind(vec![Status::New, Status::Closed])
+    .bind(Json(serde_json::json!({ "answer": 42 })))
+    .bind(None::<String>)
+    .execute(recorder.wrap(&mut conn))
+    .await?;
+
+    recorder.flush()?;
+    drop(recorder);
+
+    // replay on another connection, which has its own temporary table
+    let mut replica = new::<Postgres>().await?;
+    replica.execute(schema).await?;
+
+    let file = std::fs::File::open(&path)?;
+    let report = replay(&mut replica, BufReader::new(file)).await?;
+    std::fs::remove_file(&path)?;
+
+    assert_eq!(report.queries.len(), 1);
+    assert!(report.queries[0].error.is_none(), "{:?}", report.queries[0]);
+    assert_eq!(report.queries[0].rows_affected, 1);
+
+    let row: (
+        i64,
+        Status,
+        Vec<Status>,
+        Json<serde_json::Value>,
+        Option<String>,
+    ) = sqlx::query_as("SELECT id, status, statuses, data, note FROM recorded")
+        .fetch_one(&mut replica)
+        .await?;
+
+    assert_eq!(row.0, 1);
+    assert_eq!(row.1, Status::Open);
+    assert_eq!(row.2, [Status::New, Status::Closed]);
+    assert_eq!(row.3 .0, serde_json::json!({ "answer": 42 }));
+    assert_eq!(row.4, None);
 
-    // This test currently tests mitigations for `#1672` (use regular errors
-    // instead of panics). Once we fully support custom types, it should be
-    // updated accordingly.
-    match row {
-        Ok(_) => panic!("full support for custom types is not implemented yet"),
-        Err(e) => assert!(e
-            .to_string()
-            .contains("custom types in records are not fully supported yet")),
-    }
     Ok(())
 }
 
+#[cfg(feature = "recording")]
 #[sqlx_macros::test]
-async fn custom_type_resolution_respects_search_path() -> anyhow::Result<()> {
+async fn it_retries_recorded_queries() -> anyhow::Result<()> {
+    use sqlx::recording::QueryRecorder;
+
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .test_before_acquire(false)
+        .retry_policy(RetryPolicy::new().base_delay(Duration::from_millis(1)))
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
     let mut conn = new::<Postgres>().await?;
+    let path = env::temp_dir().join(format!(
+        "sqlx-pg-recording-retry-{}.jsonl",
+        std::process::id()
+    ));
+    let recorder = QueryRecorder::create(&path)?;
+
+    terminate_pooled_connection(&pool, &mut conn).await?;
+
+    // the query is retried on a new connection through the recorder
+    let val: i32 = sqlx::query_scalar("SELECT $1::int4")
+        .bind(42_i32)
+        .retryable(true)
+        .fetch_one(recorder.wrap(&pool))
+        .await?;
+    assert_eq!(val, 42);
 
-    conn.execute(
-        r#"
-DROP TYPE IF EXISTS some_enum_type;
-DROP SCHEMA IF EXISTS another CASCADE;
+    drop(recorder);
+    std::fs::remove_file(&path)?;
 
-CREATE SCHEMA another;
-CREATE TYPE some_enum_type AS ENUM ('a', 'b', 'c');
-CREATE TYPE another.some_enum_type AS ENUM ('d', 'e', 'f');
-    "#,
-    )
-    .await?;
+    Ok(())
+}
 
-    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct SomeEnumType(String);
+#[sqlx_macros::test]
+async fn it_cancels_queries_which_time_out() -> anyhow::Result<()> {
+    let pool = PgPoolOptions::new()
+        .statement_timeout(Duration::from_millis(200))
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
 
-    impl sqlx::Type<Postgres> for SomeEnumType {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            sqlx::postgres::PgTypeInfo::with_name("some_enum_type")
-        }
+    let res = sqlx::query("SELECT pg_sleep(30) /* it_cancels_queries_which_time_out */")
+        .execute(&pool)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::QueryTimedOut)));
 
-        fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
-            *ty == Self::type_info()
-        }
-    }
+    // the server stops executing the query, which would otherwise go on for 30 seconds
+    let mut conn = new::<Postgres>().await?;
 
-    impl<'r> sqlx::Decode<'r, Postgres> for SomeEnumType {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            Ok(Self(<String as sqlx::Decode<Postgres>>::decode(value)?))
-        }
-    }
+    for _ in 0..50 {
+        let running: i64 = sqlx::query_scalar(
+            "SELECT count(*) FROM pg_stat_activity \
+             WHERE state = 'active' AND query LIKE 'SELECT pg_sleep(30) /* it_cancels%'",
+        )
+        .fetch_one(&mut conn)
+        .await?;
 
-    impl<'q> sqlx::Encode<'q, Postgres> for SomeEnumType {
-        fn encode_by_ref(
-            &self,
-            buf: &mut sqlx::postgres::PgArgumentBuffer,
-        ) -> sqlx::encode::IsNull {
-            <String as sqlx::Encode<Postgres>>::encode_by_ref(&self.0, buf)
+        if running == 0 {
+            return Ok(());
         }
+
+        sqlx_core::rt::sleep(Duration::from_millis(100)).await;
     }
 
+    panic!("the query which timed out is still running");
+}
+
+#[sqlx_macros::test]
+async fn it_cancels_a_query_with_a_token() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
+    let token = conn.cancel_token().unwrap();
 
-    sqlx::query("set search_path = 'another'")
-        .execute(&mut conn)
-        .await?;
+    sqlx_core::rt::spawn(async move {
+        sqlx_core::rt::sleep(Duration::from_millis(200)).await;
+        token.cancel().await
+    });
 
-    let result = sqlx::query("SELECT 1 WHERE $1::some_enum_type = 'd'::some_enum_type;")
-        .bind(SomeEnumType("d".into()))
-        .fetch_all(&mut conn)
-        .await;
+    let res = conn.execute("SELECT pg_sleep(30)").await;
+    let err = res.unwrap_err().into_database_error().unwrap();
+    assert_eq!(err.code().as_deref(), Some("57014"));
 
-    let result = result.unwrap();
-    assert_eq!(result.len(), 1);
+    // the connection is usable again
+    let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(one, 1);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_pg_server_num() -> anyhow::Result<()> {
-    let conn = new::<Postgres>().await?;
+async fn it_quotes_identifiers_in_query_builder() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    assert!(conn.server_version_num().is_some());
+    let mut create = QueryBuilder::<Postgres>::new("CREATE TEMPORARY TABLE ");
+    create.push_identifier(r#"my "table""#).push(" (");
+    create
+        .separated(", ")
+        .push_identifier("Id")
+        .push_unseparated(" INT")
+        .push_identifier("select")
+        .push_unseparated(" TEXT");
+    create.push(")");
+    create.build().execute(&mut conn).await?;
+
+    let mut insert = QueryBuilder::<Postgres>::new("INSERT INTO ");
+    insert
+        .push_identifier(r#"pg_temp.my "table""#)
+        .push(" VALUES (1, 'one')");
+    insert.build().execute(&mut conn).await?;
+
+    let mut select = QueryBuilder::<Postgres>::new("SELECT ");
+    select
+        .push_identifier(r#"my "table".select"#)
+        .push(" FROM ")
+        .push_identifier(r#"my "table""#)
+        .push(" WHERE ")
+        .push_identifier("Id")
+        .push(" = 1");
+
+    let value: String = select.build_query_scalar().fetch_one(&mut conn).await?;
+    assert_eq!(value, "one");
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_copy_in() -> anyhow::Result<()> {
+async fn it_only_keeps_the_selected_columns() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
-    conn.execute(
-        r#"
-        CREATE TEMPORARY TABLE users (id INTEGER NOT NULL);
-    "#,
-    )
-    .await?;
 
-    let mut copy = conn
-        .copy_in_raw(
-            r#"
-        COPY users (id) FROM STDIN WITH (FORMAT CSV, HEADER);
-    "#,
-        )
+    let row = sqlx::query("SELECT 1::int4 AS a, 'two'::text AS b, 3::int8 AS c, NULL::text AS d")
+        .select_columns(&["c", "d", "a"])
+        .fetch_one(&mut conn)
         .await?;
 
-    copy.send("id\n1\n2\n".as_bytes()).await?;
-    let rows = copy.finish().await?;
-    assert_eq!(rows, 2);
+    let names: Vec<&str> = row.columns().iter().map(|c| c.name()).collect();
+    assert_eq!(names, ["c", "d", "a"]);
+    assert_eq!(row.columns()[2].ordinal(), 2);
 
-    // conn is safe for reuse
-    let value = sqlx::query("select 1 + 1")
-        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
+    assert_eq!(row.try_get::<i64, _>(0)?, 3);
+    assert_eq!(row.try_get::<Option<String>, _>("d")?, None);
+    assert_eq!(row.try_get::<i32, _>("a")?, 1);
+    assert!(matches!(
+        row.try_get::<String, _>("b"),
+        Err(sqlx::Error::ColumnNotFound(_))
+    ));
+
+    let (c, a): (i64, i32) = sqlx::query_as("SELECT $1::int4 AS a, 'two' AS b, 3::int8 AS c")
+        .bind(10_i32)
+        .select_columns(&["c", "a"])
         .fetch_one(&mut conn)
         .await?;
+    assert_eq!((c, a), (3, 10));
 
-    assert_eq!(2i32, value);
+    let res = sqlx::query("SELECT 1 AS a")
+        .select_columns(&["b"])
+        .fetch_one(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::ColumnNotFound(name)) if name == "b"));
+
+    // statements which don't return rows are unaffected
+    sqlx::query("CREATE TEMPORARY TABLE selected (a INT)")
+        .select_columns(&["a"])
+        .execute(&mut conn)
+        .await?;
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_abort_copy_in() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
-    conn.execute(
-        r#"
-        CREATE TEMPORARY TABLE users (id INTEGER NOT NULL);
-    "#,
-    )
-    .await?;
+async fn it_shares_the_type_cache_between_connections() -> anyhow::Result<()> {
+    let type_cache = PgTypeCache::new();
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .type_cache(type_cache.clone());
 
-    let copy = conn
-        .copy_in_raw(
-            r#"
-        COPY users (id) FROM STDIN WITH (FORMAT CSV, HEADER);
-    "#,
-        )
+    // types are resolved when preparing a statement
+    let mut conn = PgConnection::connect_with(&options).await?;
+    let row = sqlx::query("SELECT 'open'::status")
+        .fetch_one(&mut conn)
         .await?;
+    assert_eq!(row.column(0).type_info().name(), "status");
+    assert_eq!(type_cache.len(), 1);
 
-    copy.abort("this is only a test").await?;
+    // but not by simple queries, which can only use the types already cached
+    let mut other_conn = PgConnection::connect_with(&options).await?;
+    let row = other_conn.fetch_one("SELECT 'open'::status").await?;
+    assert_eq!(row.column(0).type_info().name(), "status");
 
-    // conn is safe for reuse
-    let value = sqlx::query("select 1 + 1")
-        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
-        .fetch_one(&mut conn)
-        .await?;
+    // the types cached by the connections are dropped as well
+    type_cache.clear();
+    assert!(type_cache.is_empty());
 
-    assert_eq!(2i32, value);
+    let row = other_conn.fetch_one("SELECT 'open'::status").await?;
+    assert_eq!(row.column(0).type_info().name(), "?");
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_copy_out() -> anyhow::Result<()> {
+async fn it_flushes_deferred_statements_together() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
-    {
-        let mut copy = conn
-            .copy_out_raw(
-                "
-            COPY (SELECT generate_series(1, 2) AS id) TO STDOUT WITH (FORMAT CSV, HEADER);
-        ",
-            )
-            .await?;
+    conn.execute("CREATE TEMPORARY TABLE deferred (id INT PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
 
-        assert_eq!(copy.next().await.unwrap().unwrap(), "id\n");
-        assert_eq!(copy.next().await.unwrap().unwrap(), "1\n");
-        assert_eq!(copy.next().await.unwrap().unwrap(), "2\n");
-        if copy.next().await.is_some() {
-            anyhow::bail!("Unexpected data from COPY");
-        }
+    let mut deferred = conn.defer_flush();
+
+    for (id, name) in [(1, "Alice"), (2, "Bob"), (1, "Carol")] {
+        deferred.execute(
+            sqlx::query("INSERT INTO deferred (id, name) VALUES ($1, $2)")
+                .bind(id)
+                .bind(name),
+        );
     }
 
-    // conn is safe for reuse
-    let value = sqlx::query("select 1 + 1")
-        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
-        .fetch_one(&mut conn)
+    deferred
+        .execute("UPDATE deferred SET name = upper(name)")
+        .execute(sqlx::query("SELECT 1 / $1").bind(0_i32))
+        .execute("DELETE FROM deferred WHERE id = 2");
+
+    assert_eq!(deferred.len(), 6);
+
+    let results = deferred.flush().await?;
+    assert_eq!(results.len(), 6);
+
+    assert_eq!(results[0].as_ref().unwrap().rows_affected(), 1);
+    assert_eq!(results[1].as_ref().unwrap().rows_affected(), 1);
+    assert!(matches!(&results[2], Err(sqlx::Error::Database(e)) if e.is_unique_violation()));
+    assert_eq!(results[3].as_ref().unwrap().rows_affected(), 2);
+    assert!(matches!(&results[4], Err(sqlx::Error::Database(_))));
+    assert_eq!(results[5].as_ref().unwrap().rows_affected(), 1);
+
+    // the connection is usable afterwards
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM deferred ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(names, ["ALICE"]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_begins_transactions_with_options() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let options = TransactionOptions::new()
+        .isolation_level(IsolationLevel::Serializable)
+        .read_only(true)
+        .deferrable(true);
+
+    let mut tx = conn.begin_with(options).await?;
+
+    let (isolation, read_only, deferrable): (String, String, String) = sqlx::query_as(
+        "SELECT current_setting('transaction_isolation'), current_setting('transaction_read_only'), \
+         current_setting('transaction_deferrable')",
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    assert_eq!(isolation, "serializable");
+    assert_eq!(read_only, "on");
+    assert_eq!(deferrable, "on");
+
+    // savepoints have no options
+    let res = tx.begin_with(options).await.map(|_| ());
+    assert!(matches!(res, Err(sqlx::Error::Configuration(_))));
+
+    // the transaction is still usable, and savepoints can be established without options
+    let savepoint = tx.begin_with(TransactionOptions::new()).await?;
+    savepoint.rollback().await?;
+
+    tx.commit().await?;
+
+    let isolation = conn
+        .transaction_with(
+            TransactionOptions::new().isolation_level(IsolationLevel::RepeatableRead),
+            |tx| {
+                Box::pin(async move {
+                    sqlx::query_scalar::<_, String>(
+                        "SELECT current_setting('transaction_isolation')",
+                    )
+                    .fetch_one(&mut **tx)
+                    .await
+                })
+            },
+        )
         .await?;
 
-    assert_eq!(2i32, value);
+    assert_eq!(isolation, "repeatable read");
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_encodes_custom_array_issue_1504() -> anyhow::Result<()> {
-    use sqlx::encode::IsNull;
-    use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo};
-    use sqlx::{Decode, Encode, Type, ValueRef};
-
-    #[derive(Debug, PartialEq)]
-    enum Value {
-        String(String),
-        Number(i32),
-        Array(Vec<Value>),
-    }
+async fn it_fails_acquisitions_as_soon_as_the_pool_closes() -> anyhow::Result<()> {
+    use std::time::Instant;
 
-    impl<'r> Decode<'r, Postgres> for Value {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> std::result::Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            let typ = value.type_info().into_owned();
+    setup_if_needed();
 
-            if typ == PgTypeInfo::with_name("text") {
-                let s = <String as Decode<'_, Postgres>>::decode(value)?;
+    // nothing listens on this port, so the pool retries to connect until the acquire timeout
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .port(1);
 
-                Ok(Self::String(s))
-            } else if typ == PgTypeInfo::with_name("int4") {
-                let n = <i32 as Decode<'_, Postgres>>::decode(value)?;
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(30))
+        .connect_lazy_with(options);
+
+    // one task tries to connect, the others wait for it to release its permit
+    let waiters: Vec<_> = (0..3)
+        .map(|_| {
+            let pool = pool.clone();
+            sqlx_core::rt::spawn(async move { pool.acquire().await.map(drop) })
+        })
+        .collect();
 
-                Ok(Self::Number(n))
-            } else if typ == PgTypeInfo::with_name("_text") {
-                let arr = Vec::<String>::decode(value)?;
-                let v = arr.into_iter().map(|s| Value::String(s)).collect();
+    sqlx_core::rt::sleep(Duration::from_millis(100)).await;
 
-                Ok(Self::Array(v))
-            } else if typ == PgTypeInfo::with_name("_int4") {
-                let arr = Vec::<i32>::decode(value)?;
-                let v = arr.into_iter().map(|n| Value::Number(n)).collect();
+    let start = Instant::now();
+    pool.close().await;
 
-                Ok(Self::Array(v))
-            } else {
-                Err("unknown type".into())
-            }
-        }
+    for waiter in waiters {
+        assert!(matches!(waiter.await, Err(sqlx::Error::PoolClosed)));
     }
 
-    impl Encode<'_, Postgres> for Value {
-        fn produces(&self) -> Option<PgTypeInfo> {
-            match self {
-                Self::Array(a) => {
-                    if a.len() < 1 {
-                        return Some(PgTypeInfo::with_name("_text"));
-                    }
+    assert!(start.elapsed() < Duration::from_secs(5));
 
-                    match a[0] {
-                        Self::String(_) => Some(PgTypeInfo::with_name("_text")),
-                        Self::Number(_) => Some(PgTypeInfo::with_name("_int4")),
-                        Self::Array(_) => None,
-                    }
-                }
-                Self::String(_) => Some(PgTypeInfo::with_name("text")),
-                Self::Number(_) => Some(PgTypeInfo::with_name("int4")),
-            }
-        }
+    Ok(())
+}
 
-        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
-            match self {
-                Value::String(s) => <String as Encode<'_, Postgres>>::encode_by_ref(s, buf),
-                Value::Number(n) => <i32 as Encode<'_, Postgres>>::encode_by_ref(n, buf),
-                Value::Array(arr) => arr.encode(buf),
-            }
-        }
-    }
+#[sqlx_macros::test]
+async fn it_calls_the_statement_log_handler() -> anyhow::Result<()> {
+    use std::sync::Mutex;
 
-    impl Type<Postgres> for Value {
-        fn type_info() -> PgTypeInfo {
-            PgTypeInfo::with_name("unknown")
-        }
+    setup_if_needed();
 
-        fn compatible(ty: &PgTypeInfo) -> bool {
-            [
-                PgTypeInfo::with_name("text"),
-                PgTypeInfo::with_name("_text"),
-                PgTypeInfo::with_name("int4"),
-                PgTypeInfo::with_name("_int4"),
-            ]
-            .contains(ty)
-        }
-    }
+    let statements = Arc::new(Mutex::new(Vec::new()));
+    let handler = {
+        let statements = statements.clone();
+        Arc::new(move |statement: &sqlx::StatementLogContext<'_>| {
+            statements.lock().unwrap().push((
+                statement.sql.to_owned(),
+                statement.elapsed,
+                statement.rows_returned,
+                statement.rows_affected,
+                statement.was_slow,
+            ));
+        })
+    };
+    let logged = |sql: &str| -> Vec<_> {
+        statements
+            .lock()
+            .unwrap()
+            .drain(..)
+            .filter(|(logged, ..)| logged == sql)
+            .collect()
+    };
+
+    // the handler is called even if logging is disabled
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .disable_statement_logging()
+        .log_with(handler.clone());
 
-    let mut conn = new::<Postgres>().await?;
+    let mut conn = PgConnection::connect_with(&options).await?;
 
-    let (row,): (Value,) = sqlx::query_as("SELECT $1::text[] as Dummy")
-        .bind(Value::Array(vec![
-            Value::String("Test 0".to_string()),
-            Value::String("Test 1".to_string()),
-        ]))
-        .fetch_one(&mut conn)
+    let rows = sqlx::query("SELECT * FROM generate_series(1, 3)")
+        .fetch_all(&mut conn)
         .await?;
+    assert_eq!(rows.len(), 3);
 
-    assert_eq!(
-        row,
-        Value::Array(vec![
-            Value::String("Test 0".to_string()),
-            Value::String("Test 1".to_string()),
-        ])
-    );
+    let statement = logged("SELECT * FROM generate_series(1, 3)");
+    assert_eq!(statement.len(), 1);
+    assert!(statement[0].1 > Duration::ZERO);
+    assert_eq!(statement[0].2, 3);
+    // the statements are never slow while slow statements aren't logged
+    assert!(!statement[0].4);
 
-    let (row,): (Value,) = sqlx::query_as("SELECT $1::int4[] as Dummy")
-        .bind(Value::Array(vec![
-            Value::Number(3),
-            Value::Number(2),
-            Value::Number(1),
-        ]))
-        .fetch_one(&mut conn)
+    conn.execute("CREATE TEMPORARY TABLE logged (id INT)")
+        .await?;
+    conn.execute("INSERT INTO logged (id) VALUES (1), (2)")
         .await?;
 
-    assert_eq!(
-        row,
-        Value::Array(vec![Value::Number(3), Value::Number(2), Value::Number(1)])
-    );
+    let statement = logged("INSERT INTO logged (id) VALUES (1), (2)");
+    assert_eq!(statement.len(), 1);
+    assert_eq!((statement[0].2, statement[0].3), (0, 2));
+
+    // every statement is slow with a threshold of zero
+    let options = options.log_slow_statements("warn".parse()?, Duration::ZERO);
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    conn.execute("SELECT 1").await?;
+
+    let statement = logged("SELECT 1");
+    assert_eq!(statement.len(), 1);
+    assert!(statement[0].4);
+
+    // but removed by `disable_statement_logging()`
+    let options = options.log_with(handler).disable_statement_logging();
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    conn.execute("SELECT 1").await?;
+    assert!(statements.lock().unwrap().is_empty());
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_issue_1254() -> anyhow::Result<()> {
-    #[derive(sqlx::Type)]
-    #[sqlx(type_name = "pair")]
-    struct Pair {
-        one: i32,
-        two: i32,
-    }
+async fn it_requests_results_in_text_format_for_some_types() -> anyhow::Result<()> {
+    use sqlx::postgres::types::{Oid, PgTid};
+    use sqlx::postgres::{PgTypeInfo, PgValueFormat};
 
-    // array for custom type is not supported, use wrapper
-    #[derive(sqlx::Type)]
-    #[sqlx(type_name = "_pair")]
-    struct Pairs(Vec<Pair>);
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .text_result_format(PgTypeInfo::with_name("int4"))
+        .text_result_format(PgTypeInfo::with_oid(Oid(27)));
 
-    let mut conn = new::<Postgres>().await?;
-    conn.execute(
-        "
-DROP TABLE IF EXISTS issue_1254;
-DROP TYPE IF EXISTS pair;
+    let mut conn = PgConnection::connect_with(&options).await?;
 
-CREATE TYPE pair AS (one INT4, two INT4);
-CREATE TABLE issue_1254 (id INT4 PRIMARY KEY, pairs PAIR[]);
-",
-    )
-    .await?;
+    let sql = "SELECT $1::int4 AS a, $1::int8 AS b, '(0,1)'::tid AS c";
 
-    let result = sqlx::query("INSERT INTO issue_1254 VALUES($1, $2)")
-        .bind(0)
-        .bind(Pairs(vec![Pair { one: 94, two: 87 }]))
-        .execute(&mut conn)
+    let row = sqlx::query(sql).bind(5_i32).fetch_one(&mut conn).await?;
+
+    assert_eq!(row.try_get_raw("a")?.format(), PgValueFormat::Text);
+    assert_eq!(row.try_get_raw("b")?.format(), PgValueFormat::Binary);
+    assert_eq!(row.try_get_raw("c")?.format(), PgValueFormat::Text);
+    assert_eq!(row.try_get::<i32, _>("a")?, 5);
+    assert_eq!(row.try_get::<i64, _>("b")?, 5);
+    assert_eq!(
+        row.try_get::<PgTid, _>("c")?,
+        PgTid {
+            block: 0,
+            offset: 1
+        }
+    );
+
+    // the formats follow the selected columns
+    let row = sqlx::query(sql)
+        .bind(5_i32)
+        .select_columns(&["c", "b"])
+        .fetch_one(&mut conn)
         .await?;
-    assert_eq!(result.rows_affected(), 1);
+
+    assert_eq!(row.try_get_raw(0)?.format(), PgValueFormat::Text);
+    assert_eq!(row.try_get_raw(1)?.format(), PgValueFormat::Binary);
+    assert_eq!(
+        row.try_get::<PgTid, _>(0)?,
+        PgTid {
+            block: 0,
+            offset: 1
+        }
+    );
+    assert_eq!(row.try_get::<i64, _>(1)?, 5);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_advisory_locks() -> anyhow::Result<()> {
-    let pool = PgPoolOptions::new()
-        .max_connections(2)
-        .connect(&dotenvy::var("DATABASE_URL")?)
-        .await?;
-
-    let lock1 = Arc::new(PgAdvisoryLock::new("sqlx-postgres-tests-1"));
-    let lock2 = Arc::new(PgAdvisoryLock::new("sqlx-postgres-tests-2"));
+async fn it_inserts_rows_sent_to_a_sink() -> anyhow::Result<()> {
+    use futures::{stream, SinkExt};
 
-    let conn1 = pool.acquire().await?;
-    let mut conn1_lock1 = lock1.acquire(conn1).await?;
+    let mut conn = new::<Postgres>().await?;
 
-    // try acquiring a recursive lock through a mutable reference then dropping
-    drop(lock1.acquire(&mut conn1_lock1).await?);
+    conn.execute("CREATE TEMPORARY TABLE sunk (id INT4 PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
 
-    let conn2 = pool.acquire().await?;
-    // leak so we can take it across the task boundary
-    let conn2_lock2 = lock2.acquire(conn2).await?.leak();
+    let mut sink = conn.insert_sink("sunk", &["id", "name"], 1000);
 
-    sqlx_core::rt::spawn({
-        let lock1 = lock1.clone();
-        let lock2 = lock2.clone();
+    // 3 batches, the last one inserted when the sink is closed
+    stream::iter(0..2500)
+        .map(|id| Ok((id, format!("row {id}"))))
+        .forward(&mut sink)
+        .await?;
 
-        async move {
-            let conn2_lock2 = lock1.try_acquire(conn2_lock2).await?.right_or_else(|_| {
-                panic!(
-                    "acquired lock but wasn't supposed to! Key: {:?}",
-                    lock1.key()
-                )
-            });
+    assert_eq!(sink.rows_inserted(), 2500);
+    drop(sink);
 
-            let (conn2, released) = lock2.force_release(conn2_lock2).await?;
-            assert!(released);
+    // a batch with more values than bind parameters is split
+    let mut sink = conn.insert_sink("sunk", &["id", "name"], 50_000);
 
-            // acquire both locks but let the pool release them
-            let conn2_lock1 = lock1.acquire(conn2).await?;
-            let _conn2_lock1and2 = lock2.acquire(conn2_lock1).await?;
+    for id in 2500..42_500 {
+        sink.feed((id, "row".to_owned())).await?;
+    }
 
-            anyhow::Ok(())
-        }
-    });
+    sink.close().await?;
+    assert_eq!(sink.rows_inserted(), 40_000);
+    drop(sink);
 
-    // acquire lock2 on conn1, we leak the lock1 guard so we can manually release it before lock2
-    let conn1_lock1and2 = lock2.acquire(conn1_lock1.leak()).await?;
+    let mut sink = conn.insert_sink("sunk", &["id", "name"], 10);
+    assert!(sink.send((1, "duplicate".to_owned())).await.is_err());
+    drop(sink);
 
-    // release lock1 while holding lock2
-    let (conn1_lock2, released) = lock1.force_release(conn1_lock1and2).await?;
-    assert!(released);
+    let (count, name): (i64, String) =
+        sqlx::query_as("SELECT COUNT(*), MIN(name) FROM sunk WHERE id < 2500")
+            .fetch_one(&mut conn)
+            .await?;
 
-    let conn1 = conn1_lock2.release_now().await?;
+    assert_eq!(count, 2500);
+    assert_eq!(name, "row 0");
 
-    // acquire both locks to be sure they were released
-    {
-        let conn1_lock1 = lock1.acquire(conn1).await?;
-        let _conn1_lock1and2 = lock2.acquire(conn1_lock1).await?;
-    }
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sunk")
+        .fetch_one(&mut conn)
+        .await?;
 
-    pool.close().await;
+    assert_eq!(count, 42_500);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_postgres_bytea_hex_deserialization_errors() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
-    conn.execute("SET bytea_output = 'escape';").await?;
-    for value in ["", "DEADBEEF"] {
-        let query = format!("SELECT '\\x{value}'::bytea");
-        let res: sqlx::Result<Vec<u8>> = conn.fetch_one(query.as_str()).await?.try_get(0usize);
-        // Deserialization only supports hex format so this should error and definitely not panic.
-        res.unwrap_err();
+async fn it_reconnects_after_the_connection_is_lost() -> anyhow::Result<()> {
+    use sqlx::{ReconnectingConnection, TransactionManager};
+
+    setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let mut conn = ReconnectingConnection::<PgConnection>::connect_with(options).await?;
+    let mut admin = new::<Postgres>().await?;
+
+    fn backend_pid(conn: &mut PgConnection) -> futures::future::BoxFuture<'_, sqlx::Result<i32>> {
+        Box::pin(sqlx::query_scalar("SELECT pg_backend_pid()").fetch_one(conn))
     }
-    Ok(())
-}
 
-#[sqlx_macros::test]
-async fn test_shrink_buffers() -> anyhow::Result<()> {
-    // We don't really have a good way to test that `.shrink_buffers()` functions as expected
-    // without exposing a lot of internals, but we can at least be sure it doesn't
-    // materially affect the operation of the connection.
+    async fn terminate(admin: &mut PgConnection, pid: i32) -> anyhow::Result<()> {
+        // wait for the backend to exit
+        sqlx::query("SELECT pg_terminate_backend($1, 5000)")
+            .bind(pid)
+            .execute(admin)
+            .await?;
 
-    let mut conn = new::<Postgres>().await?;
+        Ok(())
+    }
 
-    // The connection buffer is only 8 KiB by default so this should definitely force it to grow.
-    let data = vec![0u8; 32 * 1024];
+    // without retrying, the error is returned and the connection is replaced on the next use
+    let pid = conn.run(backend_pid).await?;
+    terminate(&mut admin, pid).await?;
 
-    let ret: Vec<u8> = sqlx::query_scalar("SELECT $1::bytea")
-        .bind(&data)
-        .fetch_one(&mut conn)
-        .await?;
+    conn.run(backend_pid).await.unwrap_err();
+    assert!(!conn.is_connected());
 
-    assert_eq!(ret, data);
+    let pid2 = conn.run(backend_pid).await?;
+    assert_ne!(pid, pid2);
 
-    conn.shrink_buffers();
+    // an idempotent operation is retried on a new connection
+    terminate(&mut admin, pid2).await?;
 
-    let ret: i64 = sqlx::query_scalar("SELECT $1::int8")
-        .bind(&12345678i64)
-        .fetch_one(&mut conn)
+    let pid3 = conn.run_idempotent(backend_pid).await?;
+    assert_ne!(pid2, pid3);
+    assert!(conn.is_connected());
+
+    // but not inside a transaction
+    conn.run(|conn| sqlx::postgres::PgTransactionManager::begin(conn))
         .await?;
+    terminate(&mut admin, pid3).await?;
 
-    assert_eq!(ret, 12345678i64);
+    conn.run_idempotent(backend_pid).await.unwrap_err();
+    assert!(!conn.is_connected());
 
     Ok(())
 }
@@ -39,6 +39,22 @@ CREATE TYPE float_range AS RANGE
     subtype_diff = float8mi
 );
 
+CREATE DOMAIN seat_number AS INT4 CHECK (VALUE > 0);
+
+CREATE TYPE seat_range AS RANGE
+(
+    subtype = seat_number
+);
+
+CREATE SCHEMA sched;
+
+CREATE DOMAIN sched.slot_number AS INT4 CHECK (VALUE > 0);
+
+CREATE TYPE sched.slot_range AS RANGE
+(
+    subtype = sched.slot_number
+);
+
 CREATE TABLE products (
     product_no INTEGER,
     name TEXT,
@@ -1,12 +1,18 @@
 extern crate time_ as time;
 
+use std::borrow::Cow;
 use std::net::SocketAddr;
 use std::ops::Bound;
 
-use sqlx::postgres::types::{Oid, PgCiText, PgInterval, PgMoney, PgRange};
+use sqlx::postgres::types::{
+    Oid, PgArrayIter, PgCiText, PgInterval, PgLsn, PgMoney, PgRange, PgTid, PgTxidSnapshot, PgXid,
+    PgXid8,
+};
 use sqlx::postgres::Postgres;
 use sqlx_test::{new, test_decode_type, test_prepared_type, test_type};
 
+use sqlx::Row;
+use sqlx_core::bytes::Bytes;
 use sqlx_core::executor::Executor;
 use sqlx_core::types::Text;
 use std::str::FromStr;
@@ -53,6 +59,39 @@ test_prepared_type!(byte_slice<&[u8]>(Postgres,
         == &[0_u8, 0, 0, 0, 0x52][..]
 ));
 
+test_type!(bytes<Bytes>(Postgres,
+    "E'\\\\xDEADBEEF'::bytea" == Bytes::from_static(&[0xDE, 0xAD, 0xBE, 0xEF]),
+    "E'\\\\x'::bytea" == Bytes::new(),
+));
+
+test_type!(bytes_vec<Vec<Bytes>>(Postgres,
+    "ARRAY[E'\\\\xDEADBEEF', E'\\\\x']::bytea[]"
+        == vec![Bytes::from_static(&[0xDE, 0xAD, 0xBE, 0xEF]), Bytes::new()],
+));
+
+#[sqlx_macros::test]
+async fn it_decodes_bytes_without_copying() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let row = sqlx::query("SELECT $1::bytea, ARRAY[$1]::bytea[]")
+        .bind(&b"a large image"[..])
+        .fetch_one(&mut conn)
+        .await?;
+
+    // both decoded values share the buffer of the row
+    let first: Bytes = row.try_get(0)?;
+    let second: Bytes = row.try_get(0)?;
+    assert_eq!(first, &b"a large image"[..]);
+    assert_eq!(first.as_ptr(), second.as_ptr());
+
+    let elements: Vec<Bytes> = row.try_get(1)?;
+    let more: Vec<Bytes> = row.try_get(1)?;
+    assert_eq!(elements, [first]);
+    assert_eq!(elements[0].as_ptr(), more[0].as_ptr());
+
+    Ok(())
+}
+
 test_type!(byte_array_empty<[u8; 0]>(Postgres,
     "E'\\\\x'::bytea" == [0_u8; 0],
 ));
@@ -61,6 +100,24 @@ test_type!(byte_array<[u8; 4]>(Postgres,
     "E'\\\\xDEADBEEF'::bytea" == [0xDE_u8, 0xAD, 0xBE, 0xEF],
 ));
 
+#[sqlx_macros::test]
+async fn it_rejects_byte_arrays_of_another_length() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // in both the binary and text formats
+    let res = sqlx::query_scalar::<_, [u8; 32]>("SELECT '\\xDEADBEEF'::bytea")
+        .fetch_one(&mut conn)
+        .await;
+    let err = res.unwrap_err().to_string();
+    assert!(err.contains("expected 32 bytes, got 4"), "{err}");
+
+    let row = conn.fetch_one("SELECT '\\xDEADBEEF'::bytea").await?;
+    let err = row.try_get::<[u8; 32], _>(0).unwrap_err().to_string();
+    assert!(err.contains("expected 32 bytes, got 4"), "{err}");
+
+    Ok(())
+}
+
 test_type!(str<&str>(Postgres,
     "'this is foo'" == "this is foo",
     "''" == "",
@@ -74,6 +131,26 @@ test_type!(string<String>(Postgres,
     "'this is foo'" == format!("this is foo"),
 ));
 
+#[sqlx_macros::test]
+async fn it_decodes_cow_str_borrowed() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // text format
+    let row = conn.fetch_one("SELECT 'this is foo'::text").await?;
+    let text: Cow<'_, str> = row.try_get(0)?;
+    assert!(matches!(text, Cow::Borrowed("this is foo")));
+
+    // binary format
+    let row = sqlx::query("SELECT $1::text")
+        .bind("this is foo")
+        .fetch_one(&mut conn)
+        .await?;
+    let text: Cow<'_, str> = row.try_get(0)?;
+    assert!(matches!(text, Cow::Borrowed("this is foo")));
+
+    Ok(())
+}
+
 test_type!(string_vec<Vec<String>>(Postgres,
     "array['one','two','three']::text[]"
         == vec!["one","two","three"],
@@ -89,6 +166,75 @@ test_type!(string_array<[String; 3]>(Postgres,
     "array['one','two','three']::text[]" == ["one","two","three"],
 ));
 
+test_type!(boxed_string_slice<Box<[Box<str>]>>(Postgres,
+    "array['one','two','three']::text[]"
+        == vec![Box::<str>::from("one"), "two".into(), "three".into()].into_boxed_slice(),
+    "array[]::text[]" == Box::<[Box<str>]>::default(),
+));
+
+test_type!(boxed_i32_slice<Box<[i32]>>(Postgres,
+    "'{1,2,3}'::int4[]" == vec![1_i32, 2, 3].into_boxed_slice(),
+));
+
+test_type!(boxed_bytes<Box<[u8]>>(Postgres,
+    "E'\\\\xDEADBEEF'::bytea" == vec![0xDE_u8, 0xAD, 0xBE, 0xEF].into_boxed_slice(),
+));
+
+#[sqlx_macros::test]
+async fn it_decodes_arrays_lazily() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let row = sqlx::query("SELECT $1::int4[], '{}'::int4[], '{a,NULL,c}'::text[]")
+        .bind(vec![1_i32, 2, 3])
+        .fetch_one(&mut conn)
+        .await?;
+
+    let ints: PgArrayIter<'_, i32> = row.try_get(0)?;
+    assert_eq!(ints.len(), 3);
+    assert_eq!(ints.map(Result::unwrap).sum::<i32>(), 6);
+
+    let mut empty: PgArrayIter<'_, i32> = row.try_get(1)?;
+    assert!(empty.next().is_none());
+
+    let texts: PgArrayIter<'_, Option<&str>> = row.try_get(2)?;
+    let texts = texts.collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(texts, [Some("a"), None, Some("c")]);
+
+    let mut texts: PgArrayIter<'_, &str> = row.try_get(2)?;
+    assert_eq!(texts.next().unwrap().unwrap(), "a");
+    assert!(texts.next().unwrap().is_err());
+
+    // arrays are sent as text by simple queries
+    let row = conn.fetch_one("SELECT '{1,2}'::int4[]").await?;
+    assert!(row.try_get::<PgArrayIter<'_, i32>, _>(0).is_err());
+
+    Ok(())
+}
+
+test_type!(socket_addr<SocketAddr>(Postgres,
+    "'198.51.100.47:31790'::text" == "198.51.100.47:31790".parse::<SocketAddr>().unwrap(),
+    "'[2001:db8::1]:8080'::text" == "[2001:db8::1]:8080".parse::<SocketAddr>().unwrap(),
+));
+
+test_type!(socket_addr_vec<Vec<SocketAddr>>(Postgres,
+    "array['127.0.0.1:5432', '[::1]:5432']::text[]"
+        == vec![
+            "127.0.0.1:5432".parse::<SocketAddr>().unwrap(),
+            "[::1]:5432".parse::<SocketAddr>().unwrap(),
+        ],
+));
+
+#[sqlx_macros::test]
+async fn it_rejects_invalid_socket_addrs() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let row = conn.fetch_one("SELECT '127.0.0.1'::text").await?;
+    let err = row.try_get::<SocketAddr, _>(0).unwrap_err();
+    assert!(err.to_string().contains("invalid socket address"), "{err}");
+
+    Ok(())
+}
+
 test_type!(i8(
     Postgres,
     "0::\"char\"" == 0_i8,
@@ -97,6 +243,75 @@ test_type!(i8(
 
 test_type!(Oid(Postgres, "325235::oid" == Oid(325235),));
 
+test_type!(PgLsn(
+    Postgres,
+    "'0/0'::pg_lsn" == PgLsn(0),
+    "'16/B374D848'::pg_lsn" == PgLsn(0x16_B374_D848),
+));
+
+test_type!(PgXid(Postgres, "'4294967295'::xid" == PgXid(u32::MAX),));
+
+test_type!(PgXid8(
+    Postgres,
+    "'18446744073709551615'::xid8" == PgXid8(u64::MAX),
+));
+
+test_type!(xid8_vec<Vec<PgXid8>>(Postgres,
+    "array['1','2']::xid8[]" == vec![PgXid8(1), PgXid8(2)],
+));
+
+test_decode_type!(PgTxidSnapshot(
+    Postgres,
+    "'10:20:10,14,15'::pg_snapshot"
+        == PgTxidSnapshot {
+            xmin: PgXid8(10),
+            xmax: PgXid8(20),
+            xip: vec![PgXid8(10), PgXid8(14), PgXid8(15)],
+        },
+    "'10:20:'::txid_snapshot"
+        == PgTxidSnapshot {
+            xmin: PgXid8(10),
+            xmax: PgXid8(20),
+            xip: vec![],
+        },
+));
+
+test_decode_type!(PgTid(
+    Postgres,
+    "'(0,1)'::tid"
+        == PgTid {
+            block: 0,
+            offset: 1
+        },
+    "'(4294967295,65535)'::tid"
+        == PgTid {
+            block: u32::MAX,
+            offset: u16::MAX
+        },
+));
+
+test_decode_type!(tid_vec<Vec<PgTid>>(Postgres,
+    "array['(0,1)','(2,3)']::tid[]" == vec![PgTid { block: 0, offset: 1 }, PgTid { block: 2, offset: 3 }],
+));
+
+#[sqlx_macros::test]
+async fn it_decodes_ctid() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE tuples (id INT4); INSERT INTO tuples SELECT generate_series(1, 3)",
+    )
+    .await?;
+
+    let tids: Vec<PgTid> = sqlx::query_scalar("SELECT ctid FROM tuples ORDER BY ctid")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(tids, [1, 2, 3].map(|offset| PgTid { block: 0, offset }));
+
+    Ok(())
+}
+
 test_type!(i16(
     Postgres,
     "-2144::smallint" == -2144_i16,
@@ -126,6 +341,67 @@ test_type!(i32_array<[i32; 4]>(Postgres,
 
 test_type!(i64(Postgres, "9358295312::bigint" == 9358295312_i64));
 
+test_decode_type!(i64_from_smallint<i64>(Postgres,
+    "(-32768)::smallint" == -32768_i64,
+    "2147483647::integer" == 2147483647_i64,
+));
+
+test_decode_type!(i16_from_bigint<i16>(Postgres, "123::bigint" == 123_i16));
+
+test_decode_type!(i32_vec_from_bigint<Vec<i32>>(Postgres,
+    "'{1, -2147483648}'::int8[]" == vec![1_i32, i32::MIN],
+));
+
+#[sqlx_macros::test]
+async fn it_decodes_the_current_transaction_and_snapshot() -> anyhow::Result<()> {
+    use sqlx::Connection;
+
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
+
+    let (xid, xid32, txid): (PgXid8, PgXid, i64) =
+        sqlx::query_as("SELECT pg_current_xact_id(), pg_current_xact_id()::xid, txid_current()")
+            .fetch_one(&mut *tx)
+            .await?;
+
+    assert_eq!(PgXid8::from_txid(txid), xid);
+    assert_eq!(u64::from(xid32.0), xid.0 & u64::from(u32::MAX));
+
+    let (snapshot, txid_snapshot): (PgTxidSnapshot, PgTxidSnapshot) =
+        sqlx::query_as("SELECT pg_current_snapshot(), txid_current_snapshot()")
+            .fetch_one(&mut *tx)
+            .await?;
+
+    // a transaction is not completed in its own snapshot
+    assert_eq!(snapshot, txid_snapshot);
+    assert!(!snapshot.is_visible(xid));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_integers_out_of_range() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let res = sqlx::query_scalar::<_, i32>("SELECT 3000000000::int8")
+        .fetch_one(&mut conn)
+        .await;
+
+    let Err(sqlx::Error::ColumnDecode { source, .. }) = res else {
+        panic!("expected a decode error, got {res:?}");
+    };
+    assert_eq!(
+        source.to_string(),
+        "value 3000000000 is out of range for `i32`"
+    );
+
+    // the text format is checked as well
+    let row = conn.fetch_one("SELECT (-40000)::int4").await?;
+    assert!(row.try_get::<i16, _>(0).is_err());
+
+    Ok(())
+}
+
 test_type!(f32(Postgres, "9419.122::real" == 9419.122_f32));
 
 test_type!(f64(
@@ -137,6 +413,16 @@ test_type!(f64_vec<Vec<f64>>(Postgres,
     "'{939399419.1225182,-12.0}'::float8[]" == vec![939399419.1225182_f64, -12.0]
 ));
 
+test_decode_type!(int2vector<Vec<i16>>(Postgres,
+    "'1 3 -2'::int2vector" == vec![1_i16, 3, -2],
+    "''::int2vector" == Vec::<i16>::new(),
+));
+
+test_decode_type!(oidvector<Vec<Oid>>(Postgres,
+    "'23 25'::oidvector" == vec![Oid(23), Oid(25)],
+    "''::oidvector" == Vec::<Oid>::new(),
+));
+
 test_decode_type!(bool_tuple<(bool,)>(Postgres, "row(true)" == (true,)));
 
 test_decode_type!(num_tuple<(i32, i64, f64,)>(Postgres, "row(10,515::int8,3.124::float8)" == (10,515,3.124)));
@@ -349,6 +635,104 @@ mod time_tests {
         "TIMETZ '05:10:20.115100-05'" == PgTimeTz { time: time!(5:10:20.115100), offset: UtcOffset::from_whole_seconds(-(60 * 60 * 5)).unwrap() },
         "TIMETZ '05:10:20+02'" == PgTimeTz { time: time!(5:10:20), offset: UtcOffset::from_whole_seconds(60 * 60 * 2 ).unwrap() }
     ));
+
+    #[sqlx_macros::test]
+    async fn test_time_precision() -> anyhow::Result<()> {
+        use sqlx::postgres::types::PgPrecision;
+
+        let mut conn = new::<Postgres>().await?;
+
+        conn.execute("CREATE TEMPORARY TABLE precision_test (ts TIMESTAMPTZ(0), t TIME(3))")
+            .await?;
+
+        let ts = date!(2019 - 1 - 2)
+            .with_time(time!(5:10:20.789654))
+            .assume_utc();
+        let t = time!(5:10:20.789654);
+
+        let bound_ts = PgPrecision::<_, 0>::new(ts);
+        let bound_t = PgPrecision::<_, 3>::new(t);
+
+        assert_eq!(*bound_ts, ts.replace_nanosecond(0)?);
+        assert_eq!(*bound_t, t.replace_nanosecond(789_000_000)?);
+
+        sqlx::query("INSERT INTO precision_test (ts, t) VALUES ($1, $2)")
+            .bind(bound_ts)
+            .bind(bound_t)
+            .execute(&mut conn)
+            .await?;
+
+        // Postgres would round these up
+        sqlx::query("INSERT INTO precision_test (ts, t) VALUES ($1, $2)")
+            .bind(ts)
+            .bind(t)
+            .execute(&mut conn)
+            .await?;
+
+        let rows: Vec<(PgPrecision<OffsetDateTime, 0>, PgPrecision<Time, 3>)> =
+            sqlx::query_as("SELECT ts, t FROM precision_test")
+                .fetch_all(&mut conn)
+                .await?;
+
+        assert_eq!(rows[0], (bound_ts, bound_t));
+        assert_eq!(
+            *rows[1].0,
+            ts.replace_nanosecond(0)? + time::Duration::SECOND
+        );
+        assert_eq!(*rows[1].1, t.replace_nanosecond(790_000_000)?);
+
+        // values are truncated on decode as well
+        let decoded: PgPrecision<OffsetDateTime, 0> = sqlx::query_scalar("SELECT $1::timestamptz")
+            .bind(ts)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(decoded, bound_ts);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "jiff")]
+mod jiff_tests {
+    use super::*;
+    use sqlx::types::jiff::{Date, DateTime, Time, Timestamp};
+
+    test_type!(jiff_date<Date>(
+        Postgres,
+        "DATE '2001-01-05'" == Date::constant(2001, 1, 5),
+        "DATE '2050-11-23'" == Date::constant(2050, 11, 23),
+        "DATE '1980-02-29'" == Date::constant(1980, 2, 29)
+    ));
+
+    test_type!(jiff_time<Time>(
+        Postgres,
+        "TIME '05:10:20.115100'" == Time::constant(5, 10, 20, 115_100_000),
+        "TIME '05:10:20'" == Time::constant(5, 10, 20, 0)
+    ));
+
+    test_type!(jiff_date_time<DateTime>(
+        Postgres,
+        "TIMESTAMP '2019-01-02 05:10:20'" == DateTime::constant(2019, 1, 2, 5, 10, 20, 0),
+        "TIMESTAMP '2019-01-02 05:10:20.1151'" == DateTime::constant(2019, 1, 2, 5, 10, 20, 115_100_000),
+        "TIMESTAMP '1970-01-01 00:00:00'" == DateTime::constant(1970, 1, 1, 0, 0, 0, 0)
+    ));
+
+    test_type!(jiff_timestamp<Timestamp>(
+        Postgres,
+        "TIMESTAMPTZ '2019-01-02 05:10:20.115100'"
+            == "2019-01-02T05:10:20.1151Z".parse::<Timestamp>().unwrap(),
+        "TIMESTAMPTZ '1999-12-31 23:59:59+02'"
+            == "1999-12-31T21:59:59Z".parse::<Timestamp>().unwrap()
+    ));
+
+    test_type!(jiff_timestamp_vec<Vec<Timestamp>>(Postgres,
+        "array['2019-01-02 05:10:20','2019-01-02 05:10:20.1151']::timestamptz[]"
+            == vec![
+                "2019-01-02T05:10:20Z".parse::<Timestamp>().unwrap(),
+                "2019-01-02T05:10:20.1151Z".parse::<Timestamp>().unwrap(),
+            ]
+    ));
 }
 
 #[cfg(feature = "json")]
@@ -440,6 +824,39 @@ mod json {
 
         Ok(())
     }
+
+    #[sqlx_macros::test]
+    async fn test_pg_raw_json() -> anyhow::Result<()> {
+        use sqlx::postgres::types::PgRawJson;
+
+        #[derive(serde::Deserialize)]
+        struct Event<'a> {
+            kind: &'a str,
+        }
+
+        let mut conn = new::<Postgres>().await?;
+
+        for sql in [
+            "SELECT '[{\"kind\": \"click\"}, {\"kind\": \"view\"}]'::jsonb",
+            "SELECT '[{\"kind\":  \"click\"}, {\"kind\": \"view\"}]'::json",
+        ] {
+            // unprepared, text API
+            let row: PgRow = conn.fetch_one(sql).await?;
+            let raw: PgRawJson<'_> = row.try_get(0)?;
+            let events: Vec<Event<'_>> = serde_json::from_slice(raw.as_bytes())?;
+            assert_eq!(events[0].kind, "click");
+
+            // prepared, binary API: the format version of `jsonb` is removed
+            let row: PgRow = conn.fetch_one(sqlx::query(sql)).await?;
+            let binary: PgRawJson<'_> = row.try_get(0)?;
+            assert_eq!(binary, raw);
+
+            let events: Vec<Event<'_>> = serde_json::from_slice(binary.as_bytes())?;
+            assert_eq!(events[1].kind, "view");
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "bigdecimal")]
@@ -16,3 +16,40 @@ async fn it_encodes_bool_with_any() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_decodes_cow_str_with_any() -> anyhow::Result<()> {
+    use sqlx::Row;
+    use std::borrow::Cow;
+
+    sqlx::any::install_default_drivers();
+    let mut conn = new::<Any>().await?;
+
+    let row = sqlx::query("SELECT ?")
+        .bind(Cow::Borrowed("Harrison Ford"))
+        .fetch_one(&mut conn)
+        .await?;
+    let name: Cow<'_, str> = row.try_get(0)?;
+    assert!(matches!(name, Cow::Borrowed("Harrison Ford")));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_encodes_and_decodes_byte_arrays_with_any() -> anyhow::Result<()> {
+    use sqlx::Row;
+
+    sqlx::any::install_default_drivers();
+    let mut conn = new::<Any>().await?;
+
+    let row = sqlx::query("SELECT ?")
+        .bind([0xDE_u8, 0xAD, 0xBE, 0xEF])
+        .fetch_one(&mut conn)
+        .await?;
+    let bytes: [u8; 4] = row.try_get(0)?;
+    assert_eq!(bytes, [0xDE, 0xAD, 0xBE, 0xEF]);
+
+    assert!(row.try_get::<[u8; 3], _>(0).is_err());
+
+    Ok(())
+}
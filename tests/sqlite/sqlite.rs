@@ -1,13 +1,15 @@
 use futures::TryStreamExt;
 use rand::{Rng, SeedableRng};
 use rand_xoshiro::Xoshiro256PlusPlus;
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteIndexOrigin, SqlitePoolOptions};
 use sqlx::{
-    query, sqlite::Sqlite, sqlite::SqliteRow, Column, ConnectOptions, Connection, Executor, Row,
-    SqliteConnection, SqlitePool, Statement, TypeInfo,
+    query, sqlite::Sqlite, sqlite::SqliteRow, Column, ConnectOptions, Connection, Executor,
+    IsolationLevel, QueryBuilder, Row, SqliteConnection, SqlitePool, Statement, TransactionOptions,
+    TypeInfo,
 };
 use sqlx_test::new;
 use std::sync::Arc;
+use std::time::Duration;
 
 #[sqlx_macros::test]
 async fn it_connects() -> anyhow::Result<()> {
@@ -369,6 +371,66 @@ SELECT id, other FROM users WHERE id = last_insert_rowid();
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_reports_rows_affected_and_last_insert_rowid() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE items (id INTEGER PRIMARY KEY, name TEXT)")
+        .await?;
+
+    let done = sqlx::query("INSERT INTO items (name) VALUES ('a'), ('b'), ('c')")
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(done.rows_affected(), 3);
+    assert_eq!(done.last_insert_rowid(), 3);
+
+    // statements which don't change any row don't report the changes of the previous one
+    let done = sqlx::query("SELECT * FROM items")
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(done.rows_affected(), 0);
+
+    let done = conn
+        .execute("CREATE TEMPORARY TABLE other (id INTEGER)")
+        .await?;
+    assert_eq!(done.rows_affected(), 0);
+
+    let done = sqlx::query("UPDATE items SET name = 'z' WHERE id > 1")
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(done.rows_affected(), 2);
+
+    // the results are available for each statement of a stream
+    let results: Vec<_> = conn
+        .fetch_many(
+            "INSERT INTO items (name) VALUES ('d') RETURNING id; \
+             SELECT id FROM items; \
+             DELETE FROM items WHERE name = 'z'",
+        )
+        .try_filter_map(|step| async move { Ok(step.left()) })
+        .map_ok(|done| (done.rows_affected(), done.last_insert_rowid()))
+        .try_collect()
+        .await?;
+
+    assert_eq!(results, [(1, 4), (0, 4), (2, 4)]);
+
+    assert_eq!(conn.last_insert_rowid().await?, 4);
+
+    let mut tx = conn.begin().await?;
+
+    let id: i64 = sqlx::query_scalar("INSERT INTO items (name) VALUES ('e') RETURNING id")
+        .fetch_one(&mut *tx)
+        .await?;
+
+    assert_eq!(tx.last_insert_rowid().await?, id);
+    assert_eq!(tx.lock_handle().await?.changes(), 1);
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_interleaves_reads_and_writes() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -490,6 +552,84 @@ async fn it_caches_statements() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_lists_cached_statements() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    sqlx::query("SELECT 1").execute(&mut conn).await?;
+    sqlx::query("SELECT ?").bind(2).execute(&mut conn).await?;
+    sqlx::query("SELECT 3")
+        .persistent(false)
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(conn.cached_statements().await?, ["SELECT ?", "SELECT 1"]);
+
+    // using a statement makes it the most recently used
+    sqlx::query("SELECT 1").execute(&mut conn).await?;
+    assert_eq!(conn.cached_statements().await?, ["SELECT 1", "SELECT ?"]);
+
+    conn.clear_cached_statements().await?;
+    assert!(conn.cached_statements().await?.is_empty());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rewrites_sql_before_executing_it() -> anyhow::Result<()> {
+    use std::str::FromStr;
+
+    let mut conn = SqliteConnectOptions::from_str(":memory:")?
+        .sql_rewriter(Arc::new(|sql| sql.replace("{shard}", "shard_1").into()))
+        .connect()
+        .await?;
+
+    conn.execute("CREATE TABLE {shard}_users (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    sqlx::query("INSERT INTO {shard}_users (id) VALUES (?)")
+        .bind(1)
+        .execute(&mut conn)
+        .await?;
+
+    let id: i32 = sqlx::query_scalar("SELECT id FROM shard_1_users")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(id, 1);
+
+    assert_eq!(
+        conn.cached_statements().await?,
+        [
+            "SELECT id FROM shard_1_users",
+            "INSERT INTO shard_1_users (id) VALUES (?)"
+        ]
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_prepends_comments_to_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let val: i32 = sqlx::query_scalar("SELECT ?")
+        .bind(1)
+        .with_comment("request_id='*/ abc'")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(val, 1);
+
+    let rows = sqlx::query("SELECT 2")
+        .with_comment("request_id='def'")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(rows.len(), 1);
+
+    // queries with a comment are not cached
+    assert!(conn.cached_statements().await?.is_empty());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -679,6 +819,78 @@ async fn issue_1467() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn it_decodes_optional_joins_with_flatten() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq, sqlx::FromRow)]
+    struct Owner {
+        owner_id: i64,
+        owner_name: String,
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Tweet {
+        id: i64,
+        #[sqlx(flatten)]
+        owner: Option<Owner>,
+    }
+
+    let mut conn = new::<Sqlite>().await?;
+
+    // SQLite decodes NULL as `0` or `""`, so the columns are checked for NULL
+    let tweets: Vec<Tweet> = sqlx::query_as(
+        "SELECT tweet.id, owner.id AS owner_id, owner.name AS owner_name \
+         FROM (SELECT 1 AS id, 1 AS owner_id UNION ALL SELECT 2, 2) AS tweet \
+         LEFT JOIN (SELECT 1 AS id, 'alice' AS name) AS owner ON owner.id = tweet.owner_id \
+         ORDER BY tweet.id",
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(tweets[0].id, 1);
+    assert_eq!(
+        tweets[0].owner,
+        Some(Owner {
+            owner_id: 1,
+            owner_name: "alice".into()
+        })
+    );
+    assert_eq!(tweets[1].id, 2);
+    assert_eq!(tweets[1].owner, None);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rolls_back_leaked_transactions_on_release() -> anyhow::Result<()> {
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect(":memory:")
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    // a plain `BEGIN`, which is left open when the connection is released
+    conn.execute("BEGIN").await?;
+    conn.execute("CREATE TABLE leaked_transaction (id INTEGER)")
+        .await?;
+    drop(conn);
+
+    let mut conn = pool.acquire().await?;
+
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE name = 'leaked_transaction'")
+            .fetch_one(&mut *conn)
+            .await?;
+    assert_eq!(count, 0);
+
+    // would fail with "cannot start a transaction within a transaction"
+    let mut tx = conn.begin().await?;
+    tx.rollback().await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn concurrent_read_and_write() {
     let pool: SqlitePool = SqlitePoolOptions::new()
@@ -794,3 +1006,879 @@ async fn test_multiple_set_progress_handler_calls_drop_old_handler() -> anyhow::
     assert_eq!(1, Arc::strong_count(&ref_counted_object));
     Ok(())
 }
+
+#[cfg(feature = "fault-injection")]
+#[sqlx_macros::test]
+async fn it_injects_faults() -> anyhow::Result<()> {
+    use sqlx::fault_injection::FaultInjector;
+
+    let mut conn = new::<Sqlite>().await?;
+    let faults = FaultInjector::new();
+
+    conn.execute("CREATE TEMPORARY TABLE faults (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    faults
+        .fail_next_with(sqlx::Error::PoolTimedOut)
+        .fail_next_with(sqlx::Error::RowNotFound);
+
+    let res = conn.execute("INSERT INTO faults DEFAULT VALUES").await;
+    assert!(res.is_ok());
+
+    let res = faults
+        .wrap(&mut conn)
+        .execute("INSERT INTO faults DEFAULT VALUES")
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::PoolTimedOut)));
+
+    let res = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM faults")
+        .fetch_one(faults.wrap(&mut conn))
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::RowNotFound)));
+
+    // the failed queries were not executed
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM faults")
+        .fetch_one(faults.wrap(&mut conn))
+        .await?;
+    assert_eq!(count, 1);
+
+    faults.drop_connection_after(1);
+
+    faults
+        .wrap(&mut conn)
+        .execute("INSERT INTO faults DEFAULT VALUES")
+        .await?;
+    assert!(!faults.is_dropped());
+
+    // the query is executed, but its result is lost
+    let res = faults
+        .wrap(&mut conn)
+        .execute("INSERT INTO faults DEFAULT VALUES")
+        .await;
+    assert!(
+        matches!(&res, Err(sqlx::Error::Io(e)) if e.kind() == std::io::ErrorKind::ConnectionReset)
+    );
+    assert!(faults.is_dropped());
+
+    let res = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM faults")
+        .fetch_one(faults.wrap(&mut conn))
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::Io(_))));
+
+    faults.clear();
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM faults")
+        .fetch_one(faults.wrap(&mut conn))
+        .await?;
+    assert_eq!(count, 3);
+
+    // pools can be wrapped as well
+    let pool = SqlitePool::connect("sqlite::memory:").await?;
+
+    faults.fail_next_with(sqlx::Error::PoolClosed);
+
+    let res = faults.wrap(&pool).execute("SELECT 1").await;
+    assert!(matches!(res, Err(sqlx::Error::PoolClosed)));
+
+    faults.wrap(&pool).execute("SELECT 1").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_configures_pool_from_url() -> anyhow::Result<()> {
+    let url = "sqlite::memory:?max_connections=1&acquire_timeout=1.5&idle_timeout=none&test_before_acquire=false&statement_timeout=2";
+
+    let options = SqlitePoolOptions::from_url(url)?;
+    assert_eq!(options.get_max_connections(), 1);
+    assert_eq!(options.get_acquire_timeout(), Duration::from_millis(1500));
+    assert_eq!(options.get_idle_timeout(), None);
+    assert!(!options.get_test_before_acquire());
+    assert_eq!(
+        options.get_statement_timeout(),
+        Some(Duration::from_secs(2))
+    );
+
+    // the pool settings are not passed on to the driver, which would reject them
+    let pool = options.connect(url).await?;
+    assert_eq!(pool.options().get_max_connections(), 1);
+    pool.execute("SELECT 1").await?;
+
+    let pool = SqlitePool::connect(url).await?;
+    assert_eq!(pool.options().get_max_connections(), 1);
+
+    // `connect()` applies them too, unless they were set explicitly
+    let pool = SqlitePoolOptions::new()
+        .acquire_timeout(Duration::from_secs(5))
+        .connect(url)
+        .await?;
+    assert_eq!(pool.options().get_max_connections(), 1);
+    assert_eq!(pool.options().get_acquire_timeout(), Duration::from_secs(5));
+
+    assert!(SqlitePoolOptions::from_url("sqlite::memory:?max_connections=many").is_err());
+    assert!(SqlitePoolOptions::new()
+        .connect_lazy("sqlite::memory:?max_connections=many")
+        .is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_runs_with_exclusive_pool_access() -> anyhow::Result<()> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(3)
+        .connect("sqlite::memory:")
+        .await?;
+
+    let held = pool.acquire().await?;
+    let _ = pool.acquire().await?;
+    assert_eq!(pool.size(), 2);
+
+    sqlx_core::rt::spawn(async move {
+        sqlx_core::rt::sleep(Duration::from_millis(50)).await;
+        drop(held);
+    });
+
+    let p = pool.clone();
+    let size = pool
+        .with_exclusive(|conn| {
+            Box::pin(async move {
+                // no other connection is open, or can be acquired
+                assert!(p.try_acquire().is_none());
+
+                conn.execute("VACUUM").await?;
+
+                Ok::<_, sqlx::Error>(p.size())
+            })
+        })
+        .await?;
+
+    assert_eq!(size, 1);
+    assert_eq!(pool.num_idle(), 1);
+    assert!(pool.try_acquire().is_some());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_integers_out_of_range() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // this would be truncated to 5 if decoded as a 32-bit integer
+    let res = sqlx::query_scalar::<_, i32>("SELECT 4294967301")
+        .fetch_one(&mut conn)
+        .await;
+
+    let Err(sqlx::Error::ColumnDecode { source, .. }) = res else {
+        panic!("expected a decode error, got {res:?}");
+    };
+    assert_eq!(
+        source.to_string(),
+        "value 4294967301 is out of range for `i32`"
+    );
+
+    let value: i32 = sqlx::query_scalar("SELECT 2147483647")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, i32::MAX);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_configures_the_worker_thread() -> anyhow::Result<()> {
+    let thread_names = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    // collations are called on the worker thread
+    let names = thread_names.clone();
+    let mut conn = SqliteConnectOptions::new()
+        .thread_name(|id| format!("my-worker-{id}"))
+        .thread_stack_size(8 * 1024 * 1024)
+        .collation("record_thread", move |a: &str, b: &str| {
+            let name = std::thread::current().name().map(str::to_owned);
+            names.lock().unwrap().push(name);
+            a.cmp(b)
+        })
+        .connect()
+        .await?;
+
+    let value: String =
+        sqlx::query_scalar("SELECT 'a' AS v UNION SELECT 'b' ORDER BY v COLLATE record_thread")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(value, "a");
+
+    let thread_names = thread_names.lock().unwrap();
+    assert!(!thread_names.is_empty());
+    for name in thread_names.iter() {
+        assert!(name.as_deref().unwrap().starts_with("my-worker-"));
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_runs_connections_on_a_shared_worker_pool() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteWorkerPool;
+
+    let workers = SqliteWorkerPool::new(2);
+    let options = SqliteConnectOptions::new().shared_worker_pool(workers.clone());
+
+    // more connections than threads
+    let mut conns = Vec::new();
+    for _ in 0..8 {
+        conns.push(options.connect().await?);
+    }
+
+    let results =
+        futures::future::try_join_all(conns.iter_mut().enumerate().map(|(i, conn)| async move {
+            let mut tx = conn.begin().await?;
+            sqlx::query("CREATE TABLE t (x INTEGER)")
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("INSERT INTO t (x) VALUES (?1), (?1 * 2)")
+                .bind(i as i64)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+
+            sqlx::query_scalar::<_, i64>("SELECT SUM(x) FROM t")
+                .fetch_one(&mut *conn)
+                .await
+        }))
+        .await?;
+
+    assert_eq!(results, (0..8).map(|i| i * 3).collect::<Vec<i64>>());
+
+    // locking the handle doesn't block a thread of the pool
+    let mut conn = conns.pop().unwrap();
+    let handle = conn.lock_handle().await?;
+    conns[0].ping().await?;
+    conns[1].ping().await?;
+    drop(handle);
+    conn.ping().await?;
+
+    for conn in conns {
+        conn.close().await?;
+    }
+    conn.close().await?;
+
+    assert_eq!(workers.threads(), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_runs_nested_queries_on_a_single_threaded_worker_pool() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteWorkerPool;
+
+    let options = SqliteConnectOptions::new()
+        .shared_worker_pool(SqliteWorkerPool::new(1))
+        .row_buffer_size(1);
+
+    let mut outer = options.connect().await?;
+    let mut inner = options.connect().await?;
+
+    // the only thread isn't kept waiting for room in the buffer of rows of the outer query, nor
+    // for the lock on a connection
+    let mut rows = sqlx::query_scalar::<_, i64>("SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3")
+        .fetch(&mut outer);
+    let mut sum = 0;
+
+    while let Some(x) = rows.try_next().await? {
+        let handle = inner.lock_handle().await?;
+        drop(handle);
+
+        sum += sqlx::query_scalar::<_, i64>("SELECT ?1 * 10")
+            .bind(x)
+            .fetch_one(&mut inner)
+            .await?;
+    }
+
+    drop(rows);
+    assert_eq!(sum, 60);
+
+    outer.close().await?;
+    inner.close().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_sets_db_config_options() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteDbConfig;
+
+    let mut conn = SqliteConnectOptions::new()
+        .db_config(SqliteDbConfig::EnableView, false)
+        .db_config(SqliteDbConfig::Defensive, true)
+        .connect()
+        .await?;
+
+    conn.execute("CREATE VIEW v AS SELECT 1").await?;
+    let err = conn.execute("SELECT * FROM v").await.unwrap_err();
+    assert!(err.to_string().contains("prohibited"), "{err}");
+
+    // writing to the schema is not allowed in defensive mode
+    conn.execute("PRAGMA writable_schema = ON").await?;
+    assert!(conn
+        .execute("DELETE FROM sqlite_schema WHERE name = 'v'")
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_checks_pool_readiness() -> anyhow::Result<()> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(200))
+        .connect_lazy("sqlite::memory:")?;
+
+    assert_eq!(pool.size(), 0);
+
+    // opens a connection, and keeps it in the pool
+    pool.readiness_check().await?;
+    assert_eq!(pool.size(), 1);
+    assert_eq!(pool.num_idle(), 1);
+
+    let conn = pool.acquire().await?;
+    assert!(matches!(
+        pool.readiness_check().await,
+        Err(sqlx::Error::PoolTimedOut)
+    ));
+    drop(conn);
+
+    pool.readiness_check().await?;
+
+    pool.close().await;
+    assert!(matches!(
+        pool.readiness_check().await,
+        Err(sqlx::Error::PoolClosed)
+    ));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_cancels_queries_which_time_out() -> anyhow::Result<()> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(5))
+        .statement_timeout(Duration::from_millis(100))
+        .connect("sqlite::memory:")
+        .await?;
+
+    // never completes, unless interrupted
+    let endless =
+        "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c) SELECT count(*) FROM c";
+
+    let res = sqlx::query(endless).fetch_optional(&pool).await;
+    assert!(matches!(res, Err(sqlx::Error::QueryTimedOut)));
+
+    // the connection can only be replaced once the query was interrupted and it was closed
+    let res = sqlx::query(endless).fetch_all(&pool).await;
+    assert!(matches!(res, Err(sqlx::Error::QueryTimedOut)));
+
+    let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&pool).await?;
+    assert_eq!(one, 1);
+
+    Ok(())
+}
+
+#[cfg(feature = "recording")]
+#[sqlx_macros::test]
+async fn it_records_and_replays_queries() -> anyhow::Result<()> {
+    use sqlx::recording::{replay, QueryRecorder};
+    use std::io::BufReader;
+
+    let schema = "CREATE TABLE tweets (id INTEGER PRIMARY KEY, text TEXT NOT NULL, data BLOB)";
+    let path = std::env::temp_dir().join(format!("sqlx-recording-{}.jsonl", std::process::id()));
+    let recorder = QueryRecorder::create(&path)?.capture_arguments(true);
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+    conn.execute(schema).await?;
+
+    for id in 1..=2_i64 {
+        sqlx::query("INSERT INTO tweets (id, text, data) VALUES (?, ?, ?)")
+            .bind(id)
+            .bind(format!("tweet {id}"))
+            .bind(vec![id as u8; 3])
+            .execute(recorder.wrap(&mut conn))
+            .await?;
+    }
+
+    let texts: Vec<String> =
+        sqlx::query_scalar("SELECT text FROM tweets WHERE id >= ? ORDER BY id")
+            .bind(1_i64)
+            .fetch_all(recorder.wrap(&mut conn))
+            .await?;
+    assert_eq!(texts, ["tweet 1", "tweet 2"]);
+
+    let missing: Option<String> = sqlx::query_scalar("SELECT text FROM tweets WHERE id = ?")
+        .bind(3_i64)
+        .fetch_optional(recorder.wrap(&mut conn))
+        .await?;
+    assert!(missing.is_none());
+
+    assert!(recorder
+        .wrap(&mut conn)
+        .execute("SELECT * FROM missing")
+        .await
+        .is_err());
+
+    recorder.flush()?;
+    drop(recorder);
+
+    // replay against another database
+    let mut replica = SqliteConnection::connect("sqlite::memory:").await?;
+    replica.execute(schema).await?;
+
+    let file = std::fs::File::open(&path)?;
+    let report = replay(&mut replica, BufReader::new(file)).await?;
+    std::fs::remove_file(&path)?;
+
+    let outcomes: Vec<_> = report
+        .queries
+        .iter()
+        .map(|q| (q.rows_affected, q.rows_returned, q.error.is_some()))
+        .collect();
+    assert_eq!(
+        outcomes,
+        [
+            (1, 0, false),
+            (1, 0, false),
+            (0, 2, false),
+            (0, 0, false),
+            (0, 0, true)
+        ]
+    );
+    assert!(report.queries[4].recorded_error.is_some());
+    assert_eq!(report.errors().count(), 1);
+
+    let rows: Vec<(i64, String, Vec<u8>)> =
+        sqlx::query_as("SELECT id, text, data FROM tweets ORDER BY id")
+            .fetch_all(&mut replica)
+            .await?;
+    assert_eq!(
+        rows,
+        [
+            (1, "tweet 1".to_owned(), vec![1; 3]),
+            (2, "tweet 2".to_owned(), vec![2; 3])
+        ]
+    );
+
+    // arguments are only recorded on demand
+    let recorder = QueryRecorder::create(&path)?;
+
+    sqlx::query("SELECT ?")
+        .bind("secret")
+        .execute(recorder.wrap(&mut conn))
+        .await?;
+
+    recorder.flush()?;
+    let recording = std::fs::read_to_string(&path)?;
+    std::fs::remove_file(&path)?;
+
+    assert!(recording.contains(r#""sql":"SELECT ?""#));
+    assert!(!recording.contains("secret"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_quotes_identifiers_in_query_builder() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let mut create = QueryBuilder::<Sqlite>::new("CREATE TEMPORARY TABLE ");
+    create
+        .push_identifier(r#"my "table""#)
+        .push(" (")
+        .push_identifier("select")
+        .push(" TEXT)");
+    create.build().execute(&mut conn).await?;
+
+    let mut insert = QueryBuilder::<Sqlite>::new("INSERT INTO ");
+    insert
+        .push_identifier(r#"temp.my "table""#)
+        .push(" VALUES ('one')");
+    insert.build().execute(&mut conn).await?;
+
+    let mut select = QueryBuilder::<Sqlite>::new("SELECT ");
+    select
+        .push_identifier("select")
+        .push(" FROM ")
+        .push_identifier(r#"my "table""#);
+
+    let value: String = select.build_query_scalar().fetch_one(&mut conn).await?;
+    assert_eq!(value, "one");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_only_keeps_the_selected_columns() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = sqlx::query("SELECT 1 AS a, 'two' AS b, 3 AS c, NULL AS d")
+        .select_columns(&["c", "d", "a"])
+        .fetch_one(&mut conn)
+        .await?;
+
+    let names: Vec<&str> = row.columns().iter().map(|c| c.name()).collect();
+    assert_eq!(names, ["c", "d", "a"]);
+
+    assert_eq!(row.try_get::<i64, _>(0)?, 3);
+    assert_eq!(row.try_get::<Option<String>, _>("d")?, None);
+    assert_eq!(row.try_get::<i32, _>("a")?, 1);
+    assert!(matches!(
+        row.try_get::<String, _>("b"),
+        Err(sqlx::Error::ColumnNotFound(_))
+    ));
+
+    let res = sqlx::query("SELECT 1 AS a")
+        .select_columns(&["b"])
+        .fetch_one(&mut conn)
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::ColumnNotFound(name)) if name == "b"));
+
+    // statements which don't return rows are unaffected
+    sqlx::query("CREATE TEMPORARY TABLE selected (a INTEGER)")
+        .select_columns(&["a"])
+        .execute(&mut conn)
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_begins_transactions_with_options() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // SQLite transactions are always serializable
+    let options = TransactionOptions::new().isolation_level(IsolationLevel::Serializable);
+    let mut tx = conn.begin_with(options).await?;
+
+    // savepoints have no options
+    let res = tx.begin_with(options).await.map(|_| ());
+    assert!(matches!(res, Err(sqlx::Error::Configuration(_))));
+
+    tx.rollback().await?;
+
+    for options in [
+        TransactionOptions::new().isolation_level(IsolationLevel::ReadCommitted),
+        TransactionOptions::new().read_only(true),
+        TransactionOptions::new().deferrable(true),
+    ] {
+        let res = conn.begin_with(options).await.map(|_| ());
+        assert!(matches!(res, Err(sqlx::Error::Configuration(_))));
+    }
+
+    // no transaction was left open
+    let mut tx = conn.begin().await?;
+    let res = tx.execute("BEGIN").await;
+    assert!(res.is_err(), "BEGIN should fail inside a transaction");
+    tx.rollback().await?;
+
+    let res = conn.execute("COMMIT").await;
+    assert!(res.is_err(), "COMMIT should fail outside of a transaction");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_pragma_results() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE pragma_parents (a INTEGER, b TEXT, PRIMARY KEY (a, b));
+CREATE TEMPORARY TABLE pragma_children (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL UNIQUE DEFAULT 'unnamed',
+    parent_a INTEGER,
+    parent_b TEXT,
+    untyped,
+    FOREIGN KEY (parent_a, parent_b) REFERENCES pragma_parents (a, b) ON DELETE CASCADE
+);
+CREATE INDEX temp.pragma_children_untyped ON pragma_children (untyped) WHERE untyped IS NOT NULL;
+        "#,
+    )
+    .await?;
+
+    let columns = conn.pragma_table_info("pragma_children").await?;
+    let names: Vec<&str> = columns.iter().map(|c| &*c.name).collect();
+    assert_eq!(names, ["id", "name", "parent_a", "parent_b", "untyped"]);
+
+    assert_eq!(columns[0].declared_type, "INTEGER");
+    assert_eq!(columns[0].primary_key, 1);
+    assert!(columns[1].not_null);
+    assert_eq!(columns[1].default_value.as_deref(), Some("'unnamed'"));
+    assert!(!columns[2].not_null);
+    assert_eq!(columns[2].default_value, None);
+    assert_eq!(columns[4].declared_type, "");
+
+    // the table can be qualified with its schema
+    assert_eq!(
+        conn.pragma_table_info("temp.pragma_children").await?,
+        columns
+    );
+    assert!(conn
+        .pragma_table_info("main.pragma_children")
+        .await?
+        .is_empty());
+    assert!(conn.pragma_table_info("no_such_table").await?.is_empty());
+
+    let foreign_keys = conn.pragma_foreign_key_list("pragma_children").await?;
+    assert_eq!(foreign_keys.len(), 1);
+    assert_eq!(foreign_keys[0].referenced_table, "pragma_parents");
+    assert_eq!(foreign_keys[0].columns, ["parent_a", "parent_b"]);
+    assert_eq!(
+        foreign_keys[0].referenced_columns,
+        [Some("a".to_owned()), Some("b".to_owned())]
+    );
+    assert_eq!(foreign_keys[0].on_delete, "CASCADE");
+    assert_eq!(foreign_keys[0].on_update, "NO ACTION");
+
+    let mut indexes = conn.pragma_index_list("pragma_children").await?;
+    indexes.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!(indexes.len(), 2);
+
+    assert_eq!(indexes[0].name, "pragma_children_untyped");
+    assert_eq!(indexes[0].origin, SqliteIndexOrigin::CreateIndex);
+    assert!(!indexes[0].unique);
+    assert!(indexes[0].partial);
+
+    assert_eq!(indexes[1].origin, SqliteIndexOrigin::Unique);
+    assert!(indexes[1].unique);
+    assert!(!indexes[1].partial);
+
+    let indexes = conn.pragma_index_list("pragma_parents").await?;
+    assert_eq!(indexes.len(), 1);
+    assert_eq!(indexes[0].origin, SqliteIndexOrigin::PrimaryKey);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_calls_the_statement_log_handler() -> anyhow::Result<()> {
+    use std::sync::Mutex;
+
+    let statements = Arc::new(Mutex::new(Vec::new()));
+    let handler = {
+        let statements = statements.clone();
+        Arc::new(move |statement: &sqlx::StatementLogContext<'_>| {
+            statements.lock().unwrap().push((
+                statement.sql.to_owned(),
+                statement.elapsed,
+                statement.rows_returned,
+                statement.rows_affected,
+                statement.was_slow,
+            ));
+        })
+    };
+    let logged = |sql: &str| -> Vec<_> {
+        statements
+            .lock()
+            .unwrap()
+            .drain(..)
+            .filter(|(logged, ..)| logged == sql)
+            .collect()
+    };
+
+    // the handler is called even if logging is disabled
+    let options = ":memory:"
+        .parse::<SqliteConnectOptions>()?
+        .disable_statement_logging()
+        .log_with(handler.clone());
+
+    let mut conn = options.connect().await?;
+
+    let rows = sqlx::query("SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(rows.len(), 3);
+
+    let statement = logged("SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3");
+    assert_eq!(statement.len(), 1);
+    assert!(statement[0].1 > Duration::ZERO);
+    assert_eq!(statement[0].2, 3);
+    // the statements are never slow while slow statements aren't logged
+    assert!(!statement[0].4);
+
+    conn.execute("CREATE TEMPORARY TABLE logged (id INT)")
+        .await?;
+    conn.execute("INSERT INTO logged (id) VALUES (1), (2)")
+        .await?;
+
+    let statement = logged("INSERT INTO logged (id) VALUES (1), (2)");
+    assert_eq!(statement.len(), 1);
+    assert_eq!((statement[0].2, statement[0].3), (0, 2));
+
+    // every statement is slow with a threshold of zero
+    let options = options.log_slow_statements("warn".parse()?, Duration::ZERO);
+    let mut conn = options.connect().await?;
+
+    conn.execute("SELECT 1").await?;
+
+    let statement = logged("SELECT 1");
+    assert_eq!(statement.len(), 1);
+    assert!(statement[0].4);
+
+    // but removed by `disable_statement_logging()`
+    let options = options.log_with(handler).disable_statement_logging();
+    let mut conn = options.connect().await?;
+
+    conn.execute("SELECT 1").await?;
+    assert!(statements.lock().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_results_keyed_by_a_column() -> anyhow::Result<()> {
+    use sqlx::query::OnDuplicateKey;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let users = sqlx::query_as::<_, (i64, String)>(
+        "SELECT * FROM (VALUES (1, 'alice'), (2, 'bob'), (1, 'carol'))",
+    );
+
+    let map = users
+        .fetch_all_keyed_by(&mut conn, |(id, _)| *id, OnDuplicateKey::KeepLast)
+        .await?;
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[&1].1, "carol");
+    assert_eq!(map[&2].1, "bob");
+
+    let err = sqlx::query_as::<_, (i64, String)>(
+        "SELECT * FROM (VALUES (1, 'alice'), (2, 'bob'), (1, 'carol'))",
+    )
+    .fetch_all_keyed_by(&mut conn, |(id, _)| *id, OnDuplicateKey::Error)
+    .await
+    .unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::DuplicateKey(key) if key == "1"));
+
+    // the connection is still usable after the remaining rows were dropped
+    let one: i64 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(one, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_vacuums_into_a_new_file() -> anyhow::Result<()> {
+    let dir = std::env::temp_dir();
+    let source = dir.join(format!("sqlx-vacuum-{}.db", std::process::id()));
+    let path = dir.join(format!("sqlx-vacuum-into-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&source);
+    let _ = std::fs::remove_file(&path);
+
+    let mut conn = SqliteConnection::connect_with(
+        &SqliteConnectOptions::new()
+            .filename(&source)
+            .create_if_missing(true),
+    )
+    .await?;
+
+    conn.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT); INSERT INTO users (name) VALUES ('alice'), ('bob'); DELETE FROM users WHERE name = 'bob'")
+        .await?;
+
+    conn.vacuum().await?;
+    conn.vacuum_into(&path).await?;
+
+    // the destination must not exist already
+    assert!(conn.vacuum_into(&path).await.is_err());
+
+    let mut copy =
+        SqliteConnection::connect_with(&SqliteConnectOptions::new().filename(&path)).await?;
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM users")
+        .fetch_all(&mut copy)
+        .await?;
+
+    assert_eq!(names, ["alice"]);
+
+    copy.close().await?;
+    std::fs::remove_file(&path)?;
+
+    let mut tx = conn.begin().await?;
+    assert!(tx.vacuum().await.is_err());
+    tx.rollback().await?;
+
+    conn.close().await?;
+    std::fs::remove_file(&source)?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_batches_continuing_on_errors() -> anyhow::Result<()> {
+    use sqlx::batch::{execute_batch, BatchMode};
+
+    let mut conn = SqliteConnection::connect(":memory:").await?;
+
+    let statements = [
+        "CREATE TABLE batch (id INTEGER PRIMARY KEY)",
+        "INSERT INTO batch VALUES (1)",
+        "CREATE TABLE batch (id INTEGER PRIMARY KEY)",
+        "INSERT INTO batch VALUES (2)",
+    ];
+
+    let results = execute_batch(&mut conn, statements, BatchMode::ContinueOnError).await;
+
+    assert_eq!(results.len(), 4);
+    assert!(results[2].is_err());
+    assert_eq!(results[3].as_ref().unwrap().rows_affected(), 1);
+
+    let results = execute_batch(&mut conn, statements, BatchMode::StopOnError).await;
+
+    assert_eq!(results.len(), 1);
+    assert!(results[0].is_err());
+
+    let ids: Vec<i64> = sqlx::query_scalar("SELECT id FROM batch ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(ids, [1, 2]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fails_fast_with_an_open_circuit_breaker() -> anyhow::Result<()> {
+    use sqlx::pool::CircuitBreakerConfig;
+
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .circuit_breaker(
+            CircuitBreakerConfig::new()
+                .failure_threshold(2)
+                // long enough for the circuit to be checked before it's half-open again,
+                // even on a loaded machine
+                .cooldown(Duration::from_secs(2)),
+        )
+        .connect_lazy_with(
+            SqliteConnectOptions::new().filename("target/sqlx-nonexistent-dir/circuit.db"),
+        );
+
+    // the first failures are those of the connection
+    for _ in 0..2 {
+        let error = pool.acquire().await.unwrap_err();
+        assert!(!matches!(error, sqlx::Error::CircuitOpen), "{error:?}");
+    }
+
+    assert!(matches!(
+        pool.acquire().await,
+        Err(sqlx::Error::CircuitOpen)
+    ));
+
+    // once the cooldown period elapsed, a probe is let through, which closes the circuit
+    pool.set_connect_options("sqlite::memory:".parse()?);
+    sqlx_core::rt::sleep(Duration::from_millis(2100)).await;
+
+    pool.acquire().await?;
+    pool.acquire().await?;
+
+    Ok(())
+}
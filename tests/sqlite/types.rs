@@ -6,6 +6,7 @@ use sqlx_core::row::Row;
 use sqlx_core::types::Text;
 use sqlx_test::new;
 use sqlx_test::test_type;
+use std::borrow::Cow;
 use std::net::SocketAddr;
 
 test_type!(null<Option<i32>>(Sqlite,
@@ -14,6 +15,33 @@ test_type!(null<Option<i32>>(Sqlite,
 
 test_type!(bool(Sqlite, "FALSE" == false, "TRUE" == true));
 
+#[sqlx_macros::test]
+async fn it_decodes_integers_as_bool() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE flags (flag INTEGER NOT NULL)")
+        .await?;
+    conn.execute("INSERT INTO flags (flag) VALUES (0), (1), (-1), (2), (1 << 32)")
+        .await?;
+
+    let flags: Vec<bool> = sqlx::query_scalar("SELECT flag FROM flags ORDER BY rowid")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(flags, [false, true, true, true, true]);
+
+    // `bool` is stored as `0` or `1`
+    let stored: (i64, i64) = sqlx::query_as("SELECT ?, ?")
+        .bind(false)
+        .bind(true)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(stored, (0, 1));
+
+    Ok(())
+}
+
 test_type!(i32(Sqlite, "94101" == 94101_i32));
 
 test_type!(i64(Sqlite, "9358295312" == 9358295312_i64));
@@ -30,6 +58,20 @@ test_type!(str<String>(Sqlite,
     "''" == ""
 ));
 
+#[sqlx_macros::test]
+async fn it_decodes_cow_str_borrowed() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = sqlx::query("SELECT ?")
+        .bind("this is foo")
+        .fetch_one(&mut conn)
+        .await?;
+    let text: Cow<'_, str> = row.try_get(0)?;
+    assert!(matches!(text, Cow::Borrowed("this is foo")));
+
+    Ok(())
+}
+
 test_type!(bytes<Vec<u8>>(Sqlite,
     "X'DEADBEEF'"
         == vec![0xDE_u8, 0xAD, 0xBE, 0xEF],
@@ -39,6 +81,24 @@ test_type!(bytes<Vec<u8>>(Sqlite,
         == vec![0_u8, 0, 0, 0, 0x52]
 ));
 
+test_type!(byte_array<[u8; 4]>(Sqlite,
+    "X'DEADBEEF'" == [0xDE_u8, 0xAD, 0xBE, 0xEF],
+));
+
+#[sqlx_macros::test]
+async fn it_rejects_byte_arrays_of_another_length() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let res = sqlx::query_scalar::<_, [u8; 32]>("SELECT X'DEADBEEF'")
+        .fetch_one(&mut conn)
+        .await;
+
+    let err = res.unwrap_err().to_string();
+    assert!(err.contains("expected 32 bytes, got 4"), "{err}");
+
+    Ok(())
+}
+
 #[cfg(feature = "json")]
 mod json_tests {
     use super::*;
@@ -110,6 +170,52 @@ mod chrono {
     test_type!(chrono_date_time_fixed_offset<DateTime::<FixedOffset>>(Sqlite, "SELECT datetime({0}) is datetime(?), {0}, ?",
         "'2016-11-08T03:50:23-05:00'" == DateTime::<Utc>::from(FixedOffset::west(5 * 3600).ymd(2016, 11, 08).and_hms(3, 50, 23))
     ));
+
+    #[cfg(feature = "json")]
+    test_type!(chrono_naive_date_json_array<sqlx::types::Json<Vec<NaiveDate>>>(Sqlite,
+        "'[\"2002-06-04\",\"2019-01-02\"]'"
+            == sqlx::types::Json(vec![NaiveDate::from_ymd(2002, 6, 4), NaiveDate::from_ymd(2019, 1, 2)])
+    ));
+
+    #[cfg(feature = "json")]
+    test_type!(chrono_naive_date_time_json_array<sqlx::types::Json<Vec<NaiveDateTime>>>(Sqlite,
+        "'[\"2019-01-02T05:10:20\"]'"
+            == sqlx::types::Json(vec![NaiveDate::from_ymd(2019, 1, 2).and_hms(5, 10, 20)])
+    ));
+
+    #[cfg(feature = "json")]
+    test_type!(chrono_date_time_utc_json_array<sqlx::types::Json<Vec<DateTime<Utc>>>>(Sqlite,
+        "'[\"1996-12-20T00:39:57Z\"]'" == sqlx::types::Json(vec![Utc.ymd(1996, 12, 20).and_hms(0, 39, 57)])
+    ));
+
+    #[cfg(feature = "json")]
+    #[sqlx_macros::test]
+    async fn it_applies_date_functions_to_json_arrays_of_chrono_dates() -> anyhow::Result<()> {
+        use sqlx::types::Json;
+
+        let mut conn = new::<Sqlite>().await?;
+
+        let dates = Json(vec![
+            NaiveDate::from_ymd(2024, 1, 31),
+            NaiveDate::from_ymd(2024, 2, 29),
+        ]);
+
+        let next_days: Json<Vec<NaiveDate>> =
+            sqlx::query_scalar("SELECT json_group_array(date(value, '+1 day')) FROM json_each(?)")
+                .bind(&dates)
+                .fetch_one(&mut conn)
+                .await?;
+
+        assert_eq!(
+            next_days.0,
+            [
+                NaiveDate::from_ymd(2024, 2, 1),
+                NaiveDate::from_ymd(2024, 3, 1)
+            ]
+        );
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "time")]
@@ -158,6 +264,88 @@ mod time_tests {
         "'20:45:31.133'" == time!(20:45:31.133),
         "'19:44'" == time!(19:44),
     ));
+
+    #[cfg(feature = "json")]
+    test_type!(time_primitive_date_time_json_array<sqlx::types::Json<Vec<PrimitiveDateTime>>>(
+        Sqlite,
+        "'[\"2019-01-02 05:10:20.0\",\"2018-12-01 04:09:19.543\"]'"
+            == sqlx::types::Json(vec![datetime!(2019 - 1 - 2 5:10:20), datetime!(2018 - 12 - 1 4:09:19.543)]),
+    ));
+
+    #[cfg(feature = "json")]
+    test_type!(time_date_json_array<sqlx::types::Json<Vec<Date>>>(
+        Sqlite,
+        "'[\"2002-06-04\",\"2019-01-02\"]'"
+            == sqlx::types::Json(vec![date!(2002 - 6 - 4), date!(2019 - 1 - 2)]),
+    ));
+
+    #[cfg(feature = "json")]
+    test_type!(time_time_json_array<sqlx::types::Json<Vec<Time>>>(
+        Sqlite,
+        "'[\"21:46:32.0\",\"20:45:31.133\"]'"
+            == sqlx::types::Json(vec![time!(21:46:32), time!(20:45:31.133)]),
+    ));
+
+    #[cfg(feature = "json")]
+    #[sqlx_macros::test]
+    async fn it_applies_date_functions_to_json_arrays_of_time_dates() -> anyhow::Result<()> {
+        use sqlx::types::Json;
+
+        let mut conn = new::<Sqlite>().await?;
+
+        let dates = Json(vec![date!(2024 - 1 - 31), date!(2024 - 2 - 29)]);
+
+        let next_days: Json<Vec<Date>> =
+            sqlx::query_scalar("SELECT json_group_array(date(value, '+1 day')) FROM json_each(?)")
+                .bind(&dates)
+                .fetch_one(&mut conn)
+                .await?;
+
+        assert_eq!(next_days.0, [date!(2024 - 2 - 1), date!(2024 - 3 - 1)]);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "jiff")]
+mod jiff_tests {
+    use super::*;
+    use sqlx::types::jiff::{Date, DateTime, Time, Timestamp};
+
+    test_type!(jiff_timestamp<Timestamp>(
+        Sqlite,
+        "SELECT datetime({0}) is datetime(?), {0}, ?",
+        "'2015-11-19 01:01:39+01:00'" == "2015-11-19T00:01:39Z".parse::<Timestamp>().unwrap(),
+        "'2014-10-18 00:00:38.697+00:00'" == "2014-10-18T00:00:38.697Z".parse::<Timestamp>().unwrap(),
+        "'2013-09-17 23:59-01:00'" == "2013-09-18T00:59:00Z".parse::<Timestamp>().unwrap(),
+        "'2016-03-07T22:36:55.135+03:30'" == "2016-03-07T19:06:55.135Z".parse::<Timestamp>().unwrap(),
+        "'2017-04-11 14:35:00'" == "2017-04-11T14:35:00Z".parse::<Timestamp>().unwrap(),
+    ));
+
+    test_type!(jiff_date_time<DateTime>(
+        Sqlite,
+        "SELECT datetime({0}) is datetime(?), {0}, ?",
+        "'2019-01-02 05:10:20'" == DateTime::constant(2019, 1, 2, 5, 10, 20, 0),
+        "'2018-12-01 04:09:19.543'" == DateTime::constant(2018, 12, 1, 4, 9, 19, 543_000_000),
+        "'2017-11-30 03:08'" == DateTime::constant(2017, 11, 30, 3, 8, 0, 0),
+        "'2016-10-29T02:07:17'" == DateTime::constant(2016, 10, 29, 2, 7, 17, 0),
+        "'2013-07-26 23:04:14Z'" == DateTime::constant(2013, 7, 26, 23, 4, 14, 0),
+        "'2009-03-22T19:00:10.21Z'" == DateTime::constant(2009, 3, 22, 19, 0, 10, 210_000_000),
+    ));
+
+    test_type!(jiff_date<Date>(
+        Sqlite,
+        "SELECT date({0}) is date(?), {0}, ?",
+        "'2002-06-04'" == Date::constant(2002, 6, 4),
+    ));
+
+    test_type!(jiff_time<Time>(
+        Sqlite,
+        "SELECT time({0}) is time(?), {0}, ?",
+        "'21:46:32'" == Time::constant(21, 46, 32, 0),
+        "'20:45:31.133'" == Time::constant(20, 45, 31, 133_000_000),
+        "'19:44'" == Time::constant(19, 44, 0, 0),
+    ));
 }
 
 #[cfg(feature = "bstr")]